@@ -0,0 +1,158 @@
+//! `Graph`と`CompactGraph`でBFS・Dijkstraの実行時間を比べるベンチマーク
+//!
+//! ```text
+//! cargo run --release --example compact_graph_bench
+//! ```
+
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, VecDeque};
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use rust_macro::graph::CompactGraph;
+use rust_macro::{Graph, Undirected, XorShift64};
+
+const N: usize = 200_000;
+const M: usize = 200_000;
+const ROUNDS: u32 = 20;
+
+fn bfs_graph<EW>(adj: &[Vec<(usize, Option<EW>)>], s: usize) -> Vec<u32> {
+    let mut dist = vec![u32::MAX; adj.len()];
+    let mut queue = VecDeque::new();
+    dist[s] = 0;
+    queue.push_back(s);
+    while let Some(u) = queue.pop_front() {
+        for &(v, _) in &adj[u] {
+            if dist[v] == u32::MAX {
+                dist[v] = dist[u] + 1;
+                queue.push_back(v);
+            }
+        }
+    }
+    dist
+}
+
+fn bfs_compact<EW>(g: &CompactGraph<EW>, s: usize) -> Vec<u32> {
+    let mut dist = vec![u32::MAX; g.len()];
+    let mut queue = VecDeque::new();
+    dist[s] = 0;
+    queue.push_back(s);
+    while let Some(u) = queue.pop_front() {
+        for &v in g.targets(u) {
+            let v = v as usize;
+            if dist[v] == u32::MAX {
+                dist[v] = dist[u] + 1;
+                queue.push_back(v);
+            }
+        }
+    }
+    dist
+}
+
+fn dijkstra_graph(adj: &[Vec<(usize, Option<u32>)>], s: usize) -> Vec<u64> {
+    let mut dist = vec![u64::MAX; adj.len()];
+    let mut heap = BinaryHeap::new();
+    dist[s] = 0;
+    heap.push(Reverse((0, s)));
+    while let Some(Reverse((d, u))) = heap.pop() {
+        if d > dist[u] {
+            continue;
+        }
+        for &(v, w) in &adj[u] {
+            let nd = d + w.unwrap() as u64;
+            if nd < dist[v] {
+                dist[v] = nd;
+                heap.push(Reverse((nd, v)));
+            }
+        }
+    }
+    dist
+}
+
+fn dijkstra_compact(g: &CompactGraph<u32>, s: usize) -> Vec<u64> {
+    let mut dist = vec![u64::MAX; g.len()];
+    let mut heap = BinaryHeap::new();
+    dist[s] = 0;
+    heap.push(Reverse((0, s)));
+    while let Some(Reverse((d, u))) = heap.pop() {
+        if d > dist[u] {
+            continue;
+        }
+        for (v, &w) in g.neighbors(u) {
+            let nd = d + w as u64;
+            if nd < dist[v] {
+                dist[v] = nd;
+                heap.push(Reverse((nd, v)));
+            }
+        }
+    }
+    dist
+}
+
+fn time<R>(mut f: impl FnMut() -> R) -> Duration {
+    let start = Instant::now();
+    for _ in 0..ROUNDS {
+        black_box(f());
+    }
+    start.elapsed() / ROUNDS
+}
+
+fn main() {
+    let mut rng = XorShift64::new(42);
+    // 連結になるようにランダムな木を作り、残りはランダムな辺を足す
+    let edges: Vec<(usize, usize, u32)> = (0..M)
+        .map(|i| {
+            let v = i % (N - 1) + 1;
+            let u = if i < N - 1 {
+                rng.gen_range(0..v as u64) as usize
+            } else {
+                rng.gen_range(0..N as u64) as usize
+            };
+            (u, v, rng.gen_range(1..1_000_000) as u32)
+        })
+        .collect();
+
+    let mut unweighted = Graph::<usize, (), (), Undirected>::new();
+    let mut weighted = Graph::<usize, u32, (), Undirected>::new();
+    for v in 0..N {
+        unweighted.get_or_create_id(v);
+        weighted.get_or_create_id(v);
+    }
+    for &(u, v, w) in &edges {
+        unweighted.add_edge(u, v, None);
+        unweighted.add_edge(v, u, None);
+        weighted.add_edge(u, v, Some(w));
+        weighted.add_edge(v, u, Some(w));
+    }
+    let compact_unweighted = unweighted.to_compact(());
+    let compact_weighted = weighted.to_compact(1);
+
+    let edge_bytes = |per_edge: usize| 2 * M * per_edge;
+    println!("n = {N}, m = {M} (undirected), average of {ROUNDS} runs");
+    println!(
+        "edge storage: Graph<_, (), ..> {} B, CompactGraph<()> {} B",
+        edge_bytes(std::mem::size_of::<(usize, Option<()>)>()),
+        edge_bytes(std::mem::size_of::<u32>()),
+    );
+    println!(
+        "edge storage: Graph<_, u32, ..> {} B, CompactGraph<u32> {} B",
+        edge_bytes(std::mem::size_of::<(usize, Option<u32>)>()),
+        edge_bytes(std::mem::size_of::<u32>() * 2),
+    );
+
+    assert_eq!(
+        bfs_graph(&unweighted.adj, 0),
+        bfs_compact(&compact_unweighted, 0)
+    );
+    assert_eq!(
+        dijkstra_graph(&weighted.adj, 0),
+        dijkstra_compact(&compact_weighted, 0)
+    );
+
+    let t1 = time(|| bfs_graph(&unweighted.adj, 0));
+    let t2 = time(|| bfs_compact(&compact_unweighted, 0));
+    println!("BFS:      Graph {t1:>10.2?}  CompactGraph {t2:>10.2?}");
+    let t1 = time(|| dijkstra_graph(&weighted.adj, 0));
+    let t2 = time(|| dijkstra_compact(&compact_weighted, 0));
+    println!("Dijkstra: Graph {t1:>10.2?}  CompactGraph {t2:>10.2?}");
+}
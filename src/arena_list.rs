@@ -0,0 +1,272 @@
+const NIL: usize = usize::MAX;
+
+#[derive(Debug, Clone)]
+struct Node<T> {
+    val: T,
+    prev: usize,
+    next: usize,
+    alive: bool,
+}
+
+/// 配列上に確保する双方向連結リスト
+///
+/// 要素はハンドル（追加した順の通し番号）で参照し、削除しても他の要素のハンドルは
+/// 変わりません。ヨセフス問題のような「要素を繰り返し取り除く」シミュレーションで、
+/// 生きている次の要素・前の要素をたどるのに使います。削除した要素の領域は再利用しません。
+///
+/// # 計算量
+/// - 追加・挿入・削除・前後の要素: O(1)
+///
+/// # 使用例
+/// ```
+/// # use rust_macro::ArenaList;
+/// // ヨセフス問題: 5人が輪になり、2人ごとに取り除く
+/// let mut list: ArenaList<usize> = (1..=5).collect();
+/// let mut cur = list.front().unwrap();
+/// let mut order = Vec::new();
+/// while !list.is_empty() {
+///     cur = list.next_cyclic(cur).unwrap();
+///     let next = list.next_cyclic(cur);
+///     order.push(*list.get(cur).unwrap());
+///     list.remove(cur);
+///     if let Some(next) = next {
+///         cur = next;
+///     }
+/// }
+/// assert_eq!(order, vec![2, 4, 1, 5, 3]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct ArenaList<T> {
+    nodes: Vec<Node<T>>,
+    head: usize,
+    tail: usize,
+    len: usize,
+}
+
+impl<T> Default for ArenaList<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> ArenaList<T> {
+    /// 空のリストを作成
+    pub fn new() -> Self {
+        ArenaList {
+            nodes: Vec::new(),
+            head: NIL,
+            tail: NIL,
+            len: 0,
+        }
+    }
+
+    /// 生きている要素の個数
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// 生きている要素がないかどうか
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// ハンドルhの要素が生きているかどうか
+    pub fn is_alive(&self, h: usize) -> bool {
+        self.nodes.get(h).is_some_and(|n| n.alive)
+    }
+
+    /// ハンドルhの要素（削除済みなら`None`）
+    pub fn get(&self, h: usize) -> Option<&T> {
+        self.nodes.get(h).filter(|n| n.alive).map(|n| &n.val)
+    }
+
+    /// ハンドルhの要素への可変参照（削除済みなら`None`）
+    pub fn get_mut(&mut self, h: usize) -> Option<&mut T> {
+        self.nodes
+            .get_mut(h)
+            .filter(|n| n.alive)
+            .map(|n| &mut n.val)
+    }
+
+    /// 先頭の要素のハンドル
+    pub fn front(&self) -> Option<usize> {
+        (self.head != NIL).then_some(self.head)
+    }
+
+    /// 末尾の要素のハンドル
+    pub fn back(&self) -> Option<usize> {
+        (self.tail != NIL).then_some(self.tail)
+    }
+
+    /// hの次の生きている要素のハンドル
+    pub fn next(&self, h: usize) -> Option<usize> {
+        assert!(self.is_alive(h), "removed handle");
+        let n = self.nodes[h].next;
+        (n != NIL).then_some(n)
+    }
+
+    /// hの前の生きている要素のハンドル
+    pub fn prev(&self, h: usize) -> Option<usize> {
+        assert!(self.is_alive(h), "removed handle");
+        let p = self.nodes[h].prev;
+        (p != NIL).then_some(p)
+    }
+
+    /// hの次の要素のハンドル（末尾の次は先頭）
+    pub fn next_cyclic(&self, h: usize) -> Option<usize> {
+        self.next(h).or_else(|| self.front())
+    }
+
+    /// hの前の要素のハンドル（先頭の前は末尾）
+    pub fn prev_cyclic(&self, h: usize) -> Option<usize> {
+        self.prev(h).or_else(|| self.back())
+    }
+
+    /// prevとnextの間に要素を作ってつなぐ
+    fn link(&mut self, val: T, prev: usize, next: usize) -> usize {
+        let h = self.nodes.len();
+        self.nodes.push(Node {
+            val,
+            prev,
+            next,
+            alive: true,
+        });
+        if prev == NIL {
+            self.head = h;
+        } else {
+            self.nodes[prev].next = h;
+        }
+        if next == NIL {
+            self.tail = h;
+        } else {
+            self.nodes[next].prev = h;
+        }
+        self.len += 1;
+        h
+    }
+
+    /// 末尾に追加し、ハンドルを返す
+    pub fn push_back(&mut self, val: T) -> usize {
+        self.link(val, self.tail, NIL)
+    }
+
+    /// 先頭に追加し、ハンドルを返す
+    pub fn push_front(&mut self, val: T) -> usize {
+        self.link(val, NIL, self.head)
+    }
+
+    /// hの直後に挿入し、ハンドルを返す
+    pub fn insert_after(&mut self, h: usize, val: T) -> usize {
+        assert!(self.is_alive(h), "removed handle");
+        self.link(val, h, self.nodes[h].next)
+    }
+
+    /// hの直前に挿入し、ハンドルを返す
+    pub fn insert_before(&mut self, h: usize, val: T) -> usize {
+        assert!(self.is_alive(h), "removed handle");
+        self.link(val, self.nodes[h].prev, h)
+    }
+
+    /// hを削除する。すでに削除されていれば`false`を返す
+    pub fn remove(&mut self, h: usize) -> bool {
+        if !self.is_alive(h) {
+            return false;
+        }
+        let Node { prev, next, .. } = self.nodes[h];
+        if prev == NIL {
+            self.head = next;
+        } else {
+            self.nodes[prev].next = next;
+        }
+        if next == NIL {
+            self.tail = prev;
+        } else {
+            self.nodes[next].prev = prev;
+        }
+        self.nodes[h].alive = false;
+        self.len -= 1;
+        true
+    }
+
+    /// 生きている要素を先頭から(ハンドル, 値)として列挙する
+    pub fn iter(&self) -> impl Iterator<Item = (usize, &T)> + '_ {
+        let mut h = self.head;
+        std::iter::from_fn(move || {
+            if h == NIL {
+                return None;
+            }
+            let cur = h;
+            h = self.nodes[cur].next;
+            Some((cur, &self.nodes[cur].val))
+        })
+    }
+}
+
+impl<T> FromIterator<T> for ArenaList<T> {
+    /// 先頭から順に追加する（ハンドルは0, 1, 2, ...）
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut list = Self::new();
+        for x in iter {
+            list.push_back(x);
+        }
+        list
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::XorShift64;
+
+    #[test]
+    fn test_arena_list_against_vec() {
+        let mut rng = XorShift64::new(51);
+        let mut list = ArenaList::new();
+        // 生きているハンドルを先頭から順に並べたもの
+        let mut naive: Vec<usize> = Vec::new();
+        for step in 0..1000u64 {
+            let k = naive.len() as u64;
+            match rng.gen_range(0..5) {
+                0 => naive.push(list.push_back(step)),
+                1 => naive.insert(0, list.push_front(step)),
+                2 if k > 0 => {
+                    let i = rng.gen_range(0..k) as usize;
+                    let h = list.insert_after(naive[i], step);
+                    naive.insert(i + 1, h);
+                }
+                3 if k > 0 => {
+                    let i = rng.gen_range(0..k) as usize;
+                    let h = list.insert_before(naive[i], step);
+                    naive.insert(i, h);
+                }
+                _ if k > 0 => {
+                    let i = rng.gen_range(0..k) as usize;
+                    assert!(list.remove(naive[i]));
+                    assert!(!list.remove(naive.remove(i)));
+                }
+                _ => {}
+            }
+            assert_eq!(list.len(), naive.len());
+            assert_eq!(list.front(), naive.first().copied());
+            assert_eq!(list.back(), naive.last().copied());
+            for (i, &h) in naive.iter().enumerate() {
+                assert_eq!(list.next(h), naive.get(i + 1).copied());
+                assert_eq!(list.prev(h), i.checked_sub(1).map(|j| naive[j]));
+            }
+        }
+        assert!(list.iter().map(|(h, _)| h).eq(naive.iter().copied()));
+    }
+
+    #[test]
+    fn test_arena_list_get_and_cyclic() {
+        let mut list: ArenaList<char> = "abc".chars().collect();
+        *list.get_mut(1).unwrap() = 'x';
+        assert_eq!(list.next_cyclic(2), Some(0));
+        assert_eq!(list.prev_cyclic(0), Some(2));
+        list.remove(1);
+        assert_eq!(list.get(1), None);
+        assert!(!list.is_alive(1));
+        assert_eq!(list.next(0), Some(2));
+        assert_eq!(list.iter().map(|(_, &c)| c).collect::<String>(), "ac");
+    }
+}
@@ -1,100 +1,156 @@
+use std::cmp::Ordering;
 use std::fmt;
-use std::ops::{Add, BitAnd, BitOr, BitXor};
-
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+use std::ops::{Add, BitAnd, BitOr, BitXor, Shl, Shr};
+
+/// 長さnのビット列（`Vec<u64>`に格納するため、長さに上限はない）
+///
+/// インデックスは上位ビットから数え、i=0が最上位ビットです。
+/// 整数として見た値は`to_usize`で取り出せ、加算・シフト・比較も整数としての値で行います。
+///
+/// # 使用例
+/// ```
+/// # use rust_macro::BitVec;
+/// let a = BitVec::from_usize(0b1010, 4);
+/// assert!(a.get(0));
+/// assert_eq!(a.to_string(), "1010");
+/// assert_eq!((&a << 1).to_usize(), 0b0100);
+///
+/// let wide = BitVec::unit(0, 100);
+/// assert_eq!(wide.count_ones(), 1);
+/// assert!(wide > BitVec::mask(99).resized(100));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct BitVec {
-    data: usize,
+    /// 整数として見た値の下位から64ビットずつ（n以上のビットは常に0）
+    data: Vec<u64>,
     n: usize,
 }
 
 impl BitVec {
     /// 長さnの0初期化ビット列
     pub fn new(n: usize) -> Self {
-        Self { data: 0, n }
+        Self {
+            data: vec![0; (n + 63) / 64],
+            n,
+        }
     }
 
     /// usize値から nビットにマスクして作成
     pub fn from_usize(data: usize, n: usize) -> Self {
-        Self {
-            data: data & ((1 << n) - 1),
-            n,
+        let mut bv = Self::new(n);
+        if let Some(w) = bv.data.first_mut() {
+            *w = data as u64;
         }
+        bv.normalize();
+        bv
     }
 
     /// usize値として取得
-    pub fn to_usize(self) -> usize {
-        self.data
+    ///
+    /// # パニック
+    /// 値がusizeに収まらない場合panicします
+    pub fn to_usize(&self) -> usize {
+        assert!(
+            self.data.iter().skip(1).all(|&w| w == 0)
+                && self
+                    .data
+                    .first()
+                    .map_or(true, |&w| usize::try_from(w).is_ok()),
+            "BitVec value does not fit in usize"
+        );
+        self.data.first().map_or(0, |&w| w as usize)
     }
 
     /// ビット長
-    pub fn len(self) -> usize {
+    pub fn len(&self) -> usize {
         self.n
     }
 
     /// ビット長が0かどうか
-    pub fn is_empty(self) -> bool {
+    pub fn is_empty(&self) -> bool {
         self.n == 0
     }
 
+    /// 長さをmに変えたビット列（整数としての値を保ち、はみ出す上位ビットは捨てる）
+    pub fn resized(&self, m: usize) -> Self {
+        let mut bv = Self::new(m);
+        for (dst, &src) in bv.data.iter_mut().zip(&self.data) {
+            *dst = src;
+        }
+        bv.normalize();
+        bv
+    }
+
     /// 上位ビットから見て i 番目のビットを取得（i=0が最上位）
-    pub fn get(self, i: usize) -> bool {
+    pub fn get(&self, i: usize) -> bool {
         assert!(i < self.n);
-        (self.data >> (self.n - 1 - i)) & 1 == 1
+        let p = self.n - 1 - i;
+        (self.data[p / 64] >> (p % 64)) & 1 == 1
     }
 
     /// i番目に1が立っている単位ビット列を作る（i=0が最上位）
     pub fn unit(i: usize, n: usize) -> Self {
-        assert!(i < n);
-        Self::from_usize(1 << (n - 1 - i), n)
+        Self::new(n).set(i, 1)
     }
 
     /// ビット列を縦に表示
-    pub fn dump(self) {
+    pub fn dump(&self) {
         for i in 0..self.n {
             println!("bit {:2}: {}", i, if self.get(i) { 1 } else { 0 });
         }
     }
 
     /// ビット列のイテレータ（上位bitから順に）
-    pub fn iter(self) -> BitVecIter {
-        BitVecIter {
-            data: self.data,
-            n: self.n,
-            pos: 0,
-        }
+    pub fn iter(&self) -> BitVecIter<'_> {
+        BitVecIter { bv: self, pos: 0 }
     }
 
     /// i 番目（上位0始まり）のビットを 0 または 1 に設定した新しい BitVec を返す
-    pub fn set(self, i: usize, value: u8) -> Self {
-        assert!(i < self.n);
-        assert!(value == 0 || value == 1);
-        let shift = self.n - 1 - i;
-        let mask = 1 << shift;
-        let new_data = match value {
-            0 => self.data & !mask,
-            1 => self.data | mask,
-            _ => unreachable!(),
-        };
-        Self::from_usize(new_data, self.n)
+    pub fn set(&self, i: usize, value: u8) -> Self {
+        let mut bv = self.clone();
+        bv.set_mut(i, value);
+        bv
     }
 
     pub fn set_mut(&mut self, i: usize, value: u8) {
         assert!(i < self.n);
         assert!(value == 0 || value == 1);
-        let shift = self.n - 1 - i;
-        let mask = 1 << shift;
+        let p = self.n - 1 - i;
+        let mask = 1 << (p % 64);
         match value {
-            0 => self.data &= !mask,
-            1 => self.data |= mask,
+            0 => self.data[p / 64] &= !mask,
+            1 => self.data[p / 64] |= mask,
             _ => unreachable!(),
         }
     }
 
+    /// すべてのビットが1の長さnのビット列
     pub fn mask(n: usize) -> Self {
-        assert!(n <= usize::BITS as usize);
-        Self {
-            data: (1 << n) - 1,
+        let mut bv = Self {
+            data: vec![u64::MAX; (n + 63) / 64],
             n,
+        };
+        bv.normalize();
+        bv
+    }
+
+    /// 1が立っているビットの個数
+    pub fn count_ones(&self) -> usize {
+        self.data.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    /// 0のビットの個数
+    pub fn count_zeros(&self) -> usize {
+        self.n - self.count_ones()
+    }
+
+    /// n以上の位置のビットを0にする
+    fn normalize(&mut self) {
+        let r = self.n % 64;
+        if r != 0 {
+            if let Some(w) = self.data.last_mut() {
+                *w &= (1 << r) - 1;
+            }
         }
     }
 }
@@ -109,72 +165,204 @@ impl fmt::Display for BitVec {
     }
 }
 
+// === 比較（整数としての値で比較し、等しければ長さで比較） ===
+
+impl Ord for BitVec {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let len = self.data.len().max(other.data.len());
+        let word = |bv: &BitVec, i: usize| bv.data.get(i).copied().unwrap_or(0);
+        (0..len)
+            .rev()
+            .map(|i| word(self, i).cmp(&word(other, i)))
+            .find(|o| o.is_ne())
+            .unwrap_or(Ordering::Equal)
+            .then(self.n.cmp(&other.n))
+    }
+}
+
+impl PartialOrd for BitVec {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
 // === ビット演算 ===
 
-impl Add for BitVec {
+impl Add for &BitVec {
     type Output = BitVec;
-    fn add(self, rhs: BitVec) -> BitVec {
+    /// 2^nを法とする加算
+    fn add(self, rhs: &BitVec) -> BitVec {
         assert_eq!(self.n, rhs.n);
-        BitVec::from_usize(self.data.wrapping_add(rhs.data), self.n)
+        let mut carry = false;
+        let data = self
+            .data
+            .iter()
+            .zip(&rhs.data)
+            .map(|(&a, &b)| {
+                let (s1, c1) = a.overflowing_add(b);
+                let (s2, c2) = s1.overflowing_add(u64::from(carry));
+                carry = c1 || c2;
+                s2
+            })
+            .collect();
+        let mut bv = BitVec { data, n: self.n };
+        bv.normalize();
+        bv
     }
 }
 
-impl BitXor for BitVec {
+macro_rules! impl_bitop {
+    ($tr:ident, $f:ident, $op:tt) => {
+        impl $tr for &BitVec {
+            type Output = BitVec;
+            fn $f(self, rhs: &BitVec) -> BitVec {
+                assert_eq!(self.n, rhs.n);
+                let data = self.data.iter().zip(&rhs.data).map(|(a, b)| a $op b).collect();
+                BitVec { data, n: self.n }
+            }
+        }
+    };
+}
+impl_bitop!(BitXor, bitxor, ^);
+impl_bitop!(BitAnd, bitand, &);
+impl_bitop!(BitOr, bitor, |);
+
+macro_rules! impl_owned_op {
+    ($($tr:ident, $f:ident);*) => {$(
+        impl $tr for BitVec {
+            type Output = BitVec;
+            fn $f(self, rhs: BitVec) -> BitVec {
+                (&self).$f(&rhs)
+            }
+        }
+    )*};
+}
+impl_owned_op!(Add, add; BitXor, bitxor; BitAnd, bitand; BitOr, bitor);
+
+impl Shl<usize> for &BitVec {
     type Output = BitVec;
-    fn bitxor(self, rhs: BitVec) -> BitVec {
-        assert_eq!(self.n, rhs.n);
-        BitVec::from_usize(self.data ^ rhs.data, self.n)
+    /// 整数としての左シフト（上位にはみ出したビットは捨てる）
+    fn shl(self, k: usize) -> BitVec {
+        let (ws, bs) = (k / 64, k % 64);
+        let len = self.data.len();
+        let word = |i: usize| if i < len { self.data[i] } else { 0 };
+        let data = (0..len)
+            .map(|i| {
+                if i < ws {
+                    0
+                } else if bs == 0 {
+                    word(i - ws)
+                } else {
+                    let lo = if i > ws {
+                        word(i - ws - 1) >> (64 - bs)
+                    } else {
+                        0
+                    };
+                    (word(i - ws) << bs) | lo
+                }
+            })
+            .collect();
+        let mut bv = BitVec { data, n: self.n };
+        bv.normalize();
+        bv
     }
 }
 
-impl BitAnd for BitVec {
+impl Shr<usize> for &BitVec {
     type Output = BitVec;
-    fn bitand(self, rhs: BitVec) -> BitVec {
-        assert_eq!(self.n, rhs.n);
-        BitVec::from_usize(self.data & rhs.data, self.n)
+    /// 整数としての右シフト
+    fn shr(self, k: usize) -> BitVec {
+        let (ws, bs) = (k / 64, k % 64);
+        let len = self.data.len();
+        let word = |i: usize| if i < len { self.data[i] } else { 0 };
+        let data = (0..len)
+            .map(|i| {
+                let Some(j) = i.checked_add(ws) else { return 0 };
+                if bs == 0 {
+                    word(j)
+                } else {
+                    (word(j) >> bs) | (word(j.saturating_add(1)) << (64 - bs))
+                }
+            })
+            .collect();
+        BitVec { data, n: self.n }
     }
 }
 
-impl BitOr for BitVec {
+impl Shl<usize> for BitVec {
     type Output = BitVec;
-    fn bitor(self, rhs: BitVec) -> BitVec {
-        assert_eq!(self.n, rhs.n);
-        BitVec::from_usize(self.data | rhs.data, self.n)
+    fn shl(self, k: usize) -> BitVec {
+        &self << k
+    }
+}
+
+impl Shr<usize> for BitVec {
+    type Output = BitVec;
+    fn shr(self, k: usize) -> BitVec {
+        &self >> k
     }
 }
 
 // === イテレータ ===
 
-pub struct BitVecIter {
-    data: usize,
-    n: usize,
+/// `BitVec::iter`が返す、ビット列を借用するイテレータ
+pub struct BitVecIter<'a> {
+    bv: &'a BitVec,
     pos: usize,
 }
 
-impl Iterator for BitVecIter {
+impl Iterator for BitVecIter<'_> {
     type Item = bool;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.pos >= self.n {
+        if self.pos >= self.bv.n {
             None
         } else {
-            let shift = self.n - 1 - self.pos;
-            let bit = (self.data >> shift) & 1 == 1;
+            let bit = self.bv.get(self.pos);
             self.pos += 1;
             Some(bit)
         }
     }
 }
 
-impl IntoIterator for BitVec {
+impl<'a> IntoIterator for &'a BitVec {
     type Item = bool;
-    type IntoIter = BitVecIter;
+    type IntoIter = BitVecIter<'a>;
 
     fn into_iter(self) -> Self::IntoIter {
         self.iter()
     }
 }
 
+/// `BitVec`を消費するイテレータ
+pub struct BitVecIntoIter {
+    bv: BitVec,
+    pos: usize,
+}
+
+impl Iterator for BitVecIntoIter {
+    type Item = bool;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.pos >= self.bv.n {
+            None
+        } else {
+            let bit = self.bv.get(self.pos);
+            self.pos += 1;
+            Some(bit)
+        }
+    }
+}
+
+impl IntoIterator for BitVec {
+    type Item = bool;
+    type IntoIter = BitVecIntoIter;
+
+    fn into_iter(self) -> Self::IntoIter {
+        BitVecIntoIter { bv: self, pos: 0 }
+    }
+}
+
 // === 全探索列挙 BitVecRange ===
 
 pub struct BitVecRange {
@@ -185,6 +373,7 @@ pub struct BitVecRange {
 
 impl BitVecRange {
     pub fn new(n: usize) -> Self {
+        assert!(n < usize::BITS as usize, "too many bits to enumerate");
         Self {
             n,
             curr: 0,
@@ -213,6 +402,114 @@ impl From<BitVec> for usize {
     }
 }
 
+// === 部分集合・組合せの列挙 ===
+
+impl BitVec {
+    /// このビット列の部分集合（1が立っている位置の部分集合）を、整数としての値の昇順にすべて列挙する
+    ///
+    /// 空集合と自分自身も含みます。bit DPの `sub = (sub - 1) & mask` のループの代わりに使います。
+    ///
+    /// # 計算量
+    /// 2^(1の個数) 個を列挙し、1個あたりO(n)
+    ///
+    /// # 使用例
+    /// ```
+    /// # use rust_macro::BitVec;
+    /// let mask = BitVec::from_usize(0b1010, 4);
+    /// let subs: Vec<usize> = mask.subsets().map(|s| s.to_usize()).collect();
+    /// assert_eq!(subs, vec![0b0000, 0b0010, 0b1000, 0b1010]);
+    /// ```
+    pub fn subsets(&self) -> BitVecSubsets {
+        let ones: Vec<usize> = (0..self.n)
+            .filter(|&p| (self.data[p / 64] >> (p % 64)) & 1 == 1)
+            .collect();
+        assert!(
+            ones.len() < usize::BITS as usize,
+            "too many bits to enumerate"
+        );
+        BitVecSubsets {
+            n: self.n,
+            curr: 0,
+            end: 1 << ones.len(),
+            ones,
+        }
+    }
+
+    /// 長さnで、ちょうどk個のビットが立っているビット列を、整数としての値の昇順にすべて列挙する
+    ///
+    /// # 計算量
+    /// C(n, k) 個を列挙し、1個あたりO(n)
+    ///
+    /// # 使用例
+    /// ```
+    /// # use rust_macro::BitVec;
+    /// let masks: Vec<usize> = BitVec::combinations(4, 2).map(|b| b.to_usize()).collect();
+    /// assert_eq!(masks, vec![0b0011, 0b0101, 0b0110, 0b1001, 0b1010, 0b1100]);
+    /// ```
+    pub fn combinations(n: usize, k: usize) -> BitVecCombinations {
+        BitVecCombinations {
+            n,
+            pos: (k <= n).then(|| (0..k).collect()),
+        }
+    }
+}
+
+pub struct BitVecSubsets {
+    n: usize,
+    /// 1が立っている位置（下位ビットから数えた位置）
+    ones: Vec<usize>,
+    curr: usize,
+    end: usize,
+}
+
+impl Iterator for BitVecSubsets {
+    type Item = BitVec;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.curr >= self.end {
+            return None;
+        }
+        let mut bv = BitVec::new(self.n);
+        for (j, &p) in self.ones.iter().enumerate() {
+            if (self.curr >> j) & 1 == 1 {
+                bv.data[p / 64] |= 1 << (p % 64);
+            }
+        }
+        self.curr += 1;
+        Some(bv)
+    }
+}
+
+pub struct BitVecCombinations {
+    n: usize,
+    /// 次に返す組合せの、立っているビットの位置（下位ビットから数えた位置の昇順）
+    pos: Option<Vec<usize>>,
+}
+
+impl Iterator for BitVecCombinations {
+    type Item = BitVec;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let pos = self.pos.as_mut()?;
+        let mut bv = BitVec::new(self.n);
+        for &p in pos.iter() {
+            bv.data[p / 64] |= 1 << (p % 64);
+        }
+        // 上に空きのある最も下の位置を1つ上げ、それより下を詰め直す
+        let k = pos.len();
+        match (0..k).find(|&i| pos[i] + 1 < pos.get(i + 1).copied().unwrap_or(self.n)) {
+            Some(i) => {
+                pos[i] += 1;
+                for (j, p) in pos[..i].iter_mut().enumerate() {
+                    *p = j;
+                }
+            }
+            None => self.pos = None,
+        }
+        Some(bv)
+    }
+}
+
 // === Trait による .all() API ===
 
 pub trait BitVecAll {
@@ -228,6 +525,7 @@ impl BitVecAll for BitVec {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::XorShift64;
 
     #[test]
     fn test_new() {
@@ -255,10 +553,10 @@ mod tests {
     #[test]
     fn test_get() {
         let bv = BitVec::from_usize(0b1010, 4);
-        assert_eq!(bv.get(0), true); // MSB
-        assert_eq!(bv.get(1), false);
-        assert_eq!(bv.get(2), true);
-        assert_eq!(bv.get(3), false); // LSB
+        assert!(bv.get(0)); // MSB
+        assert!(!bv.get(1));
+        assert!(bv.get(2));
+        assert!(!bv.get(3)); // LSB
     }
 
     #[test]
@@ -334,10 +632,10 @@ mod tests {
 
     #[test]
     fn test_bitvec_range() {
-        let mut range = BitVecRange::new(2);
+        let range = BitVecRange::new(2);
         let mut results = Vec::new();
 
-        while let Some(bv) = range.next() {
+        for bv in range {
             results.push(bv.to_usize());
         }
 
@@ -362,9 +660,98 @@ mod tests {
         assert_eq!(bv.len(), 0);
 
         let bv = BitVec::from_usize(0, 1);
-        assert_eq!(bv.get(0), false);
+        assert!(!bv.get(0));
 
         let bv = BitVec::from_usize(1, 1);
-        assert_eq!(bv.get(0), true);
+        assert!(bv.get(0));
+    }
+
+    #[test]
+    fn test_subsets_and_combinations_against_naive() {
+        let mut rng = XorShift64::new(4276);
+        for _ in 0..50 {
+            let n = rng.gen_range(0..11) as usize;
+            let mask = rng.gen_range(0..1 << n) as usize;
+            let subs: Vec<usize> = BitVec::from_usize(mask, n)
+                .subsets()
+                .map(|b| b.to_usize())
+                .collect();
+            let expected: Vec<usize> = (0..1 << n).filter(|s| s & !mask == 0).collect();
+            assert_eq!(subs, expected);
+            for k in 0..=n + 1 {
+                let combs: Vec<usize> = BitVec::combinations(n, k)
+                    .inspect(|b| assert_eq!(b.len(), n))
+                    .map(|b| b.to_usize())
+                    .collect();
+                let expected: Vec<usize> = (0usize..1 << n)
+                    .filter(|s| s.count_ones() as usize == k)
+                    .collect();
+                assert_eq!(combs, expected);
+            }
+        }
+        // 64ビットを超える長さでも列挙できる
+        let wide: Vec<BitVec> = BitVec::combinations(100, 2).collect();
+        assert_eq!(wide.len(), 100 * 99 / 2);
+        assert!(wide.windows(2).all(|w| w[0] < w[1]));
+        assert_eq!(BitVec::unit(0, 100).subsets().count(), 2);
+    }
+
+    #[test]
+    fn test_64_bits_does_not_overflow() {
+        let bv = BitVec::mask(64);
+        assert_eq!(bv.to_usize(), usize::MAX);
+        assert_eq!(BitVec::from_usize(usize::MAX, 64).count_ones(), 64);
+        assert!(BitVec::unit(0, 64).get(0));
+        let wrapped = BitVec::mask(64) + BitVec::from_usize(1, 64);
+        assert_eq!(wrapped.to_usize(), 0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_to_usize_too_large() {
+        BitVec::unit(0, 65).to_usize();
+    }
+
+    /// 上位ビットから並べたboolの列で素朴に計算する
+    fn naive_value(bits: &[bool]) -> u128 {
+        bits.iter().fold(0, |acc, &b| (acc << 1) | u128::from(b))
+    }
+
+    #[test]
+    fn test_wide_bitvec_against_naive() {
+        let mut rng = XorShift64::new(4275);
+        for _ in 0..200 {
+            let n = rng.gen_range(1..128) as usize;
+            let gen = |rng: &mut XorShift64| {
+                let bits: Vec<bool> = (0..n).map(|_| rng.gen_range(0..2) == 1).collect();
+                let mut bv = BitVec::new(n);
+                for (i, &b) in bits.iter().enumerate() {
+                    bv.set_mut(i, u8::from(b));
+                }
+                (bits, bv)
+            };
+            let (a_bits, a) = gen(&mut rng);
+            let (b_bits, b) = gen(&mut rng);
+            let (x, y) = (naive_value(&a_bits), naive_value(&b_bits));
+            let full = if n == 128 { u128::MAX } else { (1 << n) - 1 };
+            let value = |bv: &BitVec| naive_value(&bv.iter().collect::<Vec<_>>());
+            assert_eq!(a.iter().collect::<Vec<_>>(), a_bits);
+            assert_eq!((&a).into_iter().collect::<Vec<_>>(), a_bits);
+            assert_eq!(a.clone().into_iter().collect::<Vec<_>>(), a_bits);
+            assert_eq!(value(&(&a + &b)), x.wrapping_add(y) & full);
+            assert_eq!(value(&(&a ^ &b)), x ^ y);
+            assert_eq!(value(&(&a & &b)), x & y);
+            assert_eq!(value(&(&a | &b)), x | y);
+            assert_eq!(a.count_ones(), x.count_ones() as usize);
+            assert_eq!(a.cmp(&b), x.cmp(&y));
+            let k = rng.gen_range(0..140) as usize;
+            assert_eq!(
+                value(&(&a << k)),
+                x.checked_shl(k as u32).unwrap_or(0) & full
+            );
+            assert_eq!(value(&(&a >> k)), x.checked_shr(k as u32).unwrap_or(0));
+            let s: String = a_bits.iter().map(|&b| if b { '1' } else { '0' }).collect();
+            assert_eq!(a.to_string(), s);
+        }
     }
 }
@@ -0,0 +1,200 @@
+use std::collections::BTreeMap;
+
+/// 区間代入で値を管理する配列（Chtholly Tree / ODT）
+///
+/// 区間[lo, hi)の各整数に値を持つ配列を、同じ値が続く区間ごとにまとめて保持します。
+/// 区間代入が多い（「区間を塗ってから集計する」）問題で、区間の個数が小さく保たれるため
+/// 高速に動作します。
+///
+/// # 計算量
+/// 操作対象の区間に含まれる塊の個数をkとして
+/// - 区間代入: ならしO(log n)
+/// - 区間加算・区間の集計: O(k + log n)
+///
+/// # 使用例
+/// ```
+/// # use rust_macro::ChthollyTree;
+/// let mut t = ChthollyTree::new(0, 10, 0);
+/// t.assign(2, 7, 5);
+/// t.add(5, 10, 1);
+/// assert_eq!(t.get(6), 6);
+/// assert_eq!(t.sum(0, 10), 3 * 5 + 2 * 6 + 3 * 1);
+/// assert_eq!(t.max(0, 10), 6);
+/// assert_eq!(t.segments(4, 8).collect::<Vec<_>>(), vec![(4, 5, 5), (5, 7, 6), (7, 8, 1)]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct ChthollyTree {
+    /// 塊の左端 -> 値（塊の右端は次の塊の左端）
+    map: BTreeMap<i64, i64>,
+    lo: i64,
+    hi: i64,
+}
+
+impl ChthollyTree {
+    /// 区間[lo, hi)の各点の値がinitの配列を作成
+    pub fn new(lo: i64, hi: i64, init: i64) -> Self {
+        assert!(lo < hi);
+        ChthollyTree {
+            map: BTreeMap::from([(lo, init)]),
+            lo,
+            hi,
+        }
+    }
+
+    /// 塊の個数
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    /// 常に1つ以上の塊を持つため`false`
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    /// xの値
+    pub fn get(&self, x: i64) -> i64 {
+        assert!(self.lo <= x && x < self.hi);
+        *self.map.range(..=x).next_back().unwrap().1
+    }
+
+    /// xで塊を分割し、xから始まる塊を作る
+    fn split(&mut self, x: i64) {
+        if x < self.hi && !self.map.contains_key(&x) {
+            let v = self.get(x);
+            self.map.insert(x, v);
+        }
+    }
+
+    fn check_range(&self, l: i64, r: i64) {
+        assert!(
+            self.lo <= l && l <= r && r <= self.hi,
+            "range out of bounds"
+        );
+    }
+
+    /// 区間[l, r)の値をすべてvにする
+    pub fn assign(&mut self, l: i64, r: i64, v: i64) {
+        self.check_range(l, r);
+        if l == r {
+            return;
+        }
+        self.split(l);
+        self.split(r);
+        let keys: Vec<i64> = self.map.range(l..r).map(|(&k, _)| k).collect();
+        for k in keys {
+            self.map.remove(&k);
+        }
+        self.map.insert(l, v);
+    }
+
+    /// 区間[l, r)の値にそれぞれvを加える
+    pub fn add(&mut self, l: i64, r: i64, v: i64) {
+        self.check_range(l, r);
+        if l == r {
+            return;
+        }
+        self.split(l);
+        self.split(r);
+        for (_, x) in self.map.range_mut(l..r) {
+            *x += v;
+        }
+    }
+
+    /// 区間[l, r)と交わる塊を(左端, 右端, 値)として列挙する（端は[l, r)に切り詰める）
+    pub fn segments(&self, l: i64, r: i64) -> impl Iterator<Item = (i64, i64, i64)> + '_ {
+        self.check_range(l, r);
+        let start = if l < r {
+            *self.map.range(..=l).next_back().unwrap().0
+        } else {
+            r
+        };
+        let mut iter = self.map.range(start..r).peekable();
+        let hi = self.hi;
+        std::iter::from_fn(move || {
+            let (&s, &v) = iter.next()?;
+            let e = iter.peek().map_or(hi, |(&e, _)| e);
+            Some((s.max(l), e.min(r), v))
+        })
+    }
+
+    /// 区間[l, r)の値の総和
+    pub fn sum(&self, l: i64, r: i64) -> i64 {
+        self.segments(l, r).map(|(s, e, v)| (e - s) * v).sum()
+    }
+
+    /// 区間[l, r)の値の最小値
+    ///
+    /// # パニック
+    /// 区間が空の場合panicします
+    pub fn min(&self, l: i64, r: i64) -> i64 {
+        self.segments(l, r)
+            .map(|(_, _, v)| v)
+            .min()
+            .expect("empty range")
+    }
+
+    /// 区間[l, r)の値の最大値
+    ///
+    /// # パニック
+    /// 区間が空の場合panicします
+    pub fn max(&self, l: i64, r: i64) -> i64 {
+        self.segments(l, r)
+            .map(|(_, _, v)| v)
+            .max()
+            .expect("empty range")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::XorShift64;
+
+    #[test]
+    fn test_chtholly_tree_against_naive() {
+        let mut rng = XorShift64::new(17);
+        let (lo, hi) = (-5i64, 25i64);
+        let mut t = ChthollyTree::new(lo, hi, 3);
+        let mut naive = vec![3i64; (hi - lo) as usize];
+        for _ in 0..1000 {
+            let l = rng.gen_range_i64(lo..hi + 1);
+            let r = rng.gen_range_i64(l..hi + 1);
+            let (nl, nr) = ((l - lo) as usize, (r - lo) as usize);
+            let v = rng.gen_range_i64(-10..10);
+            match rng.gen_range(0..3) {
+                0 => {
+                    t.assign(l, r, v);
+                    naive[nl..nr].iter_mut().for_each(|x| *x = v);
+                }
+                1 => {
+                    t.add(l, r, v);
+                    naive[nl..nr].iter_mut().for_each(|x| *x += v);
+                }
+                _ => {
+                    assert_eq!(t.sum(l, r), naive[nl..nr].iter().sum::<i64>());
+                    if l < r {
+                        assert_eq!(t.min(l, r), *naive[nl..nr].iter().min().unwrap());
+                        assert_eq!(t.max(l, r), *naive[nl..nr].iter().max().unwrap());
+                    }
+                }
+            }
+        }
+        for x in lo..hi {
+            assert_eq!(t.get(x), naive[(x - lo) as usize]);
+        }
+    }
+
+    #[test]
+    fn test_chtholly_tree_assign_merges() {
+        let mut t = ChthollyTree::new(0, 100, 0);
+        for i in 0..50 {
+            t.assign(i, i + 1, i);
+        }
+        assert_eq!(t.len(), 51);
+        t.assign(0, 100, 7);
+        assert_eq!(t.len(), 1);
+        assert_eq!(t.segments(0, 100).collect::<Vec<_>>(), vec![(0, 100, 7)]);
+        assert_eq!(t.segments(3, 3).count(), 0);
+        assert!(!t.is_empty());
+    }
+}
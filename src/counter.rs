@@ -0,0 +1,211 @@
+use rustc_hash::FxHasher;
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::hash::{BuildHasherDefault, Hash};
+
+/// 要素ごとの個数を数えるマップ（多重集合）
+///
+/// 存在しないキーの個数は0として扱い、個数が0になったキーは自動で取り除きます。
+/// `*map.entry(k).or_insert(0) += 1`の代わりに使えます。
+///
+/// # 計算量
+/// - 加算・参照: 期待O(1)
+/// - 個数の多い順の列挙: O(k log k)（kはキーの種類数）
+///
+/// # 使用例
+/// ```
+/// # use rust_macro::Counter;
+/// let c: Counter<char> = "abracadabra".chars().collect();
+/// assert_eq!(c.get(&'a'), 5);
+/// assert_eq!(c.get(&'z'), 0);
+/// assert_eq!(c.most_common(2), vec![('a', 5), ('b', 2)]);
+///
+/// let d: Counter<char> = "banana".chars().collect();
+/// assert_eq!(c.intersection(&d).get(&'a'), 3);
+/// assert_eq!(c.union(&d).get(&'n'), 2);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Counter<K: Hash + Eq> {
+    map: HashMap<K, i64, BuildHasherDefault<FxHasher>>,
+}
+
+impl<K: Hash + Eq> Default for Counter<K> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Hash + Eq> Counter<K> {
+    /// 空のカウンタを作成
+    pub fn new() -> Self {
+        Counter {
+            map: HashMap::default(),
+        }
+    }
+
+    /// 個数が0でないキーの種類数
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    /// 個数が0でないキーがないかどうか
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    /// キーkの個数にdeltaを加え、加えた後の個数を返す
+    pub fn add(&mut self, k: K, delta: i64) -> i64 {
+        match self.map.entry(k) {
+            Entry::Occupied(mut e) => {
+                *e.get_mut() += delta;
+                let res = *e.get();
+                if res == 0 {
+                    e.remove();
+                }
+                res
+            }
+            Entry::Vacant(e) => {
+                if delta != 0 {
+                    e.insert(delta);
+                }
+                delta
+            }
+        }
+    }
+
+    /// キーkの個数を1増やす
+    pub fn inc(&mut self, k: K) -> i64 {
+        self.add(k, 1)
+    }
+
+    /// キーkの個数を1減らす
+    pub fn dec(&mut self, k: K) -> i64 {
+        self.add(k, -1)
+    }
+
+    /// キーkの個数（存在しなければ0）
+    pub fn get(&self, k: &K) -> i64 {
+        self.map.get(k).copied().unwrap_or(0)
+    }
+
+    /// 個数の総和
+    pub fn total(&self) -> i64 {
+        self.map.values().sum()
+    }
+
+    /// (キー, 個数)を順不同で列挙する
+    pub fn iter(&self) -> impl Iterator<Item = (&K, i64)> + '_ {
+        self.map.iter().map(|(k, &c)| (k, c))
+    }
+}
+
+impl<K: Hash + Eq + Clone> Counter<K> {
+    /// 各キーの個数の最大値をとった多重集合
+    ///
+    /// 片方にしかないキーはもう片方の個数を0として比べるため、負の個数は0になります。
+    pub fn union(&self, other: &Self) -> Self {
+        self.combine(other, i64::max)
+    }
+
+    /// 各キーの個数の最小値をとった多重集合
+    ///
+    /// 片方にしかないキーはもう片方の個数を0として比べるため、負の個数はそのまま残ります。
+    pub fn intersection(&self, other: &Self) -> Self {
+        self.combine(other, i64::min)
+    }
+
+    /// 両方のキーについて、個数にfを適用した多重集合
+    fn combine(&self, other: &Self, f: impl Fn(i64, i64) -> i64) -> Self {
+        let mut res = Self::new();
+        for k in self.map.keys().chain(other.map.keys()) {
+            if !res.map.contains_key(k) {
+                res.add(k.clone(), f(self.get(k), other.get(k)));
+            }
+        }
+        res
+    }
+}
+
+impl<K: Hash + Eq + Ord + Clone> Counter<K> {
+    /// 個数の多い順（同数ならキーの昇順）に最大n個の(キー, 個数)を返す
+    pub fn most_common(&self, n: usize) -> Vec<(K, i64)> {
+        let mut v: Vec<(K, i64)> = self.map.iter().map(|(k, &c)| (k.clone(), c)).collect();
+        v.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        v.truncate(n);
+        v
+    }
+}
+
+impl<K: Hash + Eq> FromIterator<K> for Counter<K> {
+    fn from_iter<I: IntoIterator<Item = K>>(iter: I) -> Self {
+        let mut c = Self::new();
+        for k in iter {
+            c.add(k, 1);
+        }
+        c
+    }
+}
+
+impl<K: Hash + Eq> Extend<K> for Counter<K> {
+    fn extend<I: IntoIterator<Item = K>>(&mut self, iter: I) {
+        for k in iter {
+            self.add(k, 1);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_counter_add_removes_zero() {
+        let mut c = Counter::new();
+        assert_eq!(c.inc(3), 1);
+        assert_eq!(c.add(3, 4), 5);
+        assert_eq!(c.add(7, -2), -2);
+        assert_eq!(c.len(), 2);
+        assert_eq!(c.add(3, -5), 0);
+        assert_eq!(c.dec(7), -3);
+        assert_eq!(c.len(), 1);
+        assert_eq!(c.total(), -3);
+        c.add(7, 3);
+        assert!(c.is_empty());
+        assert_eq!(c, Counter::new());
+    }
+
+    #[test]
+    fn test_counter_set_ops() {
+        let mut a: Counter<u32> = [1, 1, 2, 3, 3, 3].into_iter().collect();
+        let b: Counter<u32> = [1, 3, 3, 4, 4].into_iter().collect();
+        let u = a.union(&b);
+        assert_eq!([1, 2, 3, 4].map(|k| u.get(&k)), [2, 1, 3, 2]);
+        let i = a.intersection(&b);
+        assert_eq!([1, 2, 3, 4].map(|k| i.get(&k)), [1, 0, 2, 0]);
+        assert_eq!(i.len(), 2);
+        a.extend([2, 2]);
+        assert_eq!(a.most_common(10), vec![(2, 3), (3, 3), (1, 2)]);
+        assert_eq!(a.most_common(0), vec![]);
+    }
+
+    #[test]
+    fn test_counter_set_ops_negative() {
+        let mut a = Counter::new();
+        a.add('x', -2);
+        a.add('y', 3);
+        a.add('z', -1);
+        let mut b = Counter::new();
+        b.add('y', -4);
+        b.add('z', -5);
+        b.add('w', -3);
+
+        let u = a.union(&b);
+        assert_eq!(['w', 'x', 'y', 'z'].map(|k| u.get(&k)), [0, 0, 3, -1]);
+        assert_eq!(u.len(), 2);
+        assert_eq!(u, b.union(&a));
+
+        let i = a.intersection(&b);
+        assert_eq!(['w', 'x', 'y', 'z'].map(|k| i.get(&k)), [-3, -2, -4, -5]);
+        assert_eq!(i, b.intersection(&a));
+    }
+}
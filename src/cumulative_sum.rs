@@ -46,12 +46,34 @@ where
     /// # 戻り値
     /// 新しいCumulativeSumインスタンス
     pub fn new(arr: &[T]) -> Self {
+        Self::new_with(arr)
+    }
+
+    /// 要素の型とは別の型で和を取りながら累積和を構築
+    ///
+    /// 1e18に近い値を多数足し合わせるとi64ではオーバーフローしてしまうため、
+    /// `CumulativeSum::<i128>::new_with(&arr)` のように累積に使う型を広げて使います。
+    ///
+    /// # 引数
+    /// * `arr` - 元の配列（各要素は`T`に変換されます）
+    ///
+    /// # 戻り値
+    /// 新しいCumulativeSumインスタンス
+    ///
+    /// # 使用例
+    /// ```
+    /// # use rust_macro::CumulativeSum;
+    /// let arr = vec![i64::MAX, i64::MAX, 1];
+    /// let cum_sum = CumulativeSum::<i128>::new_with(&arr);
+    /// assert_eq!(cum_sum.sum(0, 2), 2 * i64::MAX as i128);
+    /// ```
+    pub fn new_with<S: Copy + Into<T>>(arr: &[S]) -> Self {
         let mut data = Vec::with_capacity(arr.len() + 1);
         data.push(T::default());
 
         for &val in arr {
             let last = *data.last().unwrap();
-            data.push(last + val);
+            data.push(last + val.into());
         }
 
         Self { data }
@@ -109,6 +131,17 @@ where
     /// # 戻り値
     /// 新しいCumulativeSum2Dインスタンス
     pub fn new(arr: &[Vec<T>]) -> Self {
+        Self::new_with(arr)
+    }
+
+    /// 要素の型とは別の型で和を取りながら2次元累積和を構築
+    ///
+    /// # 引数
+    /// * `arr` - 元の2次元配列（各要素は`T`に変換されます）
+    ///
+    /// # 戻り値
+    /// 新しいCumulativeSum2Dインスタンス
+    pub fn new_with<S: Copy + Into<T>>(arr: &[Vec<S>]) -> Self {
         let h = arr.len();
         let w = if h > 0 { arr[0].len() } else { 0 };
 
@@ -116,7 +149,8 @@ where
 
         for i in 0..h {
             for j in 0..w {
-                data[i + 1][j + 1] = data[i][j + 1] + data[i + 1][j] - data[i][j] + arr[i][j];
+                data[i + 1][j + 1] =
+                    data[i][j + 1] + data[i + 1][j] - data[i][j] + arr[i][j].into();
             }
         }
 
@@ -139,6 +173,116 @@ where
     }
 }
 
+/// 45度回転させた2次元累積和ライブラリ
+///
+/// セル(i, j)を(u, v) = (i + j, i + w - 1 - j)へ移す45度回転を行ってから
+/// 2次元累積和を取ります。回転後の座標で軸に平行な長方形は、元のグリッドでは
+/// 斜めの帯や菱形（マンハッタン距離の球）に対応するため、これらの和をO(1)で求められます。
+///
+/// # 計算量
+/// - 構築: O((h+w)^2)
+/// - 範囲クエリ: O(1)
+///
+/// # 使用例
+/// ```
+/// # use rust_macro::DiagonalCumulativeSum2D;
+/// let arr = vec![vec![1, 2, 3], vec![4, 5, 6], vec![7, 8, 9]];
+/// let diag = DiagonalCumulativeSum2D::new(&arr);
+/// assert_eq!(diag.sum_manhattan(1, 1, 1), 25); // 中心(1,1)から距離1以内
+/// assert_eq!(diag.sum_anti_diagonal_band(2, 3), 15); // i + j = 2 のセル
+/// ```
+#[derive(Debug, Clone)]
+pub struct DiagonalCumulativeSum2D<T>
+where
+    T: Add<Output = T> + Sub<Output = T> + Copy + Default,
+{
+    inner: CumulativeSum2D<T>,
+    h: usize,
+    w: usize,
+}
+
+impl<T> DiagonalCumulativeSum2D<T>
+where
+    T: Add<Output = T> + Sub<Output = T> + Copy + Default,
+{
+    /// 2次元配列から45度回転した累積和を構築
+    ///
+    /// # 引数
+    /// * `arr` - 元の2次元配列
+    ///
+    /// # 戻り値
+    /// 新しいDiagonalCumulativeSum2Dインスタンス
+    pub fn new(arr: &[Vec<T>]) -> Self {
+        let h = arr.len();
+        let w = if h > 0 { arr[0].len() } else { 0 };
+        let size = (h + w).saturating_sub(1);
+
+        let mut rotated = vec![vec![T::default(); size]; size];
+        for i in 0..h {
+            for j in 0..w {
+                rotated[i + j][i + w - 1 - j] = arr[i][j];
+            }
+        }
+
+        Self {
+            inner: CumulativeSum2D::new(&rotated),
+            h,
+            w,
+        }
+    }
+
+    /// 回転後の1辺の長さ（h + w - 1）
+    pub fn size(&self) -> usize {
+        (self.h + self.w).saturating_sub(1)
+    }
+
+    /// セル(i, j)の回転後の座標(u, v)を返す
+    pub fn rotate(&self, i: usize, j: usize) -> (usize, usize) {
+        assert!(i < self.h && j < self.w);
+        (i + j, i + self.w - 1 - j)
+    }
+
+    /// 回転後の座標で範囲(u1, v1)から(u2, v2)の和を計算（u2, v2は含まない）
+    ///
+    /// # 引数
+    /// * `u1` - 開始位置（含む）、`i + j` の値
+    /// * `v1` - 開始位置（含む）、`i + w - 1 - j` の値
+    /// * `u2` - 終了位置（含まない）
+    /// * `v2` - 終了位置（含まない）
+    ///
+    /// # 戻り値
+    /// 範囲の和
+    pub fn sum(&self, u1: usize, v1: usize, u2: usize, v2: usize) -> T {
+        self.inner.sum(u1, v1, u2, v2)
+    }
+
+    /// セル(i, j)からマンハッタン距離d以内にあるセルの和を計算
+    ///
+    /// グリッドの外にはみ出す部分は無視されます。
+    pub fn sum_manhattan(&self, i: usize, j: usize, d: usize) -> T {
+        let (u, v) = self.rotate(i, j);
+        let size = self.size();
+        self.inner.sum(
+            u.saturating_sub(d),
+            v.saturating_sub(d),
+            (u + d + 1).min(size),
+            (v + d + 1).min(size),
+        )
+    }
+
+    /// `i + j` が[a, b)に含まれるセルの和を計算（右上から左下への斜めの帯）
+    pub fn sum_anti_diagonal_band(&self, a: usize, b: usize) -> T {
+        let size = self.size();
+        self.inner.sum(a.min(size), 0, b.min(size), size)
+    }
+
+    /// `i + w - 1 - j` が[a, b)に含まれるセルの和を計算（左上から右下への斜めの帯）
+    pub fn sum_diagonal_band(&self, a: usize, b: usize) -> T {
+        let size = self.size();
+        self.inner.sum(0, a.min(size), size, b.min(size))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -168,4 +312,54 @@ mod tests {
         assert_eq!(cum_sum.sum(0, 0, 2, 3), 21);
         assert_eq!(cum_sum.sum(1, 1, 2, 3), 11);
     }
+
+    #[test]
+    fn test_cumulative_sum_wide_accumulator() {
+        let big = 900_000_000_000_000_000i64;
+        let arr = vec![big; 200_000];
+        let cum_sum = CumulativeSum::<i128>::new_with(&arr);
+        assert_eq!(cum_sum.sum(0, 200_000), big as i128 * 200_000);
+        assert_eq!(cum_sum.sum(10, 12), big as i128 * 2);
+
+        let arr = vec![vec![big, big], vec![big, -big]];
+        let cum_sum = CumulativeSum2D::<i128>::new_with(&arr);
+        assert_eq!(cum_sum.sum(0, 0, 2, 2), big as i128 * 2);
+        assert_eq!(cum_sum.sum(0, 0, 2, 1), big as i128 * 2);
+    }
+
+    #[test]
+    fn test_diagonal_cumulative_sum_2d() {
+        let arr = vec![vec![1, 2, 3, 4], vec![5, 6, 7, 8], vec![9, 10, 11, 12]];
+        let diag = DiagonalCumulativeSum2D::new(&arr);
+        let (h, w): (usize, usize) = (3, 4);
+
+        for i in 0..h {
+            for j in 0..w {
+                for d in 0..6 {
+                    let mut expected = 0;
+                    for (x, row) in arr.iter().enumerate() {
+                        for (y, &v) in row.iter().enumerate() {
+                            if i.abs_diff(x) + j.abs_diff(y) <= d {
+                                expected += v;
+                            }
+                        }
+                    }
+                    assert_eq!(diag.sum_manhattan(i, j, d), expected);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_diagonal_bands() {
+        let arr = vec![vec![1, 2, 3], vec![4, 5, 6]];
+        let diag = DiagonalCumulativeSum2D::new(&arr);
+
+        assert_eq!(diag.sum_anti_diagonal_band(0, 1), 1);
+        assert_eq!(diag.sum_anti_diagonal_band(1, 3), 2 + 4 + 3 + 5);
+        assert_eq!(diag.sum_anti_diagonal_band(0, 10), 21);
+        // i + w - 1 - j == 2 は (0, 0) と (1, 1)
+        assert_eq!(diag.sum_diagonal_band(2, 3), 1 + 5);
+        assert_eq!(diag.rotate(1, 2), (3, 1));
+    }
 }
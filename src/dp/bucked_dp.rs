@@ -1,7 +1,6 @@
 use rustc_hash::{FxHashMap, FxHashSet};
 use std::collections::BTreeMap;
 use std::hash::Hash;
-use std::sync::Arc;
 
 pub trait DagDPRules {
     type State: Clone + Eq + Hash;
@@ -56,76 +55,77 @@ impl Engine {
     }
 }
 
-// ===== ここから所有Ctx版 =====
-#[derive(Clone, Eq, PartialEq, Hash)]
-struct S {
-    i: usize,
-    sick: bool,
-}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
 
-struct PoisonCtx {
-    xs: Arc<[i32]>,
-    ys: Arc<[i64]>,
-}
+    // ===== ここから所有Ctx版 =====
+    #[derive(Clone, Eq, PartialEq, Hash)]
+    struct S {
+        i: usize,
+        sick: bool,
+    }
 
-struct Poison;
+    struct PoisonCtx {
+        xs: Arc<[i32]>,
+        ys: Arc<[i64]>,
+    }
 
-impl DagDPRules for Poison {
-    type State = S;
-    type Value = i64;
-    type Ctx = PoisonCtx;
+    struct Poison;
 
-    fn rank(ctx: &Self::Ctx, s: &Self::State) -> usize {
-        ctx.xs.len() - s.i
-    }
+    impl DagDPRules for Poison {
+        type State = S;
+        type Value = i64;
+        type Ctx = PoisonCtx;
 
-    fn neighbors(ctx: &Self::Ctx, s: &Self::State) -> Vec<Self::State> {
-        if s.i == ctx.xs.len() {
-            return vec![];
+        fn rank(ctx: &Self::Ctx, s: &Self::State) -> usize {
+            ctx.xs.len() - s.i
         }
-        let x = ctx.xs[s.i];
-        let mut res = Vec::with_capacity(2);
-        // スキップ
-        res.push(S {
-            i: s.i + 1,
-            sick: s.sick,
-        });
-        // 食べる（死亡手は生成しない）
-        match (s.sick, x) {
-            (false, 0) | (true, 0) => res.push(S {
-                i: s.i + 1,
-                sick: false,
-            }),
-            (false, 1) => res.push(S {
+
+        fn neighbors(ctx: &Self::Ctx, s: &Self::State) -> Vec<Self::State> {
+            if s.i == ctx.xs.len() {
+                return vec![];
+            }
+            let x = ctx.xs[s.i];
+            let mut res = Vec::with_capacity(2);
+            // スキップ
+            res.push(S {
                 i: s.i + 1,
-                sick: true,
-            }),
-            (true, 1) => {}
-            _ => unreachable!(),
+                sick: s.sick,
+            });
+            // 食べる（死亡手は生成しない）
+            match (s.sick, x) {
+                (false, 0) | (true, 0) => res.push(S {
+                    i: s.i + 1,
+                    sick: false,
+                }),
+                (false, 1) => res.push(S {
+                    i: s.i + 1,
+                    sick: true,
+                }),
+                (true, 1) => {}
+                _ => unreachable!(),
+            }
+            res
         }
-        res
-    }
 
-    fn combine(ctx: &Self::Ctx, s: &Self::State, child_vals: &[Self::Value]) -> Self::Value {
-        if s.i == ctx.xs.len() {
-            return 0;
-        }
-        let x = ctx.xs[s.i];
-        let y = ctx.ys[s.i];
-
-        // neighbors の順： [Skip, (Eatがあれば)Eat]
-        let mut best = child_vals[0]; // Skip
-        let eat_ok = matches!((s.sick, x), (false, 0) | (true, 0) | (false, 1));
-        if eat_ok {
-            best = best.max(child_vals[1] + y);
+        fn combine(ctx: &Self::Ctx, s: &Self::State, child_vals: &[Self::Value]) -> Self::Value {
+            if s.i == ctx.xs.len() {
+                return 0;
+            }
+            let x = ctx.xs[s.i];
+            let y = ctx.ys[s.i];
+
+            // neighbors の順： [Skip, (Eatがあれば)Eat]
+            let mut best = child_vals[0]; // Skip
+            let eat_ok = matches!((s.sick, x), (false, 0) | (true, 0) | (false, 1));
+            if eat_ok {
+                best = best.max(child_vals[1] + y);
+            }
+            best
         }
-        best
     }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
 
     struct SimpleDP;
 
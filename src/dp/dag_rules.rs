@@ -0,0 +1,262 @@
+use rustc_hash::FxHashMap;
+use std::fmt::Debug;
+use std::hash::Hash;
+use std::marker::PhantomData;
+
+use super::pull_dp::{ChildRef, PullDPRules, PullDpEngine};
+use super::push_dp::{PushDPRules, PushDpEngine};
+
+/// 配るDPと貰うDPの両方で解けるDAG上のDPの定義
+///
+/// 辺は `rank` の小さい状態から大きい状態へ向かうものとし、状態sの値は
+/// `init(s)` と、各入辺 `p -> s` について `trans(p, s, val[p])` を `op` でまとめたものです。
+/// 値を持つのは `init` が `Some` の状態と、そこから辺をたどって到達できる状態です。
+///
+/// `AsPush<R>` は `PushDPRules` を、`AsPull<R>` は `PullDPRules` を実装するので、
+/// 同じ定義をどちらのエンジンでも解けます（答え合わせや速い方の選択に使えます）。
+pub trait DagRules {
+    type State: Clone + Eq + Hash + Debug;
+    type Value: Clone + Debug;
+    type Ctx;
+
+    /// 辺 `s -> t` について rank(s) < rank(t) を満たす整数ランク
+    fn rank(ctx: &Self::Ctx, s: &Self::State) -> usize;
+
+    /// sから出る辺の行き先（rank が大きい側）
+    fn succs(ctx: &Self::Ctx, s: &Self::State) -> Vec<Self::State>;
+
+    /// sに入る辺の出発点（rank が小さい側）。`succs`と同じ辺集合を逆向きに返すこと
+    fn preds(ctx: &Self::Ctx, s: &Self::State) -> Vec<Self::State>;
+
+    /// モノイドの単位元
+    fn identity(ctx: &Self::Ctx) -> Self::Value;
+
+    /// モノイドの結合
+    fn op(ctx: &Self::Ctx, a: &Self::Value, b: &Self::Value) -> Self::Value;
+
+    /// 初期値（ソースのみ Some）
+    fn init(ctx: &Self::Ctx, s: &Self::State) -> Option<Self::Value>;
+
+    /// 辺 `from -> to` で運ばれる値
+    fn trans(
+        ctx: &Self::Ctx,
+        from: &Self::State,
+        to: &Self::State,
+        v_from: &Self::Value,
+    ) -> Self::Value;
+}
+
+/// `DagRules` を配るDP（`PushDpEngine`）で解くためのアダプタ
+pub struct AsPush<R>(PhantomData<R>);
+
+impl<R: DagRules> PushDPRules for AsPush<R> {
+    type State = R::State;
+    type Value = R::Value;
+    type Ctx = R::Ctx;
+
+    fn rank(ctx: &Self::Ctx, s: &Self::State) -> usize {
+        R::rank(ctx, s)
+    }
+    fn succs(ctx: &Self::Ctx, s: &Self::State) -> Vec<Self::State> {
+        R::succs(ctx, s)
+    }
+    fn identity(ctx: &Self::Ctx) -> Self::Value {
+        R::identity(ctx)
+    }
+    fn op(ctx: &Self::Ctx, into: &Self::Value, add: &Self::Value) -> Self::Value {
+        R::op(ctx, into, add)
+    }
+    fn init(ctx: &Self::Ctx, s: &Self::State) -> Option<Self::Value> {
+        R::init(ctx, s)
+    }
+    fn trans(
+        ctx: &Self::Ctx,
+        from: &Self::State,
+        to: &Self::State,
+        v_from: &Self::Value,
+    ) -> Self::Value {
+        R::trans(ctx, from, to, v_from)
+    }
+}
+
+impl<R: DagRules> AsPush<R> {
+    /// 始点から配るDPを実行し、値を持つ状態とその値を返す
+    ///
+    /// `sources` には `init` が `Some` になる状態をすべて渡してください。
+    pub fn solve(
+        ctx: &R::Ctx,
+        sources: impl IntoIterator<Item = R::State>,
+    ) -> FxHashMap<R::State, R::Value> {
+        PushDpEngine::propagate::<Self>(ctx, sources)
+    }
+}
+
+/// `DagRules` を貰うDP（`PullDpEngine`）で解くためのアダプタ
+///
+/// 値を持たない状態を表すため、値の型は `Option<R::Value>` になります。
+/// `R::Value` は参照を含まない型に限ります。
+pub struct AsPull<R>(PhantomData<R>);
+
+impl<R: DagRules> PullDPRules for AsPull<R>
+where
+    R::Value: 'static,
+{
+    type State = R::State;
+    type Value = Option<R::Value>;
+    type Ctx = R::Ctx;
+
+    fn rank(ctx: &Self::Ctx, s: &Self::State) -> usize {
+        R::rank(ctx, s)
+    }
+    fn neighbors(ctx: &Self::Ctx, s: &Self::State) -> Vec<Self::State> {
+        R::preds(ctx, s)
+    }
+    fn combine<'a, I>(ctx: &Self::Ctx, s: &Self::State, childs: I) -> Self::Value
+    where
+        I: IntoIterator<Item = ChildRef<'a, Self::State, Self::Value>>,
+        Self::State: 'a,
+        Self::Value: 'a,
+    {
+        childs.into_iter().fold(R::init(ctx, s), |acc, c| {
+            let Some(v) = c.value else {
+                return acc;
+            };
+            let inc = R::trans(ctx, c.state, s, v);
+            let acc = acc.unwrap_or_else(|| R::identity(ctx));
+            Some(R::op(ctx, &acc, &inc))
+        })
+    }
+}
+
+impl<R: DagRules> AsPull<R>
+where
+    R::Value: 'static,
+{
+    /// 終点から貰うDPを実行し、値を持つ状態とその値を返す
+    pub fn solve(
+        ctx: &R::Ctx,
+        roots: impl IntoIterator<Item = R::State>,
+    ) -> FxHashMap<R::State, R::Value> {
+        PullDpEngine::solve::<Self>(ctx, roots)
+            .into_iter()
+            .filter_map(|(s, v)| v.map(|v| (s, v)))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::XorShift64;
+
+    struct Frog;
+    impl DagRules for Frog {
+        type State = usize;
+        type Value = i64;
+        type Ctx = Vec<i64>;
+
+        fn rank(_h: &Self::Ctx, s: &Self::State) -> usize {
+            *s
+        }
+        fn succs(h: &Self::Ctx, s: &Self::State) -> Vec<Self::State> {
+            (s + 1..h.len().min(s + 3)).collect()
+        }
+        fn preds(_h: &Self::Ctx, s: &Self::State) -> Vec<Self::State> {
+            (s.saturating_sub(2)..*s).collect()
+        }
+        fn identity(_h: &Self::Ctx) -> Self::Value {
+            i64::MAX
+        }
+        fn op(_h: &Self::Ctx, a: &Self::Value, b: &Self::Value) -> Self::Value {
+            *a.min(b)
+        }
+        fn init(_h: &Self::Ctx, s: &Self::State) -> Option<Self::Value> {
+            (*s == 0).then_some(0)
+        }
+        fn trans(
+            h: &Self::Ctx,
+            from: &Self::State,
+            to: &Self::State,
+            v_from: &Self::Value,
+        ) -> Self::Value {
+            v_from + (h[*from] - h[*to]).abs()
+        }
+    }
+
+    // source(https://atcoder.jp/contests/dp/tasks/dp_a)
+    #[test]
+    fn test_frog_on_both_engines() {
+        let h = vec![10, 30, 40, 20];
+        let push = AsPush::<Frog>::solve(&h, [0]);
+        let pull = AsPull::<Frog>::solve(&h, [3]);
+        assert_eq!(push[&3], 30);
+        assert_eq!(push, pull);
+    }
+
+    // ランダムなDAGで、いくつかの始点からのパス数を両方のエンジンで数える
+    struct PathCount;
+    struct Dag {
+        succs: Vec<Vec<usize>>,
+        preds: Vec<Vec<usize>>,
+        is_source: Vec<bool>,
+    }
+    impl DagRules for PathCount {
+        type State = usize;
+        type Value = u64;
+        type Ctx = Dag;
+
+        fn rank(_g: &Self::Ctx, s: &Self::State) -> usize {
+            *s
+        }
+        fn succs(g: &Self::Ctx, s: &Self::State) -> Vec<Self::State> {
+            g.succs[*s].clone()
+        }
+        fn preds(g: &Self::Ctx, s: &Self::State) -> Vec<Self::State> {
+            g.preds[*s].clone()
+        }
+        fn identity(_g: &Self::Ctx) -> Self::Value {
+            0
+        }
+        fn op(_g: &Self::Ctx, a: &Self::Value, b: &Self::Value) -> Self::Value {
+            (a + b) % 998_244_353
+        }
+        fn init(g: &Self::Ctx, s: &Self::State) -> Option<Self::Value> {
+            g.is_source[*s].then_some(1)
+        }
+        fn trans(
+            _g: &Self::Ctx,
+            _from: &Self::State,
+            _to: &Self::State,
+            v_from: &Self::Value,
+        ) -> Self::Value {
+            *v_from
+        }
+    }
+
+    #[test]
+    fn test_random_dag_push_equals_pull() {
+        let mut rng = XorShift64::new(4241);
+        for _ in 0..30 {
+            let n = rng.gen_range(1..40) as usize;
+            let mut g = Dag {
+                succs: vec![vec![]; n],
+                preds: vec![vec![]; n],
+                is_source: (0..n).map(|_| rng.gen_range(0..5) == 0).collect(),
+            };
+            for _ in 0..3 * n {
+                let (a, b) = (
+                    rng.gen_range(0..n as u64) as usize,
+                    rng.gen_range(0..n as u64) as usize,
+                );
+                if a < b {
+                    g.succs[a].push(b);
+                    g.preds[b].push(a);
+                }
+            }
+            let sources: Vec<usize> = (0..n).filter(|&s| g.is_source[s]).collect();
+            let push = AsPush::<PathCount>::solve(&g, sources);
+            let pull = AsPull::<PathCount>::solve(&g, 0..n);
+            assert_eq!(push, pull);
+        }
+    }
+}
@@ -89,6 +89,99 @@ impl DigitDP {
         let init_state = problem.init();
         dfs(0, true, &init_state, &digits, n, &mut memo, problem)
     }
+
+    /// 状態を`0..num_states`の添字に写せる場合の`solve`（メモを平坦な`Vec`で持つ）
+    ///
+    /// `HashMap`の代わりに `(桁位置, tight, 状態の添字)` で引く`Vec<Option<usize>>`をメモに使うため、
+    /// 状態数が小さい問題では`solve`より数倍高速です。結果は`solve`と同じです。
+    ///
+    /// # 引数
+    /// * `num_states` - 状態の添字の上限（`state_index`の値はこれ未満）
+    /// * `state_index` - 状態を添字に写す関数（異なる状態は異なる添字に写すこと）
+    ///
+    /// # 計算量
+    ///
+    /// - 時間計算量: O(N × S × 10)
+    /// - 空間計算量: O(N × `num_states`)
+    ///
+    /// # 使用例
+    /// ```
+    /// # use rust_macro::dp::digit_dp::DigitDPRules;
+    /// # use rust_macro::DigitDP;
+    /// // 1000以下で桁和が3の倍数である数（0を含む）
+    /// struct Mod3;
+    /// impl DigitDPRules for Mod3 {
+    ///     type State = u32;
+    ///     fn init(&self) -> u32 {
+    ///         0
+    ///     }
+    ///     fn transition(&self, _i: usize, _tight: bool, &s: &u32, lim: u32) -> Vec<(u32, u32)> {
+    ///         (0..=lim).map(|d| (d, (s + d) % 3)).collect()
+    ///     }
+    ///     fn is_accept(&self, &s: &u32) -> bool {
+    ///         s == 0
+    ///     }
+    /// }
+    /// assert_eq!(DigitDP::solve_with_index("1000", &Mod3, 3, |&s| s as usize), 334);
+    /// ```
+    ///
+    /// # パニック
+    /// `state_index`が`num_states`以上の値を返した場合panicします
+    pub fn solve_with_index<P, F>(
+        upper: &str,
+        problem: &P,
+        num_states: usize,
+        state_index: F,
+    ) -> usize
+    where
+        P: DigitDPRules,
+        F: Fn(&P::State) -> usize,
+    {
+        struct Ctx<'a, P, F> {
+            digits: Vec<u32>,
+            num_states: usize,
+            memo: Vec<Option<usize>>,
+            problem: &'a P,
+            state_index: F,
+        }
+
+        fn dfs<P: DigitDPRules, F: Fn(&P::State) -> usize>(
+            ctx: &mut Ctx<P, F>,
+            i: usize,
+            tight: bool,
+            state: &P::State,
+        ) -> usize {
+            if i == ctx.digits.len() {
+                return if ctx.problem.is_accept(state) { 1 } else { 0 };
+            }
+            let s = (ctx.state_index)(state);
+            assert!(s < ctx.num_states, "state index out of range");
+            let key = (i * 2 + usize::from(tight)) * ctx.num_states + s;
+            if let Some(res) = ctx.memo[key] {
+                return res;
+            }
+
+            let lim = if tight { ctx.digits[i] } else { 9 };
+            let mut res = 0;
+            for (d, next_state) in ctx.problem.transition(i, tight, state, lim) {
+                res = (res + dfs(ctx, i + 1, tight && d == lim, &next_state)) % MOD;
+            }
+
+            ctx.memo[key] = Some(res);
+            res
+        }
+
+        let digits: Vec<u32> = upper.chars().map(|c| c.to_digit(10).unwrap()).collect();
+        let mut ctx = Ctx {
+            memo: vec![None; digits.len() * 2 * num_states],
+            digits,
+            num_states,
+            problem,
+            state_index,
+        };
+        let init_state = problem.init();
+        dfs(&mut ctx, 0, true, &init_state)
+    }
 }
 
 #[cfg(test)]
@@ -100,9 +193,7 @@ mod tests {
         struct Problem;
         impl DigitDPRules for Problem {
             type State = ();
-            fn init(&self) -> Self::State {
-                ()
-            }
+            fn init(&self) -> Self::State {}
             fn transition(
                 &self,
                 _i: usize,
@@ -150,9 +241,7 @@ mod tests {
         struct Problem;
         impl DigitDPRules for Problem {
             type State = ();
-            fn init(&self) -> Self::State {
-                ()
-            }
+            fn init(&self) -> Self::State {}
             fn transition(
                 &self,
                 _i: usize,
@@ -322,9 +411,7 @@ mod tests {
         struct Problem;
         impl DigitDPRules for Problem {
             type State = ();
-            fn init(&self) -> Self::State {
-                ()
-            }
+            fn init(&self) -> Self::State {}
             fn transition(
                 &self,
                 _i: usize,
@@ -341,14 +428,59 @@ mod tests {
         assert_eq!(DigitDP::solve("0", &Problem), 1);
     }
 
+    #[test]
+    fn test_solve_with_index_matches_solve() {
+        // 隣り合う桁が同じでなく、桁和がkの倍数である数
+        struct Problem {
+            k: u32,
+        }
+        impl DigitDPRules for Problem {
+            type State = (bool, u32, u32); // (is_first, last_digit, sum % k)
+            fn init(&self) -> Self::State {
+                (true, 0, 0)
+            }
+            fn transition(
+                &self,
+                _i: usize,
+                _tight: bool,
+                &(is_first, last, sum): &Self::State,
+                lim: u32,
+            ) -> Vec<(u32, Self::State)> {
+                (0..=lim)
+                    .filter(|&d| is_first || d != last)
+                    .map(|d| (d, (is_first && d == 0, d, (sum + d) % self.k)))
+                    .collect()
+            }
+            fn is_accept(&self, &(_, _, sum): &Self::State) -> bool {
+                sum == 0
+            }
+        }
+        let mut rng = crate::XorShift64::new(4239);
+        for _ in 0..50 {
+            let upper = rng.gen_range(0..1_000_000_000_000).to_string();
+            let problem = Problem {
+                k: rng.gen_range(1..20) as u32,
+            };
+            let index = |&(is_first, last, sum): &(bool, u32, u32)| {
+                (usize::from(is_first) * 10 + last as usize) * 20 + sum as usize
+            };
+            assert_eq!(
+                DigitDP::solve_with_index(&upper, &problem, 2 * 10 * 20, index),
+                DigitDP::solve(&upper, &problem)
+            );
+        }
+        assert_eq!(
+            DigitDP::solve_with_index("", &Problem { k: 1 }, 1, |_| 0),
+            1
+        );
+    }
+
     #[test]
     fn test_large_number() {
         struct Problem;
         impl DigitDPRules for Problem {
             type State = ();
-            fn init(&self) -> Self::State {
-                ()
-            }
+            fn init(&self) -> Self::State {}
             fn transition(
                 &self,
                 _i: usize,
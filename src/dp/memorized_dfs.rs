@@ -15,6 +15,12 @@ pub trait Searchable {
 
     /// ゴールノードから結果を収集（変換）します。
     fn collect(&self, node: &Self::Node) -> Self::Answer;
+
+    /// `true`を返したノードは探索しません（ゴール判定も遷移も行いません）。
+    /// ヒューリスティックな枝刈りに使います。デフォルトでは何も刈りません。
+    fn should_prune(&self, _node: &Self::Node) -> bool {
+        false
+    }
 }
 
 /// 最適なゴールを探す問題のルールを定義するトレイト
@@ -24,6 +30,15 @@ pub trait BestSearchable: Searchable {
     fn is_better(&self, new: &Self::Answer, old_best: &Self::Answer) -> bool;
 }
 
+/// ビームサーチで残すノードを選ぶための評価を定義するトレイト
+pub trait BeamSearchable: BestSearchable {
+    /// ノードの評価値の型
+    type Score: Ord;
+
+    /// ノードの評価値を返します。値が大きいノードほど優先して残されます。
+    fn score(&self, node: &Self::Node) -> Self::Score;
+}
+
 /// メモ化を利用して深さ優先探索を実行するソルバー
 pub struct MemoizedDFS;
 
@@ -57,7 +72,7 @@ impl MemoizedDFS {
             problem: &P,
             return_on_first: bool,
         ) -> bool {
-            if !visited.insert(current.clone()) {
+            if !visited.insert(current.clone()) || problem.should_prune(&current) {
                 return false;
             }
 
@@ -104,7 +119,7 @@ impl MemoizedDFS {
             best: &mut Option<P::Answer>,
             problem: &P,
         ) {
-            if !visited.insert(current.clone()) {
+            if !visited.insert(current.clone()) || problem.should_prune(&current) {
                 return;
             }
 
@@ -123,6 +138,72 @@ impl MemoizedDFS {
         dfs(start, &mut visited, &mut best, problem);
         best
     }
+
+    /// ビームサーチで最適なゴールを探索します。
+    ///
+    /// 開始ノードからの遷移回数ごとに層を作り、各層では`score`の大きい順に
+    /// 最大`beam_width`個のノードだけを次の層へ展開します。訪問済みのノードと
+    /// `should_prune`が`true`のノードはビームに入れる前に捨て、同じ層で複数の親から
+    /// 到達したノードは1つにまとめます。`search_with_best`と同じ問題定義を使えますが、
+    /// 解の最適性は保証されません。
+    ///
+    /// # 引数
+    /// * `start` - 探索を開始するノード
+    /// * `problem` - `BeamSearchable` トレイトを実装した問題定義
+    /// * `beam_width` - 各層で残すノード数の上限
+    ///
+    /// # 戻り値
+    /// 見つかった最も良いゴールの値。ゴールが見つからなければ `None`。
+    ///
+    /// # 計算量
+    /// O(D × W × (B + log(W × B)))（D: 層の数、W: ビーム幅、B: 1ノードあたりの遷移数）
+    pub fn beam_search<P: BeamSearchable>(
+        start: P::Node,
+        problem: &P,
+        beam_width: usize,
+    ) -> Option<P::Answer> {
+        use rustc_hash::FxHasher;
+        use std::collections::HashSet;
+        use std::hash::BuildHasherDefault;
+        type Hasher = BuildHasherDefault<FxHasher>;
+
+        let mut visited: HashSet<P::Node, Hasher> = HashSet::with_hasher(Hasher::default());
+        let mut best: Option<P::Answer> = None;
+        let mut layer = if problem.should_prune(&start) {
+            vec![]
+        } else {
+            vec![start]
+        };
+
+        while !layer.is_empty() {
+            let mut next_layer = vec![];
+            let mut queued: HashSet<P::Node, Hasher> = HashSet::with_hasher(Hasher::default());
+            for current in layer {
+                if !visited.insert(current.clone()) {
+                    continue;
+                }
+                if problem.is_goal(&current) {
+                    let val = problem.collect(&current);
+                    if best.as_ref().map_or(true, |b| problem.is_better(&val, b)) {
+                        best = Some(val);
+                    }
+                }
+                next_layer.extend(problem.successors(&current).into_iter().filter(|next| {
+                    !visited.contains(next)
+                        && !problem.should_prune(next)
+                        && queued.insert(next.clone())
+                }));
+            }
+            let mut scored: Vec<(P::Score, P::Node)> = next_layer
+                .into_iter()
+                .map(|node| (problem.score(&node), node))
+                .collect();
+            scored.sort_by(|a, b| b.0.cmp(&a.0));
+            scored.truncate(beam_width);
+            layer = scored.into_iter().map(|(_, node)| node).collect();
+        }
+        best
+    }
 }
 
 #[cfg(test)]
@@ -275,6 +356,140 @@ mod tests {
         assert_eq!(result, Some(6));
     }
 
+    #[test]
+    fn test_should_prune() {
+        // 奇数のノードを刈ると 0 -> 2 -> 4 -> 6 だけが残る
+        struct EvenOnly;
+        impl Searchable for EvenOnly {
+            type Node = i32;
+            type Answer = i32;
+            fn successors(&self, node: &Self::Node) -> Vec<Self::Node> {
+                MultiGoalGraph.successors(node)
+            }
+            fn is_goal(&self, node: &Self::Node) -> bool {
+                MultiGoalGraph.is_goal(node)
+            }
+            fn collect(&self, &node: &Self::Node) -> Self::Answer {
+                node
+            }
+            fn should_prune(&self, &node: &Self::Node) -> bool {
+                node % 2 == 1
+            }
+        }
+        let mut result = MemoizedDFS::search(0, &EvenOnly, false);
+        result.sort();
+        assert_eq!(result, vec![4, 6]);
+    }
+
+    #[test]
+    fn test_beam_search_width_and_prune() {
+        // 最初に5を取ると以降は増えない（罠）、1を取ると以降+10ずつ増える
+        #[derive(Clone, Hash, Eq, PartialEq)]
+        struct Node {
+            depth: u32,
+            value: i32,
+            trapped: bool,
+        }
+        struct Trap {
+            prune_trapped: bool,
+        }
+        impl Searchable for Trap {
+            type Node = Node;
+            type Answer = i32;
+            fn successors(&self, node: &Self::Node) -> Vec<Self::Node> {
+                let next = |value, trapped| Node {
+                    depth: node.depth + 1,
+                    value,
+                    trapped,
+                };
+                match node.depth {
+                    0 => vec![next(5, true), next(1, false)],
+                    3 => vec![],
+                    _ if node.trapped => vec![next(node.value, true)],
+                    _ => vec![next(node.value + 10, false)],
+                }
+            }
+            fn is_goal(&self, node: &Self::Node) -> bool {
+                node.depth == 3
+            }
+            fn collect(&self, node: &Self::Node) -> Self::Answer {
+                node.value
+            }
+            fn should_prune(&self, node: &Self::Node) -> bool {
+                self.prune_trapped && node.trapped
+            }
+        }
+        impl BestSearchable for Trap {
+            fn is_better(&self, new: &Self::Answer, old_best: &Self::Answer) -> bool {
+                new > old_best
+            }
+        }
+        impl BeamSearchable for Trap {
+            type Score = i32;
+            fn score(&self, node: &Self::Node) -> Self::Score {
+                node.value
+            }
+        }
+
+        let start = || Node {
+            depth: 0,
+            value: 0,
+            trapped: false,
+        };
+        let greedy = Trap {
+            prune_trapped: false,
+        };
+        assert_eq!(MemoizedDFS::beam_search(start(), &greedy, 1), Some(5));
+        assert_eq!(MemoizedDFS::beam_search(start(), &greedy, 2), Some(21));
+        assert_eq!(MemoizedDFS::search_with_best(start(), &greedy), Some(21));
+        let pruned = Trap {
+            prune_trapped: true,
+        };
+        assert_eq!(MemoizedDFS::beam_search(start(), &pruned, 1), Some(21));
+        assert_eq!(MemoizedDFS::beam_search(start(), &pruned, 0), None);
+    }
+
+    #[test]
+    fn test_beam_search_dedups_converging_successors() {
+        // 1, 2, 3の全てが10に遷移する。10が3回数えられると幅3のビームから20が押し出される
+        struct Converge;
+        impl Searchable for Converge {
+            type Node = i32;
+            type Answer = i32;
+            fn successors(&self, &node: &Self::Node) -> Vec<Self::Node> {
+                match node {
+                    0 => vec![1, 2, 3],
+                    1 | 2 => vec![10],
+                    3 => vec![10, 20],
+                    _ => vec![],
+                }
+            }
+            fn is_goal(&self, &node: &Self::Node) -> bool {
+                node >= 10
+            }
+            fn collect(&self, &node: &Self::Node) -> Self::Answer {
+                node
+            }
+        }
+        impl BestSearchable for Converge {
+            fn is_better(&self, new: &Self::Answer, old_best: &Self::Answer) -> bool {
+                new > old_best
+            }
+        }
+        impl BeamSearchable for Converge {
+            type Score = i32;
+            fn score(&self, &node: &Self::Node) -> Self::Score {
+                if node == 10 {
+                    100
+                } else {
+                    -node
+                }
+            }
+        }
+
+        assert_eq!(MemoizedDFS::beam_search(0, &Converge, 3), Some(20));
+    }
+
     #[test]
     fn test_complex_graph_structure() {
         #[derive(Clone, Hash, Eq, PartialEq, Debug)]
@@ -1,10 +1,12 @@
 pub mod bucked_dp;
+pub mod dag_rules;
 pub mod digit_dp;
 pub mod memorized_dfs;
 pub mod pull_dp;
 pub mod push_dp;
 
 pub use bucked_dp::{DagDPRules, Engine};
+pub use dag_rules::{AsPull, AsPush, DagRules};
 pub use digit_dp::DigitDP;
 pub use memorized_dfs::MemoizedDFS;
 pub use pull_dp::{ChildRef, Plan, PullDPRules, PullDpEngine};
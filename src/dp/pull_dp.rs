@@ -142,6 +142,7 @@ impl PullDpEngine {
 //     }
 // }
 
+#[cfg(test)]
 mod tests {
     use super::*;
     struct Ctx {
@@ -0,0 +1,271 @@
+/// 64分木の各階層をビット列で持つ集合（`FastIntSet`の内部表現）
+#[derive(Debug, Clone)]
+struct Layers {
+    /// layers[0]が要素そのもの、layers[h + 1]のビットはlayers[h]の対応するワードが非0かどうか
+    layers: Vec<Vec<u64>>,
+}
+
+impl Layers {
+    fn new(n: usize, full: bool) -> Self {
+        let mut layers = Vec::new();
+        let mut m = n;
+        loop {
+            let w = ((m + 63) / 64).max(1);
+            let mut v = vec![0u64; w];
+            if full {
+                for (i, x) in v.iter_mut().enumerate() {
+                    let cnt = m.saturating_sub(i * 64).min(64);
+                    *x = if cnt == 64 { !0 } else { (1 << cnt) - 1 };
+                }
+            }
+            layers.push(v);
+            if w == 1 {
+                break;
+            }
+            m = w;
+        }
+        Layers { layers }
+    }
+
+    fn contains(&self, x: usize) -> bool {
+        self.layers[0][x / 64] >> (x % 64) & 1 == 1
+    }
+
+    fn insert(&mut self, mut x: usize) {
+        for layer in &mut self.layers {
+            let was_empty = layer[x / 64] == 0;
+            layer[x / 64] |= 1 << (x % 64);
+            if !was_empty {
+                break;
+            }
+            x /= 64;
+        }
+    }
+
+    fn remove(&mut self, mut x: usize) {
+        for layer in &mut self.layers {
+            layer[x / 64] &= !(1 << (x % 64));
+            if layer[x / 64] != 0 {
+                break;
+            }
+            x /= 64;
+        }
+    }
+
+    /// x以上の最小の要素
+    fn next(&self, mut x: usize) -> Option<usize> {
+        for h in 0..self.layers.len() {
+            let i = x / 64;
+            if i >= self.layers[h].len() {
+                return None;
+            }
+            let d = self.layers[h][i] >> (x % 64);
+            if d == 0 {
+                x = i + 1;
+                continue;
+            }
+            x += d.trailing_zeros() as usize;
+            for g in (0..h).rev() {
+                x = x * 64 + self.layers[g][x].trailing_zeros() as usize;
+            }
+            return Some(x);
+        }
+        None
+    }
+
+    /// x以下の最大の要素
+    fn prev(&self, mut x: usize) -> Option<usize> {
+        for h in 0..self.layers.len() {
+            let i = x / 64;
+            let d = self.layers[h][i] << (63 - x % 64);
+            if d == 0 {
+                x = i.checked_sub(1)?;
+                continue;
+            }
+            x -= d.leading_zeros() as usize;
+            for g in (0..h).rev() {
+                x = x * 64 + 63 - self.layers[g][x].leading_zeros() as usize;
+            }
+            return Some(x);
+        }
+        None
+    }
+}
+
+/// 0以上n未満の整数の集合（64分木のビット列）
+///
+/// `BTreeSet<usize>`の代わりに使える高速な集合で、次の要素・前の要素の検索や
+/// 含まれない最小の値（mex）を求められます。
+///
+/// # 計算量
+/// w = 64として
+/// - 追加・削除・次の要素・前の要素・mex: O(log_w n)
+/// - 包含判定: O(1)
+///
+/// # 使用例
+/// ```
+/// # use rust_macro::FastIntSet;
+/// let mut s = FastIntSet::new(1000);
+/// s.insert(3);
+/// s.insert(500);
+/// assert_eq!(s.next(4), Some(500));
+/// assert_eq!(s.prev(499), Some(3));
+/// assert_eq!(s.mex(), 0);
+/// s.insert(0);
+/// assert_eq!(s.mex(), 1);
+/// assert!(s.remove(3));
+/// assert_eq!(s.iter().collect::<Vec<_>>(), vec![0, 500]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct FastIntSet {
+    n: usize,
+    len: usize,
+    present: Layers,
+    /// mexを求めるための、含まれない要素の集合
+    absent: Layers,
+}
+
+impl FastIntSet {
+    /// 0以上n未満の整数を扱う空の集合を作成
+    pub fn new(n: usize) -> Self {
+        FastIntSet {
+            n,
+            len: 0,
+            present: Layers::new(n, false),
+            absent: Layers::new(n, true),
+        }
+    }
+
+    /// 扱える整数の上限n
+    pub fn capacity(&self) -> usize {
+        self.n
+    }
+
+    /// 要素数
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// 空かどうか
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// xが含まれるかどうか
+    pub fn contains(&self, x: usize) -> bool {
+        x < self.n && self.present.contains(x)
+    }
+
+    /// xを追加する。すでに含まれていた場合は`false`を返す
+    ///
+    /// # パニック
+    /// x ≥ nの場合panicします
+    pub fn insert(&mut self, x: usize) -> bool {
+        assert!(x < self.n, "index out of range");
+        if self.present.contains(x) {
+            return false;
+        }
+        self.present.insert(x);
+        self.absent.remove(x);
+        self.len += 1;
+        true
+    }
+
+    /// xを削除する。含まれていなかった場合は`false`を返す
+    pub fn remove(&mut self, x: usize) -> bool {
+        if !self.contains(x) {
+            return false;
+        }
+        self.present.remove(x);
+        self.absent.insert(x);
+        self.len -= 1;
+        true
+    }
+
+    /// x以上の最小の要素
+    pub fn next(&self, x: usize) -> Option<usize> {
+        if x >= self.n {
+            return None;
+        }
+        self.present.next(x)
+    }
+
+    /// x以下の最大の要素
+    pub fn prev(&self, x: usize) -> Option<usize> {
+        if self.n == 0 {
+            return None;
+        }
+        self.present.prev(x.min(self.n - 1))
+    }
+
+    /// 最小の要素
+    pub fn min(&self) -> Option<usize> {
+        self.next(0)
+    }
+
+    /// 最大の要素
+    pub fn max(&self) -> Option<usize> {
+        self.prev(usize::MAX)
+    }
+
+    /// 含まれない最小の非負整数（すべて含まれていればn）
+    pub fn mex(&self) -> usize {
+        if self.n == 0 {
+            return 0;
+        }
+        self.absent.next(0).unwrap_or(self.n)
+    }
+
+    /// 要素を昇順に列挙する
+    pub fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+        std::iter::successors(self.min(), move |&x| self.next(x + 1))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::XorShift64;
+    use std::collections::BTreeSet;
+
+    #[test]
+    fn test_fast_int_set_against_btreeset() {
+        let mut rng = XorShift64::new(31);
+        for n in [1usize, 63, 64, 65, 300, 5000] {
+            let mut s = FastIntSet::new(n);
+            let mut naive = BTreeSet::new();
+            for _ in 0..3000 {
+                let x = rng.gen_range(0..n as u64) as usize;
+                match rng.gen_range(0..3) {
+                    0 => assert_eq!(s.insert(x), naive.insert(x)),
+                    1 => assert_eq!(s.remove(x), naive.remove(&x)),
+                    _ => {
+                        assert_eq!(s.contains(x), naive.contains(&x));
+                        assert_eq!(s.next(x), naive.range(x..).next().copied());
+                        assert_eq!(s.prev(x), naive.range(..=x).next_back().copied());
+                    }
+                }
+                assert_eq!(s.len(), naive.len());
+                let mex = (0..=n).find(|x| !naive.contains(x)).unwrap();
+                assert_eq!(s.mex(), mex);
+            }
+            assert!(s.iter().eq(naive.iter().copied()));
+            assert_eq!(s.max(), naive.last().copied());
+        }
+    }
+
+    #[test]
+    fn test_fast_int_set_full_and_bounds() {
+        let mut s = FastIntSet::new(130);
+        for x in 0..130 {
+            s.insert(x);
+        }
+        assert_eq!(s.mex(), 130);
+        assert_eq!(s.next(130), None);
+        assert_eq!(s.prev(1000), Some(129));
+        assert!(!s.contains(500));
+        assert!(!s.remove(500));
+        let empty = FastIntSet::new(0);
+        assert_eq!((empty.mex(), empty.min(), empty.max()), (0, None, None));
+    }
+}
@@ -0,0 +1,269 @@
+use std::ops::{Add, Sub};
+
+/// Fenwick木（Binary Indexed Tree）
+///
+/// 1点加算と区間和を任意の順序で処理します。`CumulativeSum`は静的な配列向け、
+/// `RangeAddSum`は区間加算が必要な場合向けで、こちらは1点加算だけで足りる場合に使います。
+///
+/// # 計算量
+/// - 構築: O(n)
+/// - 1点加算・区間和: O(log n)
+///
+/// # 使用例
+/// ```
+/// # use rust_macro::FenwickTree;
+/// let mut ft = FenwickTree::from_slice(&[1, 2, 3, 4, 5]);
+/// assert_eq!(ft.sum(1, 3), 5); // [1, 3)の和
+/// ft.add(2, 10);
+/// assert_eq!(ft.sum(0, 5), 25);
+/// assert_eq!(ft.get(2), 13);
+/// ```
+#[derive(Debug, Clone)]
+pub struct FenwickTree<T>
+where
+    T: Add<Output = T> + Sub<Output = T> + Copy + Default,
+{
+    /// 1-indexedのBIT
+    data: Vec<T>,
+}
+
+impl<T> FenwickTree<T>
+where
+    T: Add<Output = T> + Sub<Output = T> + Copy + Default,
+{
+    /// 長さnの0初期化された配列を作成
+    pub fn new(n: usize) -> Self {
+        FenwickTree {
+            data: vec![T::default(); n + 1],
+        }
+    }
+
+    /// 配列の初期値から構築
+    pub fn from_slice(arr: &[T]) -> Self {
+        let n = arr.len();
+        let mut data = vec![T::default(); n + 1];
+        data[1..].copy_from_slice(arr);
+        for i in 1..=n {
+            let j = i + (i & i.wrapping_neg());
+            if j <= n {
+                data[j] = data[j] + data[i];
+            }
+        }
+        FenwickTree { data }
+    }
+
+    /// 配列の長さ
+    pub fn len(&self) -> usize {
+        self.data.len() - 1
+    }
+
+    /// 配列の長さが0かどうか
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// i番目の要素にxを加算
+    pub fn add(&mut self, i: usize, x: T) {
+        assert!(i < self.len());
+        let mut i = i + 1;
+        while i < self.data.len() {
+            self.data[i] = self.data[i] + x;
+            i += i & i.wrapping_neg();
+        }
+    }
+
+    /// 区間[0, r)の和
+    pub fn prefix_sum(&self, r: usize) -> T {
+        assert!(r <= self.len());
+        let mut res = T::default();
+        let mut i = r;
+        while i > 0 {
+            res = res + self.data[i];
+            i -= i & i.wrapping_neg();
+        }
+        res
+    }
+
+    /// 区間[l, r)の和
+    pub fn sum(&self, l: usize, r: usize) -> T {
+        assert!(l <= r);
+        self.prefix_sum(r) - self.prefix_sum(l)
+    }
+
+    /// i番目の要素
+    pub fn get(&self, i: usize) -> T {
+        self.sum(i, i + 1)
+    }
+}
+
+/// 2次元Fenwick木
+///
+/// 1点加算と長方形領域の和を任意の順序で処理します。
+///
+/// # 計算量
+/// - 1点加算・長方形和: O(log h log w)
+///
+/// # 使用例
+/// ```
+/// # use rust_macro::FenwickTree2D;
+/// let mut ft = FenwickTree2D::new(3, 4);
+/// ft.add(0, 1, 5);
+/// ft.add(2, 3, 7);
+/// assert_eq!(ft.sum(0, 0, 2, 2), 5);
+/// assert_eq!(ft.sum(0, 0, 3, 4), 12);
+/// ```
+#[derive(Debug, Clone)]
+pub struct FenwickTree2D<T>
+where
+    T: Add<Output = T> + Sub<Output = T> + Copy + Default,
+{
+    h: usize,
+    w: usize,
+    /// 1-indexedのBIT
+    data: Vec<Vec<T>>,
+}
+
+impl<T> FenwickTree2D<T>
+where
+    T: Add<Output = T> + Sub<Output = T> + Copy + Default,
+{
+    /// h行w列の0初期化された2次元配列を作成
+    pub fn new(h: usize, w: usize) -> Self {
+        FenwickTree2D {
+            h,
+            w,
+            data: vec![vec![T::default(); w + 1]; h + 1],
+        }
+    }
+
+    /// (x, y)の要素にvを加算
+    pub fn add(&mut self, x: usize, y: usize, v: T) {
+        assert!(x < self.h && y < self.w);
+        let mut i = x + 1;
+        while i <= self.h {
+            let mut j = y + 1;
+            while j <= self.w {
+                self.data[i][j] = self.data[i][j] + v;
+                j += j & j.wrapping_neg();
+            }
+            i += i & i.wrapping_neg();
+        }
+    }
+
+    /// 範囲[0, x)×[0, y)の和
+    pub fn prefix_sum(&self, x: usize, y: usize) -> T {
+        assert!(x <= self.h && y <= self.w);
+        let mut res = T::default();
+        let mut i = x;
+        while i > 0 {
+            let mut j = y;
+            while j > 0 {
+                res = res + self.data[i][j];
+                j -= j & j.wrapping_neg();
+            }
+            i -= i & i.wrapping_neg();
+        }
+        res
+    }
+
+    /// 範囲(x1, y1)から(x2, y2)の和（x2, y2は含まない）
+    ///
+    /// 足し算を先に行うため、非負の値だけを加えていれば符号なし整数でも途中で負になりません。
+    pub fn sum(&self, x1: usize, y1: usize, x2: usize, y2: usize) -> T {
+        assert!(x1 <= x2 && y1 <= y2);
+        self.prefix_sum(x2, y2) + self.prefix_sum(x1, y1)
+            - self.prefix_sum(x1, y2)
+            - self.prefix_sum(x2, y1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::XorShift64;
+
+    #[test]
+    fn test_fenwick_tree_against_naive() {
+        let mut rng = XorShift64::new(4273);
+        for n in [0usize, 1, 2, 7, 16] {
+            let mut naive: Vec<i64> = (0..n).map(|_| rng.gen_range_i64(-10..10)).collect();
+            let mut ft = FenwickTree::from_slice(&naive);
+            assert_eq!(ft.len(), n);
+            for _ in 0..50 {
+                if n > 0 {
+                    let i = rng.gen_range(0..n as u64) as usize;
+                    let x = rng.gen_range_i64(-10..10);
+                    ft.add(i, x);
+                    naive[i] += x;
+                    assert_eq!(ft.get(i), naive[i]);
+                }
+                for l in 0..=n {
+                    for r in l..=n {
+                        assert_eq!(ft.sum(l, r), naive[l..r].iter().sum::<i64>());
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_fenwick_tree_2d_against_naive() {
+        let mut rng = XorShift64::new(42731);
+        let (h, w) = (5, 6);
+        let mut naive = vec![vec![0i64; w]; h];
+        let mut ft = FenwickTree2D::new(h, w);
+        for _ in 0..100 {
+            let (x, y) = (
+                rng.gen_range(0..h as u64) as usize,
+                rng.gen_range(0..w as u64) as usize,
+            );
+            let v = rng.gen_range_i64(-5..10);
+            ft.add(x, y, v);
+            naive[x][y] += v;
+            let x1 = rng.gen_range(0..h as u64 + 1) as usize;
+            let x2 = rng.gen_range(x1 as u64..h as u64 + 1) as usize;
+            let y1 = rng.gen_range(0..w as u64 + 1) as usize;
+            let y2 = rng.gen_range(y1 as u64..w as u64 + 1) as usize;
+            let expected: i64 = naive[x1..x2]
+                .iter()
+                .map(|row| row[y1..y2].iter().sum::<i64>())
+                .sum();
+            assert_eq!(ft.sum(x1, y1, x2, y2), expected);
+        }
+    }
+
+    #[test]
+    fn test_fenwick_tree_2d_unsigned() {
+        let mut ft = FenwickTree2D::<u64>::new(3, 3);
+        ft.add(0, 0, 5);
+        assert_eq!(ft.sum(1, 1, 2, 2), 0);
+        assert_eq!(ft.sum(0, 0, 3, 3), 5);
+
+        let mut rng = XorShift64::new(42732);
+        let (h, w) = (4, 5);
+        let mut naive = vec![vec![0u64; w]; h];
+        let mut ft = FenwickTree2D::<u64>::new(h, w);
+        for _ in 0..50 {
+            let (x, y) = (
+                rng.gen_range(0..h as u64) as usize,
+                rng.gen_range(0..w as u64) as usize,
+            );
+            let v = rng.gen_range(0..10);
+            ft.add(x, y, v);
+            naive[x][y] += v;
+            for x1 in 0..=h {
+                for x2 in x1..=h {
+                    for y1 in 0..=w {
+                        for y2 in y1..=w {
+                            let expected: u64 = naive[x1..x2]
+                                .iter()
+                                .map(|row| row[y1..y2].iter().sum::<u64>())
+                                .sum();
+                            assert_eq!(ft.sum(x1, y1, x2, y2), expected);
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,92 @@
+use super::Point;
+use std::cmp::Ordering;
+
+type P = Point<i64>;
+
+/// 偏角が[0, π)なら0、[π, 2π)なら1
+fn half(p: P) -> u8 {
+    u8::from(p.y < 0 || (p.y == 0 && p.x < 0))
+}
+
+/// 原点から見た偏角で2点を比較する（整数の外積のみを使う）
+///
+/// 偏角はx軸の正の向きを0とした[0, 2π)で比べ、同じ偏角の点どうしは`Equal`です。
+/// 原点は他のどの点よりも前とします。
+///
+/// # 使用例
+/// ```
+/// # use rust_macro::geometry::{cmp_arg, Point};
+/// # use std::cmp::Ordering;
+/// assert_eq!(cmp_arg(Point::new(1, 1), Point::new(-1, 1)), Ordering::Less);
+/// assert_eq!(cmp_arg(Point::new(-1, -1), Point::new(1, 1)), Ordering::Greater);
+/// assert_eq!(cmp_arg(Point::new(2, 2), Point::new(1, 1)), Ordering::Equal);
+/// ```
+pub fn cmp_arg(a: P, b: P) -> Ordering {
+    let o = Point::new(0, 0);
+    (a != o)
+        .cmp(&(b != o))
+        .then_with(|| half(a).cmp(&half(b)))
+        .then_with(|| 0.cmp(&a.cross(b)))
+}
+
+/// 点を原点から見た偏角の昇順に並べ替える（安定ソート）
+///
+/// # 計算量
+/// O(n log n)
+///
+/// # 使用例
+/// ```
+/// # use rust_macro::geometry::{sort_by_arg, Point};
+/// let mut pts = [(0, -1), (-1, 0), (1, 0), (0, 1), (1, 1)].map(Point::from);
+/// sort_by_arg(&mut pts);
+/// assert_eq!(pts, [(1, 0), (1, 1), (0, 1), (-1, 0), (0, -1)].map(Point::from));
+/// ```
+pub fn sort_by_arg(points: &mut [P]) {
+    points.sort_by(|&a, &b| cmp_arg(a, b));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::XorShift64;
+    use std::f64::consts::PI;
+
+    #[test]
+    fn test_sort_by_arg_against_atan2() {
+        let mut rng = XorShift64::new(111);
+        let mut pts: Vec<P> = (0..500)
+            .map(|_| Point::new(rng.gen_range_i64(-20..21), rng.gen_range_i64(-20..21)))
+            .filter(|&p| p != Point::new(0, 0))
+            .collect();
+        sort_by_arg(&mut pts);
+        let arg = |p: P| {
+            let t = (p.y as f64).atan2(p.x as f64);
+            if t < 0.0 {
+                t + 2.0 * PI
+            } else {
+                t
+            }
+        };
+        for w in pts.windows(2) {
+            assert!(arg(w[0]) <= arg(w[1]) + 1e-12);
+            assert_eq!(
+                cmp_arg(w[0], w[1]) == Ordering::Equal,
+                w[0].cross(w[1]) == 0 && w[0].dot(w[1]) > 0
+            );
+        }
+    }
+
+    #[test]
+    fn test_cmp_arg_axes_and_origin() {
+        let o = Point::new(0, 0);
+        let axes = [(5, 0), (0, 5), (-5, 0), (0, -5)].map(Point::from);
+        for (i, &a) in axes.iter().enumerate() {
+            for (j, &b) in axes.iter().enumerate() {
+                assert_eq!(cmp_arg(a, b), i.cmp(&j));
+            }
+        }
+        assert_eq!(cmp_arg(o, o), Ordering::Equal);
+        assert!(axes.iter().all(|&a| cmp_arg(o, a) == Ordering::Less));
+        assert!(axes.iter().all(|&a| cmp_arg(a, o) == Ordering::Greater));
+    }
+}
@@ -0,0 +1,184 @@
+use super::Point;
+
+type P = Point<i64>;
+
+/// 凸包（Andrewのモノトーンチェイン）
+///
+/// 凸包の頂点を、x座標最小（同じならy座標最小）の点から反時計回りに返します。
+/// 辺上にある点（3点が同一直線上にある場合の中間の点）は含めません。
+///
+/// # 計算量
+/// O(n log n)
+///
+/// # 使用例
+/// ```
+/// # use rust_macro::geometry::{convex_hull, Point};
+/// let pts: Vec<Point<i64>> = [(0, 0), (2, 0), (1, 1), (2, 2), (0, 2), (1, 0)]
+///     .into_iter()
+///     .map(Point::from)
+///     .collect();
+/// let hull = convex_hull(&pts);
+/// assert_eq!(hull, [(0, 0), (2, 0), (2, 2), (0, 2)].map(Point::from));
+/// ```
+pub fn convex_hull(points: &[P]) -> Vec<P> {
+    let mut ps = points.to_vec();
+    ps.sort();
+    ps.dedup();
+    if ps.len() <= 2 {
+        return ps;
+    }
+    let turns_left = |hull: &[P], p: P| {
+        let (a, b) = (hull[hull.len() - 2], hull[hull.len() - 1]);
+        (b - a).cross(p - b) > 0
+    };
+    let mut hull: Vec<P> = Vec::with_capacity(2 * ps.len());
+    // 下側: 左から右へ、左に曲がらない点を取り除きながら積む
+    for &p in &ps {
+        while hull.len() >= 2 && !turns_left(&hull, p) {
+            hull.pop();
+        }
+        hull.push(p);
+    }
+    // 上側: 右から左へ
+    let lower = hull.len() + 1;
+    for &p in ps.iter().rev().skip(1) {
+        while hull.len() >= lower && !turns_left(&hull, p) {
+            hull.pop();
+        }
+        hull.push(p);
+    }
+    hull.pop();
+    hull
+}
+
+/// 凸多角形の直径（最遠点対）
+///
+/// `convex_hull`が返す形（反時計回り、辺上の点を含まない）の凸多角形を受け取り、
+/// 回転キャリパー法で最も遠い2頂点を求めます。
+///
+/// # 戻り値
+/// (距離の2乗, 点, 点)
+///
+/// # 計算量
+/// O(n)
+///
+/// # 使用例
+/// ```
+/// # use rust_macro::geometry::{convex_diameter, convex_hull, Point};
+/// let pts: Vec<Point<i64>> = [(0, 0), (4, 1), (1, 3), (2, 1)].into_iter().map(Point::from).collect();
+/// let (d2, a, b) = convex_diameter(&convex_hull(&pts));
+/// assert_eq!(d2, 17);
+/// assert_eq!((a - b).norm2(), 17);
+/// ```
+///
+/// # パニック
+/// 頂点が1つもない場合panicします
+pub fn convex_diameter(hull: &[P]) -> (i64, P, P) {
+    let n = hull.len();
+    assert!(n > 0, "empty polygon");
+    let mut best = (0, hull[0], hull[0]);
+    if n == 1 {
+        return best;
+    }
+    let mut j = 1;
+    for i in 0..n {
+        let ni = (i + 1) % n;
+        while (hull[ni] - hull[i]).cross(hull[(j + 1) % n] - hull[j]) > 0 {
+            j = (j + 1) % n;
+        }
+        for k in [i, ni] {
+            let d = (hull[k] - hull[j]).norm2();
+            if d > best.0 {
+                best = (d, hull[k], hull[j]);
+            }
+        }
+    }
+    best
+}
+
+/// 凸多角形の幅（平行な2直線で挟むときの間隔の最小値）
+///
+/// `convex_hull`が返す形の凸多角形を受け取り、回転キャリパー法で求めます。
+/// 頂点が2つ以下なら0を返します。
+///
+/// # 計算量
+/// O(n)
+///
+/// # 使用例
+/// ```
+/// # use rust_macro::geometry::{convex_width, Point};
+/// let rect = [(0, 0), (4, 0), (4, 2), (0, 2)].map(Point::from);
+/// assert_eq!(convex_width(&rect), 2.0);
+/// ```
+pub fn convex_width(hull: &[P]) -> f64 {
+    let n = hull.len();
+    if n <= 2 {
+        return 0.0;
+    }
+    let area = |i: usize, j: usize| (hull[(i + 1) % n] - hull[i]).cross(hull[j] - hull[i]);
+    let mut best = f64::INFINITY;
+    let mut j = 1;
+    for i in 0..n {
+        while area(i, (j + 1) % n) > area(i, j) {
+            j = (j + 1) % n;
+        }
+        let edge = (hull[(i + 1) % n] - hull[i]).to_f64().norm();
+        best = best.min(area(i, j) as f64 / edge);
+    }
+    best
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::XorShift64;
+
+    #[test]
+    fn test_convex_hull_against_naive() {
+        let mut rng = XorShift64::new(101);
+        for _ in 0..200 {
+            let n = rng.gen_range(1..30) as usize;
+            let pts: Vec<P> = (0..n)
+                .map(|_| Point::new(rng.gen_range_i64(-6..7), rng.gen_range_i64(-6..7)))
+                .collect();
+            let hull = convex_hull(&pts);
+            let m = hull.len();
+            if m >= 3 {
+                // 反時計回りに厳密に左折し、すべての点が内側にある
+                for i in 0..m {
+                    let (a, b, c) = (hull[i], hull[(i + 1) % m], hull[(i + 2) % m]);
+                    assert!((b - a).cross(c - b) > 0);
+                    assert!(pts.iter().all(|&p| (b - a).cross(p - a) >= 0));
+                }
+            }
+            // 直径は全点対の最大距離に一致する
+            let expected = pts
+                .iter()
+                .flat_map(|&a| pts.iter().map(move |&b| (a - b).norm2()))
+                .max()
+                .unwrap();
+            assert_eq!(convex_diameter(&hull).0, expected);
+            // 幅は各辺を底辺としたときの高さの最大値の最小値
+            if m >= 3 {
+                let expected = (0..m)
+                    .map(|i| {
+                        let e = hull[(i + 1) % m] - hull[i];
+                        let h = hull.iter().map(|&p| e.cross(p - hull[i])).max().unwrap();
+                        h as f64 / e.to_f64().norm()
+                    })
+                    .fold(f64::INFINITY, f64::min);
+                assert!((convex_width(&hull) - expected).abs() < 1e-9);
+            }
+        }
+    }
+
+    #[test]
+    fn test_convex_hull_degenerate() {
+        let pts = [(0, 0), (1, 1), (2, 2), (1, 1)].map(Point::from);
+        assert_eq!(convex_hull(&pts), vec![Point::new(0, 0), Point::new(2, 2)]);
+        assert_eq!(convex_diameter(&convex_hull(&pts)).0, 8);
+        assert_eq!(convex_width(&convex_hull(&pts)), 0.0);
+        assert_eq!(convex_hull(&[Point::new(3, 3)]), vec![Point::new(3, 3)]);
+        assert!(convex_hull(&[]).is_empty());
+    }
+}
@@ -0,0 +1,14 @@
+pub mod arg_sort;
+pub mod convex;
+pub mod point;
+pub mod point3;
+pub mod segment;
+
+pub use arg_sort::{cmp_arg, sort_by_arg};
+pub use convex::{convex_diameter, convex_hull, convex_width};
+pub use point::Point;
+pub use point3::{signed_volume6, Plane, Point3};
+pub use segment::{
+    ccw, dist_point_segment, dist_segment_segment, line_intersection, on_segment,
+    segment_intersection, segments_intersect,
+};
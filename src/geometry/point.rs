@@ -0,0 +1,111 @@
+use std::ops::{Add, Mul, Neg, Sub};
+
+/// 2次元の点（ベクトル）
+///
+/// 整数座標（`i64`）での厳密な計算を主な用途とし、`f64`でも使えます。
+/// 比較はx座標、y座標の順の辞書式です。
+///
+/// # 使用例
+/// ```
+/// # use rust_macro::geometry::Point;
+/// let a = Point::new(1i64, 2);
+/// let b = Point::new(3, -1);
+/// assert_eq!(a + b, Point::new(4, 1));
+/// assert_eq!(a.dot(b), 1);
+/// assert_eq!(a.cross(b), -7);
+/// assert_eq!((b - a).norm2(), 13);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Point<T> {
+    pub x: T,
+    pub y: T,
+}
+
+impl<T> Point<T> {
+    /// 点(x, y)を作成
+    pub const fn new(x: T, y: T) -> Self {
+        Point { x, y }
+    }
+}
+
+impl<T: Copy + Add<Output = T> + Sub<Output = T> + Mul<Output = T>> Point<T> {
+    /// 内積
+    pub fn dot(self, rhs: Self) -> T {
+        self.x * rhs.x + self.y * rhs.y
+    }
+
+    /// 外積（rhsがselfから見て反時計回りなら正）
+    pub fn cross(self, rhs: Self) -> T {
+        self.x * rhs.y - self.y * rhs.x
+    }
+
+    /// 長さの2乗
+    pub fn norm2(self) -> T {
+        self.dot(self)
+    }
+}
+
+impl Point<f64> {
+    /// 長さ
+    pub fn norm(self) -> f64 {
+        self.norm2().sqrt()
+    }
+}
+
+impl Point<i64> {
+    /// 浮動小数点数の座標に変換する
+    pub fn to_f64(self) -> Point<f64> {
+        Point::new(self.x as f64, self.y as f64)
+    }
+}
+
+impl<T: Add<Output = T>> Add for Point<T> {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self {
+        Point::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Point<T> {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self {
+        Point::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl<T: Copy + Mul<Output = T>> Mul<T> for Point<T> {
+    type Output = Self;
+    /// スカラー倍
+    fn mul(self, k: T) -> Self {
+        Point::new(self.x * k, self.y * k)
+    }
+}
+
+impl<T: Neg<Output = T>> Neg for Point<T> {
+    type Output = Self;
+    fn neg(self) -> Self {
+        Point::new(-self.x, -self.y)
+    }
+}
+
+impl<T> From<(T, T)> for Point<T> {
+    fn from((x, y): (T, T)) -> Self {
+        Point::new(x, y)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_point_ops() {
+        let a = Point::new(2i64, 3);
+        assert_eq!(a * 3, Point::new(6, 9));
+        assert_eq!(-a, Point::new(-2, -3));
+        assert_eq!(a - a, Point::default());
+        assert_eq!(Point::from((1, 1)).cross(Point::new(1, 1)), 0);
+        assert!(Point::new(1, 5) < Point::new(2, 0));
+        assert_eq!(Point::new(3i64, 4).to_f64().norm(), 5.0);
+    }
+}
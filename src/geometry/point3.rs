@@ -0,0 +1,181 @@
+use std::ops::{Add, Mul, Neg, Sub};
+
+/// 3次元の点（ベクトル）
+///
+/// # 使用例
+/// ```
+/// # use rust_macro::geometry::Point3;
+/// let a = Point3::new(1i64, 0, 0);
+/// let b = Point3::new(0, 1, 0);
+/// assert_eq!(a.cross(b), Point3::new(0, 0, 1));
+/// assert_eq!(a.dot(b), 0);
+/// assert_eq!((a + b).norm2(), 2);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Point3<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+impl<T> Point3<T> {
+    /// 点(x, y, z)を作成
+    pub const fn new(x: T, y: T, z: T) -> Self {
+        Point3 { x, y, z }
+    }
+}
+
+impl<T: Copy + Add<Output = T> + Sub<Output = T> + Mul<Output = T>> Point3<T> {
+    /// 内積
+    pub fn dot(self, rhs: Self) -> T {
+        self.x * rhs.x + self.y * rhs.y + self.z * rhs.z
+    }
+
+    /// 外積
+    pub fn cross(self, rhs: Self) -> Self {
+        Point3::new(
+            self.y * rhs.z - self.z * rhs.y,
+            self.z * rhs.x - self.x * rhs.z,
+            self.x * rhs.y - self.y * rhs.x,
+        )
+    }
+
+    /// 長さの2乗
+    pub fn norm2(self) -> T {
+        self.dot(self)
+    }
+}
+
+impl Point3<f64> {
+    /// 長さ
+    pub fn norm(self) -> f64 {
+        self.norm2().sqrt()
+    }
+}
+
+impl Point3<i64> {
+    /// 浮動小数点数の座標に変換する
+    pub fn to_f64(self) -> Point3<f64> {
+        Point3::new(self.x as f64, self.y as f64, self.z as f64)
+    }
+}
+
+impl<T: Add<Output = T>> Add for Point3<T> {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self {
+        Point3::new(self.x + rhs.x, self.y + rhs.y, self.z + rhs.z)
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Point3<T> {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self {
+        Point3::new(self.x - rhs.x, self.y - rhs.y, self.z - rhs.z)
+    }
+}
+
+impl<T: Copy + Mul<Output = T>> Mul<T> for Point3<T> {
+    type Output = Self;
+    /// スカラー倍
+    fn mul(self, k: T) -> Self {
+        Point3::new(self.x * k, self.y * k, self.z * k)
+    }
+}
+
+impl<T: Neg<Output = T>> Neg for Point3<T> {
+    type Output = Self;
+    fn neg(self) -> Self {
+        Point3::new(-self.x, -self.y, -self.z)
+    }
+}
+
+impl<T> From<(T, T, T)> for Point3<T> {
+    fn from((x, y, z): (T, T, T)) -> Self {
+        Point3::new(x, y, z)
+    }
+}
+
+/// 四面体abcdの符号付き体積の6倍
+///
+/// dから見てa, b, cが反時計回りに並ぶなら正になります。0なら4点は同一平面上です。
+pub fn signed_volume6(a: Point3<i64>, b: Point3<i64>, c: Point3<i64>, d: Point3<i64>) -> i64 {
+    (b - a).cross(c - a).dot(d - a)
+}
+
+/// 整数座標の3点を通る平面 `normal · p = offset`
+///
+/// # 使用例
+/// ```
+/// # use rust_macro::geometry::{Plane, Point3};
+/// let p = |x, y, z| Point3::new(x, y, z);
+/// let plane = Plane::from_points(p(0, 0, 1), p(1, 0, 1), p(0, 1, 1)).unwrap();
+/// assert_eq!(plane.side(p(5, 5, 3)), 2);
+/// assert_eq!(plane.dist(p(5, 5, -2)), 3.0);
+/// assert!(Plane::from_points(p(0, 0, 0), p(1, 1, 1), p(2, 2, 2)).is_none());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Plane {
+    /// 法線ベクトル（a, b, cが反時計回りに見える側を向く）
+    pub normal: Point3<i64>,
+    pub offset: i64,
+}
+
+impl Plane {
+    /// 3点a, b, cを通る平面（3点が同一直線上なら`None`）
+    pub fn from_points(a: Point3<i64>, b: Point3<i64>, c: Point3<i64>) -> Option<Self> {
+        let normal = (b - a).cross(c - a);
+        if normal == Point3::default() {
+            return None;
+        }
+        Some(Plane {
+            normal,
+            offset: normal.dot(a),
+        })
+    }
+
+    /// 点pが法線の向く側にあれば正、反対側なら負、平面上なら0となる値
+    /// （平面との距離に法線の長さを掛けたもの）
+    pub fn side(&self, p: Point3<i64>) -> i64 {
+        self.normal.dot(p) - self.offset
+    }
+
+    /// 点pと平面の距離
+    pub fn dist(&self, p: Point3<i64>) -> f64 {
+        (self.side(p) as f64).abs() / self.normal.to_f64().norm()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_point3_ops() {
+        let a = Point3::new(1i64, 2, 3);
+        let b = Point3::new(4, 5, 6);
+        assert_eq!(a.cross(b), Point3::new(-3, 6, -3));
+        assert_eq!(a.cross(b).dot(a), 0);
+        assert_eq!(a.dot(b), 32);
+        assert_eq!(b - a, Point3::new(3, 3, 3));
+        assert_eq!(-a * 2, Point3::new(-2, -4, -6));
+        assert_eq!(Point3::new(2i64, 3, 6).to_f64().norm(), 7.0);
+    }
+
+    #[test]
+    fn test_plane_and_volume() {
+        let o = Point3::new(0i64, 0, 0);
+        let (x, y, z) = (
+            Point3::new(1, 0, 0),
+            Point3::new(0, 1, 0),
+            Point3::new(0, 0, 1),
+        );
+        assert_eq!(signed_volume6(o, x, y, z), 1);
+        assert_eq!(signed_volume6(o, y, x, z), -1);
+        assert_eq!(signed_volume6(o, x, y, x + y), 0);
+        // x + y + z = 1
+        let plane = Plane::from_points(x, y, z).unwrap();
+        assert_eq!(plane.side(o), -1);
+        assert!((plane.dist(o) - 1.0 / 3f64.sqrt()).abs() < 1e-12);
+        assert_eq!(plane.side(Point3::new(1, 1, -1)), 0);
+    }
+}
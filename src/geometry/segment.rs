@@ -0,0 +1,204 @@
+use super::Point;
+use crate::math::Rational;
+
+type P = Point<i64>;
+
+/// 点cが有向線分abに対してどちら側にあるか
+///
+/// # 戻り値
+/// 左側（反時計回り）なら1、右側なら-1、直線ab上なら0
+pub fn ccw(a: P, b: P, c: P) -> i64 {
+    (b - a).cross(c - a).signum()
+}
+
+/// 点pが線分ab上（端点を含む）にあるかどうか
+pub fn on_segment(p: P, a: P, b: P) -> bool {
+    (a - p).cross(b - p) == 0 && (a - p).dot(b - p) <= 0
+}
+
+/// 線分abと線分cdが共有点を持つかどうか（端点での接触や重なりを含む）
+///
+/// # 使用例
+/// ```
+/// # use rust_macro::geometry::{segments_intersect, Point};
+/// let p = |x, y| Point::new(x, y);
+/// assert!(segments_intersect(p(0, 0), p(2, 2), p(0, 2), p(2, 0)));
+/// assert!(segments_intersect(p(0, 0), p(2, 0), p(2, 0), p(3, 5)));
+/// assert!(!segments_intersect(p(0, 0), p(1, 0), p(2, 0), p(3, 0)));
+/// ```
+pub fn segments_intersect(a: P, b: P, c: P, d: P) -> bool {
+    let (d1, d2) = (ccw(a, b, c) * ccw(a, b, d), ccw(c, d, a) * ccw(c, d, b));
+    if ccw(a, b, c) == 0 && ccw(a, b, d) == 0 {
+        // 同一直線上: 区間として重なるかどうか
+        return on_segment(c, a, b)
+            || on_segment(d, a, b)
+            || on_segment(a, c, d)
+            || on_segment(b, c, d);
+    }
+    d1 <= 0 && d2 <= 0
+}
+
+/// 直線abと直線cdの交点（平行または一致する場合は`None`）
+///
+/// 座標の絶対値が10^9以下なら、交点の分母は約8×10^18、分子は約2.4×10^28以下に収まり、
+/// 交点同士の比較は常に正しく行えます。一方、交点同士の和や積は`i128`に収まらないことがあり、
+/// その場合`Rational`の演算はpanicするので、`Rational::checked_add`などを使ってください。
+///
+/// # パニック
+/// 座標の絶対値が10^9を大きく超えると、外積の計算が`i64`でオーバーフローし得ます
+///
+/// # 使用例
+/// ```
+/// # use rust_macro::geometry::{line_intersection, Point};
+/// # use rust_macro::math::Rational;
+/// let p = |x, y| Point::new(x, y);
+/// let (x, y) = line_intersection(p(0, 0), p(1, 1), p(0, 1), p(3, 1)).unwrap();
+/// assert_eq!((x, y), (Rational::from(1), Rational::from(1)));
+/// let (x, y) = line_intersection(p(0, 0), p(2, 1), p(0, 1), p(1, 0)).unwrap();
+/// assert_eq!((x, y), (Rational::new(2, 3), Rational::new(1, 3)));
+/// assert_eq!(line_intersection(p(0, 0), p(1, 1), p(1, 0), p(2, 1)), None);
+/// ```
+pub fn line_intersection(a: P, b: P, c: P, d: P) -> Option<(Rational, Rational)> {
+    let (d1, d2) = (b - a, d - c);
+    let den = d1.cross(d2) as i128;
+    if den == 0 {
+        return None;
+    }
+    // 交点は a + d1 * t (t = num / den)
+    let num = (c - a).cross(d2) as i128;
+    let x = Rational::new(a.x as i128 * den + d1.x as i128 * num, den);
+    let y = Rational::new(a.y as i128 * den + d1.y as i128 * num, den);
+    Some((x, y))
+}
+
+/// 線分abと線分cdの共有点を1つ返す（共有点がなければ`None`）
+///
+/// 同一直線上で重なる場合は、重なりに含まれる端点のうちの1つを返します。
+pub fn segment_intersection(a: P, b: P, c: P, d: P) -> Option<(Rational, Rational)> {
+    if !segments_intersect(a, b, c, d) {
+        return None;
+    }
+    line_intersection(a, b, c, d).or_else(|| {
+        [c, d, a, b]
+            .into_iter()
+            .find(|&p| on_segment(p, a, b) && on_segment(p, c, d))
+            .map(|p| (Rational::from(p.x), Rational::from(p.y)))
+    })
+}
+
+/// 点pと線分abの距離
+///
+/// # 使用例
+/// ```
+/// # use rust_macro::geometry::{dist_point_segment, Point};
+/// let p = |x, y| Point::new(x, y);
+/// assert_eq!(dist_point_segment(p(1, 1), p(0, 0), p(2, 0)), 1.0);
+/// assert_eq!(dist_point_segment(p(5, 4), p(0, 0), p(2, 0)), 5.0);
+/// ```
+pub fn dist_point_segment(p: P, a: P, b: P) -> f64 {
+    if a == b || (p - a).dot(b - a) <= 0 {
+        return (p - a).to_f64().norm();
+    }
+    if (p - b).dot(a - b) <= 0 {
+        return (p - b).to_f64().norm();
+    }
+    ((b - a).cross(p - a) as f64).abs() / (b - a).to_f64().norm()
+}
+
+/// 線分abと線分cdの距離
+pub fn dist_segment_segment(a: P, b: P, c: P, d: P) -> f64 {
+    if segments_intersect(a, b, c, d) {
+        return 0.0;
+    }
+    dist_point_segment(a, c, d)
+        .min(dist_point_segment(b, c, d))
+        .min(dist_point_segment(c, a, b))
+        .min(dist_point_segment(d, a, b))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::XorShift64;
+
+    fn p(x: i64, y: i64) -> P {
+        Point::new(x, y)
+    }
+
+    #[test]
+    fn test_line_intersection_large_coordinates() {
+        // 分子×分母が i128 に収まらない交点同士の比較
+        let a = (p(-999_999_926, -999_999_227), p(999_999_854, 999_999_246));
+        let (x1, y1) = line_intersection(
+            a.0,
+            a.1,
+            p(-999_999_108, 999_999_401),
+            p(999_999_953, -999_999_925),
+        )
+        .unwrap();
+        let (x2, _) = line_intersection(
+            a.0,
+            a.1,
+            p(-999_999_960, 999_999_249),
+            p(999_999_798, -999_999_388),
+        )
+        .unwrap();
+        assert_eq!(
+            x1,
+            Rational::new(460_000_051_458_954_943_122, 7_999_993_280_001_582_133)
+        );
+        assert_eq!(
+            x2,
+            Rational::new(-391_999_771_595_020_898_922, 3_999_996_648_000_334_697)
+        );
+        assert!(x1 > x2);
+        assert_eq!(x1.cmp(&x1), std::cmp::Ordering::Equal);
+        assert!(x1.checked_mul(x2).is_none());
+        assert!(y1 > Rational::from(102) && y1 < Rational::from(103));
+    }
+
+    #[test]
+    fn test_segment_intersection_cases() {
+        // 交差
+        let r = segment_intersection(p(0, 0), p(4, 4), p(0, 4), p(4, 0));
+        assert_eq!(r, Some((Rational::from(2), Rational::from(2))));
+        // T字で接する
+        assert!(segments_intersect(p(0, 0), p(4, 0), p(2, 0), p(2, 3)));
+        // 延長線上でのみ交わる
+        assert!(!segments_intersect(p(0, 0), p(1, 1), p(3, 0), p(2, 1)));
+        assert!(segment_intersection(p(0, 0), p(1, 1), p(3, 0), p(2, 1)).is_none());
+        // 同一直線上で重なる
+        let r = segment_intersection(p(0, 0), p(4, 0), p(3, 0), p(9, 0)).unwrap();
+        assert_eq!(r, (Rational::from(3), Rational::from(0)));
+        // 同一直線上で離れている
+        assert!(!segments_intersect(p(0, 0), p(1, 1), p(2, 2), p(3, 3)));
+        // 退化した線分（点）
+        assert!(segments_intersect(p(1, 1), p(1, 1), p(0, 0), p(2, 2)));
+        assert!(on_segment(p(1, 1), p(0, 0), p(2, 2)));
+        assert!(!on_segment(p(3, 3), p(0, 0), p(2, 2)));
+        assert_eq!(ccw(p(0, 0), p(1, 0), p(0, 1)), 1);
+    }
+
+    #[test]
+    fn test_intersection_point_on_both_segments() {
+        let mut rng = XorShift64::new(91);
+        let mut r = || p(rng.gen_range_i64(-5..6), rng.gen_range_i64(-5..6));
+        for _ in 0..2000 {
+            let (a, b, c, d) = (r(), r(), r(), r());
+            if let Some((x, y)) = segment_intersection(a, b, c, d) {
+                // 交点が両方の線分のバウンディングボックス内にあり、直線上にある
+                for (s, t) in [(a, b), (c, d)] {
+                    let (lx, hx) = (s.x.min(t.x), s.x.max(t.x));
+                    let (ly, hy) = (s.y.min(t.y), s.y.max(t.y));
+                    assert!(Rational::from(lx) <= x && x <= Rational::from(hx));
+                    assert!(Rational::from(ly) <= y && y <= Rational::from(hy));
+                    let (dx, dy) = (t.x - s.x, t.y - s.y);
+                    let lhs = (x - Rational::from(s.x)) * Rational::from(dy);
+                    assert_eq!(lhs, (y - Rational::from(s.y)) * Rational::from(dx));
+                }
+            }
+            let dist = dist_segment_segment(a, b, c, d);
+            assert_eq!(dist == 0.0, segments_intersect(a, b, c, d));
+        }
+    }
+}
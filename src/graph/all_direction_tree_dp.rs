@@ -0,0 +1,271 @@
+use rustc_hash::FxHasher;
+use std::collections::HashMap;
+use std::hash::{BuildHasherDefault, Hash};
+
+use super::{Graph, Tree};
+
+/// 全方位木DPで解く問題を定義するトレイト
+///
+/// 頂点vを根とした部分木のDP値を
+/// `add_node(merge(add_edge(子1の値, 辺1), add_edge(子2の値, 辺2), ...), v)`
+/// の形で計算できる問題を扱います。子を1つずつ合成する`merge`は結合的で、
+/// 子の順番によらず同じ結果になる必要があります（`identity`はその単位元）。
+/// 辺の重みは`add_edge`で子の値に反映します。
+pub trait AllDirectionTreeDp<I, EW, NW> {
+    /// 各ノードで計算・保持されるDP値の型
+    type Value: Clone;
+
+    /// `merge`の単位元（子がないときの合成結果）
+    fn identity(&self) -> Self::Value;
+
+    /// 子から集めた値同士を合成します
+    fn merge(&self, a: &Self::Value, b: &Self::Value) -> Self::Value;
+
+    /// 子の部分木の値に、親子間の辺を付け加えます
+    ///
+    /// # 引数
+    /// * `child_value` - 子を根とする部分木のDP値
+    /// * `edge_weight` - 親子間の辺の重み（親側の隣接リストに格納されたもの）
+    fn add_edge(&self, child_value: &Self::Value, edge_weight: Option<&EW>) -> Self::Value;
+
+    /// 子の値をすべて合成したものに、根となるノード自身を付け加えます
+    ///
+    /// # 引数
+    /// * `merged` - 子の値を`add_edge`してから`merge`したもの
+    /// * `key` - ノードのキー
+    /// * `node_weight` - ノードの重み
+    fn add_node(&self, merged: &Self::Value, key: &I, node_weight: Option<&NW>) -> Self::Value;
+}
+
+/// `AllDirectionTreeDp`に基づいて全方位木DPを実行するソルバー
+///
+/// 各ノードについて、そのノードを根としたときのDP値を求めます。
+/// 辺は両方向に張られていることを前提とし、森の場合は連結成分ごとに計算します。
+/// 再帰を使わないため、深い木でもスタックを広げる必要はありません。
+///
+/// # 計算量
+/// O(V + E)（`merge`などの呼び出し回数）
+///
+/// # 使用例
+/// ```
+/// # use rust_macro::{Graph, Tree};
+/// # use rust_macro::graph::{AllDirectionTreeDp, AllDirectionTreeDpSolver};
+/// // 各ノードから他のすべてのノードへの距離の和（辺の重み付き）
+/// struct DistSum;
+/// impl AllDirectionTreeDp<usize, u64, ()> for DistSum {
+///     type Value = (u64, u64); // (ノード数, 距離の和)
+///     fn identity(&self) -> (u64, u64) { (0, 0) }
+///     fn merge(&self, a: &(u64, u64), b: &(u64, u64)) -> (u64, u64) { (a.0 + b.0, a.1 + b.1) }
+///     fn add_edge(&self, &(cnt, sum): &(u64, u64), w: Option<&u64>) -> (u64, u64) {
+///         (cnt, sum + cnt * w.unwrap())
+///     }
+///     fn add_node(&self, &(cnt, sum): &(u64, u64), _: &usize, _: Option<&()>) -> (u64, u64) {
+///         (cnt + 1, sum)
+///     }
+/// }
+///
+/// // 0 -(1)- 1 -(2)- 2
+/// //         |
+/// //        (3)
+/// //         |
+/// //         3
+/// let mut graph = Graph::<usize, u64, (), Tree>::new();
+/// for (u, v, w) in [(0, 1, 1), (1, 2, 2), (1, 3, 3)] {
+///     graph.add_edge(u, v, Some(w));
+///     graph.add_edge(v, u, Some(w));
+/// }
+/// let result = AllDirectionTreeDpSolver.solve(&graph, &DistSum);
+/// assert_eq!(result[&0].1, 1 + 3 + 4);
+/// assert_eq!(result[&1].1, 1 + 2 + 3);
+/// assert_eq!(result[&3].1, 3 + 4 + 5);
+/// ```
+pub struct AllDirectionTreeDpSolver;
+
+impl AllDirectionTreeDpSolver {
+    /// 全方位木DPを実行し、各ノードのキーからそのノードを根としたDP値へのHashMapを返します
+    pub fn solve<I, EW, NW, P>(
+        &self,
+        graph: &Graph<I, EW, NW, Tree>,
+        problem: &P,
+    ) -> HashMap<I, P::Value, BuildHasherDefault<FxHasher>>
+    where
+        I: Clone + Eq + Hash,
+        P: AllDirectionTreeDp<I, EW, NW>,
+    {
+        let n = graph.nodes.len();
+        let node = |u: usize, merged: &P::Value| {
+            problem.add_node(
+                merged,
+                &graph.reverse_map[u],
+                graph.nodes[u].weight.as_ref(),
+            )
+        };
+
+        // BFS順と親を求める
+        let mut parent = vec![usize::MAX; n];
+        let mut visited = vec![false; n];
+        let mut order = Vec::with_capacity(n);
+        for s in 0..n {
+            if visited[s] {
+                continue;
+            }
+            visited[s] = true;
+            let head = order.len();
+            order.push(s);
+            for i in head.. {
+                let Some(&u) = order.get(i) else { break };
+                for &(v, _) in &graph.adj[u] {
+                    if !visited[v] {
+                        visited[v] = true;
+                        parent[v] = u;
+                        order.push(v);
+                    }
+                }
+            }
+        }
+
+        // down[v]: vを根とする（親方向を除いた）部分木のDP値
+        let mut down: Vec<Option<P::Value>> = vec![None; n];
+        for &u in order.iter().rev() {
+            let mut acc = problem.identity();
+            for (v, w) in &graph.adj[u] {
+                if *v != parent[u] {
+                    let lifted = problem.add_edge(down[*v].as_ref().unwrap(), w.as_ref());
+                    acc = problem.merge(&acc, &lifted);
+                }
+            }
+            down[u] = Some(node(u, &acc));
+        }
+
+        // up[v]: vの親を根とし、vの部分木を除いた木のDP値
+        let mut up: Vec<Option<P::Value>> = vec![None; n];
+        let mut result: Vec<Option<P::Value>> = vec![None; n];
+        for &u in &order {
+            let lifted: Vec<P::Value> = graph.adj[u]
+                .iter()
+                .map(|(v, w)| {
+                    let value = if *v == parent[u] { &up[u] } else { &down[*v] };
+                    problem.add_edge(value.as_ref().unwrap(), w.as_ref())
+                })
+                .collect();
+            // suffix[k] = lifted[k..]の合成
+            let mut suffix = vec![problem.identity(); lifted.len() + 1];
+            for k in (0..lifted.len()).rev() {
+                suffix[k] = problem.merge(&lifted[k], &suffix[k + 1]);
+            }
+            let mut prefix = problem.identity();
+            for (k, (v, _)) in graph.adj[u].iter().enumerate() {
+                if *v != parent[u] {
+                    up[*v] = Some(node(u, &problem.merge(&prefix, &suffix[k + 1])));
+                }
+                prefix = problem.merge(&prefix, &lifted[k]);
+            }
+            result[u] = Some(node(u, &suffix[0]));
+        }
+
+        result
+            .into_iter()
+            .enumerate()
+            .map(|(id, value)| (graph.reverse_map[id].clone(), value.unwrap()))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::XorShift64;
+
+    /// (ノード数, 距離の和)
+    struct DistSum;
+    impl AllDirectionTreeDp<usize, u64, ()> for DistSum {
+        type Value = (u64, u64);
+        fn identity(&self) -> (u64, u64) {
+            (0, 0)
+        }
+        fn merge(&self, a: &(u64, u64), b: &(u64, u64)) -> (u64, u64) {
+            (a.0 + b.0, a.1 + b.1)
+        }
+        fn add_edge(&self, &(cnt, sum): &(u64, u64), w: Option<&u64>) -> (u64, u64) {
+            (cnt, sum + cnt * w.unwrap())
+        }
+        fn add_node(&self, &(cnt, sum): &(u64, u64), _: &usize, _: Option<&()>) -> (u64, u64) {
+            (cnt + 1, sum)
+        }
+    }
+
+    #[test]
+    fn test_dist_sum_against_naive() {
+        let mut rng = XorShift64::new(4269);
+        for _ in 0..50 {
+            let n = rng.gen_range(1..30) as usize;
+            let mut adj = vec![vec![]; n];
+            let mut graph = Graph::<usize, u64, (), Tree>::new();
+            graph.get_or_create_id(0);
+            for v in 1..n {
+                let p = rng.gen_range(0..v as u64) as usize;
+                let w = rng.gen_range(1..10);
+                graph.add_edge(v, p, Some(w));
+                graph.add_edge(p, v, Some(w));
+                adj[v].push((p, w));
+                adj[p].push((v, w));
+            }
+            let result = AllDirectionTreeDpSolver.solve(&graph, &DistSum);
+            for s in 0..n {
+                let mut dist = vec![u64::MAX; n];
+                dist[s] = 0;
+                let mut stack = vec![s];
+                while let Some(u) = stack.pop() {
+                    for &(v, w) in &adj[u] {
+                        if dist[v] == u64::MAX {
+                            dist[v] = dist[u] + w;
+                            stack.push(v);
+                        }
+                    }
+                }
+                assert_eq!(result[&s], (n as u64, dist.iter().sum()));
+            }
+        }
+    }
+
+    /// 各ノードから最も遠いノードまでの「辺の重み + 行き先のノードの重み」の最大値
+    struct Farthest;
+    impl AllDirectionTreeDp<&str, i64, i64> for Farthest {
+        type Value = i64;
+        fn identity(&self) -> i64 {
+            0
+        }
+        fn merge(&self, a: &i64, b: &i64) -> i64 {
+            *a.max(b)
+        }
+        fn add_edge(&self, child: &i64, w: Option<&i64>) -> i64 {
+            child + w.unwrap_or(&1)
+        }
+        fn add_node(&self, merged: &i64, _: &&str, weight: Option<&i64>) -> i64 {
+            merged + weight.unwrap_or(&0)
+        }
+    }
+
+    #[test]
+    fn test_forest_with_str_keys_and_node_weights() {
+        let mut graph = Graph::<&str, i64, i64, Tree>::new();
+        // a -(2)- b -(None)- c,  d -(5)- e,  f
+        for (u, v, w) in [("a", "b", Some(2)), ("b", "c", None), ("d", "e", Some(5))] {
+            graph.add_edge(u, v, w);
+            graph.add_edge(v, u, w);
+        }
+        graph.add_weight_to_node("c", 10);
+        graph.add_weight_to_node("f", 7);
+        let result = AllDirectionTreeDpSolver.solve(&graph, &Farthest);
+        assert_eq!(result.len(), 6);
+        // a: a->b->c = 2 + 1 + 10
+        assert_eq!(result["a"], 13);
+        // b: max(b->a = 2, b->c = 1 + 10)
+        assert_eq!(result["b"], 11);
+        // c: 自身の重み10 + c->b->a = 1 + 2
+        assert_eq!(result["c"], 13);
+        assert_eq!(result["d"], 5);
+        assert_eq!(result["e"], 5);
+        assert_eq!(result["f"], 7);
+    }
+}
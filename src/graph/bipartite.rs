@@ -0,0 +1,272 @@
+use rustc_hash::FxHasher;
+use std::collections::{HashMap, VecDeque};
+use std::hash::{BuildHasherDefault, Hash};
+
+use super::{Graph, Undirected};
+
+/// 二部グラフの最大マッチング（Hopcroft–Karp法）
+///
+/// 左側の頂点0..n_left、右側の頂点0..n_rightの間に辺を張り、最大マッチングを求めます。
+///
+/// # 計算量
+/// O(E √V)
+///
+/// # 使用例
+/// ```
+/// # use rust_macro::graph::BipartiteMatching;
+/// let mut bm = BipartiteMatching::new(3, 3);
+/// bm.add_edge(0, 0);
+/// bm.add_edge(0, 1);
+/// bm.add_edge(1, 0);
+/// bm.add_edge(2, 1);
+/// let matching = bm.max_matching();
+/// assert_eq!(matching.len(), 2);
+/// ```
+#[derive(Debug, Clone)]
+pub struct BipartiteMatching {
+    adj: Vec<Vec<usize>>,
+    n_right: usize,
+}
+
+impl BipartiteMatching {
+    /// 左側n_left頂点、右側n_right頂点で辺のない二部グラフを作成
+    pub fn new(n_left: usize, n_right: usize) -> Self {
+        BipartiteMatching {
+            adj: vec![vec![]; n_left],
+            n_right,
+        }
+    }
+
+    /// 左側の頂点leftと右側の頂点rightの間に辺を追加
+    ///
+    /// # パニック
+    /// 頂点番号が範囲外の場合panicします
+    pub fn add_edge(&mut self, left: usize, right: usize) {
+        assert!(
+            left < self.adj.len() && right < self.n_right,
+            "vertex index out of range"
+        );
+        self.adj[left].push(right);
+    }
+
+    /// 最大マッチングを求め、マッチした (左, 右) の組を左側の番号順に返す
+    pub fn max_matching(&self) -> Vec<(usize, usize)> {
+        let n = self.adj.len();
+        let mut match_l = vec![usize::MAX; n];
+        let mut match_r = vec![usize::MAX; self.n_right];
+        loop {
+            // 未マッチの左頂点からの交互路の長さでレベル分けする
+            let mut level = vec![usize::MAX; n];
+            let mut queue: VecDeque<usize> = (0..n).filter(|&u| match_l[u] == usize::MAX).collect();
+            for &u in &queue {
+                level[u] = 0;
+            }
+            let mut found = false;
+            while let Some(u) = queue.pop_front() {
+                for &r in &self.adj[u] {
+                    match match_r[r] {
+                        usize::MAX => found = true,
+                        w if level[w] == usize::MAX => {
+                            level[w] = level[u] + 1;
+                            queue.push_back(w);
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            if !found {
+                break;
+            }
+            // レベルに沿ったDFSで増加路を互いに素にまとめて流す（再帰を使わない）
+            let mut iter = vec![0; n];
+            for s in 0..n {
+                if match_l[s] != usize::MAX {
+                    continue;
+                }
+                let mut stack = vec![s];
+                while let Some(&u) = stack.last() {
+                    if iter[u] == self.adj[u].len() {
+                        level[u] = usize::MAX;
+                        stack.pop();
+                        continue;
+                    }
+                    let r = self.adj[u][iter[u]];
+                    iter[u] += 1;
+                    let w = match_r[r];
+                    if w == usize::MAX {
+                        // スタック上の各左頂点を、それぞれ直前にたどった右頂点とマッチさせる
+                        for &x in stack.iter().rev() {
+                            let r = self.adj[x][iter[x] - 1];
+                            match_l[x] = r;
+                            match_r[r] = x;
+                        }
+                        break;
+                    }
+                    if level[w] == level[u] + 1 {
+                        stack.push(w);
+                    }
+                }
+            }
+        }
+        match_l
+            .into_iter()
+            .enumerate()
+            .filter(|&(_, r)| r != usize::MAX)
+            .collect()
+    }
+}
+
+impl<I, EW, NW> Graph<I, EW, NW, Undirected>
+where
+    I: Clone + Eq + Hash,
+{
+    /// グラフを2色に塗り分ける
+    ///
+    /// 辺は両方向に張られていることを前提とします。各連結成分で最初に見つかった
+    /// ノードを`false`とし、隣り合うノードが異なる色になるように塗ります。
+    ///
+    /// # 戻り値
+    /// 塗り分けられる場合はノードのキーから色へのHashMap、奇閉路がある場合は`None`
+    ///
+    /// # 計算量
+    /// O(V + E)
+    ///
+    /// # 使用例
+    /// ```
+    /// # use rust_macro::{Graph, Undirected};
+    /// let mut g = Graph::<usize, (), (), Undirected>::new();
+    /// for (u, v) in [(0, 1), (1, 2), (2, 3)] {
+    ///     g.add_edge(u, v, None);
+    ///     g.add_edge(v, u, None);
+    /// }
+    /// let color = g.two_coloring().unwrap();
+    /// assert_ne!(color[&0], color[&1]);
+    /// assert_eq!(color[&0], color[&2]);
+    /// g.add_edge(0, 2, None);
+    /// g.add_edge(2, 0, None);
+    /// assert!(!g.is_bipartite());
+    /// ```
+    pub fn two_coloring(&self) -> Option<HashMap<I, bool, BuildHasherDefault<FxHasher>>> {
+        let n = self.nodes.len();
+        let mut color: Vec<Option<bool>> = vec![None; n];
+        for s in 0..n {
+            if color[s].is_some() {
+                continue;
+            }
+            color[s] = Some(false);
+            let mut stack = vec![s];
+            while let Some(u) = stack.pop() {
+                let c = color[u].unwrap();
+                for &(v, _) in &self.adj[u] {
+                    match color[v] {
+                        None => {
+                            color[v] = Some(!c);
+                            stack.push(v);
+                        }
+                        Some(cv) if cv == c => return None,
+                        _ => {}
+                    }
+                }
+            }
+        }
+        Some(
+            color
+                .into_iter()
+                .enumerate()
+                .map(|(id, c)| (self.reverse_map[id].clone(), c.unwrap()))
+                .collect(),
+        )
+    }
+
+    /// 二部グラフかどうか（奇閉路を持たないかどうか）
+    pub fn is_bipartite(&self) -> bool {
+        self.two_coloring().is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::XorShift64;
+
+    /// 辺の部分集合を全探索して最大マッチングの大きさを求める
+    fn naive_matching(edges: &[(usize, usize)]) -> usize {
+        let mut best = 0;
+        for mask in 0u32..1 << edges.len() {
+            let chosen: Vec<_> = (0..edges.len()).filter(|&i| mask >> i & 1 == 1).collect();
+            let ok = chosen.iter().enumerate().all(|(a, &i)| {
+                chosen[a + 1..]
+                    .iter()
+                    .all(|&j| edges[i].0 != edges[j].0 && edges[i].1 != edges[j].1)
+            });
+            if ok {
+                best = best.max(chosen.len());
+            }
+        }
+        best
+    }
+
+    #[test]
+    fn test_max_matching_against_naive() {
+        let mut rng = XorShift64::new(4263);
+        for _ in 0..200 {
+            let n_left = rng.gen_range(1..6) as usize;
+            let n_right = rng.gen_range(1..6) as usize;
+            let m = rng.gen_range(0..12) as usize;
+            let mut bm = BipartiteMatching::new(n_left, n_right);
+            let edges: Vec<(usize, usize)> = (0..m)
+                .map(|_| {
+                    let l = rng.gen_range(0..n_left as u64) as usize;
+                    let r = rng.gen_range(0..n_right as u64) as usize;
+                    bm.add_edge(l, r);
+                    (l, r)
+                })
+                .collect();
+            let matching = bm.max_matching();
+            assert_eq!(matching.len(), naive_matching(&edges));
+            for (a, p) in matching.iter().enumerate() {
+                assert!(edges.contains(p));
+                assert!(matching[a + 1..].iter().all(|q| p.0 != q.0 && p.1 != q.1));
+            }
+        }
+    }
+
+    #[test]
+    fn test_two_coloring_against_odd_cycle_check() {
+        let mut rng = XorShift64::new(42631);
+        for _ in 0..100 {
+            let n = rng.gen_range(1..8) as usize;
+            let m = rng.gen_range(0..10) as usize;
+            let edges: Vec<(usize, usize)> = (0..m)
+                .map(|_| {
+                    let u = rng.gen_range(0..n as u64) as usize;
+                    let v = rng.gen_range(0..n as u64) as usize;
+                    (u, v)
+                })
+                .collect();
+            let mut g = Graph::<usize, (), (), Undirected>::new();
+            for v in 0..n {
+                g.get_or_create_id(v);
+            }
+            for &(u, v) in &edges {
+                g.add_edge(u, v, None);
+                g.add_edge(v, u, None);
+            }
+            // 全ての塗り方を試す
+            let expected = (0u32..1 << n).any(|mask| {
+                edges
+                    .iter()
+                    .all(|&(u, v)| (mask >> u & 1) != (mask >> v & 1))
+            });
+            match g.two_coloring() {
+                Some(color) => {
+                    assert!(expected);
+                    assert_eq!(color.len(), n);
+                    assert!(edges.iter().all(|(u, v)| color[u] != color[v]));
+                }
+                None => assert!(!expected),
+            }
+            assert_eq!(g.is_bipartite(), expected);
+        }
+    }
+}
@@ -0,0 +1,198 @@
+use std::hash::Hash;
+
+use super::{Graph, GraphType};
+
+/// 隣接先を`u32`で持つ、省メモリな静的グラフ（CSR形式）
+///
+/// 全頂点の隣接リストを1本の配列に詰めて保持します。重みなし（`EW = ()`）なら
+/// 1辺あたり4バイトで、`Graph`の`Vec<Vec<(usize, Option<EW>)>>`（1辺16バイト）に比べて
+/// BFSやDijkstraで読むメモリ量が大きく減ります。構築後に辺を追加することはできません。
+///
+/// # 計算量
+/// 構築 O(n + m)、`neighbors` O(1)（列挙は次数に比例）
+///
+/// # 使用例
+/// ```
+/// # use rust_macro::graph::CompactGraph;
+/// let g = CompactGraph::from_undirected_edges(4, &[(0, 1, ()), (1, 2, ()), (1, 3, ())]);
+/// assert_eq!(g.degree(1), 3);
+/// let mut adj: Vec<usize> = g.neighbors(1).map(|(v, _)| v).collect();
+/// adj.sort();
+/// assert_eq!(adj, vec![0, 2, 3]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompactGraph<EW> {
+    start: Vec<usize>,
+    to: Vec<u32>,
+    weight: Vec<EW>,
+}
+
+impl<EW: Clone> CompactGraph<EW> {
+    /// n頂点と有向辺 `(u, v, w)` の列からグラフを作成する
+    ///
+    /// # パニック
+    /// 頂点番号がn以上の場合、またはnが`u32::MAX`を超える場合panicします
+    pub fn from_edges(n: usize, edges: &[(usize, usize, EW)]) -> Self {
+        Self::build(n, edges, false)
+    }
+
+    /// n頂点と無向辺 `(u, v, w)` の列からグラフを作成する（各辺を両方向に追加）
+    ///
+    /// # パニック
+    /// 頂点番号がn以上の場合、またはnが`u32::MAX`を超える場合panicします
+    pub fn from_undirected_edges(n: usize, edges: &[(usize, usize, EW)]) -> Self {
+        Self::build(n, edges, true)
+    }
+
+    fn build(n: usize, edges: &[(usize, usize, EW)], bidirectional: bool) -> Self {
+        assert!(n <= u32::MAX as usize, "too many vertices");
+        let mut start = vec![0; n + 1];
+        for &(u, v, _) in edges {
+            assert!(u < n && v < n, "vertex index out of range");
+            start[u + 1] += 1;
+            if bidirectional {
+                start[v + 1] += 1;
+            }
+        }
+        for i in 0..n {
+            start[i + 1] += start[i];
+        }
+        let m = start[n];
+        let mut pos = start.clone();
+        let mut to = vec![0; m];
+        let mut weight = Vec::with_capacity(m);
+        // 重みは位置を決めてから書き込むため、いったんOptionで受ける
+        let mut slots: Vec<Option<EW>> = vec![None; m];
+        for (u, v, w) in edges {
+            to[pos[*u]] = *v as u32;
+            slots[pos[*u]] = Some(w.clone());
+            pos[*u] += 1;
+            if bidirectional {
+                to[pos[*v]] = *u as u32;
+                slots[pos[*v]] = Some(w.clone());
+                pos[*v] += 1;
+            }
+        }
+        weight.extend(slots.into_iter().map(|w| w.unwrap()));
+        CompactGraph { start, to, weight }
+    }
+}
+
+impl<EW> CompactGraph<EW> {
+    /// 頂点数
+    pub fn len(&self) -> usize {
+        self.start.len() - 1
+    }
+
+    /// 頂点がないかどうか
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// 辺数（無向辺は2本として数える）
+    pub fn num_edges(&self) -> usize {
+        self.to.len()
+    }
+
+    /// 頂点uから出る辺の数
+    pub fn degree(&self, u: usize) -> usize {
+        self.start[u + 1] - self.start[u]
+    }
+
+    /// 頂点uの隣接頂点（`u32`のスライス）
+    pub fn targets(&self, u: usize) -> &[u32] {
+        &self.to[self.start[u]..self.start[u + 1]]
+    }
+
+    /// 頂点uから出る辺 `(行き先, &重み)` を列挙する
+    pub fn neighbors(&self, u: usize) -> impl Iterator<Item = (usize, &EW)> + '_ {
+        let range = self.start[u]..self.start[u + 1];
+        self.to[range.clone()]
+            .iter()
+            .zip(&self.weight[range])
+            .map(|(&v, w)| (v as usize, w))
+    }
+}
+
+impl<I, EW, NW, T> Graph<I, EW, NW, T>
+where
+    I: Clone + Eq + Hash,
+    EW: Clone,
+    T: GraphType,
+{
+    /// 内部IDを頂点番号とする`CompactGraph`に変換する
+    ///
+    /// `adj`の辺をそのまま（向きも含めて）コピーします。重みが`None`の辺は`default`になります。
+    /// 最短路で使う場合は、`multi_source_distances`と同じく長さ1（`Distance::ONE`）を渡します。
+    ///
+    /// # 使用例
+    /// ```
+    /// # use rust_macro::graph::{Directed, Graph};
+    /// let mut graph = Graph::<&str, u64, (), Directed>::new();
+    /// graph.add_edge("a", "b", Some(0));
+    /// graph.add_edge("b", "c", None);
+    /// let compact = graph.to_compact(1);
+    /// assert_eq!(compact.neighbors(0).collect::<Vec<_>>(), vec![(1, &0)]);
+    /// assert_eq!(compact.neighbors(1).collect::<Vec<_>>(), vec![(2, &1)]);
+    /// ```
+    pub fn to_compact(&self, default: EW) -> CompactGraph<EW> {
+        let default = &default;
+        let edges: Vec<(usize, usize, EW)> = self
+            .adj
+            .iter()
+            .enumerate()
+            .flat_map(|(u, es)| {
+                es.iter()
+                    .map(move |(v, w)| (u, *v, w.clone().unwrap_or_else(|| default.clone())))
+            })
+            .collect();
+        CompactGraph::from_edges(self.nodes.len(), &edges)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::Directed;
+    use crate::XorShift64;
+
+    #[test]
+    fn test_compact_matches_graph_adjacency() {
+        let mut rng = XorShift64::new(4238);
+        let n = 50;
+        let mut graph = Graph::<usize, u64, (), Directed>::new();
+        for v in 0..n {
+            graph.add_weight_to_node(v, ());
+        }
+        for _ in 0..300 {
+            let (u, v) = (rng.gen_range(0..n as u64), rng.gen_range(0..n as u64));
+            let w = rng.gen_range(0..10);
+            graph.add_edge(u as usize, v as usize, (w > 0).then_some(w));
+        }
+        let compact = graph.to_compact(100);
+        assert_eq!(compact.len(), n);
+        assert_eq!(compact.num_edges(), 300);
+        for u in 0..n {
+            let expected: Vec<(usize, u64)> = graph.adj[u]
+                .iter()
+                .map(|&(v, w)| (v, w.unwrap_or(100)))
+                .collect();
+            let actual: Vec<(usize, u64)> = compact.neighbors(u).map(|(v, &w)| (v, w)).collect();
+            assert_eq!(actual, expected);
+            assert_eq!(compact.degree(u), expected.len());
+        }
+    }
+
+    #[test]
+    fn test_compact_undirected_and_empty() {
+        let g = CompactGraph::from_undirected_edges(3, &[(0, 1, 5), (1, 1, 2)]);
+        assert_eq!(g.num_edges(), 4);
+        assert_eq!(g.targets(1), &[0, 1, 1]);
+        assert_eq!(g.neighbors(0).collect::<Vec<_>>(), vec![(1, &5)]);
+        assert_eq!(g.degree(2), 0);
+
+        let g = CompactGraph::<()>::from_edges(0, &[]);
+        assert!(g.is_empty());
+        assert_eq!(g.num_edges(), 0);
+    }
+}
@@ -0,0 +1,179 @@
+use std::hash::Hash;
+
+use super::{Directed, Graph, Undirected};
+
+/// 内部IDで閉路を1つ探す（再帰を使わないDFS）
+///
+/// `undirected`のときは、親から来た辺を1本だけ戻らないようにします（多重辺は長さ2の閉路になる）。
+fn find_cycle_ids<EW>(adj: &[Vec<(usize, Option<EW>)>], undirected: bool) -> Option<Vec<usize>> {
+    let n = adj.len();
+    // 0: 未訪問, 1: 探索中（スタック上）, 2: 探索済み
+    let mut state = vec![0u8; n];
+    let mut parent = vec![usize::MAX; n];
+    let mut skipped = vec![false; n];
+    for s in 0..n {
+        if state[s] != 0 {
+            continue;
+        }
+        state[s] = 1;
+        let mut stack = vec![(s, 0)];
+        while let Some(&mut (u, ref mut i)) = stack.last_mut() {
+            if *i == adj[u].len() {
+                state[u] = 2;
+                stack.pop();
+                continue;
+            }
+            let v = adj[u][*i].0;
+            *i += 1;
+            if undirected && v == parent[u] && !skipped[u] {
+                skipped[u] = true;
+                continue;
+            }
+            match state[v] {
+                0 => {
+                    state[v] = 1;
+                    parent[v] = u;
+                    stack.push((v, 0));
+                }
+                1 => {
+                    let pos = stack.iter().position(|&(x, _)| x == v).unwrap();
+                    return Some(stack[pos..].iter().map(|&(x, _)| x).collect());
+                }
+                _ => {}
+            }
+        }
+    }
+    None
+}
+
+macro_rules! impl_find_cycle {
+    ($t:ty, $undirected:expr) => {
+        impl<I: Clone + Eq + Hash, EW, NW> Graph<I, EW, NW, $t> {
+            /// 閉路を1つ見つけ、その頂点列を返す
+            ///
+            /// 戻り値の隣り合う頂点の間と、末尾から先頭へは辺があります。
+            /// 自己ループは長さ1、（無向グラフの）多重辺は長さ2の閉路として扱います。
+            /// 無向グラフでは辺が両方向に張られていることを前提とします。
+            ///
+            /// # 戻り値
+            /// 閉路がなければ`None`
+            ///
+            /// # 計算量
+            /// O(V + E)
+            ///
+            /// # 使用例
+            /// ```
+            /// # use rust_macro::{Directed, Graph};
+            /// // functional graph: 0 -> 1 -> 2 -> 3 -> 1
+            /// let mut g = Graph::<usize, (), (), Directed>::new();
+            /// for (u, v) in [(0, 1), (1, 2), (2, 3), (3, 1)] {
+            ///     g.add_edge(u, v, None);
+            /// }
+            /// assert_eq!(g.find_cycle(), Some(vec![1, 2, 3]));
+            /// ```
+            pub fn find_cycle(&self) -> Option<Vec<I>> {
+                find_cycle_ids(&self.adj, $undirected).map(|cycle| {
+                    cycle
+                        .into_iter()
+                        .map(|id| self.reverse_map[id].clone())
+                        .collect()
+                })
+            }
+        }
+    };
+}
+
+impl_find_cycle!(Directed, false);
+impl_find_cycle!(Undirected, true);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::XorShift64;
+
+    /// 閉路の頂点列として正しいか（重複がなく、辺でつながっているか）
+    fn assert_valid_cycle(edges: &[(usize, usize)], cycle: &[usize], undirected: bool) {
+        let k = cycle.len();
+        assert!(k >= 1);
+        for i in 0..k {
+            assert!(!cycle[i + 1..].contains(&cycle[i]));
+        }
+        // 無向グラフでは同じ辺を往復しないよう、使った辺を消していく
+        let mut rest = edges.to_vec();
+        for i in 0..k {
+            let (u, v) = (cycle[i], cycle[(i + 1) % k]);
+            let pos = rest
+                .iter()
+                .position(|&(a, b)| (a, b) == (u, v) || (undirected && (a, b) == (v, u)))
+                .expect("missing edge in cycle");
+            rest.swap_remove(pos);
+        }
+    }
+
+    #[test]
+    fn test_find_cycle_directed_against_topological_order() {
+        let mut rng = XorShift64::new(4265);
+        for _ in 0..200 {
+            let n = rng.gen_range(1..8) as usize;
+            let m = rng.gen_range(0..10) as usize;
+            let edges: Vec<(usize, usize)> = (0..m)
+                .map(|_| {
+                    let u = rng.gen_range(0..n as u64) as usize;
+                    let v = rng.gen_range(0..n as u64) as usize;
+                    (u, v)
+                })
+                .collect();
+            let mut g = Graph::<usize, (), (), Directed>::new();
+            for v in 0..n {
+                g.get_or_create_id(v);
+            }
+            for &(u, v) in &edges {
+                g.add_edge(u, v, None);
+            }
+            match g.find_cycle() {
+                Some(cycle) => {
+                    assert_valid_cycle(&edges, &cycle, false);
+                    assert!(g.topological_order().is_none());
+                }
+                None => assert!(g.topological_order().is_some()),
+            }
+        }
+    }
+
+    #[test]
+    fn test_find_cycle_undirected_against_forest_check() {
+        let mut rng = XorShift64::new(42651);
+        for _ in 0..200 {
+            let n = rng.gen_range(1..8) as usize;
+            let m = rng.gen_range(0..8) as usize;
+            let edges: Vec<(usize, usize)> = (0..m)
+                .map(|_| {
+                    let u = rng.gen_range(0..n as u64) as usize;
+                    let v = rng.gen_range(0..n as u64) as usize;
+                    (u, v)
+                })
+                .collect();
+            let mut g = Graph::<usize, (), (), Undirected>::new();
+            let mut uf = crate::UnionFind::new(n);
+            let mut is_forest = true;
+            for v in 0..n {
+                g.get_or_create_id(v);
+            }
+            for &(u, v) in &edges {
+                g.add_edge(u, v, None);
+                if u != v {
+                    g.add_edge(v, u, None);
+                }
+                is_forest &= !uf.same(u, v);
+                uf.unite(u, v);
+            }
+            match g.find_cycle() {
+                Some(cycle) => {
+                    assert!(!is_forest);
+                    assert_valid_cycle(&edges, &cycle, true);
+                }
+                None => assert!(is_forest),
+            }
+        }
+    }
+}
@@ -1,6 +1,78 @@
+use std::collections::VecDeque;
 use std::hash::Hash;
 
-use super::{Directed, Graph};
+use super::{Dag, Directed, Graph};
+
+/// Kahn's algorithm on internal ids. Returns `None` if the graph has a cycle.
+fn topological_sort<EW>(adj: &[Vec<(usize, Option<EW>)>]) -> Option<Vec<usize>> {
+    let n = adj.len();
+    let mut indeg = vec![0; n];
+    for edges in adj {
+        for &(to, _) in edges {
+            indeg[to] += 1;
+        }
+    }
+    let mut queue: VecDeque<usize> = (0..n).filter(|&v| indeg[v] == 0).collect();
+    let mut order = Vec::with_capacity(n);
+    while let Some(u) = queue.pop_front() {
+        order.push(u);
+        for &(to, _) in &adj[u] {
+            indeg[to] -= 1;
+            if indeg[to] == 0 {
+                queue.push_back(to);
+            }
+        }
+    }
+    (order.len() == n).then_some(order)
+}
+
+macro_rules! impl_topological_order {
+    ($t:ty) => {
+        impl<I: Clone + Eq + Hash, EW, NW> Graph<I, EW, NW, $t> {
+            /// Return the node keys in topological order
+            ///
+            /// Every edge `u -> v` has `u` before `v` in the returned order.
+            /// Ties are broken by Kahn's algorithm with a FIFO queue: nodes without incoming
+            /// edges come first in order of creation, and every other node follows in the order
+            /// its last incoming edge is removed. A node created early may therefore appear
+            /// after sources created later.
+            ///
+            /// # Returns
+            ///
+            /// `None` if the graph contains a cycle (including self-loops)
+            ///
+            /// # Examples
+            ///
+            /// ```rust
+            /// # use rust_macro::*;
+            /// let mut graph = Graph::<&str, (), (), Directed>::new();
+            /// graph.add_edge("shirt", "tie", None);
+            /// graph.add_edge("tie", "jacket", None);
+            /// graph.add_edge("pants", "jacket", None);
+            /// let order = graph.topological_order().unwrap();
+            /// assert_eq!(order, vec!["shirt", "pants", "tie", "jacket"]);
+            ///
+            /// graph.add_edge("jacket", "shirt", None);
+            /// assert_eq!(graph.topological_order(), None);
+            /// ```
+            ///
+            /// # Time Complexity
+            ///
+            /// - **O(V + E)** (Kahn's algorithm)
+            pub fn topological_order(&self) -> Option<Vec<I>> {
+                topological_sort(&self.adj).map(|order| {
+                    order
+                        .into_iter()
+                        .map(|id| self.reverse_map[id].clone())
+                        .collect()
+                })
+            }
+        }
+    };
+}
+
+impl_topological_order!(Directed);
+impl_topological_order!(Dag);
 
 impl<I, EW, NW> Graph<I, EW, NW, Directed>
 where
@@ -72,6 +144,8 @@ where
     /// - Only works on `Directed` graph types (compile-time restriction)
     /// - The returned DSU uses internal node indices (0 to n-1)
     /// - Self-loops and multiple edges are handled correctly
+    /// - Both DFS passes are recursive, so a long path (around 1e5 nodes) can overflow
+    ///   the default stack; call it inside `rust_macro::run_with_stack` in that case
     pub fn to_dsu(&self) -> crate::UnionFind {
         use crate::UnionFind;
 
@@ -149,3 +223,54 @@ where
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::XorShift64;
+
+    #[test]
+    fn test_topological_order_against_edges() {
+        let mut rng = XorShift64::new(4255);
+        for _ in 0..50 {
+            let n = rng.gen_range(1..12) as usize;
+            // ランダムな順列に沿った辺だけを張るとDAG、逆向きの辺を1本足すと閉路ができる
+            let mut perm: Vec<usize> = (0..n).collect();
+            rng.shuffle(&mut perm);
+            let mut graph = Graph::<usize, (), (), Dag>::new();
+            let mut edges = vec![];
+            for v in 0..n {
+                graph.get_or_create_id(v);
+            }
+            for _ in 0..2 * n {
+                let a = rng.gen_range(0..n as u64) as usize;
+                let b = rng.gen_range(0..n as u64) as usize;
+                if a < b {
+                    graph.add_edge(perm[a], perm[b], None);
+                    edges.push((perm[a], perm[b]));
+                }
+            }
+            let order = graph.topological_order().unwrap();
+            let mut pos = vec![0; n];
+            for (i, &v) in order.iter().enumerate() {
+                pos[v] = i;
+            }
+            assert_eq!(order.len(), n);
+            assert!(edges.iter().all(|&(u, v)| pos[u] < pos[v]));
+            if let Some(&(u, v)) = edges.first() {
+                graph.add_edge(v, u, None);
+                assert_eq!(graph.topological_order(), None);
+            }
+        }
+    }
+
+    #[test]
+    fn test_topological_order_tie_breaking() {
+        let mut graph = Graph::<char, (), (), Directed>::new();
+        // 'a'は最初に作られるが、'c'からの辺があるため後回しになる
+        graph.add_edge('a', 'b', None);
+        graph.add_edge('c', 'a', None);
+        graph.get_or_create_id('d');
+        assert_eq!(graph.topological_order().unwrap(), vec!['c', 'd', 'a', 'b']);
+    }
+}
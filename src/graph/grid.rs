@@ -0,0 +1,208 @@
+use super::{Graph, GraphType};
+
+/// 上下左右の4方向
+pub const DIR4: [(isize, isize); 4] = [(-1, 0), (1, 0), (0, -1), (0, 1)];
+
+/// 斜めを含む8方向
+pub const DIR8: [(isize, isize); 8] = [
+    (-1, 0),
+    (1, 0),
+    (0, -1),
+    (0, 1),
+    (-1, -1),
+    (-1, 1),
+    (1, -1),
+    (1, 1),
+];
+
+/// グリッドからグラフを作る共通処理
+///
+/// `is_node`を満たすマスを行優先でノードにし（重みはマスの値）、各マスから`moves`の各方向へ
+/// `can_move(移動元, 移動先)`を満たす場合に重み1の辺を張ります。
+fn build_grid<V, N, F, T>(
+    input: Vec<Vec<V>>,
+    moves: &[(isize, isize)],
+    is_node: N,
+    can_move: F,
+) -> Graph<(usize, usize), usize, V, T>
+where
+    V: Clone,
+    N: Fn(&V) -> bool,
+    F: Fn(&V, &V) -> bool,
+    T: GraphType,
+{
+    let h = input.len();
+    let mut graph = Graph::new();
+
+    for i in 0..h {
+        for j in 0..input[i].len() {
+            if !is_node(&input[i][j]) {
+                continue;
+            }
+            graph.add_weight_to_node((i, j), input[i][j].clone());
+            for &(di, dj) in moves {
+                let (ni, nj) = (i.wrapping_add_signed(di), j.wrapping_add_signed(dj));
+                if ni < h && nj < input[ni].len() {
+                    let (from, to) = (&input[i][j], &input[ni][nj]);
+                    if is_node(to) && can_move(from, to) {
+                        graph.add_edge((i, j), (ni, nj), Some(1));
+                    }
+                }
+            }
+        }
+    }
+    graph
+}
+
+/// グリッドから上下左右に移動できるグラフを作成する
+///
+/// `is_connectable`を満たすマスをノード（キーは`(行, 列)`、重みはマスの値）とし、
+/// 隣り合うノード同士を重み1の辺で両方向に結びます。
+///
+/// # 計算量
+/// O(HW)
+///
+/// # 使用例
+/// ```
+/// # use rust_macro::Undirected;
+/// # use rust_macro::graph::gen_grid_graph;
+/// let grid: Vec<Vec<char>> = ["..#", "#..", "..."].iter().map(|s| s.chars().collect()).collect();
+/// let graph = gen_grid_graph::<_, _, Undirected>(grid, |&c| c == '.');
+/// assert_eq!(graph.nodes.len(), 7);
+/// assert_eq!(graph.bfs((0, 0)).unwrap()[&(2, 0)], 4);
+/// ```
+pub fn gen_grid_graph<V, F, T>(
+    input: Vec<Vec<V>>,
+    is_connectable: F,
+) -> Graph<(usize, usize), usize, V, T>
+where
+    V: Clone,
+    F: Fn(&V) -> bool,
+    T: GraphType,
+{
+    gen_grid_graph_with_moves(input, &DIR4, is_connectable)
+}
+
+/// 移動方向を指定してグリッドからグラフを作成する
+///
+/// `moves`には`DIR4`、`DIR8`のほか、桂馬飛びのような任意の`(行の差, 列の差)`の列を渡せます。
+/// 移動先が`is_connectable`を満たすノードであれば辺を張ります（途中のマスは見ません）。
+///
+/// # 使用例
+/// ```
+/// # use rust_macro::Undirected;
+/// # use rust_macro::graph::grid::{gen_grid_graph_with_moves, DIR8};
+/// let grid = vec![vec![1, 0], vec![0, 1]];
+/// let graph = gen_grid_graph_with_moves::<_, _, Undirected>(grid, &DIR8, |&x| x == 1);
+/// assert_eq!(graph.bfs((0, 0)).unwrap()[&(1, 1)], 1);
+/// ```
+pub fn gen_grid_graph_with_moves<V, F, T>(
+    input: Vec<Vec<V>>,
+    moves: &[(isize, isize)],
+    is_connectable: F,
+) -> Graph<(usize, usize), usize, V, T>
+where
+    V: Clone,
+    F: Fn(&V) -> bool,
+    T: GraphType,
+{
+    build_grid(input, moves, &is_connectable, |_, to| is_connectable(to))
+}
+
+/// 移動元と移動先のマスの値を見て辺を張るかどうかを決め、グリッドからグラフを作成する
+///
+/// すべてのマスをノードとし、`can_move(移動元, 移動先)`を満たす方向にだけ辺を張ります。
+/// 「高さの差が1以下なら移動できる」のような、向きのある条件に使います。
+///
+/// # 使用例
+/// ```
+/// # use rust_macro::Directed;
+/// # use rust_macro::graph::grid::{gen_grid_graph_by, DIR4};
+/// // 高さが同じか低いマスへだけ移動できる
+/// let grid = vec![vec![3, 2, 1], vec![1, 5, 0]];
+/// let graph = gen_grid_graph_by::<_, _, Directed>(grid, &DIR4, |a, b| b <= a);
+/// let dist = graph.bfs((0, 0)).unwrap();
+/// assert_eq!(dist[&(1, 2)], 3);
+/// assert!(!dist.contains_key(&(1, 1)));
+/// ```
+pub fn gen_grid_graph_by<V, F, T>(
+    input: Vec<Vec<V>>,
+    moves: &[(isize, isize)],
+    can_move: F,
+) -> Graph<(usize, usize), usize, V, T>
+where
+    V: Clone,
+    F: Fn(&V, &V) -> bool,
+    T: GraphType,
+{
+    build_grid(input, moves, |_| true, can_move)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{Directed, Undirected};
+    use crate::XorShift64;
+
+    #[test]
+    fn test_grid_graph_connected() {
+        let g = vec![vec![1, 0, 0], vec![1, 1, 0], vec![0, 1, 1]];
+        let graph = gen_grid_graph::<_, _, Undirected>(g.clone(), |&x| x == 1);
+        // (0,0), (1,0), (1,1), (2,1), (2,2)
+        assert_eq!(graph.nodes.len(), 5);
+        assert_eq!(graph.bfs((0, 0)).unwrap()[&(2, 2)], 4);
+        assert_eq!(graph.get_node_weight(&(1, 1)), Some(&1));
+        assert!(graph.get_node_weight(&(0, 1)).is_none());
+
+        let graph = gen_grid_graph_with_moves::<_, _, Undirected>(g, &DIR8, |&x| x == 1);
+        assert_eq!(graph.bfs((0, 0)).unwrap()[&(2, 2)], 2);
+    }
+
+    #[test]
+    fn test_grid_graph_moves_against_naive() {
+        let knight = [
+            (1, 2),
+            (2, 1),
+            (-1, 2),
+            (-2, 1),
+            (1, -2),
+            (2, -1),
+            (-1, -2),
+            (-2, -1),
+        ];
+        let mut rng = XorShift64::new(4267);
+        for _ in 0..30 {
+            let h = rng.gen_range(1..6) as usize;
+            let w = rng.gen_range(1..6) as usize;
+            let grid: Vec<Vec<u64>> = (0..h)
+                .map(|_| (0..w).map(|_| rng.gen_range(0..4)).collect())
+                .collect();
+            for moves in [&DIR4[..], &DIR8[..], &knight[..]] {
+                let graph = gen_grid_graph_by::<_, _, Directed>(grid.clone(), moves, |a, b| {
+                    a.abs_diff(*b) <= 1
+                });
+                assert_eq!(graph.nodes.len(), h * w);
+                for i in 0..h {
+                    for j in 0..w {
+                        let mut expected = vec![];
+                        for &(di, dj) in moves {
+                            let (ni, nj) = (i as isize + di, j as isize + dj);
+                            if (0..h as isize).contains(&ni) && (0..w as isize).contains(&nj) {
+                                let (ni, nj) = (ni as usize, nj as usize);
+                                if grid[i][j].abs_diff(grid[ni][nj]) <= 1 {
+                                    expected.push((ni, nj));
+                                }
+                            }
+                        }
+                        let id = graph.coord_map[&(i, j)];
+                        let actual: Vec<_> = graph.adj[id]
+                            .iter()
+                            .map(|&(v, _)| graph.reverse_map[v])
+                            .collect();
+                        assert_eq!(actual, expected);
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,197 @@
+use rustc_hash::FxHasher;
+use std::collections::HashMap;
+use std::hash::{BuildHasherDefault, Hash};
+
+use super::{Graph, Tree};
+
+/// 木の最小共通祖先（LCA）をダブリングで求める構造体
+///
+/// `Graph<_, _, _, Tree>`（辺は両方向に張られていること）から、指定した根で構築します。
+/// 根から到達できないキーや、グラフに存在しないキーに対するクエリは`None`を返します。
+///
+/// # 計算量
+/// - 構築: O(n log n)
+/// - `lca`・`dist`: O(log n)
+/// - `depth`: O(1)
+///
+/// # 使用例
+/// ```
+/// # use rust_macro::{Graph, Tree};
+/// # use rust_macro::graph::Lca;
+/// //     1
+/// //    / \
+/// //   2   3
+/// //  / \
+/// // 4   5
+/// let mut graph = Graph::<usize, (), (), Tree>::new();
+/// for (u, v) in [(1, 2), (1, 3), (2, 4), (2, 5)] {
+///     graph.add_edge(u, v, None);
+///     graph.add_edge(v, u, None);
+/// }
+/// let lca = Lca::new(&graph, &1).unwrap();
+/// assert_eq!(lca.lca(&4, &5), Some(2));
+/// assert_eq!(lca.lca(&4, &3), Some(1));
+/// assert_eq!(lca.depth(&5), Some(2));
+/// assert_eq!(lca.dist(&4, &3), Some(3));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Lca<I> {
+    coord_map: HashMap<I, usize, BuildHasherDefault<FxHasher>>,
+    reverse_map: Vec<I>,
+    depth: Vec<usize>,
+    /// up[k][v] = vの2^k個上の祖先（根より上は根自身）
+    up: Vec<Vec<usize>>,
+}
+
+impl<I: Clone + Eq + Hash> Lca<I> {
+    /// 根を`root_key`として構築する
+    ///
+    /// # 戻り値
+    /// 根がグラフに存在しない場合は`Err`
+    pub fn new<EW, NW>(graph: &Graph<I, EW, NW, Tree>, root_key: &I) -> Result<Self, String> {
+        let root = graph
+            .key2id(root_key)
+            .ok_or_else(|| "Root key not found in graph.".to_string())?;
+        let n = graph.nodes.len();
+        let mut depth = vec![usize::MAX; n];
+        let mut parent = vec![root; n];
+        // 再帰を使わずBFSで親と深さを求める
+        let mut order = vec![root];
+        depth[root] = 0;
+        let mut i = 0;
+        while i < order.len() {
+            let u = order[i];
+            i += 1;
+            for &(v, _) in &graph.adj[u] {
+                if depth[v] == usize::MAX {
+                    depth[v] = depth[u] + 1;
+                    parent[v] = u;
+                    order.push(v);
+                }
+            }
+        }
+
+        let log = (usize::BITS - n.leading_zeros()).max(1) as usize;
+        let mut up = vec![parent];
+        for k in 1..log {
+            let prev = &up[k - 1];
+            let next = (0..n).map(|v| prev[prev[v]]).collect();
+            up.push(next);
+        }
+        Ok(Lca {
+            coord_map: graph.coord_map.clone(),
+            reverse_map: graph.reverse_map.clone(),
+            depth,
+            up,
+        })
+    }
+
+    fn id(&self, key: &I) -> Option<usize> {
+        self.coord_map
+            .get(key)
+            .copied()
+            .filter(|&id| self.depth[id] != usize::MAX)
+    }
+
+    fn lca_id(&self, mut u: usize, mut v: usize) -> usize {
+        if self.depth[u] < self.depth[v] {
+            std::mem::swap(&mut u, &mut v);
+        }
+        let diff = self.depth[u] - self.depth[v];
+        for (k, up) in self.up.iter().enumerate() {
+            if diff >> k & 1 == 1 {
+                u = up[u];
+            }
+        }
+        if u == v {
+            return u;
+        }
+        for up in self.up.iter().rev() {
+            if up[u] != up[v] {
+                u = up[u];
+                v = up[v];
+            }
+        }
+        self.up[0][u]
+    }
+
+    /// uとvの最小共通祖先
+    pub fn lca(&self, u: &I, v: &I) -> Option<I> {
+        let (u, v) = (self.id(u)?, self.id(v)?);
+        Some(self.reverse_map[self.lca_id(u, v)].clone())
+    }
+
+    /// 根からの深さ（辺の本数）
+    pub fn depth(&self, u: &I) -> Option<usize> {
+        self.id(u).map(|u| self.depth[u])
+    }
+
+    /// uとvの距離（辺の本数）
+    pub fn dist(&self, u: &I, v: &I) -> Option<usize> {
+        let (u, v) = (self.id(u)?, self.id(v)?);
+        Some(self.depth[u] + self.depth[v] - 2 * self.depth[self.lca_id(u, v)])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::XorShift64;
+
+    #[test]
+    fn test_lca_against_naive() {
+        let mut rng = XorShift64::new(4257);
+        for _ in 0..30 {
+            let n = rng.gen_range(1..60) as usize;
+            let parent: Vec<usize> = (0..n)
+                .map(|v| {
+                    if v == 0 {
+                        0
+                    } else {
+                        rng.gen_range(0..v as u64) as usize
+                    }
+                })
+                .collect();
+            let mut graph = Graph::<usize, (), (), Tree>::new();
+            graph.get_or_create_id(0);
+            for (v, &p) in parent.iter().enumerate().skip(1) {
+                graph.add_edge(v, p, None);
+                graph.add_edge(p, v, None);
+            }
+            let lca = Lca::new(&graph, &0).unwrap();
+            let ancestors = |mut v: usize| {
+                let mut res = vec![v];
+                while v != 0 {
+                    v = parent[v];
+                    res.push(v);
+                }
+                res
+            };
+            for u in 0..n {
+                let au = ancestors(u);
+                assert_eq!(lca.depth(&u), Some(au.len() - 1));
+                for v in 0..n {
+                    let av = ancestors(v);
+                    let w = *au.iter().find(|x| av.contains(x)).unwrap();
+                    assert_eq!(lca.lca(&u, &v), Some(w));
+                    let dw = ancestors(w).len();
+                    assert_eq!(lca.dist(&u, &v), Some(au.len() + av.len() - 2 * dw));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_lca_missing_and_unreachable_keys() {
+        let mut graph = Graph::<&str, (), (), Tree>::new();
+        graph.add_edge("a", "b", None);
+        graph.add_edge("b", "a", None);
+        graph.add_weight_to_node("island", ());
+        assert!(Lca::new(&graph, &"z").is_err());
+        let lca = Lca::new(&graph, &"b").unwrap();
+        assert_eq!(lca.lca(&"a", &"b"), Some("b"));
+        assert_eq!(lca.depth(&"a"), Some(1));
+        assert_eq!(lca.lca(&"a", &"island"), None);
+        assert_eq!(lca.dist(&"z", &"a"), None);
+    }
+}
@@ -0,0 +1,290 @@
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+#[derive(Debug, Clone)]
+struct InnerEdge {
+    to: usize,
+    rev: usize,
+    cap: i64,
+    cost: i64,
+}
+
+/// 最小費用流で追加した辺の状態
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FlowEdge {
+    pub from: usize,
+    pub to: usize,
+    pub cap: i64,
+    pub flow: i64,
+    pub cost: i64,
+}
+
+/// 最小費用流（ポテンシャル付きDijkstra法による逐次最短路）
+///
+/// 頂点0..nの有向グラフに容量と費用を持つ辺を追加し、sからtへの最小費用流を求めます。
+/// 辺の費用は非負である必要があります。
+///
+/// # 計算量
+/// O(F (n + m) log (n + m))（Fは流量）
+///
+/// # 使用例
+/// ```
+/// # use rust_macro::graph::MinCostFlow;
+/// let mut mcf = MinCostFlow::new(4);
+/// mcf.add_edge(0, 1, 2, 1);
+/// mcf.add_edge(0, 2, 1, 2);
+/// mcf.add_edge(1, 3, 1, 1);
+/// mcf.add_edge(2, 3, 2, 1);
+/// mcf.add_edge(1, 2, 1, 0);
+/// // 流量1ごとの費用は2, 2, 3
+/// assert_eq!(mcf.min_cost_flow(0, 3, 10), vec![(0, 0), (2, 4), (3, 7)]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct MinCostFlow {
+    graph: Vec<Vec<InnerEdge>>,
+    /// i番目に追加した辺の (from, graph[from]内の位置)
+    pos: Vec<(usize, usize)>,
+}
+
+impl MinCostFlow {
+    /// n頂点で辺のないグラフを作成
+    pub fn new(n: usize) -> Self {
+        MinCostFlow {
+            graph: vec![vec![]; n],
+            pos: vec![],
+        }
+    }
+
+    /// fromからtoへ容量cap、単位流量あたりの費用costの辺を追加し、その番号を返す
+    ///
+    /// # パニック
+    /// 頂点番号が範囲外の場合や、cap・costが負の場合panicします
+    pub fn add_edge(&mut self, from: usize, to: usize, cap: i64, cost: i64) -> usize {
+        let n = self.graph.len();
+        assert!(from < n && to < n, "vertex index out of range");
+        assert!(
+            cap >= 0 && cost >= 0,
+            "capacity and cost must be non-negative"
+        );
+        let id = self.pos.len();
+        let from_len = self.graph[from].len();
+        let to_len = self.graph[to].len() + usize::from(from == to);
+        self.pos.push((from, from_len));
+        self.graph[from].push(InnerEdge {
+            to,
+            rev: to_len,
+            cap,
+            cost,
+        });
+        self.graph[to].push(InnerEdge {
+            to: from,
+            rev: from_len,
+            cap: 0,
+            cost: -cost,
+        });
+        id
+    }
+
+    /// i番目に追加した辺の現在の状態
+    pub fn get_edge(&self, i: usize) -> FlowEdge {
+        let (from, j) = self.pos[i];
+        let e = &self.graph[from][j];
+        let re = &self.graph[e.to][e.rev];
+        FlowEdge {
+            from,
+            to: e.to,
+            cap: e.cap + re.cap,
+            flow: re.cap,
+            cost: e.cost,
+        }
+    }
+
+    /// 追加したすべての辺の現在の状態
+    pub fn edges(&self) -> Vec<FlowEdge> {
+        (0..self.pos.len()).map(|i| self.get_edge(i)).collect()
+    }
+
+    /// sからtへ流量`flow_limit`まで流したときの (流量, 費用)
+    pub fn flow(&mut self, s: usize, t: usize, flow_limit: i64) -> (i64, i64) {
+        *self.min_cost_flow(s, t, flow_limit).last().unwrap()
+    }
+
+    /// sからtへ流量`flow_limit`まで流し、流量と最小費用の関係（折れ線）の頂点を返す
+    ///
+    /// 戻り値は (流量, 費用) の列で、先頭は(0, 0)、末尾は実際に流した流量とその費用です。
+    /// 流量xの最小費用は、xを挟む2頂点の線形補間で求められます（費用は流量の凸関数）。
+    ///
+    /// # パニック
+    /// sとtが等しい場合や範囲外の場合panicします
+    pub fn min_cost_flow(&mut self, s: usize, t: usize, flow_limit: i64) -> Vec<(i64, i64)> {
+        let n = self.graph.len();
+        assert!(s < n && t < n && s != t, "invalid source or sink");
+        let mut potential = vec![0i64; n];
+        let (mut flow, mut cost) = (0, 0);
+        let mut slope = vec![(0, 0)];
+        let mut prev_cost_per_flow = -1;
+        while flow < flow_limit {
+            // 被約費用 cost + p[from] - p[to] >= 0 の下でDijkstra
+            let mut dist = vec![i64::MAX; n];
+            let mut prev = vec![(usize::MAX, usize::MAX); n];
+            let mut heap = BinaryHeap::new();
+            dist[s] = 0;
+            heap.push(Reverse((0, s)));
+            while let Some(Reverse((d, u))) = heap.pop() {
+                if d > dist[u] {
+                    continue;
+                }
+                for (i, e) in self.graph[u].iter().enumerate() {
+                    if e.cap == 0 {
+                        continue;
+                    }
+                    let nd = d + e.cost + potential[u] - potential[e.to];
+                    if nd < dist[e.to] {
+                        dist[e.to] = nd;
+                        prev[e.to] = (u, i);
+                        heap.push(Reverse((nd, e.to)));
+                    }
+                }
+            }
+            if dist[t] == i64::MAX {
+                break;
+            }
+            for v in 0..n {
+                if dist[v] != i64::MAX {
+                    potential[v] += dist[v];
+                }
+            }
+            // 最短路に沿って流せるだけ流す
+            let mut f = flow_limit - flow;
+            let mut v = t;
+            while v != s {
+                let (u, i) = prev[v];
+                f = f.min(self.graph[u][i].cap);
+                v = u;
+            }
+            let mut v = t;
+            while v != s {
+                let (u, i) = prev[v];
+                self.graph[u][i].cap -= f;
+                let (to, rev) = (self.graph[u][i].to, self.graph[u][i].rev);
+                self.graph[to][rev].cap += f;
+                v = u;
+            }
+            let d = potential[t] - potential[s];
+            flow += f;
+            cost += f * d;
+            // 単位費用が変わらない区間はまとめて1本の線分にする
+            if prev_cost_per_flow == d {
+                slope.pop();
+            }
+            slope.push((flow, cost));
+            prev_cost_per_flow = d;
+        }
+        slope
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::XorShift64;
+
+    /// 小さいグラフで、各辺の流量を全探索して最小費用を求める
+    fn brute_force(n: usize, edges: &[(usize, usize, i64, i64)], flow: i64) -> Option<i64> {
+        fn rec(
+            i: usize,
+            n: usize,
+            edges: &[(usize, usize, i64, i64)],
+            balance: &mut Vec<i64>,
+            cost: i64,
+            flow: i64,
+            best: &mut Option<i64>,
+        ) {
+            if i == edges.len() {
+                let ok = (0..n).all(|v| match v {
+                    0 => balance[v] == -flow,
+                    _ if v == n - 1 => balance[v] == flow,
+                    _ => balance[v] == 0,
+                });
+                if ok && best.map_or(true, |b| cost < b) {
+                    *best = Some(cost);
+                }
+                return;
+            }
+            let (u, v, cap, c) = edges[i];
+            for f in 0..=cap {
+                balance[u] -= f;
+                balance[v] += f;
+                rec(i + 1, n, edges, balance, cost + f * c, flow, best);
+                balance[u] += f;
+                balance[v] -= f;
+            }
+        }
+        let mut best = None;
+        rec(0, n, edges, &mut vec![0; n], 0, flow, &mut best);
+        best
+    }
+
+    #[test]
+    fn test_min_cost_flow_against_brute_force() {
+        let mut rng = XorShift64::new(4262);
+        for _ in 0..100 {
+            let n = rng.gen_range(2..5) as usize;
+            let m = rng.gen_range(1..6) as usize;
+            let edges: Vec<(usize, usize, i64, i64)> = (0..m)
+                .map(|_| {
+                    let u = rng.gen_range(0..n as u64) as usize;
+                    let v = rng.gen_range(0..n as u64) as usize;
+                    (u, v, rng.gen_range_i64(0..3), rng.gen_range_i64(0..5))
+                })
+                .collect();
+            let mut mcf = MinCostFlow::new(n);
+            for &(u, v, cap, cost) in &edges {
+                mcf.add_edge(u, v, cap, cost);
+            }
+            let slope = mcf.min_cost_flow(0, n - 1, i64::MAX);
+            let (max_flow, _) = *slope.last().unwrap();
+            assert_eq!(brute_force(n, &edges, max_flow + 1), None);
+            for x in 0..=max_flow {
+                // 折れ線上で流量xに対応する費用
+                let k = slope.iter().position(|&(f, _)| f >= x).unwrap();
+                let expected = brute_force(n, &edges, x).unwrap();
+                if k == 0 || slope[k].0 == x {
+                    assert_eq!(slope[k].1, expected);
+                } else {
+                    let ((f0, c0), (f1, c1)) = (slope[k - 1], slope[k]);
+                    assert_eq!(c0 + (c1 - c0) / (f1 - f0) * (x - f0), expected);
+                }
+            }
+            let total: i64 = mcf
+                .edges()
+                .iter()
+                .inspect(|e| assert!(0 <= e.flow && e.flow <= e.cap))
+                .map(|e| e.flow * e.cost)
+                .sum();
+            assert_eq!(total, slope.last().unwrap().1);
+        }
+    }
+
+    #[test]
+    fn test_flow_with_limit_and_edge_state() {
+        let mut mcf = MinCostFlow::new(3);
+        let a = mcf.add_edge(0, 1, 5, 2);
+        let b = mcf.add_edge(1, 2, 3, 1);
+        mcf.add_edge(0, 2, 1, 10);
+        assert_eq!(mcf.flow(0, 2, 2), (2, 6));
+        assert_eq!(
+            mcf.get_edge(a),
+            FlowEdge {
+                from: 0,
+                to: 1,
+                cap: 5,
+                flow: 2,
+                cost: 2
+            }
+        );
+        assert_eq!(mcf.get_edge(b).flow, 2);
+        // 残りは 0->1->2 に1、0->2 に1
+        assert_eq!(mcf.flow(0, 2, 10), (2, 13));
+    }
+}
@@ -1,19 +1,43 @@
+pub mod all_direction_tree_dp;
+pub mod bipartite;
+pub mod compact;
+pub mod cycle;
 pub mod directed;
+pub mod grid;
+pub mod lca;
+pub mod min_cost_flow;
+pub mod shortest_path;
 pub mod tree;
 
+pub use all_direction_tree_dp::{AllDirectionTreeDp, AllDirectionTreeDpSolver};
+pub use bipartite::BipartiteMatching;
+pub use compact::CompactGraph;
+pub use grid::gen_grid_graph;
+pub use lca::Lca;
+pub use min_cost_flow::{FlowEdge, MinCostFlow};
+pub use shortest_path::{Distance, DistanceMode};
+
 use rustc_hash::FxHasher;
 use std::{
     collections::HashMap,
     fmt::Debug,
     hash::{BuildHasherDefault, Hash},
     marker::PhantomData,
+    str::FromStr,
 };
 
-pub trait GraphType {}
+use crate::Scanner;
+
+pub trait GraphType {
+    /// 入力から辺を読み込むときに逆向きの辺も追加するかどうか
+    const BIDIRECTIONAL: bool = false;
+}
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Undirected {}
-impl GraphType for Undirected {}
+impl GraphType for Undirected {
+    const BIDIRECTIONAL: bool = true;
+}
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Directed {}
@@ -21,7 +45,9 @@ impl GraphType for Directed {}
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Tree {}
-impl GraphType for Tree {}
+impl GraphType for Tree {
+    const BIDIRECTIONAL: bool = true;
+}
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Dag {}
@@ -144,6 +170,59 @@ impl<I: Clone + Eq + Hash, EW, NW, T: GraphType> Graph<I, EW, NW, T> {
     }
 }
 
+impl<EW: Clone, NW, T: GraphType> Graph<usize, EW, NW, T> {
+    /// m本の辺 `u v` （`weighted`なら `u v w`）を読み込んでグラフを作成する
+    ///
+    /// 頂点0..nをこの順に作成するため、キーと内部IDは一致します。
+    /// `Undirected`と`Tree`では各辺を両方向に追加し、`Directed`と`Dag`では`u -> v`のみ追加します。
+    ///
+    /// # 引数
+    /// * `sc` - 入力元の`Scanner`（nとmは読み込み済みであること）
+    /// * `one_indexed` - 頂点番号が1始まりなら`true`（0始まりに変換して格納します）
+    /// * `weighted` - 各行に辺の重みが続くなら`true`（`false`なら重みは`None`）
+    ///
+    /// # 使用例
+    /// ```
+    /// # use rust_macro::{Graph, Scanner, Undirected};
+    /// let mut sc = Scanner::new("3 2\n1 2 5\n2 3 7\n".as_bytes());
+    /// let (n, m): (usize, usize) = (sc.read(), sc.read());
+    /// let g = Graph::<usize, i64, (), Undirected>::read_edges(&mut sc, n, m, true, true);
+    /// assert_eq!(g.adj[0], vec![(1, Some(5))]);
+    /// assert_eq!(g.adj[1], vec![(0, Some(5)), (2, Some(7))]);
+    /// ```
+    ///
+    /// # パニック
+    /// 頂点番号がn以上（1始まりなら0またはnより大きい）の場合panicします
+    pub fn read_edges(
+        sc: &mut Scanner,
+        n: usize,
+        m: usize,
+        one_indexed: bool,
+        weighted: bool,
+    ) -> Self
+    where
+        EW: FromStr,
+        EW::Err: Debug,
+    {
+        let mut graph = Self::new();
+        for v in 0..n {
+            graph.create_id(v);
+        }
+        let offset = usize::from(one_indexed);
+        for _ in 0..m {
+            let u = sc.read::<usize>() - offset;
+            let v = sc.read::<usize>() - offset;
+            assert!(u < n && v < n, "vertex index out of range");
+            let w: Option<EW> = if weighted { Some(sc.read()) } else { None };
+            if T::BIDIRECTIONAL {
+                graph.adj[v].push((u, w.clone()));
+            }
+            graph.adj[u].push((v, w));
+        }
+        graph
+    }
+}
+
 // impl<I: Clone, EW, NW, T: GraphType> Index<usize> for Graph<I, EW, NW, T> {
 //     type Output = (I, Vec<I>);
 
@@ -157,43 +236,6 @@ impl<I: Clone + Eq + Hash, EW, NW, T: GraphType> Graph<I, EW, NW, T> {
 //     }
 // }
 
-#[allow(dead_code)]
-fn gen_grid_graph<V, F, T>(
-    input: Vec<Vec<V>>,
-    is_connectable: F,
-) -> Graph<(usize, usize), usize, V, T>
-where
-    V: Clone,
-    F: Fn(&V) -> bool,
-    T: GraphType,
-{
-    let h = input.len();
-    let w = input[0].len();
-    let mut graph = Graph::new();
-
-    for i in 0..h {
-        for j in 0..w {
-            if is_connectable(&input[i][j]) {
-                graph.add_weight_to_node((i, j), input[i][j].clone());
-
-                if i > 0 && is_connectable(&input[i - 1][j]) {
-                    graph.add_edge((i, j), (i - 1, j), Some(1));
-                }
-                if i + 1 < h && is_connectable(&input[i + 1][j]) {
-                    graph.add_edge((i, j), (i + 1, j), Some(1));
-                }
-                if j > 0 && is_connectable(&input[i][j - 1]) {
-                    graph.add_edge((i, j), (i, j - 1), Some(1));
-                }
-                if j + 1 < w && is_connectable(&input[i][j + 1]) {
-                    graph.add_edge((i, j), (i, j + 1), Some(1));
-                }
-            }
-        }
-    }
-    graph
-}
-
 // pub use tree::{TreeDP, TreePostorder, TreePreorder};
 
 #[cfg(test)]
@@ -231,6 +273,32 @@ mod tests {
         assert_eq!(graph.get_node_weight(&1), Some(&5));
     }
 
+    #[test]
+    fn test_read_edges_directed_and_tree() {
+        let input = "0 1\n0 2\n2 3\n";
+        let g = Graph::<usize, usize, (), Directed>::read_edges(
+            &mut Scanner::new(input.as_bytes()),
+            5,
+            3,
+            false,
+            false,
+        );
+        assert_eq!(g.nodes.len(), 5);
+        assert_eq!(g.adj[0], vec![(1, None), (2, None)]);
+        assert!(g.adj[1].is_empty());
+        assert!(g.adj[4].is_empty());
+
+        let g = Graph::<usize, usize, (), Tree>::read_edges(
+            &mut Scanner::new(input.as_bytes()),
+            4,
+            3,
+            false,
+            false,
+        );
+        assert_eq!(g.adj[2], vec![(0, None), (3, None)]);
+        assert_eq!(g.reverse_map, vec![0, 1, 2, 3]);
+    }
+
     #[test]
     fn test_get_node() {
         let mut graph = Graph::<usize, usize, usize, Undirected>::new();
@@ -0,0 +1,381 @@
+use rustc_hash::FxHasher;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::hash::{BuildHasherDefault, Hash};
+use std::ops::Add;
+
+use super::{Graph, GraphType};
+
+/// 最短距離の計算に使える辺の重みの型
+pub trait Distance: Copy + Ord + Add<Output = Self> {
+    const ZERO: Self;
+    const ONE: Self;
+}
+
+macro_rules! impl_distance {
+    ($($t:ty),*) => {
+        $(impl Distance for $t {
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+        })*
+    };
+}
+impl_distance!(u32, u64, usize, i32, i64);
+
+/// 最短距離の計算方法
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DistanceMode {
+    /// 重みを無視し、すべての辺を長さ1とみなすBFS。O(V + E)
+    Bfs,
+    /// 重みが0または1の辺のみを持つグラフの0-1 BFS。O(V + E)
+    ZeroOneBfs,
+    /// 重みが非負のグラフのDijkstra法。O((V + E) log V)
+    Dijkstra,
+}
+
+impl<I, EW, NW, T> Graph<I, EW, NW, T>
+where
+    I: Clone + Eq + Hash,
+    EW: Distance,
+    T: GraphType,
+{
+    /// 複数の始点から各ノードへの最短距離（最も近い始点までの距離）を求める
+    ///
+    /// 「最も近い出口までの距離」のような問題で、すべての始点を距離0として同時に探索します。
+    /// 重みが`None`の辺は長さ1とみなします。
+    ///
+    /// # 引数
+    /// * `starts` - 始点のキーの列
+    /// * `mode` - 計算方法（`DistanceMode`）
+    ///
+    /// # 戻り値
+    /// 到達可能なノードのキーから距離へのHashMap。始点に存在しないキーが含まれる場合は`Err`
+    ///
+    /// # 使用例
+    /// ```
+    /// # use rust_macro::{Graph, Undirected};
+    /// # use rust_macro::graph::DistanceMode;
+    /// let mut g = Graph::<usize, u64, (), Undirected>::new();
+    /// for (u, v, w) in [(0, 1, 4), (1, 2, 1), (2, 3, 1), (3, 4, 4)] {
+    ///     g.add_edge(u, v, Some(w));
+    ///     g.add_edge(v, u, Some(w));
+    /// }
+    /// let dist = g.multi_source_distances([0, 4], DistanceMode::Dijkstra).unwrap();
+    /// assert_eq!((dist[&1], dist[&2], dist[&3]), (4, 5, 4));
+    /// let hops = g.multi_source_distances([0, 4], DistanceMode::Bfs).unwrap();
+    /// assert_eq!(hops[&2], 2);
+    /// ```
+    ///
+    /// # パニック
+    /// `ZeroOneBfs`で重みが0, 1以外の辺をたどった場合panicします
+    pub fn multi_source_distances(
+        &self,
+        starts: impl IntoIterator<Item = I>,
+        mode: DistanceMode,
+    ) -> Result<HashMap<I, EW, BuildHasherDefault<FxHasher>>, String> {
+        let mut dist: Vec<Option<EW>> = vec![None; self.nodes.len()];
+        let sources = self.init_sources(starts, &mut dist)?;
+        let weight = |w: &Option<EW>| w.unwrap_or(EW::ONE);
+
+        match mode {
+            DistanceMode::Bfs => self.bfs_from(&mut dist, sources),
+            DistanceMode::ZeroOneBfs => {
+                let mut queue: VecDeque<(EW, usize)> =
+                    sources.into_iter().map(|s| (EW::ZERO, s)).collect();
+                while let Some((d, u)) = queue.pop_front() {
+                    if dist[u] != Some(d) {
+                        continue;
+                    }
+                    for (v, w) in &self.adj[u] {
+                        let w = weight(w);
+                        assert!(w == EW::ZERO || w == EW::ONE, "edge weight must be 0 or 1");
+                        let nd = d + w;
+                        if dist[*v].map_or(true, |old| nd < old) {
+                            dist[*v] = Some(nd);
+                            if w == EW::ZERO {
+                                queue.push_front((nd, *v));
+                            } else {
+                                queue.push_back((nd, *v));
+                            }
+                        }
+                    }
+                }
+            }
+            DistanceMode::Dijkstra => {
+                let mut heap: BinaryHeap<Reverse<(EW, usize)>> = sources
+                    .into_iter()
+                    .map(|s| Reverse((EW::ZERO, s)))
+                    .collect();
+                while let Some(Reverse((d, u))) = heap.pop() {
+                    if dist[u] != Some(d) {
+                        continue;
+                    }
+                    for (v, w) in &self.adj[u] {
+                        let nd = d + weight(w);
+                        if dist[*v].map_or(true, |old| nd < old) {
+                            dist[*v] = Some(nd);
+                            heap.push(Reverse((nd, *v)));
+                        }
+                    }
+                }
+            }
+        }
+
+        Ok(self.collect_distances(dist))
+    }
+
+    /// 重みが0または1の辺だけを持つグラフで、始点から各ノードへの最短距離を0-1 BFSで求める
+    ///
+    /// 優先度付きキューの代わりに両端キューを使うため、Dijkstra法より高速です。
+    /// 「隣のマスへは0、壁を壊して進むと1」のようなグリッドの問題でよく使います。
+    /// 重みが`None`の辺は長さ1とみなします。
+    ///
+    /// # 計算量
+    /// O(V + E)
+    ///
+    /// # 使用例
+    /// ```
+    /// # use rust_macro::{Directed, Graph};
+    /// let mut g = Graph::<usize, usize, (), Directed>::new();
+    /// g.add_edge(0, 1, Some(1));
+    /// g.add_edge(0, 2, Some(0));
+    /// g.add_edge(2, 1, Some(0));
+    /// let dist = g.bfs01(0).unwrap();
+    /// assert_eq!(dist[&1], 0);
+    /// ```
+    ///
+    /// # パニック
+    /// 重みが0, 1以外の辺をたどった場合panicします
+    pub fn bfs01(&self, start: I) -> Result<HashMap<I, EW, BuildHasherDefault<FxHasher>>, String> {
+        self.multi_source_distances([start], DistanceMode::ZeroOneBfs)
+    }
+}
+
+impl<I, EW, NW, T> Graph<I, EW, NW, T>
+where
+    I: Clone + Eq + Hash,
+    T: GraphType,
+{
+    /// 始点の距離を0にしてIDの列を返す（重複は除く）。存在しないキーがあれば`Err`
+    fn init_sources<D: Distance>(
+        &self,
+        starts: impl IntoIterator<Item = I>,
+        dist: &mut [Option<D>],
+    ) -> Result<Vec<usize>, String> {
+        let mut sources = vec![];
+        for key in starts {
+            let id = self
+                .key2id(&key)
+                .ok_or_else(|| "Start key not found in graph.".to_string())?;
+            if dist[id].is_none() {
+                dist[id] = Some(D::ZERO);
+                sources.push(id);
+            }
+        }
+        Ok(sources)
+    }
+
+    /// 重みを無視したBFSで、sourcesからの距離をdistに書き込む
+    fn bfs_from<D: Distance>(&self, dist: &mut [Option<D>], sources: Vec<usize>) {
+        let mut queue: VecDeque<usize> = sources.into();
+        while let Some(u) = queue.pop_front() {
+            let d = dist[u].unwrap() + D::ONE;
+            for &(v, _) in &self.adj[u] {
+                if dist[v].is_none() {
+                    dist[v] = Some(d);
+                    queue.push_back(v);
+                }
+            }
+        }
+    }
+
+    /// 到達したノードのキーから距離へのHashMapにする
+    fn collect_distances<D>(
+        &self,
+        dist: Vec<Option<D>>,
+    ) -> HashMap<I, D, BuildHasherDefault<FxHasher>> {
+        dist.into_iter()
+            .enumerate()
+            .filter_map(|(id, d)| d.map(|d| (self.reverse_map[id].clone(), d)))
+            .collect()
+    }
+
+    /// 重みを無視して、始点から各ノードへの距離（辺の本数）をBFSで求める
+    ///
+    /// 辺の重みの型に制約がないため、グリッドから作ったグラフなどにそのまま使えます。
+    ///
+    /// # 戻り値
+    /// 到達可能なノードのキーから距離へのHashMap。始点がグラフに存在しない場合は`Err`
+    ///
+    /// # 計算量
+    /// O(V + E)
+    ///
+    /// # 使用例
+    /// ```
+    /// # use rust_macro::{Graph, Undirected};
+    /// let mut g = Graph::<char, (), (), Undirected>::new();
+    /// for (u, v) in [('a', 'b'), ('b', 'c'), ('a', 'd')] {
+    ///     g.add_edge(u, v, None);
+    ///     g.add_edge(v, u, None);
+    /// }
+    /// g.add_weight_to_node('z', ());
+    /// let dist = g.bfs('a').unwrap();
+    /// assert_eq!((dist[&'b'], dist[&'c'], dist[&'d']), (1, 2, 1));
+    /// assert!(!dist.contains_key(&'z'));
+    /// assert!(g.bfs('x').is_err());
+    /// ```
+    pub fn bfs(&self, start: I) -> Result<HashMap<I, usize, BuildHasherDefault<FxHasher>>, String> {
+        self.bfs_multi([start])
+    }
+
+    /// 重みを無視して、複数の始点から各ノードへの距離（最も近い始点までの辺の本数）を求める
+    ///
+    /// `multi_source_distances`の`DistanceMode::Bfs`と同じ探索を、辺の重みの型によらず行います。
+    /// 始点に存在しないキーが含まれる場合は`Err`を返します。
+    ///
+    /// # 計算量
+    /// O(V + E)
+    ///
+    /// # 使用例
+    /// ```
+    /// # use rust_macro::{Directed, Graph};
+    /// let mut g = Graph::<usize, (), (), Directed>::new();
+    /// for (u, v) in [(0, 1), (1, 2), (2, 3), (4, 3)] {
+    ///     g.add_edge(u, v, None);
+    /// }
+    /// let dist = g.bfs_multi([0, 4]).unwrap();
+    /// assert_eq!((dist[&2], dist[&3]), (2, 1));
+    /// ```
+    pub fn bfs_multi(
+        &self,
+        starts: impl IntoIterator<Item = I>,
+    ) -> Result<HashMap<I, usize, BuildHasherDefault<FxHasher>>, String> {
+        let mut dist: Vec<Option<usize>> = vec![None; self.nodes.len()];
+        let sources = self.init_sources(starts, &mut dist)?;
+        self.bfs_from(&mut dist, sources);
+        Ok(self.collect_distances(dist))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::Directed;
+    use crate::XorShift64;
+
+    /// Bellman-Ford風の素朴な計算
+    fn naive(n: usize, edges: &[(usize, usize, u64)], starts: &[usize]) -> Vec<Option<u64>> {
+        let mut dist = vec![None; n];
+        for &s in starts {
+            dist[s] = Some(0);
+        }
+        for _ in 0..n {
+            for &(u, v, w) in edges {
+                if let Some(d) = dist[u] {
+                    if dist[v].map_or(true, |old| d + w < old) {
+                        dist[v] = Some(d + w);
+                    }
+                }
+            }
+        }
+        dist
+    }
+
+    #[test]
+    fn test_multi_source_distances_against_naive() {
+        let mut rng = XorShift64::new(4242);
+        for _ in 0..100 {
+            let n = rng.gen_range(1..15) as usize;
+            let m = rng.gen_range(0..40) as usize;
+            let max_w = if rng.gen_range(0..2) == 0 { 2 } else { 10 };
+            let edges: Vec<(usize, usize, u64)> = (0..m)
+                .map(|_| {
+                    let u = rng.gen_range(0..n as u64) as usize;
+                    let v = rng.gen_range(0..n as u64) as usize;
+                    (u, v, rng.gen_range(0..max_w))
+                })
+                .collect();
+            let mut g = Graph::<usize, u64, (), Directed>::new();
+            for v in 0..n {
+                g.get_or_create_id(v);
+            }
+            for &(u, v, w) in &edges {
+                g.add_edge(u, v, Some(w));
+            }
+            let starts: Vec<usize> = (0..rng.gen_range(1..4))
+                .map(|_| rng.gen_range(0..n as u64) as usize)
+                .collect();
+
+            let mut modes = vec![(DistanceMode::Dijkstra, edges.clone())];
+            if max_w == 2 {
+                modes.push((DistanceMode::ZeroOneBfs, edges.clone()));
+            }
+            let unit: Vec<_> = edges.iter().map(|&(u, v, _)| (u, v, 1)).collect();
+            let hops = g.bfs_multi(starts.clone()).unwrap();
+            for (v, d) in naive(n, &unit, &starts).into_iter().enumerate() {
+                assert_eq!(hops.get(&v).map(|&h| h as u64), d);
+            }
+            modes.push((DistanceMode::Bfs, unit));
+            for (mode, es) in modes {
+                let dist = g.multi_source_distances(starts.clone(), mode).unwrap();
+                let expected = naive(n, &es, &starts);
+                for (v, &d) in expected.iter().enumerate() {
+                    assert_eq!(dist.get(&v).copied(), d, "{:?}", mode);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_bfs01_on_grid_matches_dijkstra() {
+        // '.'へは0、'#'へは1で進めるグリッド（壁を壊した回数の最小値）
+        let grid = ["..#..", "##.#.", "..#..", ".###.", "....."];
+        let mut rng = XorShift64::new(4252);
+        for _ in 0..3 {
+            let cells: Vec<Vec<u8>> = grid
+                .iter()
+                .map(|row| {
+                    row.bytes()
+                        .map(|c| if rng.gen_range(0..4) == 0 { b'#' } else { c })
+                        .collect()
+                })
+                .collect();
+            let (h, w) = (cells.len(), cells[0].len());
+            let mut g = Graph::<(usize, usize), u32, (), Directed>::new();
+            for i in 0..h {
+                for j in 0..w {
+                    for (di, dj) in [(0, 1), (1, 0), (0, usize::MAX), (usize::MAX, 0)] {
+                        let (ni, nj) = (i.wrapping_add(di), j.wrapping_add(dj));
+                        if ni < h && nj < w {
+                            let cost = u32::from(cells[ni][nj] == b'#');
+                            g.add_edge((i, j), (ni, nj), Some(cost));
+                        }
+                    }
+                }
+            }
+            let dist = g.bfs01((0, 0)).unwrap();
+            let expected = g
+                .multi_source_distances([(0, 0)], DistanceMode::Dijkstra)
+                .unwrap();
+            assert_eq!(dist, expected);
+            assert_eq!(dist.len(), h * w);
+        }
+    }
+
+    #[test]
+    fn test_multi_source_distances_missing_start_and_none_weight() {
+        let mut g = Graph::<&str, usize, (), Directed>::new();
+        g.add_edge("a", "b", None);
+        g.add_edge("b", "c", Some(0));
+        let dist = g
+            .multi_source_distances(["a"], DistanceMode::ZeroOneBfs)
+            .unwrap();
+        assert_eq!((dist["a"], dist["b"], dist["c"]), (0, 1, 1));
+        let dist = g.multi_source_distances(["c"], DistanceMode::Bfs).unwrap();
+        assert_eq!(dist.len(), 1);
+        assert!(g.multi_source_distances(["z"], DistanceMode::Bfs).is_err());
+        assert!(g.bfs("z").is_err());
+        assert!(g.bfs_multi(["b", "z"]).is_err());
+        assert_eq!(g.bfs_multi(["c", "b"]).unwrap().len(), 2);
+    }
+}
@@ -53,6 +53,9 @@ where
     P: TreeDp<I, EW, NW>,
 {
     /// 木DPを実行し、結果をキーと値のペアを持つHashMapとして返します。
+    ///
+    /// 再帰の深さは木の高さに比例します。パス状の木など深さが10^5を超えうる場合は、
+    /// `rust_macro::run_with_stack`でスタックを広げたスレッドから呼び出してください。
     fn solve(
         &self,
         graph: &Graph<I, EW, NW, Tree>,
@@ -184,4 +187,20 @@ mod tests {
         assert_eq!(dp_values[&"3"], 1); // 葉"3"の結果
         assert_eq!(dp_values[&"4"], 1); // 葉"4"の結果
     }
+
+    #[test]
+    fn test_subtree_size_on_deep_path() {
+        // 深さ2e5のパスでも、スタックを広げれば再帰で解ける
+        let n = 200_000usize;
+        let root_size = crate::run_with_stack(256, move || {
+            let mut graph = Graph::<usize, (), (), Tree>::new();
+            for v in 1..n {
+                graph.add_edge(v - 1, v, None);
+                graph.add_edge(v, v - 1, None);
+            }
+            let dp_values = TreeDpSolver.solve(&graph, &0, &SubtreeSizeProblem).unwrap();
+            dp_values[&0]
+        });
+        assert_eq!(root_size, n);
+    }
 }
@@ -1,7 +1,12 @@
+use std::ops::{Add, Sub};
+
+use crate::utils::Compress2D;
+
 /// 1次元imos法のライブラリ
 ///
 /// imos法は区間加算を効率的に処理するデータ構造です。
 /// 複数の区間に値を加算した後、一度の累積和計算で全体の結果を得ることができます。
+/// 要素の型は整数に限らず、`f64` や `i128`、剰余類など加減算ができる型を使えます。
 ///
 /// # 計算量
 /// - 区間加算: O(1)
@@ -16,11 +21,20 @@
 /// let result = imos.build();
 /// assert_eq!(result, vec![0, 2, 5, 5, 3]);
 /// ```
-pub struct Imos1D {
-    data: Vec<i64>,
+#[derive(Debug, Clone)]
+pub struct Imos1D<T>
+where
+    T: Add<Output = T> + Sub<Output = T> + Copy + Default,
+{
+    data: Vec<T>,
+    /// 累積和を計算済みかどうか
+    built: bool,
 }
 
-impl Imos1D {
+impl<T> Imos1D<T>
+where
+    T: Add<Output = T> + Sub<Output = T> + Copy + Default,
+{
     /// 長さnのimos配列を作成
     ///
     /// # 引数
@@ -30,7 +44,8 @@ impl Imos1D {
     /// 新しいImos1Dインスタンス
     pub fn new(n: usize) -> Self {
         Imos1D {
-            data: vec![0; n + 1],
+            data: vec![T::default(); n + 1],
+            built: false,
         }
     }
 
@@ -41,29 +56,133 @@ impl Imos1D {
     /// * `r` - 区間の終了位置（含まない）
     /// * `x` - 加算する値
     ///
-    /// # 注意
-    /// lとrが配列の範囲外の場合は何もしません
-    pub fn add(&mut self, l: usize, r: usize, x: i64) {
-        if l < self.data.len() {
-            self.data[l] += x;
+    /// # パニック
+    /// `l <= r <= n` を満たさない場合や、`build_into`の後に`reset`せず呼び出した場合panicします。
+    /// 範囲外を切り捨てたい場合は`add_clamped`、エラーとして扱いたい場合は`try_add`を使ってください。
+    pub fn add(&mut self, l: usize, r: usize, x: T) {
+        if let Err(e) = self.try_add(l, r, x) {
+            panic!("{}", e);
+        }
+    }
+
+    /// 区間[l, r)にxを加算し、範囲外の場合はエラーを返す
+    ///
+    /// # 戻り値
+    /// `l <= r <= n` を満たさない場合や、`build_into`の後に`reset`していない場合は
+    /// 何もせず`Err`を返します。
+    ///
+    /// # 使用例
+    /// ```
+    /// # use rust_macro::Imos1D;
+    /// let mut imos = Imos1D::new(3);
+    /// assert!(imos.try_add(0, 3, 1).is_ok());
+    /// assert!(imos.try_add(1, 4, 1).is_err());
+    /// assert_eq!(imos.build(), vec![1, 1, 1]);
+    /// ```
+    pub fn try_add(&mut self, l: usize, r: usize, x: T) -> Result<(), String> {
+        if self.built {
+            return Err("call reset() before adding after build_into()".to_string());
+        }
+        let n = self.data.len() - 1;
+        if l > r || r > n {
+            return Err(format!(
+                "range [{}, {}) is out of bounds for length {}",
+                l, r, n
+            ));
+        }
+        self.data[l] = self.data[l] + x;
+        self.data[r] = self.data[r] - x;
+        Ok(())
+    }
+
+    /// 区間[l, r)と[0, n)の共通部分にxを加算
+    ///
+    /// 範囲外の部分は切り捨てられます。共通部分が空の場合は何もしません。
+    pub fn add_clamped(&mut self, l: usize, r: usize, x: T) {
+        let n = self.data.len() - 1;
+        let r = r.min(n);
+        if l < r {
+            self.add(l, r, x);
         }
-        if r < self.data.len() {
-            self.data[r] -= x;
+    }
+
+    /// 円環上の区間[l, r)にxを加算
+    ///
+    /// `r < l` の場合は末尾を回り込む区間[l, n) ∪ [0, r)として扱います。
+    /// `l == r` の場合は空区間です。
+    ///
+    /// # 引数
+    /// * `l` - 区間の開始位置（含む）
+    /// * `r` - 区間の終了位置（含まない）
+    /// * `x` - 加算する値
+    ///
+    /// # 使用例
+    /// ```
+    /// # use rust_macro::Imos1D;
+    /// let mut imos = Imos1D::new(5);
+    /// imos.add_wrap(3, 1, 1);  // [3, 5)と[0, 1)に1を加算
+    /// imos.add_wrap(1, 3, 2);  // 通常の区間[1, 3)
+    /// assert_eq!(imos.build(), vec![1, 2, 2, 1, 1]);
+    /// ```
+    pub fn add_wrap(&mut self, l: usize, r: usize, x: T) {
+        let n = self.data.len() - 1;
+        assert!(l <= n && r <= n);
+        if l <= r {
+            self.add(l, r, x);
+        } else {
+            self.add(l, n, x);
+            self.add(0, r, x);
         }
     }
 
     /// 累積和を計算し、長さnの配列を返す
     ///
     /// この関数を呼び出すと、すべての区間加算が適用された最終的な配列が返されます。
+    /// 内部の配列をそのまま返すのでコピーは発生しません。
     ///
     /// # 戻り値
     /// 累積和が計算された配列（長さn）
-    pub fn build(&mut self) -> Vec<i64> {
-        for i in 1..self.data.len() {
-            self.data[i] += self.data[i - 1];
-        }
+    pub fn build(mut self) -> Vec<T> {
+        self.build_into();
         self.data.pop(); // n+1 -> n
-        self.data.clone()
+        self.data
+    }
+
+    /// 内部の配列上で累積和を計算し、その参照を返す
+    ///
+    /// 複数回呼び出しても同じ結果を返します。再び区間加算を行う場合は、
+    /// 先に`reset`を呼び出してください。
+    ///
+    /// # 戻り値
+    /// 累積和が計算された配列（長さn）
+    ///
+    /// # 使用例
+    /// ```
+    /// # use rust_macro::Imos1D;
+    /// let mut imos = Imos1D::new(3);
+    /// imos.add(0, 2, 1);
+    /// assert_eq!(imos.build_into(), &[1, 1, 0]);
+    /// imos.reset();
+    /// imos.add(1, 3, 2);
+    /// assert_eq!(imos.build_into(), &[0, 2, 2]);
+    /// ```
+    pub fn build_into(&mut self) -> &[T] {
+        if !self.built {
+            for i in 1..self.data.len() {
+                self.data[i] = self.data[i] + self.data[i - 1];
+            }
+            self.built = true;
+        }
+        let n = self.data.len() - 1;
+        &self.data[..n]
+    }
+
+    /// すべての値を0に戻し、再び区間加算できる状態にする
+    ///
+    /// 内部の配列は再利用されるため、新たなメモリ確保は発生しません。
+    pub fn reset(&mut self) {
+        self.data.fill(T::default());
+        self.built = false;
     }
 }
 
@@ -85,13 +204,35 @@ impl Imos1D {
 /// let result = imos.build();
 /// assert_eq!(result, vec![vec![1, 1, 0], vec![1, 3, 2], vec![0, 2, 2]]);
 /// ```
-pub struct Imos2D {
-    data: Vec<Vec<i64>>,
+///
+/// 問題文で長方形の角が両端を含む形で与えられる場合は、`inclusive`で
+/// 座標の解釈を切り替えるか、`add_inclusive`を使います。
+///
+/// ```
+/// # use rust_macro::Imos2D;
+/// let mut imos = Imos2D::new(3, 3).inclusive();
+/// imos.add(0, 0, 1, 1, 1);  // (0,0)から(1,1)の長方形（両端を含む）に1を加算
+/// imos.add(1, 1, 2, 2, 2);  // (1,1)から(2,2)の長方形（両端を含む）に2を加算
+/// assert_eq!(imos.build(), vec![vec![1, 1, 0], vec![1, 3, 2], vec![0, 2, 2]]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Imos2D<T>
+where
+    T: Add<Output = T> + Sub<Output = T> + Copy + Default,
+{
+    data: Vec<Vec<T>>,
     h: usize,
     w: usize,
+    /// 右下の座標を含む（閉区間）として解釈するかどうか
+    inclusive: bool,
+    /// 累積和を計算済みかどうか
+    built: bool,
 }
 
-impl Imos2D {
+impl<T> Imos2D<T>
+where
+    T: Add<Output = T> + Sub<Output = T> + Copy + Default,
+{
     /// 高さh, 幅wのimos配列を作成
     ///
     /// # 引数
@@ -102,14 +243,24 @@ impl Imos2D {
     /// 新しいImos2Dインスタンス
     pub fn new(h: usize, w: usize) -> Self {
         Imos2D {
-            data: vec![vec![0; w + 1]; h + 1],
+            data: vec![vec![T::default(); w + 1]; h + 1],
             h,
             w,
+            inclusive: false,
+            built: false,
         }
     }
 
+    /// `add`・`try_add`・`add_clamped`の右下の座標(x2, y2)を含む（閉区間）として解釈するようにする
+    pub fn inclusive(mut self) -> Self {
+        self.inclusive = true;
+        self
+    }
+
     /// 左上(x1, y1), 右下(x2, y2)の長方形にxを加算 (x2, y2は含まない)
     ///
+    /// `inclusive`を指定した場合は(x2, y2)も含みます。
+    ///
     /// # 引数
     /// * `x1` - 左上の行座標（含む）
     /// * `y1` - 左上の列座標（含む）
@@ -117,46 +268,322 @@ impl Imos2D {
     /// * `y2` - 右下の列座標（含まない）
     /// * `x` - 加算する値
     ///
-    /// # 注意
-    /// 座標が配列の範囲外の場合は何もしません
-    pub fn add(&mut self, x1: usize, y1: usize, x2: usize, y2: usize, x: i64) {
-        if x1 < self.h + 1 && y1 < self.w + 1 {
-            self.data[x1][y1] += x;
+    /// # パニック
+    /// `x1 <= x2 <= h` かつ `y1 <= y2 <= w` を満たさない場合や、`build_into`の後に`reset`せず
+    /// 呼び出した場合panicします。
+    /// 範囲外を切り捨てたい場合は`add_clamped`、エラーとして扱いたい場合は`try_add`を使ってください。
+    pub fn add(&mut self, x1: usize, y1: usize, x2: usize, y2: usize, x: T) {
+        if let Err(e) = self.try_add(x1, y1, x2, y2, x) {
+            panic!("{}", e);
+        }
+    }
+
+    /// 左上(x1, y1), 右下(x2, y2)の長方形にxを加算 (x2, y2も含む)
+    ///
+    /// `inclusive`の指定にかかわらず、常に右下の座標を含むものとして扱います。
+    ///
+    /// # パニック
+    /// `x1 <= x2 + 1 <= h` かつ `y1 <= y2 + 1 <= w` を満たさない場合panicします。
+    pub fn add_inclusive(&mut self, x1: usize, y1: usize, x2: usize, y2: usize, x: T) {
+        if let Err(e) = self.try_add_half_open(x1, y1, x2 + 1, y2 + 1, x) {
+            panic!("{}", e);
+        }
+    }
+
+    /// 長方形にxを加算し、範囲外の場合はエラーを返す
+    ///
+    /// # 戻り値
+    /// `x1 <= x2 <= h` かつ `y1 <= y2 <= w` を満たさない場合や、`build_into`の後に`reset`して
+    /// いない場合は何もせず`Err`を返します。
+    pub fn try_add(
+        &mut self,
+        x1: usize,
+        y1: usize,
+        x2: usize,
+        y2: usize,
+        x: T,
+    ) -> Result<(), String> {
+        let (x2, y2) = self.to_half_open(x2, y2);
+        self.try_add_half_open(x1, y1, x2, y2, x)
+    }
+
+    /// 長方形と[0, h)×[0, w)の共通部分にxを加算
+    ///
+    /// 範囲外の部分は切り捨てられます。共通部分が空の場合は何もしません。
+    ///
+    /// # パニック
+    /// `build_into`の後に`reset`せず呼び出した場合panicします。
+    pub fn add_clamped(&mut self, x1: usize, y1: usize, x2: usize, y2: usize, x: T) {
+        assert!(!self.built, "call reset() before adding after build_into()");
+        let (x2, y2) = self.to_half_open(x2, y2);
+        let (x2, y2) = (x2.min(self.h), y2.min(self.w));
+        if x1 < x2 && y1 < y2 {
+            self.apply(x1, y1, x2, y2, x);
         }
-        if x2 < self.h + 1 && y1 < self.w + 1 {
-            self.data[x2][y1] -= x;
+    }
+
+    /// 右下の座標を半開区間の終端に変換する
+    fn to_half_open(&self, x2: usize, y2: usize) -> (usize, usize) {
+        if self.inclusive {
+            (x2 + 1, y2 + 1)
+        } else {
+            (x2, y2)
         }
-        if x1 < self.h + 1 && y2 < self.w + 1 {
-            self.data[x1][y2] -= x;
+    }
+
+    fn try_add_half_open(
+        &mut self,
+        x1: usize,
+        y1: usize,
+        x2: usize,
+        y2: usize,
+        x: T,
+    ) -> Result<(), String> {
+        if self.built {
+            return Err("call reset() before adding after build_into()".to_string());
         }
-        if x2 < self.h + 1 && y2 < self.w + 1 {
-            self.data[x2][y2] += x;
+        if x1 > x2 || x2 > self.h || y1 > y2 || y2 > self.w {
+            return Err(format!(
+                "rectangle [{}, {}) x [{}, {}) is out of bounds for {}x{}",
+                x1, x2, y1, y2, self.h, self.w
+            ));
         }
+        self.apply(x1, y1, x2, y2, x);
+        Ok(())
+    }
+
+    fn apply(&mut self, x1: usize, y1: usize, x2: usize, y2: usize, x: T) {
+        self.data[x1][y1] = self.data[x1][y1] + x;
+        self.data[x2][y1] = self.data[x2][y1] - x;
+        self.data[x1][y2] = self.data[x1][y2] - x;
+        self.data[x2][y2] = self.data[x2][y2] + x;
     }
 
     /// 2次元累積和を計算し、h×wの配列を返す
     ///
     /// この関数を呼び出すと、すべての長方形加算が適用された最終的な2次元配列が返されます。
     ///
+    /// 内部の配列をそのまま返すのでコピーは発生しません。
+    ///
     /// # 戻り値
     /// 2次元累積和が計算された配列（h×w）
-    pub fn build(&mut self) -> Vec<Vec<i64>> {
-        for i in 0..=self.h {
-            for j in 1..=self.w {
-                self.data[i][j] += self.data[i][j - 1];
-            }
+    pub fn build(mut self) -> Vec<Vec<T>> {
+        self.build_into();
+        self.data.truncate(self.h);
+        for row in &mut self.data {
+            row.truncate(self.w);
         }
-        for j in 0..=self.w {
-            for i in 1..=self.h {
-                self.data[i][j] += self.data[i - 1][j];
+        self.data
+    }
+
+    /// 内部の配列上で2次元累積和を計算し、各行の参照を返す
+    ///
+    /// 複数回呼び出しても同じ結果を返します。再び長方形加算を行う場合は、
+    /// 先に`reset`を呼び出してください。
+    ///
+    /// # 戻り値
+    /// 2次元累積和が計算された各行（h行、各行の長さw）
+    ///
+    /// # 使用例
+    /// ```
+    /// # use rust_macro::Imos2D;
+    /// let mut imos = Imos2D::new(2, 2);
+    /// imos.add(0, 0, 1, 2, 1);
+    /// assert_eq!(imos.build_into(), vec![&[1, 1], &[0, 0]]);
+    /// imos.reset();
+    /// imos.add(1, 1, 2, 2, 3);
+    /// assert_eq!(imos.build_into(), vec![&[0, 0], &[0, 3]]);
+    /// ```
+    pub fn build_into(&mut self) -> Vec<&[T]> {
+        if !self.built {
+            for i in 0..=self.h {
+                for j in 1..=self.w {
+                    self.data[i][j] = self.data[i][j] + self.data[i][j - 1];
+                }
+            }
+            for j in 0..=self.w {
+                for i in 1..=self.h {
+                    self.data[i][j] = self.data[i][j] + self.data[i - 1][j];
+                }
             }
+            self.built = true;
         }
-        self.data
+        self.data[..self.h]
             .iter()
-            .take(self.h)
-            .map(|row| row[..self.w].to_vec())
+            .map(|row| &row[..self.w])
             .collect()
     }
+
+    /// すべての値を0に戻し、再び長方形加算できる状態にする
+    ///
+    /// 内部の配列は再利用されるため、新たなメモリ確保は発生しません。
+    pub fn reset(&mut self) {
+        for row in &mut self.data {
+            row.fill(T::default());
+        }
+        self.built = false;
+    }
+}
+
+/// 座標圧縮つき1次元imos法のライブラリ
+///
+/// 座標が1e18程度まで大きくなり配列を確保できない場合に使います。
+/// 区間加算のイベントだけを保持しておき、構築時に座標をソートして累積します。
+///
+/// # 計算量
+/// 区間加算の回数をqとして
+/// - 区間加算: O(1)
+/// - 構築: O(q log q)
+///
+/// # 使用例
+/// ```
+/// # use rust_macro::SparseImos1D;
+/// let mut imos = SparseImos1D::new();
+/// imos.add(-1_000_000_000_000, 5, 1);
+/// imos.add(3, 1_000_000_000_000, 2);
+/// let result = imos.build();
+/// assert_eq!(
+///     result,
+///     vec![(-1_000_000_000_000, 1), (3, 3), (5, 2), (1_000_000_000_000, 0)]
+/// );
+/// ```
+#[derive(Debug, Clone)]
+pub struct SparseImos1D<T>
+where
+    T: Add<Output = T> + Sub<Output = T> + Copy + Default,
+{
+    events: Vec<(i64, T, bool)>,
+}
+
+impl<T> Default for SparseImos1D<T>
+where
+    T: Add<Output = T> + Sub<Output = T> + Copy + Default,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> SparseImos1D<T>
+where
+    T: Add<Output = T> + Sub<Output = T> + Copy + Default,
+{
+    /// 空のimosを作成
+    pub fn new() -> Self {
+        SparseImos1D { events: Vec::new() }
+    }
+
+    /// 区間[l, r)にxを加算
+    ///
+    /// # 引数
+    /// * `l` - 区間の開始座標（含む）
+    /// * `r` - 区間の終了座標（含まない）
+    /// * `x` - 加算する値
+    pub fn add(&mut self, l: i64, r: i64, x: T) {
+        assert!(l <= r);
+        if l < r {
+            self.events.push((l, x, true));
+            self.events.push((r, x, false));
+        }
+    }
+
+    /// 累積和を計算し、値が変化しうる座標とその座標からの値の組を返す
+    ///
+    /// 返り値の`(c_k, v_k)`は、区間[c_k, c_{k+1})の値がv_kであることを表します。
+    /// 最初の座標より前と最後の座標以降の値は0（`T::default()`）です。
+    ///
+    /// # 戻り値
+    /// 座標の昇順に並んだ(座標, 値)のベクタ
+    pub fn build(mut self) -> Vec<(i64, T)> {
+        self.events.sort_by_key(|&(c, _, _)| c);
+        let mut result: Vec<(i64, T)> = Vec::new();
+        let mut cur = T::default();
+        for (c, x, is_start) in self.events {
+            cur = if is_start { cur + x } else { cur - x };
+            match result.last_mut() {
+                Some(last) if last.0 == c => last.1 = cur,
+                _ => result.push((c, cur)),
+            }
+        }
+        result
+    }
+}
+
+/// 座標圧縮つき2次元imos法のライブラリ
+///
+/// 長方形加算のイベントを保持しておき、構築時に両軸を座標圧縮して
+/// 圧縮後のグリッド上で2次元imos法を行います。
+///
+/// # 計算量
+/// 長方形加算の回数をqとして
+/// - 長方形加算: O(1)
+/// - 構築: O(q^2)
+///
+/// # 使用例
+/// ```
+/// # use rust_macro::SparseImos2D;
+/// let mut imos = SparseImos2D::new();
+/// imos.add(0, 0, 10, 10, 1);
+/// imos.add(5, 5, 20, 20, 2);
+/// let (xs, ys, grid) = imos.build();
+/// assert_eq!(xs, vec![0, 5, 10, 20]);
+/// assert_eq!(ys, vec![0, 5, 10, 20]);
+/// // [5, 10) x [5, 10) の値
+/// assert_eq!(grid[1][1], 3);
+/// ```
+#[derive(Debug, Clone)]
+pub struct SparseImos2D<T>
+where
+    T: Add<Output = T> + Sub<Output = T> + Copy + Default,
+{
+    rects: Vec<(i64, i64, i64, i64, T)>,
+}
+
+impl<T> Default for SparseImos2D<T>
+where
+    T: Add<Output = T> + Sub<Output = T> + Copy + Default,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> SparseImos2D<T>
+where
+    T: Add<Output = T> + Sub<Output = T> + Copy + Default,
+{
+    /// 空のimosを作成
+    pub fn new() -> Self {
+        SparseImos2D { rects: Vec::new() }
+    }
+
+    /// 左上(x1, y1), 右下(x2, y2)の長方形にxを加算 (x2, y2は含まない)
+    pub fn add(&mut self, x1: i64, y1: i64, x2: i64, y2: i64, x: T) {
+        assert!(x1 <= x2 && y1 <= y2);
+        if x1 < x2 && y1 < y2 {
+            self.rects.push((x1, y1, x2, y2, x));
+        }
+    }
+
+    /// 2次元累積和を計算し、圧縮後の座標とグリッドを返す
+    ///
+    /// 返り値の`(xs, ys, grid)`について、`grid[i][j]`は
+    /// [xs[i], xs[i+1]) × [ys[j], ys[j+1]) の領域の値を表します。
+    ///
+    /// # 戻り値
+    /// (x座標のリスト, y座標のリスト, xs.len()×ys.len()のグリッド)
+    pub fn build(self) -> (Vec<i64>, Vec<i64>, Vec<Vec<T>>) {
+        let c = Compress2D::from_rects(self.rects.iter().map(|r| (r.0, r.1, r.2, r.3)));
+        let (h, w) = c.size();
+        let mut imos = Imos2D::new(h, w);
+        for &(x1, y1, x2, y2, x) in &self.rects {
+            let (i1, j1, i2, j2) = c.rect(&x1, &y1, &x2, &y2);
+            imos.add(i1, j1, i2, j2, x);
+        }
+        let xs = (0..h).map(|i| *c.xs().rev(i)).collect();
+        let ys = (0..w).map(|j| *c.ys().rev(j)).collect();
+        (xs, ys, imos.build())
+    }
 }
 
 #[cfg(test)]
@@ -172,6 +599,62 @@ mod tests {
         assert_eq!(res, vec![0, 2, 5, 5, 3]);
     }
 
+    #[test]
+    fn test_imos1d_build_into_and_reset() {
+        let mut imos = Imos1D::new(4);
+        imos.add(0, 4, 1);
+        imos.add(1, 2, 5);
+        assert_eq!(imos.build_into(), &[1, 6, 1, 1]);
+        assert_eq!(imos.build_into(), &[1, 6, 1, 1]);
+
+        imos.reset();
+        assert_eq!(imos.build_into(), &[0, 0, 0, 0]);
+        imos.reset();
+        imos.add(2, 4, -3);
+        assert_eq!(imos.build(), vec![0, 0, -3, -3]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_imos1d_add_after_build_into() {
+        let mut imos = Imos1D::new(4);
+        imos.build_into();
+        imos.add(0, 1, 1);
+    }
+
+    #[test]
+    fn test_imos1d_try_add_and_clamped() {
+        let mut imos = Imos1D::new(4);
+        assert!(imos.try_add(3, 5, 1).is_err());
+        assert!(imos.try_add(2, 1, 1).is_err());
+        assert!(imos.try_add(1, 4, 1).is_ok());
+        imos.add_clamped(2, 10, 5);
+        imos.add_clamped(7, 10, 100);
+        assert_eq!(imos.build_into(), &[0, 1, 6, 6]);
+        assert!(imos.try_add(0, 1, 1).is_err());
+        assert_eq!(imos.build_into(), &[0, 1, 6, 6]);
+        imos.reset();
+        assert!(imos.try_add(0, 1, 1).is_ok());
+        assert_eq!(imos.build(), vec![1, 0, 0, 0]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_imos1d_add_out_of_range() {
+        let mut imos = Imos1D::new(4);
+        imos.add(0, 5, 1);
+    }
+
+    #[test]
+    fn test_imos1d_add_wrap() {
+        let mut imos = Imos1D::new(6);
+        imos.add_wrap(4, 2, 1);
+        imos.add_wrap(2, 4, 10);
+        imos.add_wrap(3, 3, 100);
+        imos.add_wrap(5, 0, 1000);
+        assert_eq!(imos.build(), vec![1, 1, 10, 10, 1, 1001]);
+    }
+
     #[test]
     fn test_imos2d_basic() {
         let mut imos = Imos2D::new(3, 3);
@@ -180,4 +663,130 @@ mod tests {
         let res = imos.build();
         assert_eq!(res, vec![vec![1, 1, 0], vec![1, 3, 2], vec![0, 2, 2],]);
     }
+
+    #[test]
+    fn test_imos2d_try_add_and_clamped() {
+        let mut imos = Imos2D::new(2, 2);
+        assert!(imos.try_add(0, 0, 3, 1, 1).is_err());
+        assert!(imos.try_add(0, 0, 1, 1, 1).is_ok());
+        imos.add_clamped(1, 0, 5, 5, 2);
+        imos.add_clamped(0, 3, 5, 5, 100);
+        assert_eq!(imos.build(), vec![vec![1, 0], vec![2, 2]]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_imos2d_add_out_of_range() {
+        let mut imos = Imos2D::new(2, 2);
+        imos.add(0, 0, 2, 3, 1);
+    }
+
+    #[test]
+    fn test_imos2d_inclusive() {
+        let mut half_open = Imos2D::new(3, 4);
+        let mut inclusive = Imos2D::new(3, 4).inclusive();
+        let mut explicit = Imos2D::new(3, 4);
+        for &(x1, y1, x2, y2, v) in &[(0, 0, 1, 2, 1), (1, 1, 2, 3, 10), (2, 3, 2, 3, 100)] {
+            half_open.add(x1, y1, x2 + 1, y2 + 1, v);
+            inclusive.add(x1, y1, x2, y2, v);
+            explicit.add_inclusive(x1, y1, x2, y2, v);
+        }
+        inclusive.add_clamped(2, 0, 9, 0, 1000);
+        half_open.add(2, 0, 3, 1, 1000);
+        explicit.add_inclusive(2, 0, 2, 0, 1000);
+
+        let expected = half_open.build();
+        assert_eq!(inclusive.build(), expected);
+        assert_eq!(explicit.build(), expected);
+    }
+
+    #[test]
+    fn test_imos2d_reset() {
+        let mut imos = Imos2D::new(2, 3);
+        imos.add(0, 0, 2, 3, 7);
+        imos.reset();
+        imos.add(1, 1, 2, 3, 1);
+        assert_eq!(imos.build(), vec![vec![0, 0, 0], vec![0, 1, 1]]);
+    }
+
+    #[test]
+    fn test_imos2d_build_into_and_reset() {
+        let mut imos = Imos2D::new(2, 3).inclusive();
+        imos.add(0, 0, 1, 2, 1);
+        imos.add(1, 1, 1, 1, 5);
+        assert_eq!(imos.build_into(), vec![&[1, 1, 1], &[1, 6, 1]]);
+        assert_eq!(imos.build_into(), vec![&[1, 1, 1], &[1, 6, 1]]);
+        assert!(imos.try_add(0, 0, 0, 0, 1).is_err());
+
+        imos.reset();
+        assert_eq!(imos.build_into(), vec![&[0, 0, 0], &[0, 0, 0]]);
+        imos.reset();
+        imos.add(0, 2, 1, 2, -3);
+        assert_eq!(imos.build(), vec![vec![0, 0, -3], vec![0, 0, -3]]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_imos2d_add_after_build_into() {
+        let mut imos = Imos2D::new(2, 2);
+        imos.build_into();
+        imos.add_clamped(0, 0, 1, 1, 1);
+    }
+
+    #[test]
+    fn test_imos1d_generic() {
+        let mut imos = Imos1D::<f64>::new(4);
+        imos.add(0, 2, 0.5);
+        imos.add(1, 4, 0.25);
+        assert_eq!(imos.build(), vec![0.5, 0.75, 0.25, 0.25]);
+
+        let mut imos = Imos1D::<i128>::new(2);
+        imos.add(0, 2, i64::MAX as i128);
+        imos.add(0, 1, i64::MAX as i128);
+        assert_eq!(imos.build(), vec![2 * i64::MAX as i128, i64::MAX as i128]);
+    }
+
+    #[test]
+    fn test_imos2d_generic() {
+        let mut imos = Imos2D::<f64>::new(2, 2);
+        imos.add(0, 0, 2, 2, 1.5);
+        imos.add(1, 0, 2, 1, -0.5);
+        assert_eq!(imos.build(), vec![vec![1.5, 1.5], vec![1.0, 1.5]]);
+    }
+
+    #[test]
+    fn test_sparse_imos1d() {
+        let mut imos = SparseImos1D::new();
+        imos.add(1, 4, 2);
+        imos.add(2, 5, 3);
+        imos.add(4, 4, 100);
+        assert_eq!(imos.build(), vec![(1, 2), (2, 5), (4, 3), (5, 0)]);
+
+        let imos = SparseImos1D::<i64>::new();
+        assert!(imos.build().is_empty());
+        let imos = SparseImos1D::<i64>::default();
+        assert!(imos.build().is_empty());
+        let (xs, ys, grid) = SparseImos2D::<i64>::default().build();
+        assert!(xs.is_empty() && ys.is_empty() && grid.is_empty());
+    }
+
+    #[test]
+    fn test_sparse_imos2d_matches_dense() {
+        let rects = [(0, 0, 2, 2, 1), (1, 1, 3, 3, 2), (0, 2, 3, 3, 5)];
+        let mut dense = Imos2D::new(3, 3);
+        let mut sparse = SparseImos2D::new();
+        for &(x1, y1, x2, y2, v) in &rects {
+            dense.add(x1, y1, x2, y2, v);
+            sparse.add(x1 as i64, y1 as i64, x2 as i64, y2 as i64, v);
+        }
+        let dense = dense.build();
+        let (xs, ys, grid) = sparse.build();
+        for (i, row) in dense.iter().enumerate() {
+            for (j, &v) in row.iter().enumerate() {
+                let ci = xs.partition_point(|&x| x <= i as i64) - 1;
+                let cj = ys.partition_point(|&y| y <= j as i64) - 1;
+                assert_eq!(grid[ci][cj], v);
+            }
+        }
+    }
 }
@@ -0,0 +1,207 @@
+use std::collections::BTreeMap;
+
+/// 互いに交わらない半開区間の集合を管理するデータ構造
+///
+/// 区間の追加・削除に応じて、隣接・重複する区間を自動で併合・分割します。
+/// 隣接する区間（[1, 3)と[3, 5)など）は1つの区間[1, 5)として保持されます。
+///
+/// # 計算量
+/// 区間の個数をkとして
+/// - 追加・削除: ならし O(log k)
+/// - 点の包含判定: O(log k)
+///
+/// # 使用例
+/// ```
+/// # use rust_macro::IntervalSet;
+/// let mut set = IntervalSet::new();
+/// set.insert(0, 3);
+/// set.insert(5, 8);
+/// set.insert(3, 4);
+/// assert_eq!(set.iter().collect::<Vec<_>>(), vec![(0, 4), (5, 8)]);
+/// assert!(set.covered(7));
+/// assert_eq!(set.mex(), 4);
+/// set.remove(1, 6);
+/// assert_eq!(set.iter().collect::<Vec<_>>(), vec![(0, 1), (6, 8)]);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct IntervalSet {
+    /// 区間の左端 -> 右端
+    map: BTreeMap<i64, i64>,
+}
+
+impl IntervalSet {
+    /// 空の集合を作成
+    pub fn new() -> Self {
+        IntervalSet {
+            map: BTreeMap::new(),
+        }
+    }
+
+    /// 区間の個数
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    /// 区間を1つも含まないかどうか
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    /// 区間[l, r)を追加する
+    ///
+    /// # 戻り値
+    /// 新たに覆われた整数の個数
+    pub fn insert(&mut self, l: i64, r: i64) -> i64 {
+        if l >= r {
+            return 0;
+        }
+        let (mut l, mut r) = (l, r);
+        let mut removed = 0;
+        // lを含むか、lで終わる区間と併合する
+        if let Some((&pl, &pr)) = self.map.range(..=l).next_back() {
+            if pr >= l {
+                if pr >= r {
+                    return 0;
+                }
+                l = pl;
+                removed += pr - pl;
+                self.map.remove(&pl);
+            }
+        }
+        // [l, r]と交わる残りの区間を併合する
+        while let Some((&nl, &nr)) = self.map.range(l..=r).next() {
+            r = r.max(nr);
+            removed += nr - nl;
+            self.map.remove(&nl);
+        }
+        self.map.insert(l, r);
+        (r - l) - removed
+    }
+
+    /// 区間[l, r)を取り除く
+    ///
+    /// # 戻り値
+    /// 取り除かれた整数の個数
+    pub fn remove(&mut self, l: i64, r: i64) -> i64 {
+        if l >= r {
+            return 0;
+        }
+        let mut removed = 0;
+        // lをまたぐ区間を分割する
+        if let Some((&pl, &pr)) = self.map.range(..l).next_back() {
+            if pr > l {
+                self.map.insert(pl, l);
+                if pr > r {
+                    self.map.insert(r, pr);
+                    return r - l;
+                }
+                removed += pr - l;
+            }
+        }
+        while let Some((&nl, &nr)) = self.map.range(l..r).next() {
+            self.map.remove(&nl);
+            if nr > r {
+                self.map.insert(r, nr);
+                removed += r - nl;
+                break;
+            }
+            removed += nr - nl;
+        }
+        removed
+    }
+
+    /// xを含む区間を返す
+    pub fn get(&self, x: i64) -> Option<(i64, i64)> {
+        self.map
+            .range(..=x)
+            .next_back()
+            .filter(|&(_, &r)| x < r)
+            .map(|(&l, &r)| (l, r))
+    }
+
+    /// xがいずれかの区間に含まれるかどうか
+    pub fn covered(&self, x: i64) -> bool {
+        self.get(x).is_some()
+    }
+
+    /// 区間[l, r)全体が1つの区間に含まれるかどうか
+    pub fn covers(&self, l: i64, r: i64) -> bool {
+        l >= r || self.get(l).is_some_and(|(_, pr)| r <= pr)
+    }
+
+    /// x以上でどの区間にも含まれない最小の整数
+    pub fn mex_from(&self, x: i64) -> i64 {
+        self.get(x).map_or(x, |(_, r)| r)
+    }
+
+    /// どの区間にも含まれない最小の非負整数
+    pub fn mex(&self) -> i64 {
+        self.mex_from(0)
+    }
+
+    /// 区間を左端の昇順に列挙する
+    pub fn iter(&self) -> impl Iterator<Item = (i64, i64)> + '_ {
+        self.map.iter().map(|(&l, &r)| (l, r))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// [-10, 10)の各整数が覆われているかを愚直に管理して比較する
+    #[test]
+    fn test_interval_set_against_naive() {
+        let mut set = IntervalSet::new();
+        let mut naive = [false; 20];
+        let ops = [
+            (true, -3, 2),
+            (true, 4, 6),
+            (true, 2, 4),
+            (false, 0, 1),
+            (true, -10, -8),
+            (false, 5, 9),
+            (true, 7, 10),
+            (false, -9, 8),
+            (true, -5, 9),
+            (false, 3, 3),
+            (true, 1, 1),
+            (false, -10, 10),
+        ];
+        for &(ins, l, r) in &ops {
+            let changed = (l..r).filter(|&x| naive[(x + 10) as usize] != ins).count() as i64;
+            let res = if ins {
+                set.insert(l, r)
+            } else {
+                set.remove(l, r)
+            };
+            assert_eq!(res, changed);
+            for x in l..r {
+                naive[(x + 10) as usize] = ins;
+            }
+            for x in -10..10 {
+                assert_eq!(set.covered(x), naive[(x + 10) as usize]);
+            }
+            let intervals: Vec<_> = set.iter().collect();
+            assert!(intervals.windows(2).all(|w| w[0].1 < w[1].0));
+            assert!(intervals.iter().all(|&(l, r)| l < r));
+        }
+        assert!(set.is_empty());
+    }
+
+    #[test]
+    fn test_interval_set_mex_and_covers() {
+        let mut set = IntervalSet::new();
+        assert_eq!(set.mex(), 0);
+        set.insert(0, 2);
+        set.insert(3, 5);
+        assert_eq!(set.mex(), 2);
+        set.insert(2, 3);
+        assert_eq!(set.len(), 1);
+        assert_eq!(set.mex(), 5);
+        assert_eq!(set.mex_from(10), 10);
+        assert!(set.covers(1, 5));
+        assert!(!set.covers(1, 6));
+        assert_eq!(set.get(4), Some((0, 5)));
+    }
+}
@@ -0,0 +1,534 @@
+use std::marker::PhantomData;
+
+use crate::segment_tree::{Max, Min};
+use crate::Monoid;
+
+/// 遅延セグメント木に載せる「モノイドと、それに作用する写像」
+///
+/// `mapping(f, op(a, b)) == op(mapping(f, a), mapping(f, b))`（準同型）と
+/// `mapping(composition(f, g), x) == mapping(f, mapping(g, x))` を満たす必要があります。
+pub trait MapMonoid {
+    /// 値のモノイド
+    type M: Monoid;
+    /// 写像の型
+    type F: Clone;
+    /// 恒等写像
+    fn identity_map() -> Self::F;
+    /// 写像fを値xに作用させる
+    fn mapping(f: &Self::F, x: &<Self::M as Monoid>::S) -> <Self::M as Monoid>::S;
+    /// 合成写像 f∘g（gを作用させた後にfを作用させる）
+    fn composition(f: &Self::F, g: &Self::F) -> Self::F;
+}
+
+/// 遅延評価セグメント木
+///
+/// 区間への写像の作用（区間加算・区間代入など）と区間の総積を処理します。
+/// よく使う組み合わせは`AddSum`・`AddMin`・`AddMax`・`AssignSum`・`AssignMin`・`AssignMax`
+/// として用意しています。区間和を扱うものは、値を`(和, 区間の長さ)`の組で持ちます。
+///
+/// # 計算量
+/// - 構築: O(n)
+/// - 1点取得・更新、区間作用、区間の総積、二分探索: O(log n)
+///
+/// # 使用例
+/// ```
+/// # use rust_macro::LazySegmentTree;
+/// # use rust_macro::lazy_segment_tree::{AddMin, AssignSum};
+/// let mut seg = LazySegmentTree::<AddMin<i64>>::from(vec![5, 3, 7, 9, 1]);
+/// seg.apply_range(1, 4, 10);
+/// assert_eq!(seg.prod(0, 4), 5);
+/// assert_eq!(seg.prod(1, 4), 13);
+///
+/// let v: Vec<(i64, usize)> = vec![1, 2, 3, 4].into_iter().map(|x| (x, 1)).collect();
+/// let mut sum = LazySegmentTree::<AssignSum<i64>>::from(v);
+/// sum.apply_range(0, 3, Some(5));
+/// assert_eq!(sum.prod(0, 4).0, 19);
+/// ```
+pub struct LazySegmentTree<F: MapMonoid> {
+    n: usize,
+    size: usize,
+    log: u32,
+    d: Vec<<F::M as Monoid>::S>,
+    lz: Vec<F::F>,
+}
+
+impl<F: MapMonoid> Clone for LazySegmentTree<F> {
+    fn clone(&self) -> Self {
+        LazySegmentTree {
+            n: self.n,
+            size: self.size,
+            log: self.log,
+            d: self.d.clone(),
+            lz: self.lz.clone(),
+        }
+    }
+}
+
+impl<F: MapMonoid> From<Vec<<F::M as Monoid>::S>> for LazySegmentTree<F> {
+    /// 配列から構築する
+    fn from(v: Vec<<F::M as Monoid>::S>) -> Self {
+        let n = v.len();
+        let size = n.next_power_of_two();
+        let log = size.trailing_zeros();
+        let mut d = vec![F::M::identity(); 2 * size];
+        for (i, x) in v.into_iter().enumerate() {
+            d[size + i] = x;
+        }
+        let lz = vec![F::identity_map(); size];
+        let mut seg = LazySegmentTree {
+            n,
+            size,
+            log,
+            d,
+            lz,
+        };
+        for i in (1..size).rev() {
+            seg.update(i);
+        }
+        seg
+    }
+}
+
+impl<F: MapMonoid> LazySegmentTree<F> {
+    /// 長さnの、すべての要素が単位元の遅延セグメント木を作成
+    pub fn new(n: usize) -> Self {
+        Self::from(vec![F::M::identity(); n])
+    }
+
+    /// 配列の長さ
+    pub fn len(&self) -> usize {
+        self.n
+    }
+
+    /// 配列の長さが0かどうか
+    pub fn is_empty(&self) -> bool {
+        self.n == 0
+    }
+
+    /// p番目の要素をxにする
+    pub fn set(&mut self, p: usize, x: <F::M as Monoid>::S) {
+        assert!(p < self.n);
+        let p = p + self.size;
+        self.push_path(p);
+        self.d[p] = x;
+        self.update_path(p);
+    }
+
+    /// p番目の要素
+    pub fn get(&mut self, p: usize) -> <F::M as Monoid>::S {
+        assert!(p < self.n);
+        let p = p + self.size;
+        self.push_path(p);
+        self.d[p].clone()
+    }
+
+    /// 区間[l, r)の総積（空区間なら単位元）
+    pub fn prod(&mut self, l: usize, r: usize) -> <F::M as Monoid>::S {
+        assert!(l <= r && r <= self.n);
+        if l == r {
+            return F::M::identity();
+        }
+        let (mut l, mut r) = (l + self.size, r + self.size);
+        for i in (1..=self.log).rev() {
+            if ((l >> i) << i) != l {
+                self.push(l >> i);
+            }
+            if ((r >> i) << i) != r {
+                self.push((r - 1) >> i);
+            }
+        }
+        let mut sml = F::M::identity();
+        let mut smr = F::M::identity();
+        while l < r {
+            if l & 1 == 1 {
+                sml = F::M::op(&sml, &self.d[l]);
+                l += 1;
+            }
+            if r & 1 == 1 {
+                r -= 1;
+                smr = F::M::op(&self.d[r], &smr);
+            }
+            l >>= 1;
+            r >>= 1;
+        }
+        F::M::op(&sml, &smr)
+    }
+
+    /// 全体の総積
+    pub fn all_prod(&self) -> <F::M as Monoid>::S {
+        self.d[1].clone()
+    }
+
+    /// p番目の要素に写像fを作用させる
+    pub fn apply(&mut self, p: usize, f: F::F) {
+        assert!(p < self.n);
+        let p = p + self.size;
+        self.push_path(p);
+        self.d[p] = F::mapping(&f, &self.d[p]);
+        self.update_path(p);
+    }
+
+    /// 区間[l, r)の各要素に写像fを作用させる
+    pub fn apply_range(&mut self, l: usize, r: usize, f: F::F) {
+        assert!(l <= r && r <= self.n);
+        if l == r {
+            return;
+        }
+        let (l, r) = (l + self.size, r + self.size);
+        for i in (1..=self.log).rev() {
+            if ((l >> i) << i) != l {
+                self.push(l >> i);
+            }
+            if ((r >> i) << i) != r {
+                self.push((r - 1) >> i);
+            }
+        }
+        {
+            let (mut l, mut r) = (l, r);
+            while l < r {
+                if l & 1 == 1 {
+                    self.all_apply(l, &f);
+                    l += 1;
+                }
+                if r & 1 == 1 {
+                    r -= 1;
+                    self.all_apply(r, &f);
+                }
+                l >>= 1;
+                r >>= 1;
+            }
+        }
+        for i in 1..=self.log {
+            if ((l >> i) << i) != l {
+                self.update(l >> i);
+            }
+            if ((r >> i) << i) != r {
+                self.update((r - 1) >> i);
+            }
+        }
+    }
+
+    /// `g(prod(l, r))`が真となる最大のrを返す
+    ///
+    /// gは単調で、`g(identity)`が真である必要があります。
+    pub fn max_right<G>(&mut self, l: usize, g: G) -> usize
+    where
+        G: Fn(&<F::M as Monoid>::S) -> bool,
+    {
+        assert!(l <= self.n);
+        assert!(g(&F::M::identity()));
+        if l == self.n {
+            return self.n;
+        }
+        let mut l = l + self.size;
+        self.push_path(l);
+        let mut sm = F::M::identity();
+        loop {
+            while l % 2 == 0 {
+                l >>= 1;
+            }
+            if !g(&F::M::op(&sm, &self.d[l])) {
+                while l < self.size {
+                    self.push(l);
+                    l *= 2;
+                    let res = F::M::op(&sm, &self.d[l]);
+                    if g(&res) {
+                        sm = res;
+                        l += 1;
+                    }
+                }
+                return l - self.size;
+            }
+            sm = F::M::op(&sm, &self.d[l]);
+            l += 1;
+            if l & l.wrapping_neg() == l {
+                return self.n;
+            }
+        }
+    }
+
+    /// `g(prod(l, r))`が真となる最小のlを返す
+    ///
+    /// gは単調で、`g(identity)`が真である必要があります。
+    pub fn min_left<G>(&mut self, r: usize, g: G) -> usize
+    where
+        G: Fn(&<F::M as Monoid>::S) -> bool,
+    {
+        assert!(r <= self.n);
+        assert!(g(&F::M::identity()));
+        if r == 0 {
+            return 0;
+        }
+        let mut r = r + self.size;
+        self.push_path(r - 1);
+        let mut sm = F::M::identity();
+        loop {
+            r -= 1;
+            while r > 1 && r % 2 == 1 {
+                r >>= 1;
+            }
+            if !g(&F::M::op(&self.d[r], &sm)) {
+                while r < self.size {
+                    self.push(r);
+                    r = 2 * r + 1;
+                    let res = F::M::op(&self.d[r], &sm);
+                    if g(&res) {
+                        sm = res;
+                        r -= 1;
+                    }
+                }
+                return r + 1 - self.size;
+            }
+            sm = F::M::op(&self.d[r], &sm);
+            if r & r.wrapping_neg() == r {
+                return 0;
+            }
+        }
+    }
+
+    fn update(&mut self, k: usize) {
+        self.d[k] = F::M::op(&self.d[2 * k], &self.d[2 * k + 1]);
+    }
+
+    fn all_apply(&mut self, k: usize, f: &F::F) {
+        self.d[k] = F::mapping(f, &self.d[k]);
+        if k < self.size {
+            self.lz[k] = F::composition(f, &self.lz[k]);
+        }
+    }
+
+    fn push(&mut self, k: usize) {
+        let f = std::mem::replace(&mut self.lz[k], F::identity_map());
+        self.all_apply(2 * k, &f);
+        self.all_apply(2 * k + 1, &f);
+    }
+
+    /// 葉pの祖先の遅延を根から順に伝播する
+    fn push_path(&mut self, p: usize) {
+        for i in (1..=self.log).rev() {
+            self.push(p >> i);
+        }
+    }
+
+    /// 葉pの祖先の値を下から再計算する
+    fn update_path(&mut self, p: usize) {
+        for i in 1..=self.log {
+            self.update(p >> i);
+        }
+    }
+}
+
+/// 区間加算・区間和（値は`(和, 区間の長さ)`）
+pub struct AddSum<T>(PhantomData<T>);
+
+/// 区間加算・区間最小値
+pub struct AddMin<T>(PhantomData<T>);
+
+/// 区間加算・区間最大値
+pub struct AddMax<T>(PhantomData<T>);
+
+/// 区間代入・区間和（値は`(和, 区間の長さ)`、写像`None`は恒等写像）
+pub struct AssignSum<T>(PhantomData<T>);
+
+/// 区間代入・区間最小値（写像`None`は恒等写像）
+pub struct AssignMin<T>(PhantomData<T>);
+
+/// 区間代入・区間最大値（写像`None`は恒等写像）
+pub struct AssignMax<T>(PhantomData<T>);
+
+/// `(和, 区間の長さ)`の和のモノイド
+pub struct SumWithLen<T>(PhantomData<T>);
+
+macro_rules! impl_map_monoids {
+    ($($t:ty),*) => {$(
+        impl Monoid for SumWithLen<$t> {
+            type S = ($t, usize);
+            fn identity() -> ($t, usize) {
+                (0, 0)
+            }
+            fn op(a: &($t, usize), b: &($t, usize)) -> ($t, usize) {
+                (a.0 + b.0, a.1 + b.1)
+            }
+        }
+
+        impl MapMonoid for AddSum<$t> {
+            type M = SumWithLen<$t>;
+            type F = $t;
+            fn identity_map() -> $t {
+                0
+            }
+            fn mapping(&f: &$t, &(sum, len): &($t, usize)) -> ($t, usize) {
+                (sum + f * len as $t, len)
+            }
+            fn composition(f: &$t, g: &$t) -> $t {
+                f + g
+            }
+        }
+
+        impl MapMonoid for AddMin<$t> {
+            type M = Min<$t>;
+            type F = $t;
+            fn identity_map() -> $t {
+                0
+            }
+            fn mapping(&f: &$t, &x: &$t) -> $t {
+                // 単位元（空区間）には作用させない
+                if x == <$t>::MAX {
+                    x
+                } else {
+                    x + f
+                }
+            }
+            fn composition(f: &$t, g: &$t) -> $t {
+                f + g
+            }
+        }
+
+        impl MapMonoid for AddMax<$t> {
+            type M = Max<$t>;
+            type F = $t;
+            fn identity_map() -> $t {
+                0
+            }
+            fn mapping(&f: &$t, &x: &$t) -> $t {
+                if x == <$t>::MIN {
+                    x
+                } else {
+                    x + f
+                }
+            }
+            fn composition(f: &$t, g: &$t) -> $t {
+                f + g
+            }
+        }
+
+        impl MapMonoid for AssignSum<$t> {
+            type M = SumWithLen<$t>;
+            type F = Option<$t>;
+            fn identity_map() -> Option<$t> {
+                None
+            }
+            fn mapping(f: &Option<$t>, &(sum, len): &($t, usize)) -> ($t, usize) {
+                match *f {
+                    Some(a) => (a * len as $t, len),
+                    None => (sum, len),
+                }
+            }
+            fn composition(f: &Option<$t>, g: &Option<$t>) -> Option<$t> {
+                f.or(*g)
+            }
+        }
+
+        impl MapMonoid for AssignMin<$t> {
+            type M = Min<$t>;
+            type F = Option<$t>;
+            fn identity_map() -> Option<$t> {
+                None
+            }
+            fn mapping(f: &Option<$t>, &x: &$t) -> $t {
+                f.unwrap_or(x)
+            }
+            fn composition(f: &Option<$t>, g: &Option<$t>) -> Option<$t> {
+                f.or(*g)
+            }
+        }
+
+        impl MapMonoid for AssignMax<$t> {
+            type M = Max<$t>;
+            type F = Option<$t>;
+            fn identity_map() -> Option<$t> {
+                None
+            }
+            fn mapping(f: &Option<$t>, &x: &$t) -> $t {
+                f.unwrap_or(x)
+            }
+            fn composition(f: &Option<$t>, g: &Option<$t>) -> Option<$t> {
+                f.or(*g)
+            }
+        }
+    )*};
+}
+impl_map_monoids!(i32, i64, i128, isize, u32, u64, u128, usize);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::XorShift64;
+
+    #[test]
+    fn test_add_and_assign_against_naive() {
+        let mut rng = XorShift64::new(4272);
+        for n in [1usize, 2, 5, 8, 13] {
+            let mut naive: Vec<i64> = (0..n).map(|_| rng.gen_range_i64(-10..10)).collect();
+            let with_len = |v: &[i64]| v.iter().map(|&x| (x, 1)).collect::<Vec<_>>();
+            let mut add_sum = LazySegmentTree::<AddSum<i64>>::from(with_len(&naive));
+            let mut add_min = LazySegmentTree::<AddMin<i64>>::from(naive.clone());
+            let mut assign_sum = LazySegmentTree::<AssignSum<i64>>::from(with_len(&naive));
+            let mut assign_max = LazySegmentTree::<AssignMax<i64>>::from(naive.clone());
+            // add系とassign系で別々の配列を追う
+            let mut naive2 = naive.clone();
+            for _ in 0..100 {
+                let l = rng.gen_range(0..n as u64 + 1) as usize;
+                let r = rng.gen_range(l as u64..n as u64 + 1) as usize;
+                let x = rng.gen_range_i64(-10..10);
+                match rng.gen_range(0..3) {
+                    0 => {
+                        add_sum.apply_range(l, r, x);
+                        add_min.apply_range(l, r, x);
+                        assign_sum.apply_range(l, r, Some(x));
+                        assign_max.apply_range(l, r, Some(x));
+                        naive[l..r].iter_mut().for_each(|v| *v += x);
+                        naive2[l..r].iter_mut().for_each(|v| *v = x);
+                    }
+                    1 if l < n => {
+                        add_sum.set(l, (naive[l] + x, 1));
+                        add_min.apply(l, x);
+                        assign_sum.set(l, (x, 1));
+                        assign_max.set(l, x);
+                        naive[l] += x;
+                        naive2[l] = x;
+                        assert_eq!(add_min.get(l), naive[l]);
+                        assert_eq!(assign_max.get(l), x);
+                    }
+                    _ => {
+                        let w = &naive[l..r];
+                        let w2 = &naive2[l..r];
+                        assert_eq!(add_sum.prod(l, r), (w.iter().sum(), r - l));
+                        let min = w.iter().copied().min().unwrap_or(i64::MAX);
+                        assert_eq!(add_min.prod(l, r), min);
+                        assert_eq!(assign_sum.prod(l, r).0, w2.iter().sum::<i64>());
+                        let max = w2.iter().copied().max().unwrap_or(i64::MIN);
+                        assert_eq!(assign_max.prod(l, r), max);
+                    }
+                }
+            }
+            assert_eq!(add_sum.all_prod().0, naive.iter().sum::<i64>());
+        }
+    }
+
+    #[test]
+    fn test_max_right_and_min_left_against_naive() {
+        let mut rng = XorShift64::new(42721);
+        for n in [1usize, 3, 8, 11] {
+            let mut naive: Vec<i64> = (0..n).map(|_| rng.gen_range_i64(0..5)).collect();
+            let mut seg = LazySegmentTree::<AddSum<i64>>::from(
+                naive.iter().map(|&x| (x, 1)).collect::<Vec<_>>(),
+            );
+            for _ in 0..50 {
+                let l = rng.gen_range(0..n as u64 + 1) as usize;
+                let r = rng.gen_range(l as u64..n as u64 + 1) as usize;
+                let x = rng.gen_range_i64(0..3);
+                seg.apply_range(l, r, x);
+                naive[l..r].iter_mut().for_each(|v| *v += x);
+                let lim = rng.gen_range_i64(0..30);
+                let sum = |l: usize, r: usize| naive[l..r].iter().sum::<i64>();
+                for p in 0..=n {
+                    let expected = (p..=n).rev().find(|&r| sum(p, r) <= lim).unwrap();
+                    assert_eq!(seg.max_right(p, |s| s.0 <= lim), expected);
+                    let expected = (0..=p).find(|&l| sum(l, p) <= lim).unwrap();
+                    assert_eq!(seg.min_left(p, |s| s.0 <= lim), expected);
+                }
+            }
+        }
+    }
+}
@@ -1,18 +1,68 @@
 //! A collection of useful utilities for competitive programming in Rust
 
+pub mod arena_list;
 pub mod bit_vec;
+pub mod chtholly_tree;
+pub mod counter;
 pub mod cumulative_sum;
 pub mod dp;
+pub mod fast_int_set;
+pub mod fenwick_tree;
+pub mod geometry;
 pub mod graph;
 pub mod imos;
+pub mod interval_set;
+pub mod lazy_segment_tree;
+pub mod link_cut_tree;
 pub mod macro_utils;
+pub mod math;
+pub mod median_heap;
+pub mod meldable_heap;
+pub mod ordered_multiset;
+pub mod prefix_counts;
+pub mod range_add_sum;
+pub mod rectangle_sum;
+pub mod segment_tree;
+pub mod sliding_window;
+pub mod sparse_table;
+pub mod sqrt_decomposition;
+pub mod strings;
+pub mod undo;
 pub mod union_find;
 pub mod utils;
 
-pub use bit_vec::{BitVec, BitVecAll, BitVecIter, BitVecRange};
-pub use cumulative_sum::{CumulativeSum, CumulativeSum2D};
+pub use arena_list::ArenaList;
+pub use bit_vec::{
+    BitVec, BitVecAll, BitVecCombinations, BitVecIntoIter, BitVecIter, BitVecRange, BitVecSubsets,
+};
+pub use chtholly_tree::ChthollyTree;
+pub use counter::Counter;
+pub use cumulative_sum::{CumulativeSum, CumulativeSum2D, DiagonalCumulativeSum2D};
 pub use dp::{DigitDP, DpValue, MemoizedDFS};
-pub use graph::{Directed, Graph, Node, Tree, Undirected};
-pub use imos::{Imos1D, Imos2D};
-pub use union_find::{PersistentUnionFind, UnionFind};
-pub use utils::{fmt_bitvec, fmt_u2bit, is_palindrome, to_base, yesno, Compress};
+pub use fast_int_set::FastIntSet;
+pub use fenwick_tree::{FenwickTree, FenwickTree2D};
+pub use graph::{
+    AllDirectionTreeDp, AllDirectionTreeDpSolver, Directed, Graph, Node, Tree, Undirected,
+};
+pub use imos::{Imos1D, Imos2D, SparseImos1D, SparseImos2D};
+pub use interval_set::IntervalSet;
+pub use lazy_segment_tree::{LazySegmentTree, MapMonoid};
+pub use link_cut_tree::LinkCutTree;
+pub use median_heap::MedianHeap;
+pub use meldable_heap::MeldableHeap;
+pub use ordered_multiset::OrderedMultiset;
+pub use prefix_counts::PrefixCounts;
+pub use range_add_sum::RangeAddSum;
+pub use rectangle_sum::{rectangle_sum_offline, RectangleSum};
+pub use segment_tree::{Monoid, SegmentTree};
+pub use sliding_window::{sliding_window_min_max, SlidingWindowMinMax};
+pub use sparse_table::SparseTable;
+pub use sqrt_decomposition::{SqrtDecomposition, SqrtRules};
+pub use undo::{UndoMap, UndoVec};
+pub use union_find::{MappedUnionFind, PersistentUnionFind, RollbackUnionFind, UnionFind};
+pub use utils::{
+    fmt_bitvec, fmt_u2bit, from_base, is_palindrome, next_permutation, permutations,
+    prev_permutation, print_yesno, run_cases, run_length_decode, run_length_encode, run_with_stack,
+    to_base, yesno, yesno_custom, Compress, Compress2D, OutputWriter, Scanner, TimeKeeper,
+    XorShift64, YESNO,
+};
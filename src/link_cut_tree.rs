@@ -0,0 +1,353 @@
+use crate::Monoid;
+
+const NIL: usize = usize::MAX;
+
+/// Link-Cut木（動的な森の連結性とパス上の総積）
+///
+/// 頂点0..nからなる森に対して、辺の追加・削除と連結判定、パス上の値の総積を
+/// オンラインで処理します。値は`Monoid`で合成し、`path_prod(u, v)`はuからvへ向かう順に
+/// `op`を適用します（可換でなくても構いません）。
+/// 森であることを保つため、閉路を作る辺の追加はできません。
+///
+/// # 計算量
+/// 各操作 償却O(log n)
+///
+/// # 使用例
+/// ```
+/// # use rust_macro::{LinkCutTree, Monoid};
+/// struct Sum;
+/// impl Monoid for Sum {
+///     type S = i64;
+///     fn identity() -> i64 { 0 }
+///     fn op(a: &i64, b: &i64) -> i64 { a + b }
+/// }
+///
+/// let mut lct = LinkCutTree::<Sum>::from(vec![1, 2, 3, 4]);
+/// assert!(lct.link(0, 1));
+/// assert!(lct.link(1, 2));
+/// assert!(!lct.link(0, 2)); // 閉路になる
+/// assert!(lct.connected(0, 2));
+/// assert_eq!(lct.path_prod(0, 2), Some(6));
+/// assert!(lct.cut(0, 1));
+/// assert!(!lct.connected(0, 2));
+/// assert_eq!(lct.path_prod(0, 3), None);
+/// ```
+pub struct LinkCutTree<M: Monoid> {
+    ch: Vec<[usize; 2]>,
+    par: Vec<usize>,
+    rev: Vec<bool>,
+    val: Vec<M::S>,
+    /// スプレー木の部分木を左から右へ合成した値
+    sum: Vec<M::S>,
+    /// 右から左へ合成した値（反転用）
+    rsum: Vec<M::S>,
+}
+
+impl<M: Monoid> From<Vec<M::S>> for LinkCutTree<M> {
+    /// 各頂点の値を指定して、辺のない森を作成
+    fn from(val: Vec<M::S>) -> Self {
+        let n = val.len();
+        LinkCutTree {
+            ch: vec![[NIL; 2]; n],
+            par: vec![NIL; n],
+            rev: vec![false; n],
+            sum: val.clone(),
+            rsum: val.clone(),
+            val,
+        }
+    }
+}
+
+impl<M: Monoid> LinkCutTree<M> {
+    /// n頂点の、すべての値が単位元で辺のない森を作成
+    pub fn new(n: usize) -> Self {
+        Self::from(vec![M::identity(); n])
+    }
+
+    /// 頂点数
+    pub fn len(&self) -> usize {
+        self.val.len()
+    }
+
+    /// 頂点がないかどうか
+    pub fn is_empty(&self) -> bool {
+        self.val.is_empty()
+    }
+
+    /// 頂点vの値
+    pub fn get(&self, v: usize) -> &M::S {
+        &self.val[v]
+    }
+
+    /// 頂点vの値をxにする
+    pub fn set(&mut self, v: usize, x: M::S) {
+        self.expose(v);
+        self.val[v] = x;
+        self.update(v);
+    }
+
+    /// 頂点uとvが同じ木に属するかどうか
+    pub fn connected(&mut self, u: usize, v: usize) -> bool {
+        u == v || self.find_root(u) == self.find_root(v)
+    }
+
+    /// 辺(u, v)を追加する。uとvがすでに連結なら何もせず`false`を返す
+    pub fn link(&mut self, u: usize, v: usize) -> bool {
+        if self.connected(u, v) {
+            return false;
+        }
+        self.evert(u);
+        self.par[u] = v;
+        true
+    }
+
+    /// 辺(u, v)を削除する。そのような辺がなければ何もせず`false`を返す
+    pub fn cut(&mut self, u: usize, v: usize) -> bool {
+        if u == v {
+            return false;
+        }
+        self.evert(u);
+        self.expose(v);
+        // 辺があるなら、パスu-vはuとvの2頂点だけからなる
+        if self.ch[v][0] != u {
+            return false;
+        }
+        self.push(u);
+        if self.ch[u] != [NIL; 2] {
+            return false;
+        }
+        self.ch[v][0] = NIL;
+        self.par[u] = NIL;
+        self.update(v);
+        true
+    }
+
+    /// uからvへのパス上の頂点の値を順に合成したもの（連結でなければ`None`）
+    pub fn path_prod(&mut self, u: usize, v: usize) -> Option<M::S> {
+        if !self.connected(u, v) {
+            return None;
+        }
+        self.evert(u);
+        self.expose(v);
+        Some(self.sum[v].clone())
+    }
+
+    /// 頂点vを含む木で、現在の根をvにする
+    pub fn evert(&mut self, v: usize) {
+        self.expose(v);
+        self.toggle(v);
+        self.push(v);
+    }
+
+    /// 頂点vを含む木の根
+    pub fn find_root(&mut self, v: usize) -> usize {
+        self.expose(v);
+        let mut x = v;
+        loop {
+            self.push(x);
+            match self.ch[x][0] {
+                NIL => break,
+                l => x = l,
+            }
+        }
+        self.splay(x);
+        x
+    }
+
+    fn is_splay_root(&self, x: usize) -> bool {
+        let p = self.par[x];
+        p == NIL || (self.ch[p][0] != x && self.ch[p][1] != x)
+    }
+
+    fn toggle(&mut self, x: usize) {
+        self.ch[x].swap(0, 1);
+        std::mem::swap(&mut self.sum[x], &mut self.rsum[x]);
+        self.rev[x] ^= true;
+    }
+
+    fn push(&mut self, x: usize) {
+        if self.rev[x] {
+            for c in self.ch[x] {
+                if c != NIL {
+                    self.toggle(c);
+                }
+            }
+            self.rev[x] = false;
+        }
+    }
+
+    fn update(&mut self, x: usize) {
+        let [l, r] = self.ch[x];
+        let (mut sum, mut rsum) = (self.val[x].clone(), self.val[x].clone());
+        if l != NIL {
+            sum = M::op(&self.sum[l], &sum);
+            rsum = M::op(&rsum, &self.rsum[l]);
+        }
+        if r != NIL {
+            sum = M::op(&sum, &self.sum[r]);
+            rsum = M::op(&self.rsum[r], &rsum);
+        }
+        self.sum[x] = sum;
+        self.rsum[x] = rsum;
+    }
+
+    fn rotate(&mut self, x: usize) {
+        let p = self.par[x];
+        let g = self.par[p];
+        let d = usize::from(self.ch[p][1] == x);
+        let b = self.ch[x][d ^ 1];
+        if !self.is_splay_root(p) {
+            let pd = usize::from(self.ch[g][1] == p);
+            self.ch[g][pd] = x;
+        }
+        self.par[x] = g;
+        self.ch[x][d ^ 1] = p;
+        self.par[p] = x;
+        self.ch[p][d] = b;
+        if b != NIL {
+            self.par[b] = p;
+        }
+        self.update(p);
+        self.update(x);
+    }
+
+    fn splay(&mut self, x: usize) {
+        let mut path = vec![x];
+        let mut y = x;
+        while !self.is_splay_root(y) {
+            y = self.par[y];
+            path.push(y);
+        }
+        for &y in path.iter().rev() {
+            self.push(y);
+        }
+        while !self.is_splay_root(x) {
+            let p = self.par[x];
+            if !self.is_splay_root(p) {
+                let g = self.par[p];
+                if (self.ch[g][1] == p) == (self.ch[p][1] == x) {
+                    self.rotate(p);
+                } else {
+                    self.rotate(x);
+                }
+            }
+            self.rotate(x);
+        }
+    }
+
+    /// 根からxまでのパスを1本のスプレー木にまとめ、xをその根にする
+    fn expose(&mut self, x: usize) {
+        let mut prev = NIL;
+        let mut cur = x;
+        while cur != NIL {
+            self.splay(cur);
+            self.ch[cur][1] = prev;
+            self.update(cur);
+            prev = cur;
+            cur = self.par[cur];
+        }
+        self.splay(x);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::XorShift64;
+
+    struct Concat;
+    impl Monoid for Concat {
+        type S = Vec<usize>;
+        fn identity() -> Vec<usize> {
+            vec![]
+        }
+        fn op(a: &Vec<usize>, b: &Vec<usize>) -> Vec<usize> {
+            a.iter().chain(b).copied().collect()
+        }
+    }
+
+    /// 素朴な森でuからvへのパス（頂点列）を求める
+    fn naive_path(adj: &[Vec<usize>], u: usize, v: usize) -> Option<Vec<usize>> {
+        let mut prev = vec![usize::MAX; adj.len()];
+        let mut stack = vec![u];
+        prev[u] = u;
+        while let Some(x) = stack.pop() {
+            for &y in &adj[x] {
+                if prev[y] == usize::MAX {
+                    prev[y] = x;
+                    stack.push(y);
+                }
+            }
+        }
+        if prev[v] == usize::MAX {
+            return None;
+        }
+        let mut path = vec![v];
+        while *path.last().unwrap() != u {
+            path.push(prev[*path.last().unwrap()]);
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    #[test]
+    fn test_link_cut_tree_against_naive() {
+        let mut rng = XorShift64::new(4246);
+        for _ in 0..20 {
+            let n = rng.gen_range(1..12) as usize;
+            let mut vals: Vec<usize> = (0..n).collect();
+            let mut lct =
+                LinkCutTree::<Concat>::from(vals.iter().map(|&x| vec![x]).collect::<Vec<_>>());
+            let mut adj = vec![vec![]; n];
+            for _ in 0..300 {
+                let u = rng.gen_range(0..n as u64) as usize;
+                let v = rng.gen_range(0..n as u64) as usize;
+                match rng.gen_range(0..4) {
+                    0 => {
+                        let ok = naive_path(&adj, u, v).is_none();
+                        assert_eq!(lct.link(u, v), ok);
+                        if ok {
+                            adj[u].push(v);
+                            adj[v].push(u);
+                        }
+                    }
+                    1 => {
+                        let ok = adj[u].contains(&v);
+                        assert_eq!(lct.cut(u, v), ok);
+                        if ok {
+                            adj[u].retain(|&x| x != v);
+                            adj[v].retain(|&x| x != u);
+                        }
+                    }
+                    2 => {
+                        vals[u] = rng.gen_range(0..100) as usize;
+                        lct.set(u, vec![vals[u]]);
+                        assert_eq!(lct.get(u), &vec![vals[u]]);
+                    }
+                    _ => {
+                        let expected = naive_path(&adj, u, v)
+                            .map(|p| p.into_iter().map(|x| vals[x]).collect::<Vec<_>>());
+                        assert_eq!(lct.connected(u, v), expected.is_some());
+                        assert_eq!(lct.path_prod(u, v), expected);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_find_root_and_evert() {
+        let mut lct = LinkCutTree::<Concat>::new(5);
+        assert_eq!(lct.len(), 5);
+        for (u, v) in [(1, 0), (2, 1), (3, 1)] {
+            assert!(lct.link(u, v));
+        }
+        assert_eq!(lct.find_root(3), 0);
+        lct.evert(2);
+        assert_eq!(lct.find_root(3), 2);
+        assert_eq!(lct.find_root(4), 4);
+        assert!(!lct.cut(0, 2));
+        assert!(!lct.cut(4, 4));
+        assert_eq!(lct.path_prod(0, 3), Some(vec![]));
+    }
+}
@@ -20,10 +20,219 @@ macro_rules! println {
     }};
 }
 
+/// Prints the elements of a vector on one line, separated by `sep` (default: space)
+///
+/// `printvec!(vec => output)` / `printvec!(vec, sep => output)` write to `output`
+/// (any `std::io::Write`) instead of stdout.
 #[macro_export]
 macro_rules! printvec {
     ($vec:expr) => {
-        println!("{}", $vec.iter().join(" "));
+        $crate::printvec!($vec, " ")
+    };
+    ($vec:expr, $sep:expr) => {
+        $crate::printvec!($vec, $sep => ::std::io::stdout().lock())
+    };
+    ($vec:expr => $output:expr) => {
+        $crate::printvec!($vec, " " => $output)
+    };
+    ($vec:expr, $sep:expr => $output:expr) => {{
+        let mut out = $crate::OutputWriter::new($output);
+        out.writeln_iter($vec.iter(), $sep);
+    }};
+}
+
+/// Prints a 2D vector one row per line, separating elements by `sep` (default: space)
+///
+/// `printgrid!(grid => output)` / `printgrid!(grid, sep => output)` write to `output`
+/// (any `std::io::Write`) instead of stdout.
+#[macro_export]
+macro_rules! printgrid {
+    ($grid:expr) => {
+        $crate::printgrid!($grid, " ")
+    };
+    ($grid:expr, $sep:expr) => {
+        $crate::printgrid!($grid, $sep => ::std::io::stdout().lock())
+    };
+    ($grid:expr => $output:expr) => {
+        $crate::printgrid!($grid, " " => $output)
+    };
+    ($grid:expr, $sep:expr => $output:expr) => {{
+        let mut out = $crate::OutputWriter::new($output);
+        out.write_grid(&$grid, $sep);
+    }};
+}
+
+/// 複数テストケースの問題を処理する
+///
+/// 標準入力の先頭からテストケース数Tを読み、`solve(&mut Scanner, &mut OutputWriter<W>)`を
+/// T回呼び出します。出力は最後にまとめて書き出されます。
+/// `solve_cases!(solve, input, output)` のように入出力先を指定することもできます。
+///
+/// # 使用例
+/// ```no_run
+/// # use rust_macro::{solve_cases, OutputWriter, Scanner};
+/// use std::io::Write;
+///
+/// fn solve<W: Write>(sc: &mut Scanner, out: &mut OutputWriter<W>) {
+///     let n: usize = sc.read();
+///     writeln!(out, "{}", n * 2).unwrap();
+/// }
+///
+/// fn main() {
+///     solve_cases!(solve);
+/// }
+/// ```
+#[macro_export]
+macro_rules! solve_cases {
+    ($solve:expr) => {
+        $crate::solve_cases!($solve, ::std::io::stdin(), ::std::io::stdout().lock())
+    };
+    ($solve:expr, $input:expr, $output:expr) => {{
+        let _ = $crate::run_cases($input, $output, $solve);
+    }};
+}
+
+/// 式とその値を標準エラー出力に表示する（ローカル実行時のみ）
+///
+/// `local` featureが有効な場合、またはdebug_assertionsが有効な場合（非releaseビルド）にのみ
+/// `[file:line] 式 = 値` の形式で出力します。それ以外では何も展開されず式も評価されないため、
+/// 提出時に消し忘れても出力や実行時間に影響しません。
+///
+/// # 使用例
+/// ```
+/// # use rust_macro::debug;
+/// let n = 3;
+/// let v = vec![1, 2, 3];
+/// debug!(n, v, n * 2);
+/// assert_eq!(v.len(), n); // 値はムーブされない
+/// ```
+#[cfg(any(feature = "local", debug_assertions))]
+#[macro_export]
+macro_rules! debug {
+    ($($e:expr),* $(,)?) => {
+        $(
+            eprintln!("[{}:{}] {} = {:?}", file!(), line!(), stringify!($e), &$e);
+        )*
+    };
+}
+
+/// 式とその値を標準エラー出力に表示する（ローカル実行時のみ）
+///
+/// 提出用のビルドでは何も展開されません。
+#[cfg(not(any(feature = "local", debug_assertions)))]
+#[macro_export]
+macro_rules! debug {
+    ($($e:expr),* $(,)?) => {};
+}
+
+/// 任意の次元の多次元ベクタを初期値で作成する
+///
+/// `mat![init; d1; d2; ...; dk]` は `vec![vec![...vec![init; dk]...; d2]; d1]` と同じです。
+/// 初期値は`Clone`を実装している必要があります。
+///
+/// # 使用例
+/// ```
+/// # use rust_macro::mat;
+/// let mut dp = mat![0i64; 2; 3; 4];
+/// dp[1][2][3] = 5;
+/// assert_eq!(dp.len(), 2);
+/// assert_eq!(dp[0].len(), 3);
+/// assert_eq!(dp[0][0].len(), 4);
+/// assert_eq!(dp[1][2][3], 5);
+/// ```
+#[macro_export]
+macro_rules! mat {
+    ($init:expr; $n:expr) => {
+        vec![$init; $n]
+    };
+    ($init:expr; $n:expr $(; $rest:expr)+) => {
+        vec![$crate::mat![$init $(; $rest)+]; $n]
+    };
+}
+
+/// 入力を宣言的に読み込む
+///
+/// `from sc,` を先頭に付けると指定した`Scanner`から、付けない場合は標準入力から読み込みます。
+/// 型には`usize`などの`FromStr`を実装した型に加えて、以下を指定できます。
+///
+/// - `Chars`: 1トークンを`Vec<char>`として読む
+/// - `Bytes`: 1トークンを`Vec<u8>`として読む
+/// - `Usize1`: 1-indexedの値を0-indexedの`usize`として読む
+/// - `[T; n]`: n個の`T`を`Vec`として読む（入れ子にできます）
+/// - `(T1, T2, ...)`: タプルとして読む
+///
+/// # 使用例
+/// ```
+/// # use rust_macro::{input, Scanner};
+/// let mut sc = Scanner::new("2 3\n1 2 3\n4 5 6\n#.\n.#\n2 x\n".as_bytes());
+/// input! {
+///     from sc,
+///     h: usize,
+///     w: usize,
+///     a: [[i64; w]; h],
+///     grid: [Chars; h],
+///     mut p: (Usize1, String),
+/// }
+/// p.0 += 10;
+/// assert_eq!(a, vec![vec![1, 2, 3], vec![4, 5, 6]]);
+/// assert_eq!(grid, vec![vec!['#', '.'], vec!['.', '#']]);
+/// assert_eq!(p, (11, "x".to_string()));
+/// ```
+#[macro_export]
+macro_rules! input {
+    (from $sc:expr, $($rest:tt)*) => {
+        $crate::__input_inner!(@from [$sc] $($rest)*);
+    };
+    ($($rest:tt)*) => {
+        $crate::__input_inner!(@stdin $($rest)*);
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __input_inner {
+    (@from [$sc:expr]) => {};
+    (@from [$sc:expr] mut $var:ident : $t:tt $(, $($rest:tt)*)?) => {
+        let mut $var = $crate::__input_read!($sc, $t);
+        $crate::__input_inner!(@from [$sc] $($($rest)*)?);
+    };
+    (@from [$sc:expr] $var:ident : $t:tt $(, $($rest:tt)*)?) => {
+        let $var = $crate::__input_read!($sc, $t);
+        $crate::__input_inner!(@from [$sc] $($($rest)*)?);
+    };
+    (@stdin) => {};
+    (@stdin mut $var:ident : $t:tt $(, $($rest:tt)*)?) => {
+        let mut $var = $crate::utils::scanner::with_stdin(|sc| $crate::__input_read!(sc, $t));
+        $crate::__input_inner!(@stdin $($($rest)*)?);
+    };
+    (@stdin $var:ident : $t:tt $(, $($rest:tt)*)?) => {
+        let $var = $crate::utils::scanner::with_stdin(|sc| $crate::__input_read!(sc, $t));
+        $crate::__input_inner!(@stdin $($($rest)*)?);
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __input_read {
+    ($sc:expr, [$t:tt; $len:expr]) => {
+        (0..$len)
+            .map(|_| $crate::__input_read!($sc, $t))
+            .collect::<Vec<_>>()
+    };
+    ($sc:expr, ($($t:tt),* $(,)?)) => {
+        ($($crate::__input_read!($sc, $t)),*)
+    };
+    ($sc:expr, Chars) => {
+        $sc.read_chars()
+    };
+    ($sc:expr, Bytes) => {
+        $sc.read::<String>().into_bytes()
+    };
+    ($sc:expr, Usize1) => {
+        $sc.read::<usize>() - 1
+    };
+    ($sc:expr, $t:ty) => {
+        $sc.read::<$t>()
     };
 }
 
@@ -38,9 +247,9 @@ mod tests {
         let mut output = Vec::new();
         {
             let mut cursor = std::io::Cursor::new(&mut output);
-            let _ = write!(cursor, "{}", "Hello");
-            let _ = write!(cursor, "{}", " ");
-            let _ = write!(cursor, "{}", "World");
+            let _ = write!(cursor, "Hello");
+            let _ = write!(cursor, " ");
+            let _ = write!(cursor, "World");
         }
         assert_eq!(output, b"Hello World");
     }
@@ -50,9 +259,82 @@ mod tests {
         let mut output = Vec::new();
         {
             let mut cursor = std::io::Cursor::new(&mut output);
-            let _ = writeln!(cursor, "{}", "Hello");
-            let _ = writeln!(cursor, "{}", "World");
+            let _ = writeln!(cursor, "Hello");
+            let _ = writeln!(cursor, "World");
         }
         assert_eq!(output, b"Hello\nWorld\n");
     }
+
+    #[test]
+    fn test_input_macro() {
+        let mut sc = crate::Scanner::new("3\n1 2 3\nabc\n2 5 2.5\n1 2\n3 4\n".as_bytes());
+        input! {
+            from sc,
+            n: usize,
+            a: [i64; n],
+            s: Bytes,
+            t: (Usize1, u32, f64),
+            pairs: [(i32, i32); 2],
+        }
+        assert_eq!(a, vec![1, 2, 3]);
+        assert_eq!(s, b"abc".to_vec());
+        assert_eq!(t, (1, 5, 2.5));
+        assert_eq!(pairs, vec![(1, 2), (3, 4)]);
+    }
+
+    #[test]
+    fn test_debug_macro() {
+        let v = [1, 2, 3];
+        let s = String::from("abc");
+        debug!(v, s, v.len() + 1,);
+        debug!();
+        assert_eq!(v.len(), 3);
+        assert_eq!(s, "abc");
+    }
+
+    #[test]
+    fn test_mat_macro() {
+        let a = mat![1u8; 3];
+        assert_eq!(a, vec![1, 1, 1]);
+        let n = 2;
+        let b = mat![None::<usize>; n + 1; n];
+        assert_eq!(b, vec![vec![None; 2]; 3]);
+        let c = mat![String::from("x"); 1; 2; 1; 2];
+        assert_eq!(c[0][1][0], vec!["x".to_string(), "x".to_string()]);
+    }
+
+    #[test]
+    fn test_printvec_and_printgrid() {
+        let v = [1, 2, 3];
+        let mut output = Vec::new();
+        printvec!(v => &mut output);
+        printvec!(v, "" => &mut output);
+        printvec!(&v[1..], "\n" => &mut output);
+        printvec!(Vec::<i32>::new() => &mut output);
+        assert_eq!(output, b"1 2 3\n123\n2\n3\n\n");
+
+        let mut output = Vec::new();
+        let grid = vec![vec!['#', '.'], vec!['.', '#']];
+        printgrid!(grid, "" => &mut output);
+        printgrid!([vec![1, 2], vec![3, 4]] => &mut output);
+        assert_eq!(output, b"#.\n.#\n1 2\n3 4\n");
+
+        // 出力先を省略すると標準出力に書き出す
+        printvec!(v);
+        printgrid!(grid, ", ");
+    }
+
+    #[test]
+    fn test_solve_cases_macro() {
+        let mut output = Vec::new();
+        solve_cases!(
+            |sc: &mut crate::Scanner, out: &mut crate::OutputWriter<_>| {
+                let x: i32 = sc.read();
+                writeln!(out, "{}", -x).unwrap();
+            },
+            "2\n5\n-3\n".as_bytes(),
+            &mut output
+        );
+        assert_eq!(output, b"-5\n3\n");
+    }
 }
@@ -0,0 +1,286 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{Add, Sub};
+use std::str::FromStr;
+
+/// 1桁（リム）あたりの基数
+const BASE: u64 = 1_000_000_000;
+/// 1リムあたりの10進桁数
+const BASE_DIGITS: usize = 9;
+
+/// 多倍長の非負整数
+///
+/// 10^9進数の桁を下位から順に保持します。10進数の文字列との相互変換、加減算、比較、
+/// 小さい整数との乗除算ができます。
+///
+/// # 計算量
+/// 桁数をnとして
+/// - 加減算・比較・小さい整数との乗除算: O(n)
+/// - 10進文字列との変換: O(n)
+///
+/// # 使用例
+/// ```
+/// # use rust_macro::math::BigUint;
+/// let a: BigUint = "999999999999999999999".parse().unwrap();
+/// let b = BigUint::from(1u64);
+/// assert_eq!((&a + &b).to_string(), "1000000000000000000000");
+/// assert_eq!((&a - &a).to_string(), "0");
+/// assert_eq!(a.mul_small(2).to_string(), "1999999999999999999998");
+/// assert_eq!(a.div_rem_small(7), ("142857142857142857142".parse().unwrap(), 5));
+/// assert!(a > b);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct BigUint {
+    /// 下位からの10^9進数の桁（最上位の桁は0でない。0のときは空）
+    limbs: Vec<u32>,
+}
+
+impl BigUint {
+    /// 0
+    pub fn zero() -> Self {
+        BigUint { limbs: Vec::new() }
+    }
+
+    /// 0かどうか
+    pub fn is_zero(&self) -> bool {
+        self.limbs.is_empty()
+    }
+
+    fn normalize(mut self) -> Self {
+        while self.limbs.last() == Some(&0) {
+            self.limbs.pop();
+        }
+        self
+    }
+
+    /// self * x
+    pub fn mul_small(&self, x: u32) -> Self {
+        let mut limbs = Vec::with_capacity(self.limbs.len() + 1);
+        let mut carry = 0u64;
+        for &d in &self.limbs {
+            let v = d as u64 * x as u64 + carry;
+            limbs.push((v % BASE) as u32);
+            carry = v / BASE;
+        }
+        while carry > 0 {
+            limbs.push((carry % BASE) as u32);
+            carry /= BASE;
+        }
+        BigUint { limbs }.normalize()
+    }
+
+    /// (self / x, self % x)
+    ///
+    /// # パニック
+    /// xが0の場合panicします
+    pub fn div_rem_small(&self, x: u32) -> (Self, u32) {
+        assert!(x != 0, "division by zero");
+        let mut limbs = vec![0; self.limbs.len()];
+        let mut rem = 0u64;
+        for i in (0..self.limbs.len()).rev() {
+            let cur = rem * BASE + self.limbs[i] as u64;
+            limbs[i] = (cur / x as u64) as u32;
+            rem = cur % x as u64;
+        }
+        (BigUint { limbs }.normalize(), rem as u32)
+    }
+
+    /// self - rhs。rhsの方が大きい場合は`None`
+    pub fn checked_sub(&self, rhs: &Self) -> Option<Self> {
+        if *self < *rhs {
+            return None;
+        }
+        let mut limbs = Vec::with_capacity(self.limbs.len());
+        let mut borrow = 0i64;
+        for (i, &d) in self.limbs.iter().enumerate() {
+            let mut v = d as i64 - borrow - rhs.limbs.get(i).map_or(0, |&x| x as i64);
+            borrow = 0;
+            if v < 0 {
+                v += BASE as i64;
+                borrow = 1;
+            }
+            limbs.push(v as u32);
+        }
+        Some(BigUint { limbs }.normalize())
+    }
+}
+
+impl From<u64> for BigUint {
+    fn from(mut x: u64) -> Self {
+        let mut limbs = Vec::new();
+        while x > 0 {
+            limbs.push((x % BASE) as u32);
+            x /= BASE;
+        }
+        BigUint { limbs }
+    }
+}
+
+impl FromStr for BigUint {
+    type Err = String;
+
+    /// 10進数の文字列から変換する
+    fn from_str(s: &str) -> Result<Self, String> {
+        if s.is_empty() || !s.bytes().all(|c| c.is_ascii_digit()) {
+            return Err(format!("invalid number: {:?}", s));
+        }
+        let bytes = s.as_bytes();
+        let mut limbs = Vec::with_capacity(bytes.len() / BASE_DIGITS + 1);
+        let mut end = bytes.len();
+        while end > 0 {
+            let start = end.saturating_sub(BASE_DIGITS);
+            let v = bytes[start..end]
+                .iter()
+                .fold(0u32, |acc, &c| acc * 10 + (c - b'0') as u32);
+            limbs.push(v);
+            end = start;
+        }
+        Ok(BigUint { limbs }.normalize())
+    }
+}
+
+impl fmt::Display for BigUint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.limbs.split_last() {
+            None => write!(f, "0"),
+            Some((top, rest)) => {
+                write!(f, "{}", top)?;
+                for d in rest.iter().rev() {
+                    write!(f, "{:09}", d)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl Ord for BigUint {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.limbs
+            .len()
+            .cmp(&other.limbs.len())
+            .then_with(|| self.limbs.iter().rev().cmp(other.limbs.iter().rev()))
+    }
+}
+
+impl PartialOrd for BigUint {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Add for &BigUint {
+    type Output = BigUint;
+    fn add(self, rhs: &BigUint) -> BigUint {
+        let n = self.limbs.len().max(rhs.limbs.len());
+        let mut limbs = Vec::with_capacity(n + 1);
+        let mut carry = 0u32;
+        for i in 0..n {
+            let v = self.limbs.get(i).copied().unwrap_or(0)
+                + rhs.limbs.get(i).copied().unwrap_or(0)
+                + carry;
+            carry = u32::from(v >= BASE as u32);
+            limbs.push(v - carry * BASE as u32);
+        }
+        if carry > 0 {
+            limbs.push(carry);
+        }
+        BigUint { limbs }
+    }
+}
+
+impl Add for BigUint {
+    type Output = BigUint;
+    fn add(self, rhs: BigUint) -> BigUint {
+        &self + &rhs
+    }
+}
+
+impl Sub for &BigUint {
+    type Output = BigUint;
+
+    /// # パニック
+    /// 結果が負になる場合panicします
+    fn sub(self, rhs: &BigUint) -> BigUint {
+        self.checked_sub(rhs)
+            .expect("BigUint subtraction underflow")
+    }
+}
+
+impl Sub for BigUint {
+    type Output = BigUint;
+    fn sub(self, rhs: BigUint) -> BigUint {
+        &self - &rhs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_biguint_parse_display() {
+        for s in [
+            "0",
+            "7",
+            "1000000000",
+            "999999999",
+            "123456789012345678901234567890",
+        ] {
+            assert_eq!(s.parse::<BigUint>().unwrap().to_string(), s);
+        }
+        assert_eq!("000120".parse::<BigUint>().unwrap().to_string(), "120");
+        assert!("".parse::<BigUint>().is_err());
+        assert!("-1".parse::<BigUint>().is_err());
+        assert_eq!(BigUint::from(u64::MAX).to_string(), u64::MAX.to_string());
+        assert!(BigUint::from(0).is_zero());
+    }
+
+    #[test]
+    fn test_biguint_arith_against_u128() {
+        let vals: Vec<u128> = vec![
+            0,
+            1,
+            999_999_999,
+            1_000_000_000,
+            u64::MAX as u128,
+            1 << 100,
+            12345678901234567890123456789,
+        ];
+        for &a in &vals {
+            for &b in &vals {
+                let (x, y): (BigUint, BigUint) = (
+                    a.to_string().parse().unwrap(),
+                    b.to_string().parse().unwrap(),
+                );
+                assert_eq!((&x + &y).to_string(), (a + b).to_string());
+                assert_eq!(x.cmp(&y), a.cmp(&b));
+                match x.checked_sub(&y) {
+                    Some(d) => assert_eq!(d.to_string(), (a - b).to_string()),
+                    None => assert!(a < b),
+                }
+            }
+            for k in [1u32, 3, 1_000_000_007] {
+                if let Some(p) = a.checked_mul(k as u128) {
+                    assert_eq!(x_of(a).mul_small(k).to_string(), p.to_string());
+                }
+                let (q, r) = x_of(a).div_rem_small(k);
+                assert_eq!(
+                    (q.to_string(), r as u128),
+                    ((a / k as u128).to_string(), a % k as u128)
+                );
+            }
+            assert!(x_of(a).mul_small(0).is_zero());
+        }
+    }
+
+    fn x_of(a: u128) -> BigUint {
+        a.to_string().parse().unwrap()
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_biguint_sub_underflow() {
+        let _ = BigUint::from(1) - BigUint::from(2);
+    }
+}
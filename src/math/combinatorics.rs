@@ -0,0 +1,153 @@
+use super::modint::ModInt;
+
+/// 階乗とその逆元のテーブルを使って二項係数などを求める構造体
+///
+/// テーブルは必要に応じて自動で拡張されます。法Mは素数である必要があります。
+///
+/// # 計算量
+/// - 構築・拡張: O(n)
+/// - 各クエリ: テーブルが足りていればO(1)
+///
+/// # 使用例
+/// ```
+/// # use rust_macro::math::Combinatorics;
+/// let mut comb = Combinatorics::<998_244_353>::new(10);
+/// assert_eq!(comb.binom(5, 2).val(), 10);
+/// assert_eq!(comb.comb(5, 2).val(), 10);
+/// assert_eq!(comb.perm(5, 2).val(), 20);
+/// assert_eq!(comb.multiset(3, 2).val(), 6);
+/// assert_eq!(comb.catalan(4).val(), 14);
+/// // テーブルは自動で拡張される
+/// assert_eq!(comb.binom(100, 50), comb.fact(100) / (comb.fact(50) * comb.fact(50)));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Combinatorics<const M: u64> {
+    fact: Vec<ModInt<M>>,
+    inv_fact: Vec<ModInt<M>>,
+}
+
+impl<const M: u64> Combinatorics<M> {
+    /// n!までのテーブルを作成
+    pub fn new(n: usize) -> Self {
+        let mut comb = Combinatorics {
+            fact: vec![ModInt::new(1)],
+            inv_fact: vec![ModInt::new(1)],
+        };
+        comb.ensure(n);
+        comb
+    }
+
+    /// n!までテーブルを拡張する（足りない場合は2倍以上に広げる）
+    fn ensure(&mut self, n: usize) {
+        let cur = self.fact.len();
+        if n < cur {
+            return;
+        }
+        assert!((n as u64) < M, "n must be smaller than the modulus");
+        let size = (n + 1).max(cur * 2).min(M as usize);
+        for i in cur..size {
+            let f = self.fact[i - 1] * ModInt::from(i);
+            self.fact.push(f);
+        }
+        self.inv_fact.resize(size, ModInt::default());
+        self.inv_fact[size - 1] = self.fact[size - 1].inv();
+        for i in (cur..size - 1).rev() {
+            self.inv_fact[i] = self.inv_fact[i + 1] * ModInt::from(i + 1);
+        }
+    }
+
+    /// n!
+    pub fn fact(&mut self, n: usize) -> ModInt<M> {
+        self.ensure(n);
+        self.fact[n]
+    }
+
+    /// 1 / n!
+    pub fn inv_fact(&mut self, n: usize) -> ModInt<M> {
+        self.ensure(n);
+        self.inv_fact[n]
+    }
+
+    /// 二項係数 nCk（k > nのときは0）
+    pub fn binom(&mut self, n: usize, k: usize) -> ModInt<M> {
+        if k > n {
+            return ModInt::default();
+        }
+        self.ensure(n);
+        self.fact[n] * self.inv_fact[k] * self.inv_fact[n - k]
+    }
+
+    /// 二項係数 nCk（`binom`と同じ）
+    pub fn comb(&mut self, n: usize, k: usize) -> ModInt<M> {
+        self.binom(n, k)
+    }
+
+    /// 順列の数 nPk（k > nのときは0）
+    pub fn perm(&mut self, n: usize, k: usize) -> ModInt<M> {
+        if k > n {
+            return ModInt::default();
+        }
+        self.ensure(n);
+        self.fact[n] * self.inv_fact[n - k]
+    }
+
+    /// 重複組合せ nHk（n種類から重複を許してk個選ぶ方法の数）
+    pub fn multiset(&mut self, n: usize, k: usize) -> ModInt<M> {
+        if n == 0 {
+            return ModInt::from(u8::from(k == 0));
+        }
+        self.binom(n + k - 1, k)
+    }
+
+    /// n番目のカタラン数 (2n)! / ((n+1)! n!)
+    pub fn catalan(&mut self, n: usize) -> ModInt<M> {
+        self.ensure((2 * n).max(n + 1));
+        self.fact[2 * n] * self.inv_fact[n + 1] * self.inv_fact[n]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_binom_against_pascal() {
+        let mut comb = Combinatorics::<1_000_000_007>::new(0);
+        let mut pascal = vec![vec![0u64; 31]; 31];
+        for n in 0..31 {
+            pascal[n][0] = 1;
+            for k in 1..=n {
+                pascal[n][k] = (pascal[n - 1][k - 1] + pascal[n - 1][k]) % 1_000_000_007;
+            }
+        }
+        for (n, row) in pascal.iter().enumerate() {
+            for (k, &v) in row.iter().enumerate() {
+                assert_eq!(comb.binom(n, k).val(), v);
+            }
+        }
+    }
+
+    #[test]
+    fn test_perm_multiset_catalan() {
+        let mut comb = Combinatorics::<998_244_353>::new(4);
+        assert_eq!(comb.perm(6, 3).val(), 120);
+        assert_eq!(comb.perm(3, 4).val(), 0);
+        assert_eq!(comb.multiset(0, 0).val(), 1);
+        assert_eq!(comb.multiset(0, 3).val(), 0);
+        assert_eq!(comb.multiset(4, 3).val(), 20);
+        let catalan: Vec<u64> = (0..8).map(|n| comb.catalan(n).val()).collect();
+        assert_eq!(catalan, vec![1, 1, 2, 5, 14, 42, 132, 429]);
+        assert_eq!((comb.fact(20) * comb.inv_fact(20)).val(), 1);
+        let mut fresh = Combinatorics::<998_244_353>::new(0);
+        assert_eq!(fresh.catalan(0).val(), 1);
+        assert_eq!(fresh.comb(10, 3), fresh.binom(10, 3));
+        let mut small = Combinatorics::<7>::new(3);
+        assert_eq!(small.binom(6, 2).val(), 1);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_table_exceeds_modulus() {
+        Combinatorics::<7>::new(7);
+    }
+}
@@ -0,0 +1,384 @@
+/// 最大公約数（常に非負）
+///
+/// # 使用例
+/// ```
+/// # use rust_macro::math::gcd;
+/// assert_eq!(gcd(12, 18), 6);
+/// assert_eq!(gcd(-4, 6), 2);
+/// assert_eq!(gcd(0, 0), 0);
+/// ```
+pub fn gcd(a: i64, b: i64) -> i64 {
+    let (mut a, mut b) = (a.unsigned_abs(), b.unsigned_abs());
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a as i64
+}
+
+/// 最小公倍数（常に非負）。オーバーフローする場合は`None`
+pub fn checked_lcm(a: i64, b: i64) -> Option<i64> {
+    if a == 0 || b == 0 {
+        return Some(0);
+    }
+    (a / gcd(a, b)).checked_mul(b).map(i64::abs)
+}
+
+/// 最小公倍数（常に非負）
+///
+/// # パニック
+/// 結果がi64に収まらない場合panicします。上限で打ち切りたい場合は`checked_lcm`を使ってください。
+pub fn lcm(a: i64, b: i64) -> i64 {
+    checked_lcm(a, b).expect("lcm overflow")
+}
+
+/// 拡張ユークリッドの互除法
+///
+/// # 戻り値
+/// `a * x + b * y = g` を満たす `(g, x, y)`。gは`gcd(a, b)`（非負）
+///
+/// # 使用例
+/// ```
+/// # use rust_macro::math::ext_gcd;
+/// let (g, x, y) = ext_gcd(240, 46);
+/// assert_eq!(g, 2);
+/// assert_eq!(240 * x + 46 * y, 2);
+/// ```
+pub fn ext_gcd(a: i64, b: i64) -> (i64, i64, i64) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (1, 0);
+    let (mut old_y, mut y) = (0, 1);
+    while r != 0 {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_x, x) = (x, old_x - q * x);
+        (old_y, y) = (y, old_y - q * y);
+    }
+    if old_r < 0 {
+        (-old_r, -old_x, -old_y)
+    } else {
+        (old_r, old_x, old_y)
+    }
+}
+
+/// a^e mod m を繰り返し二乗法で求める
+///
+/// # 使用例
+/// ```
+/// # use rust_macro::math::{inv_mod, pow_mod};
+/// assert_eq!(pow_mod(2, 10, 1000), 24);
+/// assert_eq!(pow_mod(3, 0, 1), 0);
+/// assert_eq!(inv_mod(3, 10), Some(7));
+/// assert_eq!(inv_mod(-3, 10), Some(3));
+/// assert_eq!(inv_mod(4, 10), None);
+/// ```
+///
+/// # パニック
+/// mが0の場合panicします
+pub fn pow_mod(a: u64, mut e: u64, m: u64) -> u64 {
+    assert!(m > 0);
+    let m128 = m as u128;
+    let mut base = (a % m) as u128;
+    let mut res = 1 % m128;
+    while e > 0 {
+        if e & 1 == 1 {
+            res = res * base % m128;
+        }
+        base = base * base % m128;
+        e >>= 1;
+    }
+    res as u64
+}
+
+/// aの法mでの逆元を[0, m)で返す。aとmが互いに素でない場合は`None`
+///
+/// mが素数でなくても使えます。
+///
+/// # パニック
+/// mが正でない場合panicします
+pub fn inv_mod(a: i64, m: i64) -> Option<i64> {
+    assert!(m > 0);
+    let (g, x, _) = ext_gcd(a.rem_euclid(m), m);
+    (g == 1).then(|| x.rem_euclid(m))
+}
+
+/// aの法mでの逆元（`inv_mod`の別名）
+pub fn mod_inv(a: i64, m: i64) -> Option<i64> {
+    inv_mod(a, m)
+}
+
+/// 中国剰余定理で連立合同式 x ≡ r_i (mod m_i) を解く
+///
+/// 法は互いに素でなくても構いません。
+///
+/// # 戻り値
+/// 解が存在すれば `(x, lcm)`。xは`[0, lcm)`に正規化され、解全体は x mod lcm です。
+/// 矛盾する場合や、lcmがi64に収まらない場合は`None`。空の入力に対しては`(0, 1)`
+///
+/// # 計算量
+/// O(k log max m)
+///
+/// # 使用例
+/// ```
+/// # use rust_macro::math::crt;
+/// assert_eq!(crt(&[(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+/// assert_eq!(crt(&[(1, 4), (3, 6)]), Some((9, 12)));
+/// assert_eq!(crt(&[(0, 4), (1, 6)]), None);
+/// ```
+///
+/// # パニック
+/// 法に正でないものが含まれる場合panicします
+pub fn crt(congruences: &[(i64, i64)]) -> Option<(i64, i64)> {
+    let (mut r0, mut m0) = (0i64, 1i64);
+    for &(r, m) in congruences {
+        assert!(m > 0);
+        let r = r.rem_euclid(m);
+        // x = r0 + m0 * t として m0 * t ≡ r - r0 (mod m) を解く
+        let (g, p, _) = ext_gcd(m0, m);
+        if (r - r0) % g != 0 {
+            return None;
+        }
+        let mg = m / g;
+        let t = ((r - r0) / g) as i128 * p as i128 % mg as i128;
+        let lcm = (m0 as i128) * mg as i128;
+        let x = (r0 as i128 + m0 as i128 * t).rem_euclid(lcm);
+        r0 = x as i64;
+        m0 = i64::try_from(lcm).ok()?;
+    }
+    Some((r0, m0))
+}
+
+/// a / b を負の無限大方向に丸めた値
+///
+/// # パニック
+/// bが0の場合panicします
+pub fn floor_div(a: i64, b: i64) -> i64 {
+    let q = a / b;
+    if (a % b != 0) && ((a < 0) != (b < 0)) {
+        q - 1
+    } else {
+        q
+    }
+}
+
+/// a / b を正の無限大方向に丸めた値
+///
+/// # 使用例
+/// ```
+/// # use rust_macro::math::{ceil_div, floor_div};
+/// assert_eq!(ceil_div(7, 2), 4);
+/// assert_eq!(ceil_div(-7, 2), -3);
+/// assert_eq!(floor_div(-7, 2), -4);
+/// ```
+///
+/// # パニック
+/// bが0の場合panicします
+pub fn ceil_div(a: i64, b: i64) -> i64 {
+    let q = a / b;
+    if (a % b != 0) && ((a < 0) == (b < 0)) {
+        q + 1
+    } else {
+        q
+    }
+}
+
+/// floor(sqrt(n))を誤差なく求める
+///
+/// 浮動小数点数で近似した後に整数演算で補正します。
+pub fn isqrt(n: u64) -> u64 {
+    let mut x = (n as f64).sqrt() as u64;
+    while !x.checked_mul(x).is_some_and(|v| v <= n) {
+        x -= 1;
+    }
+    while (x + 1).checked_mul(x + 1).is_some_and(|v| v <= n) {
+        x += 1;
+    }
+    x
+}
+
+/// floor(cbrt(n))を誤差なく求める
+///
+/// # 使用例
+/// ```
+/// # use rust_macro::math::{icbrt, isqrt};
+/// assert_eq!(isqrt(99), 9);
+/// assert_eq!(isqrt(u64::MAX), 4294967295);
+/// assert_eq!(icbrt(1_000_000_000_000_000_000), 1_000_000);
+/// assert_eq!(icbrt(999_999_999_999_999_999), 999_999);
+/// ```
+pub fn icbrt(n: u64) -> u64 {
+    let cube = |x: u64| x.checked_mul(x).and_then(|v| v.checked_mul(x));
+    let mut x = (n as f64).cbrt() as u64;
+    while !cube(x).is_some_and(|v| v <= n) {
+        x -= 1;
+    }
+    while cube(x + 1).is_some_and(|v| v <= n) {
+        x += 1;
+    }
+    x
+}
+
+/// sum_{i=0}^{n-1} floor((a * i + b) / m) を求める
+///
+/// a, bは負でもかまいません。
+///
+/// # 計算量
+/// O(log m)
+///
+/// # 使用例
+/// ```
+/// # use rust_macro::math::floor_sum;
+/// assert_eq!(floor_sum(4, 10, 6, 3), 3); // 0 + 0 + 1 + 2
+/// assert_eq!(floor_sum(3, 2, -1, 0), -2); // 0 + (-1) + (-1)
+/// ```
+///
+/// # パニック
+/// `n < 0` または `m <= 0` の場合panicします
+pub fn floor_sum(n: i64, m: i64, a: i64, b: i64) -> i64 {
+    assert!(n >= 0 && m > 0);
+    let mut ans = 0;
+    // a, bを[0, m)に収める
+    let a2 = a.rem_euclid(m);
+    ans -= n * (n - 1) / 2 * ((a2 - a) / m);
+    let b2 = b.rem_euclid(m);
+    ans -= n * ((b2 - b) / m);
+    ans + floor_sum_unsigned(n as u64, m as u64, a2 as u64, b2 as u64) as i64
+}
+
+/// 0 <= a, b < m の場合のfloor_sum
+fn floor_sum_unsigned(mut n: u64, mut m: u64, mut a: u64, mut b: u64) -> u64 {
+    let mut ans = 0;
+    loop {
+        if a >= m {
+            ans += n * (n - 1) / 2 * (a / m);
+            a %= m;
+        }
+        if b >= m {
+            ans += n * (b / m);
+            b %= m;
+        }
+        let y_max = a * n + b;
+        if y_max < m {
+            break;
+        }
+        n = y_max / m;
+        b = y_max % m;
+        std::mem::swap(&mut m, &mut a);
+    }
+    ans
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gcd_lcm() {
+        assert_eq!(gcd(0, 5), 5);
+        assert_eq!(gcd(-12, -8), 4);
+        assert_eq!(lcm(4, 6), 12);
+        assert_eq!(lcm(-4, 6), 12);
+        assert_eq!(lcm(0, 6), 0);
+        assert_eq!(checked_lcm(1 << 40, (1 << 40) - 1), None);
+        assert_eq!(checked_lcm(1 << 31, 3), Some(3 << 31));
+    }
+
+    #[test]
+    fn test_ext_gcd() {
+        for a in -20..=20 {
+            for b in -20..=20 {
+                let (g, x, y) = ext_gcd(a, b);
+                assert_eq!(g, gcd(a, b));
+                assert_eq!(a * x + b * y, g);
+            }
+        }
+    }
+
+    #[test]
+    fn test_floor_ceil_div() {
+        for a in -30i64..=30 {
+            for b in (-7i64..=7).filter(|&b| b != 0) {
+                let f = (a as f64 / b as f64).floor() as i64;
+                let c = (a as f64 / b as f64).ceil() as i64;
+                assert_eq!(floor_div(a, b), f);
+                assert_eq!(ceil_div(a, b), c);
+            }
+        }
+    }
+
+    #[test]
+    fn test_isqrt_icbrt() {
+        for n in 0u64..2000 {
+            let r = isqrt(n);
+            assert!(r * r <= n && (r + 1) * (r + 1) > n);
+            let c = icbrt(n);
+            assert!(c * c * c <= n && (c + 1) * (c + 1) * (c + 1) > n);
+        }
+        for k in [(1u64 << 32) - 1, 999_999_999, 3_037_000_499] {
+            assert_eq!(isqrt(k * k), k);
+            assert_eq!(isqrt(k * k - 1), k - 1);
+        }
+        assert_eq!(icbrt(u64::MAX), 2_642_245);
+    }
+
+    #[test]
+    fn test_floor_sum() {
+        for n in 0..12 {
+            for m in 1..8 {
+                for a in -10..10 {
+                    for b in -10..10 {
+                        let naive: i64 = (0..n).map(|i| floor_div(a * i + b, m)).sum();
+                        assert_eq!(floor_sum(n, m, a, b), naive);
+                    }
+                }
+            }
+        }
+        assert_eq!(
+            floor_sum(1_000_000_000, 998_244_353, 999_999_937, 123_456_789),
+            500_879_334_926_059_603
+        );
+    }
+
+    #[test]
+    fn test_pow_mod_inv_mod() {
+        for m in 1u64..30 {
+            for a in 0u64..30 {
+                let mut naive = 1 % m;
+                for e in 0u64..10 {
+                    assert_eq!(pow_mod(a, e, m), naive);
+                    naive = naive * a % m;
+                }
+                let inv = inv_mod(a as i64, m as i64);
+                match (0..m).find(|&x| a * x % m == 1 % m) {
+                    Some(x) => assert_eq!(inv, Some(x as i64)),
+                    None => assert_eq!(inv, None),
+                }
+            }
+        }
+        let p = 1_000_000_000_000_000_003u64;
+        assert_eq!(pow_mod(123_456_789, p - 1, p), 1);
+    }
+
+    #[test]
+    fn test_crt_against_naive() {
+        for m1 in 1..13 {
+            for m2 in 1..13 {
+                for r1 in -3..m1 {
+                    for r2 in 0..m2 {
+                        let l = lcm(m1, m2);
+                        let naive =
+                            (0..l).find(|x| (x - r1).rem_euclid(m1) == 0 && (x - r2) % m2 == 0);
+                        assert_eq!(crt(&[(r1, m1), (r2, m2)]), naive.map(|x| (x, l)));
+                    }
+                }
+            }
+        }
+        assert_eq!(crt(&[]), Some((0, 1)));
+        assert_eq!(mod_inv(3, 10), Some(7));
+        let (p, q) = (1_000_000_007, 998_244_353);
+        assert_eq!(
+            crt(&[(5, p), (7, q)]).map(|(x, l)| (x % p, x % q, l)),
+            Some((5, 7, p * q))
+        );
+        assert_eq!(crt(&[(0, p), (0, q), (0, 1_000_000_009)]), None);
+    }
+}
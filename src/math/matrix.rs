@@ -0,0 +1,483 @@
+use std::ops::{Add, Div, Index, IndexMut, Mul, Sub};
+
+use super::ModInt;
+
+/// 除算が逆元との積になる（体をなす）要素の型
+///
+/// 掃き出し法や行列式は`1 / p`を使うため、整数型では切り捨てで誤った結果になります。
+/// 整数行列の行列式には`Matrix::<i64>::det_bareiss`を使います。
+pub trait Field:
+    Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Copy
+    + Default
+    + From<u8>
+    + PartialEq
+{
+}
+
+impl<const M: u64> Field for ModInt<M> {}
+impl Field for f64 {}
+impl Field for f32 {}
+
+/// 行優先で要素を保持する行列
+///
+/// 要素の型`T`は`Default`を0、`T::from(1)`を1として扱います。
+/// 積と累乗は`i64`や`ModInt`、`f64`などで使えます。
+/// 掃き出し・ランク・行列式は[`Field`]を実装した型に限られます。
+///
+/// # 計算量
+/// - 積: O(nmk)
+/// - 累乗: O(n^3 log k)
+/// - 行列式・ランク: O(n^3)
+///
+/// # 使用例
+/// ```
+/// # use rust_macro::math::Matrix;
+/// // フィボナッチ数列
+/// let m = Matrix::from_vec(vec![vec![1i64, 1], vec![1, 0]]);
+/// let p = m.pow(10);
+/// assert_eq!(p[(0, 1)], 55);
+///
+/// let a = Matrix::from_vec(vec![vec![2.0, 1.0], vec![4.0, 5.0]]);
+/// assert_eq!(a.det(), 6.0);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Matrix<T> {
+    h: usize,
+    w: usize,
+    data: Vec<T>,
+}
+
+impl<T> Matrix<T>
+where
+    T: Add<Output = T> + Mul<Output = T> + Copy + Default + From<u8>,
+{
+    /// h×wの零行列を作成
+    pub fn new(h: usize, w: usize) -> Self {
+        Matrix {
+            h,
+            w,
+            data: vec![T::default(); h * w],
+        }
+    }
+
+    /// n×nの単位行列を作成
+    pub fn identity(n: usize) -> Self {
+        let mut m = Self::new(n, n);
+        for i in 0..n {
+            m[(i, i)] = T::from(1);
+        }
+        m
+    }
+
+    /// 2次元ベクタから作成
+    ///
+    /// # パニック
+    /// 各行の長さが揃っていない場合panicします
+    pub fn from_vec(rows: Vec<Vec<T>>) -> Self {
+        let h = rows.len();
+        let w = rows.first().map_or(0, |r| r.len());
+        assert!(
+            rows.iter().all(|r| r.len() == w),
+            "rows have different lengths"
+        );
+        Matrix {
+            h,
+            w,
+            data: rows.into_iter().flatten().collect(),
+        }
+    }
+
+    /// (行数, 列数)
+    pub fn size(&self) -> (usize, usize) {
+        (self.h, self.w)
+    }
+
+    /// 2次元ベクタに変換する
+    pub fn to_vec(&self) -> Vec<Vec<T>> {
+        self.data
+            .chunks(self.w.max(1))
+            .map(|r| r.to_vec())
+            .collect()
+    }
+
+    /// 行列の積 self * rhs
+    ///
+    /// # パニック
+    /// selfの列数とrhsの行数が異なる場合panicします
+    pub fn mul(&self, rhs: &Self) -> Self {
+        assert_eq!(self.w, rhs.h, "dimension mismatch");
+        let mut res = Self::new(self.h, rhs.w);
+        for i in 0..self.h {
+            for k in 0..self.w {
+                let a = self[(i, k)];
+                for j in 0..rhs.w {
+                    res[(i, j)] = res[(i, j)] + a * rhs[(k, j)];
+                }
+            }
+        }
+        res
+    }
+
+    /// 行列のk乗
+    ///
+    /// # パニック
+    /// 正方行列でない場合panicします
+    pub fn pow(&self, mut k: u64) -> Self {
+        assert_eq!(self.h, self.w, "matrix must be square");
+        let mut base = self.clone();
+        let mut res = Self::identity(self.h);
+        while k > 0 {
+            if k & 1 == 1 {
+                res = res.mul(&base);
+            }
+            base = base.mul(&base);
+            k >>= 1;
+        }
+        res
+    }
+}
+
+impl<T: Field> Matrix<T> {
+    /// 掃き出し法で簡約化した行階段形に変形し、ランクを返す（体上の行列）
+    pub fn gauss_jordan(&mut self) -> usize {
+        let zero = T::default();
+        let mut rank = 0;
+        for col in 0..self.w {
+            let Some(pivot) = (rank..self.h).find(|&i| self[(i, col)] != zero) else {
+                continue;
+            };
+            if pivot != rank {
+                for j in 0..self.w {
+                    self.data.swap(pivot * self.w + j, rank * self.w + j);
+                }
+            }
+            let inv = T::from(1) / self[(rank, col)];
+            for j in 0..self.w {
+                self[(rank, j)] = self[(rank, j)] * inv;
+            }
+            for i in (0..self.h).filter(|&i| i != rank) {
+                let f = self[(i, col)];
+                if f != zero {
+                    for j in 0..self.w {
+                        self[(i, j)] = self[(i, j)] - f * self[(rank, j)];
+                    }
+                }
+            }
+            rank += 1;
+        }
+        rank
+    }
+
+    /// ランク
+    pub fn rank(&self) -> usize {
+        self.clone().gauss_jordan()
+    }
+
+    /// 行列式
+    ///
+    /// # パニック
+    /// 正方行列でない場合panicします
+    pub fn det(&self) -> T {
+        assert_eq!(self.h, self.w, "matrix must be square");
+        let zero = T::default();
+        let mut a = self.clone();
+        let mut res = T::from(1);
+        for col in 0..a.w {
+            let Some(pivot) = (col..a.h).find(|&i| a[(i, col)] != zero) else {
+                return zero;
+            };
+            if pivot != col {
+                for j in 0..a.w {
+                    a.data.swap(pivot * a.w + j, col * a.w + j);
+                }
+                res = zero - res;
+            }
+            let p = a[(col, col)];
+            res = res * p;
+            let inv = T::from(1) / p;
+            for i in col + 1..a.h {
+                let f = a[(i, col)] * inv;
+                if f != zero {
+                    for j in col..a.w {
+                        a[(i, j)] = a[(i, j)] - f * a[(col, j)];
+                    }
+                }
+            }
+        }
+        res
+    }
+}
+
+impl Matrix<i64> {
+    /// 整数行列の行列式（Bareissのアルゴリズム）
+    ///
+    /// 割り算が常に割り切れる形で消去するため、分数を経由せずに計算できます。
+    /// 途中の値は全て元の行列の小行列式で、内部ではi128で計算します。
+    ///
+    /// # 計算量
+    /// O(n^3)
+    ///
+    /// # パニック
+    /// 正方行列でない場合、または小行列式がi64に収まらない場合panicします
+    pub fn det_bareiss(&self) -> i64 {
+        assert_eq!(self.h, self.w, "matrix must be square");
+        let n = self.h;
+        let mut a: Vec<i128> = self.data.iter().map(|&x| x as i128).collect();
+        let mut sign = 1;
+        let mut prev = 1i128;
+        for k in 0..n {
+            if a[k * n + k] == 0 {
+                let Some(pivot) = (k + 1..n).find(|&i| a[i * n + k] != 0) else {
+                    return 0;
+                };
+                for j in 0..n {
+                    a.swap(pivot * n + j, k * n + j);
+                }
+                sign = -sign;
+            }
+            for i in k + 1..n {
+                for j in k + 1..n {
+                    let v = a[i * n + j] * a[k * n + k] - a[i * n + k] * a[k * n + j];
+                    a[i * n + j] = v / prev;
+                    assert!(i64::try_from(a[i * n + j]).is_ok(), "minor overflows i64");
+                }
+            }
+            prev = a[k * n + k];
+        }
+        if n == 0 {
+            1
+        } else {
+            sign * a[n * n - 1] as i64
+        }
+    }
+}
+
+impl<T> Index<(usize, usize)> for Matrix<T> {
+    type Output = T;
+    fn index(&self, (i, j): (usize, usize)) -> &T {
+        &self.data[i * self.w + j]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Matrix<T> {
+    fn index_mut(&mut self, (i, j): (usize, usize)) -> &mut T {
+        &mut self.data[i * self.w + j]
+    }
+}
+
+/// GF(2)上の行列（各行をビット列で保持）
+///
+/// 行の加算がXORになるため、掃き出しを64列ずつまとめて行えます。
+/// XOR基底やビット演算の連立方程式に使います。
+///
+/// # 計算量
+/// 掃き出し: O(h w min(h, w) / 64)
+///
+/// # 使用例
+/// ```
+/// # use rust_macro::math::BitMatrix;
+/// // 3, 5, 6 のXOR基底の大きさ
+/// let m = BitMatrix::from_rows(&[3, 5, 6], 3);
+/// assert_eq!(m.rank(), 2);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BitMatrix {
+    h: usize,
+    w: usize,
+    rows: Vec<Vec<u64>>,
+}
+
+impl BitMatrix {
+    /// h×wの零行列を作成
+    pub fn new(h: usize, w: usize) -> Self {
+        BitMatrix {
+            h,
+            w,
+            rows: vec![vec![0; (w + 63) / 64]; h],
+        }
+    }
+
+    /// 各行をw(≤64)ビットの整数で与えて作成（j列目がjビット目に対応）
+    pub fn from_rows(rows: &[u64], w: usize) -> Self {
+        assert!(w <= 64);
+        let mut m = Self::new(rows.len(), w);
+        for (row, &x) in m.rows.iter_mut().zip(rows) {
+            if w > 0 {
+                row[0] = if w == 64 { x } else { x & ((1 << w) - 1) };
+            }
+        }
+        m
+    }
+
+    /// (行数, 列数)
+    pub fn size(&self) -> (usize, usize) {
+        (self.h, self.w)
+    }
+
+    /// (i, j)成分
+    pub fn get(&self, i: usize, j: usize) -> bool {
+        assert!(j < self.w);
+        self.rows[i][j / 64] >> (j % 64) & 1 == 1
+    }
+
+    /// (i, j)成分を設定する
+    pub fn set(&mut self, i: usize, j: usize, b: bool) {
+        assert!(j < self.w);
+        if b {
+            self.rows[i][j / 64] |= 1 << (j % 64);
+        } else {
+            self.rows[i][j / 64] &= !(1 << (j % 64));
+        }
+    }
+
+    /// 掃き出し法で簡約化した行階段形に変形し、ランクを返す
+    pub fn gauss_jordan(&mut self) -> usize {
+        let mut rank = 0;
+        for col in 0..self.w {
+            let Some(pivot) = (rank..self.h).find(|&i| self.get(i, col)) else {
+                continue;
+            };
+            self.rows.swap(pivot, rank);
+            let pivot_row = self.rows[rank].clone();
+            for i in (0..self.h).filter(|&i| i != rank) {
+                if self.get(i, col) {
+                    for (a, b) in self.rows[i].iter_mut().zip(&pivot_row) {
+                        *a ^= b;
+                    }
+                }
+            }
+            rank += 1;
+        }
+        rank
+    }
+
+    /// ランク
+    pub fn rank(&self) -> usize {
+        self.clone().gauss_jordan()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::ModInt;
+
+    type Mint = ModInt<998_244_353>;
+
+    #[test]
+    fn test_matrix_mul_pow() {
+        let a = Matrix::from_vec(vec![vec![1i64, 2, 3], vec![4, 5, 6]]);
+        let b = Matrix::from_vec(vec![vec![1i64, 0], vec![0, 1], vec![1, 1]]);
+        assert_eq!(a.mul(&b).to_vec(), vec![vec![4, 5], vec![10, 11]]);
+        assert_eq!(a.size(), (2, 3));
+
+        let fib = Matrix::from_vec(vec![
+            vec![Mint::new(1), Mint::new(1)],
+            vec![Mint::new(1), Mint::new(0)],
+        ]);
+        assert_eq!(fib.pow(0), Matrix::identity(2));
+        // F(100) mod 998244353
+        assert_eq!(fib.pow(100)[(0, 1)].val(), 494958974);
+    }
+
+    #[test]
+    fn test_matrix_det_rank() {
+        let m = |v: Vec<Vec<i64>>| {
+            Matrix::from_vec(
+                v.into_iter()
+                    .map(|r| r.into_iter().map(Mint::from).collect())
+                    .collect(),
+            )
+        };
+        assert_eq!(m(vec![vec![0, 1], vec![1, 0]]).det(), Mint::from(-1));
+        assert_eq!(
+            m(vec![vec![2, -3, 1], vec![2, 0, -1], vec![1, 4, 5]])
+                .det()
+                .val(),
+            49
+        );
+        assert_eq!(m(vec![vec![1, 2], vec![2, 4]]).det().val(), 0);
+        assert_eq!(
+            m(vec![vec![1, 2, 3], vec![2, 4, 6], vec![1, 0, 1]]).rank(),
+            2
+        );
+
+        let mut a = m(vec![vec![0, 2, 4], vec![1, 1, 1]]);
+        assert_eq!(a.gauss_jordan(), 2);
+        assert_eq!(a, m(vec![vec![1, 0, -1], vec![0, 1, 2]]));
+    }
+
+    #[test]
+    fn test_matrix_det_bareiss() {
+        let a = Matrix::from_vec(vec![vec![2i64, 1], vec![4, 5]]);
+        assert_eq!(a.det_bareiss(), 6);
+        assert_eq!(Matrix::<i64>::new(0, 0).det_bareiss(), 1);
+        assert_eq!(
+            Matrix::from_vec(vec![vec![0i64, 1], vec![1, 0]]).det_bareiss(),
+            -1
+        );
+
+        // 置換の和による愚直な行列式と比較する
+        fn naive(a: &[Vec<i64>], used: &mut Vec<bool>, row: usize) -> i64 {
+            if row == a.len() {
+                return 1;
+            }
+            let mut res = 0;
+            let mut sign = 1;
+            for j in 0..a.len() {
+                if used[j] {
+                    continue;
+                }
+                used[j] = true;
+                res += sign * a[row][j] * naive(a, used, row + 1);
+                used[j] = false;
+                sign = -sign;
+            }
+            res
+        }
+        let mut rng = crate::XorShift64::new(4170);
+        for _ in 0..200 {
+            let n = rng.gen_range(1..6) as usize;
+            let v: Vec<Vec<i64>> = (0..n)
+                .map(|_| (0..n).map(|_| rng.gen_range(0..11) as i64 - 5).collect())
+                .collect();
+            let expected = naive(&v, &mut vec![false; n], 0);
+            assert_eq!(Matrix::from_vec(v.clone()).det_bareiss(), expected);
+            let m = Matrix::from_vec(
+                v.iter()
+                    .map(|r| r.iter().map(|&x| Mint::from(x)).collect())
+                    .collect(),
+            );
+            assert_eq!(m.det(), Mint::from(expected));
+        }
+    }
+
+    #[test]
+    fn test_bit_matrix() {
+        let mut m = BitMatrix::new(3, 130);
+        m.set(0, 129, true);
+        m.set(1, 129, true);
+        m.set(1, 5, true);
+        m.set(2, 5, true);
+        assert!(m.get(1, 5));
+        assert_eq!(m.rank(), 2);
+        m.set(2, 0, true);
+        assert_eq!(m.gauss_jordan(), 3);
+        assert!(m.get(0, 0));
+
+        // 線形独立なベクトルの数を愚直なXOR基底と比較する
+        let xs = [7u64, 11, 12, 1, 6, 10, 13];
+        let mut basis: Vec<u64> = Vec::new();
+        for &x in &xs {
+            let y = basis.iter().fold(x, |y, &b| y.min(y ^ b));
+            if y != 0 {
+                basis.push(y);
+            }
+        }
+        assert_eq!(BitMatrix::from_rows(&xs, 4).rank(), basis.len());
+    }
+}
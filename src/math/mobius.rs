@@ -0,0 +1,178 @@
+use std::ops::{Add, Mul, Sub};
+
+/// 1以上n以下の整数のメビウス関数μの値の表（添字0は0）
+///
+/// # 計算量
+/// O(n log n)
+///
+/// # 使用例
+/// ```
+/// # use rust_macro::math::mobius_table;
+/// assert_eq!(mobius_table(10), vec![0, 1, -1, -1, 0, -1, 1, -1, 0, 0, 1]);
+/// ```
+pub fn mobius_table(n: usize) -> Vec<i64> {
+    let mut mu = vec![0i64; n + 1];
+    if n >= 1 {
+        mu[1] = 1;
+    }
+    // sum_{d|i} μ(d) = [i == 1] から順に決める
+    for i in 1..=n {
+        for j in (2 * i..=n).step_by(i) {
+            mu[j] -= mu[i];
+        }
+    }
+    mu
+}
+
+/// 約数に関するゼータ変換: a[i] <- sum_{d | i} a[d]
+///
+/// 添字1..a.len()を対象とし、a[0]は変更しません。
+///
+/// # 計算量
+/// O(n log n)
+pub fn divisor_zeta<T>(a: &mut [T])
+where
+    T: Add<Output = T> + Copy,
+{
+    let n = a.len();
+    for i in (1..n).rev() {
+        for j in (2 * i..n).step_by(i) {
+            a[j] = a[j] + a[i];
+        }
+    }
+}
+
+/// 約数に関するメビウス変換（`divisor_zeta`の逆変換）
+pub fn divisor_mobius<T>(a: &mut [T])
+where
+    T: Sub<Output = T> + Copy,
+{
+    let n = a.len();
+    for i in 1..n {
+        for j in (2 * i..n).step_by(i) {
+            a[j] = a[j] - a[i];
+        }
+    }
+}
+
+/// 倍数に関するゼータ変換: a[i] <- sum_{i | j} a[j]
+///
+/// 添字1..a.len()を対象とし、a[0]は変更しません。
+///
+/// # 計算量
+/// O(n log n)
+pub fn multiple_zeta<T>(a: &mut [T])
+where
+    T: Add<Output = T> + Copy,
+{
+    let n = a.len();
+    for i in 1..n {
+        for j in (2 * i..n).step_by(i) {
+            a[i] = a[i] + a[j];
+        }
+    }
+}
+
+/// 倍数に関するメビウス変換（`multiple_zeta`の逆変換）
+pub fn multiple_mobius<T>(a: &mut [T])
+where
+    T: Sub<Output = T> + Copy,
+{
+    let n = a.len();
+    for i in (1..n).rev() {
+        for j in (2 * i..n).step_by(i) {
+            a[i] = a[i] - a[j];
+        }
+    }
+}
+
+/// gcd畳み込み: c[k] = sum_{gcd(i, j) = k} a[i] * b[j]
+///
+/// 添字1..nを対象とし、返り値の長さは短い方の長さ、c[0]は`T::default()`です。
+///
+/// # 計算量
+/// O(n log n)
+///
+/// # 使用例
+/// ```
+/// # use rust_macro::math::gcd_convolution;
+/// // 1以上6以下の整数の組でgcdがkになるものの個数
+/// let ones = vec![0, 1, 1, 1, 1, 1, 1];
+/// assert_eq!(gcd_convolution(&ones, &ones), vec![0, 23, 7, 3, 1, 1, 1]);
+/// ```
+pub fn gcd_convolution<T>(a: &[T], b: &[T]) -> Vec<T>
+where
+    T: Add<Output = T> + Sub<Output = T> + Mul<Output = T> + Copy + Default,
+{
+    let n = a.len().min(b.len());
+    let mut fa = a[..n].to_vec();
+    let mut fb = b[..n].to_vec();
+    multiple_zeta(&mut fa);
+    multiple_zeta(&mut fb);
+    let mut c: Vec<T> = fa.iter().zip(&fb).map(|(&x, &y)| x * y).collect();
+    multiple_mobius(&mut c);
+    if n > 0 {
+        c[0] = T::default();
+    }
+    c
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::{gcd, Sieve};
+
+    #[test]
+    fn test_mobius_table() {
+        let n = 200;
+        let mu = mobius_table(n);
+        let sieve = Sieve::new(n);
+        for (x, &m) in mu.iter().enumerate().skip(1) {
+            let f = sieve.factorize(x);
+            let expected = if f.iter().any(|&(_, e)| e > 1) {
+                0
+            } else if f.len() % 2 == 0 {
+                1
+            } else {
+                -1
+            };
+            assert_eq!(m, expected);
+        }
+        assert_eq!(mobius_table(0), vec![0]);
+    }
+
+    #[test]
+    fn test_zeta_mobius_roundtrip() {
+        let orig: Vec<i64> = (0..30).map(|i| (i * i % 7) as i64 - 3).collect();
+        let mut a = orig.clone();
+        divisor_zeta(&mut a);
+        for (i, &v) in a.iter().enumerate().skip(1) {
+            let s: i64 = (1..=i).filter(|d| i % d == 0).map(|d| orig[d]).sum();
+            assert_eq!(v, s);
+        }
+        divisor_mobius(&mut a);
+        assert_eq!(a, orig);
+
+        multiple_zeta(&mut a);
+        for (i, &v) in a.iter().enumerate().skip(1) {
+            let s: i64 = orig.iter().skip(i).step_by(i).sum();
+            assert_eq!(v, s);
+        }
+        multiple_mobius(&mut a);
+        assert_eq!(a, orig);
+    }
+
+    #[test]
+    fn test_gcd_convolution() {
+        let a: Vec<i64> = (0..20).map(|i| i % 5).collect();
+        let b: Vec<i64> = (0..25).map(|i| 3 - i % 4).collect();
+        let c = gcd_convolution(&a, &b);
+        let mut naive = vec![0; 20];
+        for i in 1..20 {
+            for j in 1..20 {
+                naive[gcd(i as i64, j as i64) as usize] += a[i] * b[j];
+            }
+        }
+        assert_eq!(c, naive);
+    }
+}
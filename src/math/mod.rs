@@ -0,0 +1,26 @@
+pub mod bigint;
+pub mod combinatorics;
+pub mod int;
+pub mod matrix;
+pub mod mobius;
+pub mod modint;
+pub mod montgomery;
+pub mod poly;
+pub mod primes;
+pub mod rational;
+
+pub use bigint::BigUint;
+pub use combinatorics::Combinatorics;
+pub use int::{
+    ceil_div, checked_lcm, crt, ext_gcd, floor_div, floor_sum, gcd, icbrt, inv_mod, isqrt, lcm,
+    mod_inv, pow_mod,
+};
+pub use matrix::{BitMatrix, Field, Matrix};
+pub use mobius::{
+    divisor_mobius, divisor_zeta, gcd_convolution, mobius_table, multiple_mobius, multiple_zeta,
+};
+pub use modint::{ModInt, ModInt1000000007, ModInt998244353};
+pub use montgomery::Montgomery64;
+pub use poly::{convolution, Poly};
+pub use primes::{divisors, factorize, is_prime, segmented_primes, Sieve};
+pub use rational::Rational;
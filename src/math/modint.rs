@@ -0,0 +1,226 @@
+use std::fmt;
+use std::iter::{Product, Sum};
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+use super::int::inv_mod;
+
+/// 法Mで計算する整数
+///
+/// 四則演算の演算子がそのまま使えます。除算は逆元を掛けることで行うため、
+/// 割る数がMと互いに素である必要があります。
+///
+/// # 使用例
+/// ```
+/// # use rust_macro::math::{ModInt998244353 as Mint, ModInt};
+/// let a = Mint::new(3);
+/// let b = Mint::from(-1i64);
+/// assert_eq!((a + b).val(), 2);
+/// assert_eq!(b.val(), 998244352);
+/// assert_eq!((a / a).val(), 1);
+/// assert_eq!(Mint::new(2).pow(10).val(), 1024);
+/// assert_eq!(format!("{}", a * b), "998244350");
+///
+/// let x: ModInt<7> = (1..=6u64).map(ModInt::new).product();
+/// assert_eq!(x.val(), 6); // ウィルソンの定理
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct ModInt<const M: u64> {
+    val: u64,
+}
+
+/// 法998244353のModInt
+pub type ModInt998244353 = ModInt<998_244_353>;
+/// 法1000000007のModInt
+pub type ModInt1000000007 = ModInt<1_000_000_007>;
+
+impl<const M: u64> ModInt<M> {
+    /// 法M
+    pub const MOD: u64 = M;
+
+    /// 値xをMで割った余りから作成
+    pub fn new(x: u64) -> Self {
+        ModInt { val: x % M }
+    }
+
+    /// [0, M)の代表値
+    pub fn val(self) -> u64 {
+        self.val
+    }
+
+    /// self^kを繰り返し二乗法で求める
+    pub fn pow(self, mut k: u64) -> Self {
+        let mut base = self;
+        let mut res = Self::new(1);
+        while k > 0 {
+            if k & 1 == 1 {
+                res *= base;
+            }
+            base *= base;
+            k >>= 1;
+        }
+        res
+    }
+
+    /// 乗法の逆元
+    ///
+    /// # パニック
+    /// selfとMが互いに素でない場合panicします
+    pub fn inv(self) -> Self {
+        match inv_mod(self.val as i64, M as i64) {
+            Some(x) => Self::new(x as u64),
+            None => panic!("{} has no inverse modulo {}", self.val, M),
+        }
+    }
+}
+
+macro_rules! impl_from_unsigned {
+    ($($t:ty),*) => {$(
+        impl<const M: u64> From<$t> for ModInt<M> {
+            fn from(x: $t) -> Self {
+                Self::new((x as u64) % M)
+            }
+        }
+    )*};
+}
+
+macro_rules! impl_from_signed {
+    ($($t:ty),*) => {$(
+        impl<const M: u64> From<$t> for ModInt<M> {
+            fn from(x: $t) -> Self {
+                Self::new((x as i128).rem_euclid(M as i128) as u64)
+            }
+        }
+    )*};
+}
+
+impl_from_unsigned!(u8, u16, u32, u64, usize);
+impl_from_signed!(i8, i16, i32, i64, isize);
+
+impl<const M: u64> fmt::Display for ModInt<M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.val)
+    }
+}
+
+impl<const M: u64> fmt::Debug for ModInt<M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.val)
+    }
+}
+
+impl<const M: u64> Add for ModInt<M> {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self {
+        let v = self.val + rhs.val;
+        ModInt {
+            val: if v >= M { v - M } else { v },
+        }
+    }
+}
+
+impl<const M: u64> Sub for ModInt<M> {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self {
+        ModInt {
+            val: if self.val >= rhs.val {
+                self.val - rhs.val
+            } else {
+                self.val + M - rhs.val
+            },
+        }
+    }
+}
+
+impl<const M: u64> Mul for ModInt<M> {
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self {
+        ModInt {
+            val: (self.val as u128 * rhs.val as u128 % M as u128) as u64,
+        }
+    }
+}
+
+impl<const M: u64> Div for ModInt<M> {
+    type Output = Self;
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn div(self, rhs: Self) -> Self {
+        self * rhs.inv()
+    }
+}
+
+impl<const M: u64> Neg for ModInt<M> {
+    type Output = Self;
+    fn neg(self) -> Self {
+        ModInt::default() - self
+    }
+}
+
+macro_rules! impl_assign_ops {
+    ($($tr:ident, $f:ident, $op:tt);*) => {$(
+        impl<const M: u64> $tr for ModInt<M> {
+            fn $f(&mut self, rhs: Self) {
+                *self = *self $op rhs;
+            }
+        }
+    )*};
+}
+
+impl_assign_ops!(AddAssign, add_assign, +; SubAssign, sub_assign, -; MulAssign, mul_assign, *; DivAssign, div_assign, /);
+
+impl<const M: u64> Sum for ModInt<M> {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::default(), |a, b| a + b)
+    }
+}
+
+impl<const M: u64> Product for ModInt<M> {
+    fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::new(1), |a, b| a * b)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type Mint = ModInt1000000007;
+
+    #[test]
+    fn test_modint_arith() {
+        let m = Mint::MOD as i64;
+        for a in [-5i64, 0, 1, 7, m - 1, 123_456_789_012] {
+            for b in [-3i64, 1, 2, m - 2, 987_654_321] {
+                let (x, y) = (Mint::from(a), Mint::from(b));
+                let (ra, rb) = (a.rem_euclid(m) as i128, b.rem_euclid(m) as i128);
+                let m = m as i128;
+                assert_eq!((x + y).val() as i128, (ra + rb) % m);
+                assert_eq!((x - y).val() as i128, (ra - rb).rem_euclid(m));
+                assert_eq!((x * y).val() as i128, ra * rb % m);
+                assert_eq!(x / y * y, x);
+                assert_eq!((-x + x).val(), 0);
+            }
+        }
+    }
+
+    #[test]
+    fn test_modint_pow_inv_assign() {
+        let mut x = Mint::new(2);
+        assert_eq!(x.pow(0).val(), 1);
+        assert_eq!(x.pow(Mint::MOD - 1).val(), 1);
+        x += Mint::new(3);
+        x *= Mint::new(4);
+        x -= Mint::new(1);
+        x /= Mint::new(19);
+        assert_eq!(x.val(), 1);
+        // 法が合成数でも互いに素なら逆元がある
+        assert_eq!((ModInt::<12>::new(5).inv() * ModInt::new(5)).val(), 1);
+        let s: Mint = (1..=100u32).map(Mint::from).sum();
+        assert_eq!(s.val(), 5050);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_modint_no_inverse() {
+        ModInt::<12>::new(4).inv();
+    }
+}
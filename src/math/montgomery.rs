@@ -0,0 +1,162 @@
+/// 実行時に与える奇数の法nでの乗算をMontgomery乗算で高速に行う構造体
+///
+/// 値はMontgomery表現（a * 2^64 mod n）で扱います。`to_mont`で変換してから
+/// `mul`・`add`・`sub`・`pow`で計算し、最後に`from_mont`で元に戻します。
+/// 乗算ごとのu128の除算がなくなるため、Miller–Rabin法などの内側のループで使います。
+///
+/// # 計算量
+/// 各演算 O(1)（`pow`はO(log e)）
+///
+/// # 使用例
+/// ```
+/// # use rust_macro::math::Montgomery64;
+/// let mg = Montgomery64::new(1_000_000_007);
+/// let a = mg.to_mont(123_456_789);
+/// let b = mg.to_mont(987_654_321);
+/// assert_eq!(mg.from_mont(mg.mul(a, b)), 123_456_789 * 987_654_321 % 1_000_000_007);
+/// assert_eq!(mg.pow_mod(3, 1_000_000_006), 1);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Montgomery64 {
+    n: u64,
+    /// n * n_inv ≡ 1 (mod 2^64)
+    n_inv: u64,
+    /// 2^128 mod n
+    r2: u64,
+}
+
+impl Montgomery64 {
+    /// 法nを指定して作成
+    ///
+    /// # パニック
+    /// nが偶数の場合panicします
+    pub fn new(n: u64) -> Self {
+        assert!(n % 2 == 1, "modulus must be odd");
+        // ニュートン法で2^64を法とする逆元を求める（1回ごとに正しいビット数が2倍になる）
+        let mut n_inv = n;
+        for _ in 0..5 {
+            n_inv = n_inv.wrapping_mul(2u64.wrapping_sub(n.wrapping_mul(n_inv)));
+        }
+        let r = ((1u128 << 64) % n as u128) as u64;
+        let r2 = (r as u128 * r as u128 % n as u128) as u64;
+        Montgomery64 { n, n_inv, r2 }
+    }
+
+    /// 法n
+    pub fn modulus(&self) -> u64 {
+        self.n
+    }
+
+    /// t * 2^(-64) mod n（t < n * 2^64）
+    fn reduce(&self, t: u128) -> u64 {
+        let m = (t as u64).wrapping_mul(self.n_inv);
+        let mn = ((m as u128 * self.n as u128) >> 64) as u64;
+        let hi = (t >> 64) as u64;
+        // tとm * nの下位64bitは等しいので、上位だけの差をとればよい
+        if hi < mn {
+            hi.wrapping_sub(mn).wrapping_add(self.n)
+        } else {
+            hi - mn
+        }
+    }
+
+    /// 通常の値をMontgomery表現に変換する
+    pub fn to_mont(&self, a: u64) -> u64 {
+        self.reduce((a % self.n) as u128 * self.r2 as u128)
+    }
+
+    /// Montgomery表現を通常の値に戻す
+    pub fn from_mont(&self, a: u64) -> u64 {
+        self.reduce(a as u128)
+    }
+
+    /// Montgomery表現での積
+    pub fn mul(&self, a: u64, b: u64) -> u64 {
+        self.reduce(a as u128 * b as u128)
+    }
+
+    /// Montgomery表現での和
+    pub fn add(&self, a: u64, b: u64) -> u64 {
+        let (s, overflow) = a.overflowing_add(b);
+        if overflow || s >= self.n {
+            s.wrapping_sub(self.n)
+        } else {
+            s
+        }
+    }
+
+    /// Montgomery表現での差
+    pub fn sub(&self, a: u64, b: u64) -> u64 {
+        if a >= b {
+            a - b
+        } else {
+            a.wrapping_sub(b).wrapping_add(self.n)
+        }
+    }
+
+    /// Montgomery表現でのa^e
+    pub fn pow(&self, a: u64, mut e: u64) -> u64 {
+        let mut base = a;
+        let mut res = self.to_mont(1);
+        while e > 0 {
+            if e & 1 == 1 {
+                res = self.mul(res, base);
+            }
+            base = self.mul(base, base);
+            e >>= 1;
+        }
+        res
+    }
+
+    /// 通常の値でa^e mod nを求める
+    pub fn pow_mod(&self, a: u64, e: u64) -> u64 {
+        self.from_mont(self.pow(self.to_mont(a), e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::pow_mod;
+    use crate::XorShift64;
+
+    #[test]
+    fn test_montgomery_against_u128() {
+        let mut rng = XorShift64::new(99);
+        for n in [
+            1u64,
+            3,
+            998_244_353,
+            1_000_000_000_000_000_003,
+            u64::MAX,
+            (1 << 63) + 1,
+        ] {
+            let mg = Montgomery64::new(n);
+            assert_eq!(mg.modulus(), n);
+            for _ in 0..200 {
+                let a = rng.next_u64() % n;
+                let b = rng.next_u64() % n;
+                let (ma, mb) = (mg.to_mont(a), mg.to_mont(b));
+                assert_eq!(mg.from_mont(ma), a);
+                let prod = (a as u128 * b as u128 % n as u128) as u64;
+                assert_eq!(mg.from_mont(mg.mul(ma, mb)), prod);
+                assert_eq!(
+                    mg.from_mont(mg.add(ma, mb)),
+                    ((a as u128 + b as u128) % n as u128) as u64
+                );
+                assert_eq!(
+                    mg.from_mont(mg.sub(ma, mb)),
+                    ((a as u128 + n as u128 - b as u128) % n as u128) as u64
+                );
+                let e = rng.next_u64();
+                assert_eq!(mg.pow_mod(a, e), pow_mod(a, e, n));
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_montgomery_even_modulus() {
+        Montgomery64::new(1 << 20);
+    }
+}
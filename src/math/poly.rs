@@ -0,0 +1,373 @@
+use std::ops::{Add, Mul, Sub};
+
+use super::modint::ModInt;
+
+/// 法Mの原始根を求める
+fn primitive_root<const M: u64>() -> ModInt<M> {
+    let mut factors = Vec::new();
+    let mut x = M - 1;
+    let mut d = 2;
+    while d * d <= x {
+        if x % d == 0 {
+            factors.push(d);
+            while x % d == 0 {
+                x /= d;
+            }
+        }
+        d += 1;
+    }
+    if x > 1 {
+        factors.push(x);
+    }
+    (2..)
+        .map(ModInt::<M>::new)
+        .find(|g| factors.iter().all(|&p| g.pow((M - 1) / p).val() != 1))
+        .unwrap()
+}
+
+/// 数論変換（aの長さは2べき）
+fn ntt<const M: u64>(a: &mut [ModInt<M>], root: ModInt<M>, invert: bool) {
+    let n = a.len();
+    let mut j = 0;
+    for i in 1..n {
+        let mut bit = n >> 1;
+        while j & bit != 0 {
+            j ^= bit;
+            bit >>= 1;
+        }
+        j |= bit;
+        if i < j {
+            a.swap(i, j);
+        }
+    }
+    let mut len = 2;
+    while len <= n {
+        let mut w = root.pow((M - 1) / len as u64);
+        if invert {
+            w = w.inv();
+        }
+        for chunk in a.chunks_mut(len) {
+            let (lo, hi) = chunk.split_at_mut(len / 2);
+            let mut wn = ModInt::new(1);
+            for (u, v) in lo.iter_mut().zip(hi.iter_mut()) {
+                let t = *v * wn;
+                *v = *u - t;
+                *u += t;
+                wn *= w;
+            }
+        }
+        len <<= 1;
+    }
+    if invert {
+        let n_inv = ModInt::from(n).inv();
+        for x in a.iter_mut() {
+            *x *= n_inv;
+        }
+    }
+}
+
+/// 畳み込み c[k] = sum_{i+j=k} a[i] * b[j]
+///
+/// 長い場合は数論変換（NTT）を使います。Mは`M - 1`が結果の長さ以上の2べきで割り切れる
+/// 素数（998244353など）である必要があります。
+///
+/// # 計算量
+/// O((n + m) log (n + m))
+///
+/// # 使用例
+/// ```
+/// # use rust_macro::math::{convolution, ModInt998244353 as Mint};
+/// let a: Vec<Mint> = [1, 2, 3].iter().map(|&x| Mint::new(x)).collect();
+/// let b: Vec<Mint> = [4, 5].iter().map(|&x| Mint::new(x)).collect();
+/// let c: Vec<u64> = convolution(&a, &b).iter().map(|x| x.val()).collect();
+/// assert_eq!(c, vec![4, 13, 22, 15]);
+/// ```
+pub fn convolution<const M: u64>(a: &[ModInt<M>], b: &[ModInt<M>]) -> Vec<ModInt<M>> {
+    if a.is_empty() || b.is_empty() {
+        return Vec::new();
+    }
+    let n = a.len() + b.len() - 1;
+    if a.len().min(b.len()) <= 32 {
+        let mut c = vec![ModInt::default(); n];
+        for (i, &x) in a.iter().enumerate() {
+            for (j, &y) in b.iter().enumerate() {
+                c[i + j] += x * y;
+            }
+        }
+        return c;
+    }
+    let size = n.next_power_of_two();
+    assert!(
+        (M - 1) % size as u64 == 0,
+        "modulus {} does not support NTT of length {}",
+        M,
+        size
+    );
+    let root = primitive_root::<M>();
+    let mut fa = a.to_vec();
+    let mut fb = b.to_vec();
+    fa.resize(size, ModInt::default());
+    fb.resize(size, ModInt::default());
+    ntt(&mut fa, root, false);
+    ntt(&mut fb, root, false);
+    for (x, &y) in fa.iter_mut().zip(&fb) {
+        *x *= y;
+    }
+    ntt(&mut fa, root, true);
+    fa.truncate(n);
+    fa
+}
+
+/// 法Mの形式的冪級数・多項式
+///
+/// 係数を低次から順に保持します。積は`convolution`で計算するため、法はNTTに
+/// 適した素数（998244353など）である必要があります。
+/// `inv`・`log`・`exp`はmod x^nで計算します。
+///
+/// # 計算量
+/// 次数をnとして
+/// - 積・除算・inv・log・exp: O(n log n)
+///
+/// # 使用例
+/// ```
+/// # use rust_macro::math::{ModInt998244353 as Mint, Poly};
+/// type P = Poly<998_244_353>;
+/// let f = P::from_u64(&[1, 1]); // 1 + x
+/// let g = &f * &f;
+/// assert_eq!(g, P::from_u64(&[1, 2, 1]));
+/// let (q, r) = g.div_rem(&P::from_u64(&[1, 1]));
+/// assert_eq!((q, r), (f.clone(), P::new(vec![])));
+///
+/// // 1 / (1 - x) = 1 + x + x^2 + ...
+/// let h = P::from_u64(&[1, 998244352]).inv(4);
+/// assert_eq!(h, P::from_u64(&[1, 1, 1, 1]));
+/// assert_eq!(f.eval(Mint::new(3)), Mint::new(4));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Poly<const M: u64> {
+    coef: Vec<ModInt<M>>,
+}
+
+impl<const M: u64> Poly<M> {
+    /// 係数（低次から）から作成
+    pub fn new(coef: Vec<ModInt<M>>) -> Self {
+        Poly { coef }.trimmed()
+    }
+
+    /// 整数の係数（低次から）から作成
+    pub fn from_u64(coef: &[u64]) -> Self {
+        Self::new(coef.iter().map(|&x| ModInt::new(x)).collect())
+    }
+
+    /// 係数（低次から、末尾の0は除く）
+    pub fn coef(&self) -> &[ModInt<M>] {
+        &self.coef
+    }
+
+    /// x^iの係数
+    pub fn get(&self, i: usize) -> ModInt<M> {
+        self.coef.get(i).copied().unwrap_or_default()
+    }
+
+    /// 係数の個数（次数+1、零多項式では0）
+    pub fn len(&self) -> usize {
+        self.coef.len()
+    }
+
+    /// 零多項式かどうか
+    pub fn is_empty(&self) -> bool {
+        self.coef.is_empty()
+    }
+
+    fn trimmed(mut self) -> Self {
+        while self.coef.last().is_some_and(|x| x.val() == 0) {
+            self.coef.pop();
+        }
+        self
+    }
+
+    /// mod x^nをとる
+    pub fn truncate(&self, n: usize) -> Self {
+        Self::new(self.coef[..n.min(self.len())].to_vec())
+    }
+
+    /// xでの値
+    pub fn eval(&self, x: ModInt<M>) -> ModInt<M> {
+        self.coef
+            .iter()
+            .rev()
+            .fold(ModInt::default(), |acc, &c| acc * x + c)
+    }
+
+    /// 微分
+    pub fn derivative(&self) -> Self {
+        Self::new(
+            self.coef
+                .iter()
+                .enumerate()
+                .skip(1)
+                .map(|(i, &c)| c * ModInt::from(i))
+                .collect(),
+        )
+    }
+
+    /// 定数項を0とする不定積分
+    pub fn integral(&self) -> Self {
+        let mut coef = vec![ModInt::default(); self.len() + 1];
+        for (i, &c) in self.coef.iter().enumerate() {
+            coef[i + 1] = c * ModInt::from(i + 1).inv();
+        }
+        Self::new(coef)
+    }
+
+    /// 1 / self mod x^n
+    ///
+    /// # パニック
+    /// 定数項が0の場合panicします
+    pub fn inv(&self, n: usize) -> Self {
+        assert!(self.get(0).val() != 0, "constant term must be non-zero");
+        let mut g = Self::new(vec![self.get(0).inv()]);
+        let mut k = 1;
+        while k < n {
+            k *= 2;
+            // g <- g * (2 - f * g)
+            let fg = (&self.truncate(k) * &g).truncate(k);
+            let two_minus = &Self::new(vec![ModInt::new(2)]) - &fg;
+            g = (&g * &two_minus).truncate(k);
+        }
+        g.truncate(n)
+    }
+
+    /// log(self) mod x^n
+    ///
+    /// # パニック
+    /// 定数項が1でない場合panicします
+    pub fn log(&self, n: usize) -> Self {
+        assert!(self.get(0).val() == 1, "constant term must be 1");
+        let d = &self.derivative() * &self.inv(n);
+        d.truncate(n.saturating_sub(1)).integral()
+    }
+
+    /// exp(self) mod x^n
+    ///
+    /// # パニック
+    /// 定数項が0でない場合panicします
+    pub fn exp(&self, n: usize) -> Self {
+        assert!(self.get(0).val() == 0, "constant term must be 0");
+        let one = Self::new(vec![ModInt::new(1)]);
+        let mut g = one.clone();
+        let mut k = 1;
+        while k < n {
+            k *= 2;
+            // g <- g * (1 - log g + f)
+            let t = &(&one - &g.log(k)) + &self.truncate(k);
+            g = (&g * &t).truncate(k);
+        }
+        g.truncate(n)
+    }
+
+    /// 多項式としての除算 (商, 余り)
+    ///
+    /// # パニック
+    /// rhsが零多項式の場合panicします
+    pub fn div_rem(&self, rhs: &Self) -> (Self, Self) {
+        assert!(!rhs.is_empty(), "division by zero polynomial");
+        if self.len() < rhs.len() {
+            return (Self::default(), self.clone());
+        }
+        let m = self.len() - rhs.len() + 1;
+        let rev = |p: &Self| Self::new(p.coef.iter().rev().copied().collect());
+        let q = (&rev(self) * &rev(rhs).inv(m)).truncate(m);
+        let mut qc = q.coef;
+        qc.resize(m, ModInt::default());
+        qc.reverse();
+        let q = Self::new(qc);
+        let r = self - &(rhs * &q);
+        (q, r)
+    }
+}
+
+impl<const M: u64> Add for &Poly<M> {
+    type Output = Poly<M>;
+    fn add(self, rhs: &Poly<M>) -> Poly<M> {
+        let n = self.len().max(rhs.len());
+        Poly::new((0..n).map(|i| self.get(i) + rhs.get(i)).collect())
+    }
+}
+
+impl<const M: u64> Sub for &Poly<M> {
+    type Output = Poly<M>;
+    fn sub(self, rhs: &Poly<M>) -> Poly<M> {
+        let n = self.len().max(rhs.len());
+        Poly::new((0..n).map(|i| self.get(i) - rhs.get(i)).collect())
+    }
+}
+
+impl<const M: u64> Mul for &Poly<M> {
+    type Output = Poly<M>;
+    fn mul(self, rhs: &Poly<M>) -> Poly<M> {
+        Poly::new(convolution(&self.coef, &rhs.coef))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::ModInt998244353 as Mint;
+
+    fn naive_conv(a: &[Mint], b: &[Mint]) -> Vec<Mint> {
+        let mut c = vec![Mint::default(); (a.len() + b.len()).saturating_sub(1)];
+        for (i, &x) in a.iter().enumerate() {
+            for (j, &y) in b.iter().enumerate() {
+                c[i + j] += x * y;
+            }
+        }
+        c
+    }
+
+    #[test]
+    fn test_convolution_ntt() {
+        let a: Vec<Mint> = (0..100u64).map(|i| Mint::new(i * i + 7)).collect();
+        let b: Vec<Mint> = (0..77u64).map(|i| Mint::new(998244352 - i)).collect();
+        assert_eq!(convolution(&a, &b), naive_conv(&a, &b));
+        assert!(convolution(&a, &[]).is_empty());
+    }
+
+    #[test]
+    fn test_poly_div_rem() {
+        let f = Poly::<998_244_353>::from_u64(&(1..80).collect::<Vec<_>>());
+        let g = Poly::from_u64(&(0..40).map(|i| i * 3 + 2).collect::<Vec<_>>());
+        let (q, r) = f.div_rem(&g);
+        assert!(r.len() < g.len());
+        assert_eq!(&(&g * &q) + &r, f);
+        assert_eq!(g.div_rem(&f), (Poly::default(), g.clone()));
+    }
+
+    #[test]
+    fn test_poly_inv_log_exp() {
+        let n = 70;
+        let f = Poly::<998_244_353>::from_u64(&(1..60).collect::<Vec<_>>());
+        let inv = f.inv(n);
+        assert_eq!((&f * &inv).truncate(n), Poly::from_u64(&[1]));
+
+        // exp(log(f)) = f
+        let lg = f.log(n);
+        assert_eq!(lg.exp(n), f.truncate(n));
+
+        // exp(x) = sum x^k / k!
+        let e = Poly::<998_244_353>::from_u64(&[0, 1]).exp(6);
+        let mut fact = Mint::new(1);
+        for k in 0..6 {
+            if k > 0 {
+                fact *= Mint::from(k);
+            }
+            assert_eq!(e.get(k) * fact, Mint::new(1));
+        }
+        assert_eq!(
+            Poly::<998_244_353>::from_u64(&[5, 3, 2])
+                .derivative()
+                .integral(),
+            Poly::from_u64(&[0, 3, 2])
+        );
+    }
+}
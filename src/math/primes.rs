@@ -0,0 +1,441 @@
+use super::int::isqrt;
+use super::montgomery::Montgomery64;
+
+/// 最小素因数のテーブルを持つ線形篩
+///
+/// n以下の整数について素数判定と素因数分解、約数列挙を高速に行います。
+/// n = 10^7程度まで構築できます。
+///
+/// # 計算量
+/// - 構築: O(n)
+/// - 素数判定: O(1)
+/// - 素因数分解: O(log x)
+/// - 約数列挙: O(約数の個数)
+///
+/// # 使用例
+/// ```
+/// # use rust_macro::math::Sieve;
+/// let sieve = Sieve::new(1000);
+/// assert!(sieve.is_prime(97));
+/// assert!(!sieve.is_prime(91));
+/// assert_eq!(sieve.primes().take(5).collect::<Vec<_>>(), vec![2, 3, 5, 7, 11]);
+/// assert_eq!(sieve.factorize(360), vec![(2, 3), (3, 2), (5, 1)]);
+/// assert_eq!(sieve.divisors(12), vec![1, 2, 3, 4, 6, 12]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Sieve {
+    /// spf[x] = xの最小素因数（x < 2 のときは0）
+    spf: Vec<u32>,
+    /// n以下の素数（昇順）
+    primes: Vec<u32>,
+}
+
+impl Sieve {
+    /// n以下の整数についての篩を作成
+    pub fn new(n: usize) -> Self {
+        let mut spf = vec![0u32; n + 1];
+        let mut primes = vec![];
+        for i in 2..=n {
+            if spf[i] == 0 {
+                spf[i] = i as u32;
+                primes.push(i as u32);
+            }
+            // i * p の最小素因数がpとなるpについてだけ書き込むため、各合成数は1回ずつ処理される
+            for &p in &primes {
+                if p > spf[i] || i * p as usize > n {
+                    break;
+                }
+                spf[i * p as usize] = p;
+            }
+        }
+        Sieve { spf, primes }
+    }
+
+    /// 篩の上限n
+    pub fn limit(&self) -> usize {
+        self.spf.len() - 1
+    }
+
+    /// xが素数かどうか
+    ///
+    /// # パニック
+    /// xが上限を超える場合panicします
+    pub fn is_prime(&self, x: usize) -> bool {
+        x >= 2 && self.spf[x] as usize == x
+    }
+
+    /// xの最小素因数（x < 2 のときは`None`）
+    pub fn smallest_prime_factor(&self, x: usize) -> Option<usize> {
+        (x >= 2).then(|| self.spf[x] as usize)
+    }
+
+    /// 上限以下の素数を昇順に列挙する
+    pub fn primes(&self) -> impl Iterator<Item = usize> + '_ {
+        self.primes.iter().map(|&p| p as usize)
+    }
+
+    /// xを素因数分解し、(素因数, 指数)を素因数の昇順で返す
+    ///
+    /// # パニック
+    /// xが上限を超える場合panicします
+    pub fn factorize(&self, mut x: usize) -> Vec<(usize, u32)> {
+        let mut res: Vec<(usize, u32)> = Vec::new();
+        while x >= 2 {
+            let p = self.spf[x] as usize;
+            match res.last_mut() {
+                Some((q, e)) if *q == p => *e += 1,
+                _ => res.push((p, 1)),
+            }
+            x /= p;
+        }
+        res
+    }
+
+    /// xの約数を昇順に列挙する（x = 0 のときは空）
+    ///
+    /// # パニック
+    /// xが上限を超える場合panicします
+    pub fn divisors(&self, x: usize) -> Vec<usize> {
+        if x == 0 {
+            return vec![];
+        }
+        let mut res = vec![1];
+        for (p, e) in self.factorize(x) {
+            let len = res.len();
+            let mut pk = 1;
+            for _ in 0..e {
+                pk *= p;
+                for i in 0..len {
+                    res.push(res[i] * pk);
+                }
+            }
+        }
+        res.sort_unstable();
+        res
+    }
+}
+
+/// nの約数を試し割りで昇順に列挙する（n = 0 のときは空）
+///
+/// 篩を作るほどではない1つの大きな値（10^12程度まで）に使います。
+///
+/// # 計算量
+/// O(√n)
+///
+/// # 使用例
+/// ```
+/// # use rust_macro::math::divisors;
+/// assert_eq!(divisors(36), vec![1, 2, 3, 4, 6, 9, 12, 18, 36]);
+/// ```
+pub fn divisors(n: u64) -> Vec<u64> {
+    let mut small = vec![];
+    let mut large = vec![];
+    let mut d = 1;
+    while d * d <= n {
+        if n % d == 0 {
+            small.push(d);
+            if d * d != n {
+                large.push(n / d);
+            }
+        }
+        d += 1;
+    }
+    small.extend(large.into_iter().rev());
+    small
+}
+
+/// 区間[l, r)に含まれる素数を区間篩で列挙する
+///
+/// √r以下の素数で区間内の倍数を除くため、rが10^12程度でも区間幅が小さければ高速です。
+///
+/// # 計算量
+/// O(√r log log r + (r - l) log log r)
+///
+/// # 使用例
+/// ```
+/// # use rust_macro::math::segmented_primes;
+/// assert_eq!(
+///     segmented_primes(1_000_000_000_000, 1_000_000_000_100),
+///     vec![1_000_000_000_039, 1_000_000_000_061, 1_000_000_000_063, 1_000_000_000_091]
+/// );
+/// ```
+pub fn segmented_primes(l: u64, r: u64) -> Vec<u64> {
+    if l >= r {
+        return Vec::new();
+    }
+    let l = l.max(2);
+    if l >= r {
+        return Vec::new();
+    }
+    let base = Sieve::new(isqrt(r - 1) as usize);
+    let mut is_prime = vec![true; (r - l) as usize];
+    for p in base.primes() {
+        let p = p as u64;
+        let start = (p * p).max((l + p - 1) / p * p);
+        for m in (start..r).step_by(p as usize) {
+            is_prime[(m - l) as usize] = false;
+        }
+    }
+    is_prime
+        .iter()
+        .enumerate()
+        .filter(|&(_, &b)| b)
+        .map(|(i, _)| l + i as u64)
+        .collect()
+}
+
+/// 64ビット整数の決定的なMiller–Rabin素数判定
+///
+/// u64の範囲で正しい判定になる7つの底を使います。
+///
+/// # 計算量
+/// O(log n)（Montgomery乗算を使用）
+///
+/// # 使用例
+/// ```
+/// # use rust_macro::math::is_prime;
+/// assert!(is_prime(1_000_000_000_000_000_003));
+/// assert!(!is_prime(1_000_000_007 * 998_244_353));
+/// assert!(!is_prime(1));
+/// ```
+pub fn is_prime(n: u64) -> bool {
+    if n < 2 {
+        return false;
+    }
+    for p in [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37] {
+        if n % p == 0 {
+            return n == p;
+        }
+    }
+    let mg = Montgomery64::new(n);
+    let s = (n - 1).trailing_zeros();
+    let d = (n - 1) >> s;
+    let one = mg.to_mont(1);
+    let minus_one = mg.to_mont(n - 1);
+    [2, 325, 9375, 28178, 450775, 9780504, 1795265022]
+        .into_iter()
+        .filter(|a| a % n != 0)
+        .all(|a| {
+            let mut x = mg.pow(mg.to_mont(a), d);
+            if x == one || x == minus_one {
+                return true;
+            }
+            for _ in 1..s {
+                x = mg.mul(x, x);
+                if x == minus_one {
+                    return true;
+                }
+            }
+            false
+        })
+}
+
+fn gcd_u64(mut a: u64, mut b: u64) -> u64 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// 合成数nの1でもnでもない約数を1つ、Pollardのρ法（Brentの変種）で見つける
+fn find_factor(n: u64) -> u64 {
+    if n % 2 == 0 {
+        return 2;
+    }
+    let mg = Montgomery64::new(n);
+    // 何回分の差をまとめてからgcdを取るか
+    const BATCH: u64 = 128;
+    for c in 1.. {
+        let c = mg.to_mont(c);
+        let f = |x: u64| mg.add(mg.mul(x, x), c);
+        let (mut x, mut y, mut ys) = (0, mg.to_mont(2), 0);
+        let (mut g, mut q, mut r) = (1, mg.to_mont(1), 1);
+        while g == 1 {
+            x = y;
+            for _ in 0..r {
+                y = f(y);
+            }
+            let mut k = 0;
+            while k < r && g == 1 {
+                ys = y;
+                for _ in 0..BATCH.min(r - k) {
+                    y = f(y);
+                    q = mg.mul(q, mg.sub(x, y));
+                }
+                // Montgomery表現のままでもnとのgcdは変わらない
+                g = gcd_u64(q, n);
+                k += BATCH;
+            }
+            r *= 2;
+        }
+        if g == n {
+            // まとめすぎて積が0になった場合は1つずつ戻ってやり直す
+            g = 1;
+            while g == 1 {
+                ys = f(ys);
+                g = gcd_u64(mg.sub(x, ys), n);
+            }
+        }
+        if g != n {
+            return g;
+        }
+    }
+    unreachable!()
+}
+
+/// 64ビット整数nを素因数分解し、(素因数, 指数)を素因数の昇順で返す
+///
+/// 小さい素因数を試し割りで取り除いた後、Miller–Rabin法とPollardのρ法で分解します。
+/// 10^18程度の値でも高速です（篩が作れる範囲なら`Sieve::factorize`の方が速い）。
+///
+/// # 計算量
+/// 期待O(n^(1/4) log n)
+///
+/// # 使用例
+/// ```
+/// # use rust_macro::math::factorize;
+/// assert_eq!(factorize(360), vec![(2, 3), (3, 2), (5, 1)]);
+/// assert_eq!(
+///     factorize(1_000_000_007 * 998_244_353),
+///     vec![(998_244_353, 1), (1_000_000_007, 1)]
+/// );
+/// assert_eq!(factorize(1), vec![]);
+/// ```
+pub fn factorize(mut n: u64) -> Vec<(u64, u32)> {
+    let mut primes = vec![];
+    for p in [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37] {
+        while n % p == 0 {
+            primes.push(p);
+            n /= p;
+        }
+    }
+    let mut stack = vec![n];
+    while let Some(m) = stack.pop() {
+        if m == 1 {
+            continue;
+        }
+        if is_prime(m) {
+            primes.push(m);
+        } else {
+            let d = find_factor(m);
+            stack.push(d);
+            stack.push(m / d);
+        }
+    }
+    primes.sort_unstable();
+    let mut res: Vec<(u64, u32)> = Vec::new();
+    for p in primes {
+        match res.last_mut() {
+            Some((q, e)) if *q == p => *e += 1,
+            _ => res.push((p, 1)),
+        }
+    }
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::XorShift64;
+
+    fn naive_is_prime(x: u64) -> bool {
+        x >= 2 && (2..).take_while(|d| d * d <= x).all(|d| x % d != 0)
+    }
+
+    #[test]
+    fn test_sieve() {
+        let sieve = Sieve::new(1000);
+        assert_eq!(sieve.limit(), 1000);
+        for x in 0..=1000 {
+            assert_eq!(sieve.is_prime(x), naive_is_prime(x as u64));
+            let f = sieve.factorize(x);
+            if x >= 1 {
+                let prod: usize = f.iter().map(|&(p, e)| p.pow(e)).product();
+                assert_eq!(prod, x);
+                assert!(f.iter().all(|&(p, _)| sieve.is_prime(p)));
+                assert!(f.windows(2).all(|w| w[0].0 < w[1].0));
+            }
+        }
+        assert_eq!(sieve.primes().count(), 168);
+        assert_eq!(sieve.smallest_prime_factor(1), None);
+        assert_eq!(sieve.smallest_prime_factor(91), Some(7));
+        assert_eq!(Sieve::new(1).primes().count(), 0);
+        assert_eq!(Sieve::new(0).limit(), 0);
+    }
+
+    #[test]
+    fn test_divisors_against_naive() {
+        let sieve = Sieve::new(2000);
+        for x in 0..=2000usize {
+            let expected: Vec<usize> = (1..=x).filter(|d| x % d == 0).collect();
+            assert_eq!(sieve.divisors(x), expected);
+            let expected: Vec<u64> = expected.into_iter().map(|d| d as u64).collect();
+            assert_eq!(divisors(x as u64), expected);
+        }
+        assert_eq!(divisors(999_999_999_989), vec![1, 999_999_999_989]);
+    }
+
+    #[test]
+    fn test_miller_rabin_and_pollard_rho() {
+        let sieve = Sieve::new(100_000);
+        for x in 0..=100_000u64 {
+            assert_eq!(is_prime(x), sieve.is_prime(x as usize));
+        }
+        // 強擬素数やu64の端の値
+        for x in [
+            3_215_031_751u64,
+            2_152_302_898_747,
+            3_474_749_660_383,
+            341_550_071_728_321,
+        ] {
+            assert!(!is_prime(x));
+        }
+        assert!(is_prime((1 << 61) - 1));
+        assert!(is_prime(18_446_744_073_709_551_557));
+        assert!(!is_prime(u64::MAX));
+
+        let mut rng = XorShift64::new(4281);
+        for _ in 0..300 {
+            let x = match rng.gen_range(0..3) {
+                0 => rng.gen_range(1..100_000),
+                1 => rng.gen_range(1..1 << 62),
+                // 大きい素数同士の積
+                _ => {
+                    let mut big_prime = || loop {
+                        let p = rng.gen_range(1 << 29..1 << 31);
+                        if is_prime(p) {
+                            break p;
+                        }
+                    };
+                    big_prime() * big_prime()
+                }
+            };
+            let f = factorize(x);
+            let prod: u64 = f.iter().map(|&(p, e)| p.pow(e)).product();
+            assert_eq!(prod, x);
+            assert!(f.iter().all(|&(p, _)| is_prime(p)));
+            assert!(f.windows(2).all(|w| w[0].0 < w[1].0));
+            if x <= 100_000 {
+                let expected: Vec<(u64, u32)> = sieve
+                    .factorize(x as usize)
+                    .into_iter()
+                    .map(|(p, e)| (p as u64, e))
+                    .collect();
+                assert_eq!(f, expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_segmented_primes() {
+        for l in 0..40 {
+            for r in l..60 {
+                let expected: Vec<u64> = (l..r).filter(|&x| naive_is_prime(x)).collect();
+                assert_eq!(segmented_primes(l, r), expected);
+            }
+        }
+        let big = segmented_primes(999_999_999_900, 1_000_000_000_000);
+        assert!(big.iter().all(|&x| naive_is_prime(x)));
+        assert_eq!(big.len(), 4);
+    }
+}
@@ -0,0 +1,299 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+fn gcd128(a: i128, b: i128) -> i128 {
+    let (mut a, mut b) = (a.unsigned_abs(), b.unsigned_abs());
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a as i128
+}
+
+/// 有理数（既約分数、分母は正）
+///
+/// 分子・分母を`i128`で保持します。幾何の交点座標のように、整数座標から
+/// 誤差なく計算したい値に使います。
+///
+/// 比較は互除法の要領で整数部分を順に比べるため、分子・分母が`i128`に収まる限り
+/// オーバーフローしません。四則演算は約分してから掛け算しますが、既約な結果の分子・分母が
+/// `i128`に収まらない場合はpanicします（誤った値は返しません）。値が大きくなり得る場合は
+/// `checked_add`などを使ってください。
+///
+/// # 使用例
+/// ```
+/// # use rust_macro::math::Rational;
+/// let a = Rational::new(1, 3);
+/// let b = Rational::new(-2, 4);
+/// assert_eq!(a + b, Rational::new(-1, 6));
+/// assert_eq!((a * b).to_string(), "-1/6");
+/// assert!(b < a);
+/// assert_eq!(Rational::from(3) / Rational::new(3, 2), Rational::from(2));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rational {
+    num: i128,
+    den: i128,
+}
+
+impl Rational {
+    /// num / den
+    ///
+    /// # パニック
+    /// denが0の場合panicします
+    pub fn new(num: i128, den: i128) -> Self {
+        assert!(den != 0, "zero denominator");
+        let g = gcd128(num, den);
+        let s = den.signum();
+        Rational {
+            num: s * num / g,
+            den: s * den / g,
+        }
+    }
+
+    /// 分子
+    pub fn num(&self) -> i128 {
+        self.num
+    }
+
+    /// 分母（正）
+    pub fn den(&self) -> i128 {
+        self.den
+    }
+
+    /// 整数かどうか
+    pub fn is_integer(&self) -> bool {
+        self.den == 1
+    }
+
+    /// 浮動小数点数に変換する
+    pub fn to_f64(&self) -> f64 {
+        self.num as f64 / self.den as f64
+    }
+
+    /// 和。結果が表せない場合は`None`
+    pub fn checked_add(self, rhs: Rational) -> Option<Rational> {
+        let g = gcd128(self.den, rhs.den);
+        let t = self
+            .num
+            .checked_mul(rhs.den / g)?
+            .checked_add(rhs.num.checked_mul(self.den / g)?)?;
+        // tと分母の公約数はgの約数に限られる
+        let g2 = gcd128(t, g);
+        let den = (self.den / g).checked_mul(rhs.den / g2)?;
+        Some(Rational::new(t / g2, den))
+    }
+
+    /// 差。結果が表せない場合は`None`
+    pub fn checked_sub(self, rhs: Rational) -> Option<Rational> {
+        self.checked_add(-rhs)
+    }
+
+    /// 積。結果が表せない場合は`None`
+    pub fn checked_mul(self, rhs: Rational) -> Option<Rational> {
+        let g1 = gcd128(self.num, rhs.den);
+        let g2 = gcd128(rhs.num, self.den);
+        let num = (self.num / g1).checked_mul(rhs.num / g2)?;
+        let den = (self.den / g2).checked_mul(rhs.den / g1)?;
+        Some(Rational { num, den })
+    }
+
+    /// 商。rhsが0の場合や結果が表せない場合は`None`
+    pub fn checked_div(self, rhs: Rational) -> Option<Rational> {
+        if rhs.num == 0 {
+            return None;
+        }
+        let s = rhs.num.signum();
+        self.checked_mul(Rational {
+            num: s * rhs.den,
+            den: s * rhs.num,
+        })
+    }
+}
+
+impl From<i64> for Rational {
+    fn from(x: i64) -> Self {
+        Rational {
+            num: x as i128,
+            den: 1,
+        }
+    }
+}
+
+impl fmt::Display for Rational {
+    /// 整数なら"n"、そうでなければ"p/q"の形式で出力する
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.den == 1 {
+            write!(f, "{}", self.num)
+        } else {
+            write!(f, "{}/{}", self.num, self.den)
+        }
+    }
+}
+
+impl Ord for Rational {
+    /// a/b と c/d を整数部分から順に比べる（連分数展開）ので、掛け算によるオーバーフローは起きない
+    fn cmp(&self, other: &Self) -> Ordering {
+        let (mut a, mut b, mut c, mut d) = (self.num, self.den, other.num, other.den);
+        // 逆数を比べているときはtrue
+        let mut flipped = false;
+        loop {
+            let (q1, r1) = (a.div_euclid(b), a.rem_euclid(b));
+            let (q2, r2) = (c.div_euclid(d), c.rem_euclid(d));
+            let ord = match (q1.cmp(&q2), r1 == 0, r2 == 0) {
+                (Ordering::Equal, true, true) => Ordering::Equal,
+                (Ordering::Equal, true, false) => Ordering::Less,
+                (Ordering::Equal, false, true) => Ordering::Greater,
+                (Ordering::Equal, false, false) => {
+                    // r1/b < r2/d ⇔ b/r1 > d/r2
+                    (a, b, c, d) = (b, r1, d, r2);
+                    flipped = !flipped;
+                    continue;
+                }
+                (ord, _, _) => ord,
+            };
+            return if flipped { ord.reverse() } else { ord };
+        }
+    }
+}
+
+impl PartialOrd for Rational {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Add for Rational {
+    type Output = Rational;
+    /// # パニック
+    /// 結果が表せない場合panicします
+    fn add(self, rhs: Rational) -> Rational {
+        self.checked_add(rhs).expect("Rational overflow")
+    }
+}
+
+impl Sub for Rational {
+    type Output = Rational;
+    fn sub(self, rhs: Rational) -> Rational {
+        self + -rhs
+    }
+}
+
+impl Mul for Rational {
+    type Output = Rational;
+    /// # パニック
+    /// 結果が表せない場合panicします
+    fn mul(self, rhs: Rational) -> Rational {
+        self.checked_mul(rhs).expect("Rational overflow")
+    }
+}
+
+impl Div for Rational {
+    type Output = Rational;
+
+    /// # パニック
+    /// rhsが0の場合や、結果が表せない場合panicします
+    fn div(self, rhs: Rational) -> Rational {
+        assert!(rhs.num != 0, "division by zero");
+        self.checked_div(rhs).expect("Rational overflow")
+    }
+}
+
+impl Neg for Rational {
+    type Output = Rational;
+    fn neg(self) -> Rational {
+        Rational {
+            num: -self.num,
+            den: self.den,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::XorShift64;
+
+    #[test]
+    fn test_rational_normalize_and_ops() {
+        assert_eq!(Rational::new(6, -4), Rational::new(-3, 2));
+        assert_eq!(Rational::new(0, -5), Rational::from(0));
+        assert_eq!(Rational::new(0, -5).den(), 1);
+        let a = Rational::new(3, 4);
+        let b = Rational::new(5, 6);
+        assert_eq!(a + b, Rational::new(19, 12));
+        assert_eq!(a - b, Rational::new(-1, 12));
+        assert_eq!(a * b, Rational::new(5, 8));
+        assert_eq!(a / b, Rational::new(9, 10));
+        assert!(Rational::new(4, 2).is_integer());
+        assert_eq!(Rational::new(7, 2).to_f64(), 3.5);
+        assert_eq!(Rational::from(-7).to_string(), "-7");
+    }
+
+    #[test]
+    fn test_rational_ordering() {
+        let mut v = [
+            Rational::new(1, 2),
+            Rational::new(-1, 3),
+            Rational::new(2, 3),
+            Rational::from(0),
+        ];
+        v.sort();
+        assert_eq!(
+            v.iter().map(|r| r.to_string()).collect::<Vec<_>>(),
+            vec!["-1/3", "0", "1/2", "2/3"]
+        );
+    }
+
+    /// a * b を符号と256ビットの絶対値 (上位, 下位) で返す
+    fn wide_mul(a: i128, b: i128) -> (bool, u128, u128) {
+        let (x, y) = (a.unsigned_abs(), b.unsigned_abs());
+        let mask = u64::MAX as u128;
+        let (x1, x0, y1, y0) = (x >> 64, x & mask, y >> 64, y & mask);
+        let (lo, mid1, mid2, hi) = (x0 * y0, x1 * y0, x0 * y1, x1 * y1);
+        let (mid, c1) = mid1.overflowing_add(mid2);
+        let (lo, c2) = lo.overflowing_add(mid << 64);
+        let hi = hi + (mid >> 64) + ((c1 as u128) << 64) + c2 as u128;
+        ((a < 0) != (b < 0) && x != 0 && y != 0, hi, lo)
+    }
+
+    fn naive_cmp(p: Rational, q: Rational) -> Ordering {
+        let (s1, h1, l1) = wide_mul(p.num(), q.den());
+        let (s2, h2, l2) = wide_mul(q.num(), p.den());
+        match (s1, s2) {
+            (false, false) => (h1, l1).cmp(&(h2, l2)),
+            (true, true) => (h2, l2).cmp(&(h1, l1)),
+            (true, false) => Ordering::Less,
+            (false, true) => Ordering::Greater,
+        }
+    }
+
+    #[test]
+    fn test_rational_large_values() {
+        let mut rng = XorShift64::new(4229);
+        let big = |rng: &mut XorShift64, bits: u32| {
+            let x = ((rng.gen_range(0..u64::MAX) as i128) << 64
+                | rng.gen_range(0..u64::MAX) as i128)
+                >> (128 - bits);
+            if rng.gen_range(0..2) == 0 {
+                x
+            } else {
+                -x
+            }
+        };
+        for _ in 0..2000 {
+            let p = Rational::new(big(&mut rng, 100), big(&mut rng, 70).abs().max(1));
+            let q = Rational::new(big(&mut rng, 100), big(&mut rng, 70).abs().max(1));
+            assert_eq!(p.cmp(&q), naive_cmp(p, q));
+            assert_eq!(p.cmp(&p), Ordering::Equal);
+            assert_eq!(p.checked_sub(p), Some(Rational::from(0)));
+            assert_eq!(p.checked_div(p), Some(Rational::from(1)));
+        }
+        let huge = Rational::new(i128::MAX / 2, 5);
+        assert_eq!(huge.checked_add(huge).map(|r| r.num()), Some(i128::MAX - 1));
+        assert_eq!(huge.checked_mul(huge), None);
+        assert_eq!(huge.checked_div(Rational::from(0)), None);
+        let tiny = Rational::new(1, i128::MAX);
+        assert_eq!(tiny.checked_add(Rational::new(1, i128::MAX - 1)), None);
+    }
+}
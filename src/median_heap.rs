@@ -0,0 +1,208 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+
+/// 中央値を管理する2つのヒープ
+///
+/// 小さい側の半分を最大ヒープ、大きい側の半分を最小ヒープで管理します。
+/// 任意の値の削除は遅延削除で行います。
+/// 要素数が偶数のときの中央値は小さい方（下側中央値）です。
+///
+/// # 計算量
+/// - 追加・削除: ならし O(log n)
+/// - 中央値・偏差の和: ならし O(log n)
+///
+/// # 使用例
+/// ```
+/// # use rust_macro::MedianHeap;
+/// let mut mh = MedianHeap::new();
+/// for x in [1, 5, 2, 10] {
+///     mh.insert(x);
+/// }
+/// assert_eq!(mh.median(), Some(2));
+/// // 全要素を2に揃えるコスト: 1 + 3 + 0 + 8
+/// assert_eq!(mh.sum_of_deviations(), 12);
+/// assert!(mh.erase(1));
+/// assert_eq!(mh.median(), Some(5));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct MedianHeap {
+    low: BinaryHeap<i64>,
+    high: BinaryHeap<Reverse<i64>>,
+    /// 各ヒープで削除予定の値の個数
+    del_low: HashMap<i64, usize>,
+    del_high: HashMap<i64, usize>,
+    /// 削除予定のものを除いた各ヒープの要素数と総和
+    low_len: usize,
+    high_len: usize,
+    low_sum: i64,
+    high_sum: i64,
+    /// 値ごとの個数
+    count: HashMap<i64, usize>,
+}
+
+impl MedianHeap {
+    /// 空のMedianHeapを作成
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 要素数
+    pub fn len(&self) -> usize {
+        self.low_len + self.high_len
+    }
+
+    /// 要素が空かどうか
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// 値xを追加する
+    pub fn insert(&mut self, x: i64) {
+        *self.count.entry(x).or_insert(0) += 1;
+        match self.low_top() {
+            Some(m) if x > m => {
+                self.high.push(Reverse(x));
+                self.high_len += 1;
+                self.high_sum += x;
+            }
+            _ => {
+                self.low.push(x);
+                self.low_len += 1;
+                self.low_sum += x;
+            }
+        }
+        self.rebalance();
+    }
+
+    /// 値xを1つ削除する
+    ///
+    /// # 戻り値
+    /// xが含まれていて削除できた場合は`true`
+    pub fn erase(&mut self, x: i64) -> bool {
+        match self.count.get_mut(&x) {
+            Some(c) if *c > 0 => *c -= 1,
+            _ => return false,
+        }
+        // 小さい側の最大値以下ならxは小さい側にある
+        if self.low_top().is_some_and(|m| x <= m) {
+            *self.del_low.entry(x).or_insert(0) += 1;
+            self.low_len -= 1;
+            self.low_sum -= x;
+        } else {
+            *self.del_high.entry(x).or_insert(0) += 1;
+            self.high_len -= 1;
+            self.high_sum -= x;
+        }
+        self.rebalance();
+        true
+    }
+
+    /// 中央値（要素数が偶数の場合は下側中央値）
+    pub fn median(&mut self) -> Option<i64> {
+        self.low_top()
+    }
+
+    /// 全要素と中央値との差の絶対値の和
+    ///
+    /// 全要素を1つの値に揃えるときの最小コストに等しくなります。
+    pub fn sum_of_deviations(&mut self) -> i64 {
+        match self.median() {
+            Some(m) => {
+                m * self.low_len as i64 - self.low_sum + self.high_sum - m * self.high_len as i64
+            }
+            None => 0,
+        }
+    }
+
+    fn low_top(&mut self) -> Option<i64> {
+        while let Some(&x) = self.low.peek() {
+            match self.del_low.get_mut(&x) {
+                Some(c) if *c > 0 => {
+                    *c -= 1;
+                    self.low.pop();
+                }
+                _ => return Some(x),
+            }
+        }
+        None
+    }
+
+    fn high_top(&mut self) -> Option<i64> {
+        while let Some(&Reverse(x)) = self.high.peek() {
+            match self.del_high.get_mut(&x) {
+                Some(c) if *c > 0 => {
+                    *c -= 1;
+                    self.high.pop();
+                }
+                _ => return Some(x),
+            }
+        }
+        None
+    }
+
+    /// low_len == high_len または low_len == high_len + 1 を保つ
+    fn rebalance(&mut self) {
+        if self.low_len > self.high_len + 1 {
+            let x = self.low_top().unwrap();
+            self.low.pop();
+            self.low_len -= 1;
+            self.low_sum -= x;
+            self.high.push(Reverse(x));
+            self.high_len += 1;
+            self.high_sum += x;
+        } else if self.low_len < self.high_len {
+            let x = self.high_top().unwrap();
+            self.high.pop();
+            self.high_len -= 1;
+            self.high_sum -= x;
+            self.low.push(x);
+            self.low_len += 1;
+            self.low_sum += x;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::XorShift64;
+
+    #[test]
+    fn test_median_heap_against_naive() {
+        let mut rng = XorShift64::new(1);
+        let mut mh = MedianHeap::new();
+        let mut naive: Vec<i64> = Vec::new();
+        for _ in 0..2000 {
+            if naive.is_empty() || rng.gen_range(0..3) > 0 {
+                let x = rng.gen_range_i64(-20..20);
+                mh.insert(x);
+                naive.push(x);
+            } else {
+                let x = rng.gen_range_i64(-20..20);
+                let pos = naive.iter().position(|&v| v == x);
+                assert_eq!(mh.erase(x), pos.is_some());
+                if let Some(p) = pos {
+                    naive.swap_remove(p);
+                }
+            }
+            naive.sort();
+            assert_eq!(mh.len(), naive.len());
+            let m = (!naive.is_empty()).then(|| naive[(naive.len() - 1) / 2]);
+            assert_eq!(mh.median(), m);
+            let dev: i64 = naive.iter().map(|&v| (v - m.unwrap_or(0)).abs()).sum();
+            assert_eq!(mh.sum_of_deviations(), dev);
+        }
+    }
+
+    #[test]
+    fn test_median_heap_empty() {
+        let mut mh = MedianHeap::new();
+        assert_eq!(mh.median(), None);
+        assert_eq!(mh.sum_of_deviations(), 0);
+        assert!(!mh.erase(3));
+        mh.insert(3);
+        assert!(mh.erase(3));
+        assert!(mh.is_empty());
+        assert_eq!(mh.median(), None);
+    }
+}
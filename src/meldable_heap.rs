@@ -0,0 +1,245 @@
+use std::fmt;
+use std::mem;
+
+struct Node<T> {
+    val: T,
+    /// 右の背骨の長さ（null path length）
+    rank: usize,
+    left: Option<Box<Node<T>>>,
+    right: Option<Box<Node<T>>>,
+}
+
+fn rank<T>(t: &Option<Box<Node<T>>>) -> usize {
+    t.as_ref().map_or(0, |n| n.rank)
+}
+
+fn meld<T: Ord>(a: Option<Box<Node<T>>>, b: Option<Box<Node<T>>>) -> Option<Box<Node<T>>> {
+    match (a, b) {
+        (None, t) | (t, None) => t,
+        (Some(mut a), Some(mut b)) => {
+            if a.val < b.val {
+                mem::swap(&mut a, &mut b);
+            }
+            a.right = meld(a.right.take(), Some(b));
+            if rank(&a.left) < rank(&a.right) {
+                mem::swap(&mut a.left, &mut a.right);
+            }
+            a.rank = rank(&a.right) + 1;
+            Some(a)
+        }
+    }
+}
+
+/// 併合可能なヒープ（leftist heap）
+///
+/// `BinaryHeap`と同じく最大値を取り出すヒープで、2つのヒープを対数時間で併合できます。
+/// 木DPで子のヒープを親にまとめる場合などに使います。最小値を取り出したい場合は
+/// `std::cmp::Reverse`で包んでください。
+///
+/// # 計算量
+/// - 追加・最大値の削除・併合: O(log n)
+/// - 最大値の参照: O(1)
+///
+/// # 使用例
+/// ```
+/// # use rust_macro::MeldableHeap;
+/// let mut a: MeldableHeap<i32> = [3, 1, 4].into_iter().collect();
+/// let b: MeldableHeap<i32> = [1, 5, 9].into_iter().collect();
+/// a.meld(b);
+/// assert_eq!(a.len(), 6);
+/// assert_eq!(a.pop(), Some(9));
+/// assert_eq!(a.peek(), Some(&5));
+/// ```
+pub struct MeldableHeap<T> {
+    root: Option<Box<Node<T>>>,
+    len: usize,
+}
+
+impl<T: Ord> Default for MeldableHeap<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Ord> MeldableHeap<T> {
+    /// 空のヒープを作成
+    pub fn new() -> Self {
+        MeldableHeap { root: None, len: 0 }
+    }
+
+    /// 要素数
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// 空かどうか
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// 最大値
+    pub fn peek(&self) -> Option<&T> {
+        self.root.as_ref().map(|n| &n.val)
+    }
+
+    /// xを追加する
+    pub fn push(&mut self, x: T) {
+        let node = Box::new(Node {
+            val: x,
+            rank: 1,
+            left: None,
+            right: None,
+        });
+        self.root = meld(self.root.take(), Some(node));
+        self.len += 1;
+    }
+
+    /// 最大値を削除して返す
+    pub fn pop(&mut self) -> Option<T> {
+        let mut root = self.root.take()?;
+        self.root = meld(root.left.take(), root.right.take());
+        self.len -= 1;
+        Some(root.val)
+    }
+
+    /// otherのすべての要素をselfに移す
+    pub fn meld(&mut self, mut other: Self) {
+        self.root = meld(self.root.take(), other.root.take());
+        self.len += mem::take(&mut other.len);
+    }
+}
+
+impl<T> Drop for MeldableHeap<T> {
+    /// 左の子は深くなり得るため、再帰せずに解放する
+    fn drop(&mut self) {
+        let mut stack: Vec<Box<Node<T>>> = self.root.take().into_iter().collect();
+        while let Some(mut n) = stack.pop() {
+            stack.extend(n.left.take());
+            stack.extend(n.right.take());
+        }
+    }
+}
+
+impl<T> MeldableHeap<T> {
+    /// 根から幅優先順に並べたノード（子は必ず親より後ろに来る）
+    fn nodes(&self) -> Vec<&Node<T>> {
+        let mut nodes: Vec<&Node<T>> = self.root.as_deref().into_iter().collect();
+        let mut i = 0;
+        while let Some(n) = nodes.get(i) {
+            let n = *n;
+            nodes.extend(n.left.as_deref());
+            nodes.extend(n.right.as_deref());
+            i += 1;
+        }
+        nodes
+    }
+}
+
+impl<T: Clone> Clone for MeldableHeap<T> {
+    /// `Drop`と同様に、深い左の鎖でも再帰せずに複製する
+    fn clone(&self) -> Self {
+        let nodes = self.nodes();
+        // 幅優先順での各ノードの子の位置
+        let mut children = vec![(None, None); nodes.len()];
+        let mut next = 1;
+        for (i, n) in nodes.iter().enumerate() {
+            if n.left.is_some() {
+                children[i].0 = Some(next);
+                next += 1;
+            }
+            if n.right.is_some() {
+                children[i].1 = Some(next);
+                next += 1;
+            }
+        }
+        // 後ろ（葉の側）から組み立てる
+        let mut built: Vec<Option<Box<Node<T>>>> = (0..nodes.len()).map(|_| None).collect();
+        for i in (0..nodes.len()).rev() {
+            let (l, r) = children[i];
+            built[i] = Some(Box::new(Node {
+                val: nodes[i].val.clone(),
+                rank: nodes[i].rank,
+                left: l.and_then(|j| built[j].take()),
+                right: r.and_then(|j| built[j].take()),
+            }));
+        }
+        MeldableHeap {
+            root: built.into_iter().next().flatten(),
+            len: self.len,
+        }
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for MeldableHeap<T> {
+    /// 要素を幅優先順に表示する（`BinaryHeap`と同様に順序は不定）
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list()
+            .entries(self.nodes().into_iter().map(|n| &n.val))
+            .finish()
+    }
+}
+
+impl<T: Ord> FromIterator<T> for MeldableHeap<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut h = Self::new();
+        for x in iter {
+            h.push(x);
+        }
+        h
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::XorShift64;
+    use std::cmp::Reverse;
+    use std::collections::BinaryHeap;
+
+    #[test]
+    fn test_meldable_heap_against_binary_heap() {
+        let mut rng = XorShift64::new(9);
+        let mut heaps: Vec<MeldableHeap<u64>> = (0..5).map(|_| MeldableHeap::new()).collect();
+        let mut naive: Vec<BinaryHeap<u64>> = (0..5).map(|_| BinaryHeap::new()).collect();
+        for _ in 0..2000 {
+            let i = rng.gen_range(0..5) as usize;
+            match rng.gen_range(0..4) {
+                0 | 1 => {
+                    let x = rng.gen_range(0..100);
+                    heaps[i].push(x);
+                    naive[i].push(x);
+                }
+                2 => assert_eq!(heaps[i].pop(), naive[i].pop()),
+                _ => {
+                    let j = rng.gen_range(0..5) as usize;
+                    if i != j {
+                        let h = mem::take(&mut heaps[j]);
+                        heaps[i].meld(h);
+                        let b = mem::take(&mut naive[j]);
+                        naive[i].extend(b);
+                    }
+                }
+            }
+            assert_eq!(heaps[i].len(), naive[i].len());
+            assert_eq!(heaps[i].peek(), naive[i].peek());
+        }
+    }
+
+    #[test]
+    fn test_meldable_heap_min_and_deep_drop() {
+        let mut h: MeldableHeap<Reverse<i32>> = [5, 2, 8].into_iter().map(Reverse).collect();
+        assert_eq!(h.pop(), Some(Reverse(2)));
+        assert_eq!(h.peek(), Some(&Reverse(5)));
+        // 昇順に追加すると左の子が長い鎖になる
+        let big: MeldableHeap<i32> = (0..200_000).collect();
+        assert_eq!(big.peek(), Some(&199_999));
+        let mut copy = big.clone();
+        assert!(format!("{:?}", big).starts_with("[199999, 199998, "));
+        drop(big);
+        assert_eq!(copy.len(), 200_000);
+        for x in (199_990..200_000).rev() {
+            assert_eq!(copy.pop(), Some(x));
+        }
+        assert_eq!(format!("{:?}", MeldableHeap::<i32>::new()), "[]");
+    }
+}
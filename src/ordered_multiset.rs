@@ -0,0 +1,313 @@
+use crate::XorShift64;
+
+const NIL: usize = usize::MAX;
+
+#[derive(Debug, Clone)]
+struct Node<T> {
+    val: T,
+    priority: u64,
+    size: usize,
+    left: usize,
+    right: usize,
+}
+
+/// 順序付き多重集合（treap）
+///
+/// C++の`std::multiset`に相当し、さらにk番目の要素やx未満の要素数を対数時間で求められます。
+/// 削除したノードの領域は再利用されます。
+///
+/// # 計算量
+/// - 挿入・削除・k番目の要素・個数の数え上げ・前後の要素の検索: 期待O(log n)
+///
+/// # 使用例
+/// ```
+/// # use rust_macro::OrderedMultiset;
+/// let mut s = OrderedMultiset::new();
+/// for x in [5, 1, 3, 3, 9] {
+///     s.insert(x);
+/// }
+/// assert_eq!(s.kth(2), Some(&3));
+/// assert_eq!(s.count_less(&4), 3);
+/// assert_eq!(s.predecessor(&3), Some(&1));
+/// assert_eq!(s.successor(&3), Some(&5));
+/// assert!(s.erase_one(&3));
+/// assert_eq!(s.count(&3), 1);
+/// assert_eq!(s.iter().copied().collect::<Vec<_>>(), vec![1, 3, 5, 9]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct OrderedMultiset<T> {
+    nodes: Vec<Node<T>>,
+    free: Vec<usize>,
+    root: usize,
+    rng: XorShift64,
+}
+
+impl<T: Ord> Default for OrderedMultiset<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Ord> OrderedMultiset<T> {
+    /// 空の多重集合を作成
+    pub fn new() -> Self {
+        OrderedMultiset {
+            nodes: Vec::new(),
+            free: Vec::new(),
+            root: NIL,
+            rng: XorShift64::new(0x5eed),
+        }
+    }
+
+    /// 要素数
+    pub fn len(&self) -> usize {
+        self.size(self.root)
+    }
+
+    /// 空かどうか
+    pub fn is_empty(&self) -> bool {
+        self.root == NIL
+    }
+
+    /// xを1つ追加する
+    pub fn insert(&mut self, x: T) {
+        let (l, r) = self.split(self.root, &x, false);
+        let node = Node {
+            val: x,
+            priority: self.rng.next_u64(),
+            size: 1,
+            left: NIL,
+            right: NIL,
+        };
+        let t = match self.free.pop() {
+            Some(i) => {
+                self.nodes[i] = node;
+                i
+            }
+            None => {
+                self.nodes.push(node);
+                self.nodes.len() - 1
+            }
+        };
+        let l = self.merge(l, t);
+        self.root = self.merge(l, r);
+    }
+
+    /// xを1つ削除する。xが含まれていなければ何もせず`false`を返す
+    pub fn erase_one(&mut self, x: &T) -> bool {
+        let (l, r) = self.split(self.root, x, true);
+        let (m, r) = self.split(r, x, false);
+        let found = m != NIL;
+        let m = if found {
+            self.free.push(m);
+            self.merge(self.nodes[m].left, self.nodes[m].right)
+        } else {
+            m
+        };
+        let l = self.merge(l, m);
+        self.root = self.merge(l, r);
+        found
+    }
+
+    /// x未満の要素の個数
+    pub fn count_less(&self, x: &T) -> usize {
+        self.count_by(x, true)
+    }
+
+    /// x以下の要素の個数
+    pub fn count_less_eq(&self, x: &T) -> usize {
+        self.count_by(x, false)
+    }
+
+    /// xの個数
+    pub fn count(&self, x: &T) -> usize {
+        self.count_less_eq(x) - self.count_less(x)
+    }
+
+    /// 小さい方からk番目（0始まり）の要素
+    pub fn kth(&self, mut k: usize) -> Option<&T> {
+        let mut t = self.root;
+        while t != NIL {
+            let ls = self.size(self.nodes[t].left);
+            if k < ls {
+                t = self.nodes[t].left;
+            } else if k == ls {
+                return Some(&self.nodes[t].val);
+            } else {
+                k -= ls + 1;
+                t = self.nodes[t].right;
+            }
+        }
+        None
+    }
+
+    /// x未満で最大の要素
+    pub fn predecessor(&self, x: &T) -> Option<&T> {
+        self.count_less(x).checked_sub(1).and_then(|k| self.kth(k))
+    }
+
+    /// xより大きい最小の要素
+    pub fn successor(&self, x: &T) -> Option<&T> {
+        self.kth(self.count_less_eq(x))
+    }
+
+    /// 最小の要素
+    pub fn first(&self) -> Option<&T> {
+        self.kth(0)
+    }
+
+    /// 最大の要素
+    pub fn last(&self) -> Option<&T> {
+        self.len().checked_sub(1).and_then(|k| self.kth(k))
+    }
+
+    /// 要素を昇順に列挙する
+    pub fn iter(&self) -> impl Iterator<Item = &T> + '_ {
+        let mut stack = Vec::new();
+        let mut t = self.root;
+        std::iter::from_fn(move || {
+            while t != NIL {
+                stack.push(t);
+                t = self.nodes[t].left;
+            }
+            let u = stack.pop()?;
+            t = self.nodes[u].right;
+            Some(&self.nodes[u].val)
+        })
+    }
+
+    fn size(&self, t: usize) -> usize {
+        if t == NIL {
+            0
+        } else {
+            self.nodes[t].size
+        }
+    }
+
+    fn update(&mut self, t: usize) {
+        self.nodes[t].size = 1 + self.size(self.nodes[t].left) + self.size(self.nodes[t].right);
+    }
+
+    fn count_by(&self, x: &T, strict: bool) -> usize {
+        let mut t = self.root;
+        let mut res = 0;
+        while t != NIL {
+            let v = &self.nodes[t].val;
+            if v < x || (!strict && v == x) {
+                res += self.size(self.nodes[t].left) + 1;
+                t = self.nodes[t].right;
+            } else {
+                t = self.nodes[t].left;
+            }
+        }
+        res
+    }
+
+    /// tを(x未満, x以上)に分割する（strictでなければ(x以下, xより大きい)）
+    fn split(&mut self, t: usize, x: &T, strict: bool) -> (usize, usize) {
+        if t == NIL {
+            return (NIL, NIL);
+        }
+        let v = &self.nodes[t].val;
+        if v < x || (!strict && v == x) {
+            let (l, r) = self.split(self.nodes[t].right, x, strict);
+            self.nodes[t].right = l;
+            self.update(t);
+            (t, r)
+        } else {
+            let (l, r) = self.split(self.nodes[t].left, x, strict);
+            self.nodes[t].left = r;
+            self.update(t);
+            (l, t)
+        }
+    }
+
+    fn merge(&mut self, l: usize, r: usize) -> usize {
+        if l == NIL {
+            return r;
+        }
+        if r == NIL {
+            return l;
+        }
+        if self.nodes[l].priority > self.nodes[r].priority {
+            let m = self.merge(self.nodes[l].right, r);
+            self.nodes[l].right = m;
+            self.update(l);
+            l
+        } else {
+            let m = self.merge(l, self.nodes[r].left);
+            self.nodes[r].left = m;
+            self.update(r);
+            r
+        }
+    }
+}
+
+impl<T: Ord> FromIterator<T> for OrderedMultiset<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut s = Self::new();
+        for x in iter {
+            s.insert(x);
+        }
+        s
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ordered_multiset_against_naive() {
+        let mut rng = XorShift64::new(11);
+        let mut s = OrderedMultiset::new();
+        let mut naive: Vec<i64> = Vec::new();
+        for _ in 0..2000 {
+            let x = rng.gen_range_i64(0..50);
+            match rng.gen_range(0..3) {
+                0 => {
+                    s.insert(x);
+                    let p = naive.partition_point(|&v| v <= x);
+                    naive.insert(p, x);
+                }
+                1 => {
+                    let expected = naive.binary_search(&x).is_ok();
+                    if let Ok(p) = naive.binary_search(&x) {
+                        naive.remove(p);
+                    }
+                    assert_eq!(s.erase_one(&x), expected);
+                }
+                _ => {
+                    let less = naive.partition_point(|&v| v < x);
+                    let le = naive.partition_point(|&v| v <= x);
+                    assert_eq!(s.count_less(&x), less);
+                    assert_eq!(s.count(&x), le - less);
+                    assert_eq!(s.predecessor(&x), less.checked_sub(1).map(|i| &naive[i]));
+                    assert_eq!(s.successor(&x), naive.get(le));
+                    let k = rng.gen_range(0..naive.len() as u64 + 1) as usize;
+                    assert_eq!(s.kth(k), naive.get(k));
+                }
+            }
+            assert_eq!(s.len(), naive.len());
+        }
+        assert!(s.iter().eq(naive.iter()));
+        assert_eq!(s.first(), naive.first());
+        assert_eq!(s.last(), naive.last());
+    }
+
+    #[test]
+    fn test_ordered_multiset_empty_and_reuse() {
+        let mut s: OrderedMultiset<String> =
+            ["b", "a", "b"].iter().map(|x| x.to_string()).collect();
+        assert!(!s.erase_one(&"c".to_string()));
+        assert!(s.erase_one(&"b".to_string()));
+        assert!(s.erase_one(&"b".to_string()));
+        assert!(s.erase_one(&"a".to_string()));
+        assert!(s.is_empty());
+        assert_eq!(s.kth(0), None);
+        assert_eq!(s.last(), None);
+        s.insert("z".to_string());
+        assert_eq!(s.nodes.len(), 3);
+        assert_eq!(s.first().map(|x| x.as_str()), Some("z"));
+    }
+}
@@ -0,0 +1,224 @@
+/// 値の出現回数に関する静的な区間クエリを処理するデータ構造
+///
+/// 配列の値を内部で座圧し、「区間[l, r)に値xがいくつあるか」「区間[l, r)にx以下の値が
+/// いくつあるか」に答えます。値の種類数が少ない場合は値ごとの累積個数テーブル、
+/// 多い場合はマージソート木をバックエンドとして自動で選択します。
+///
+/// # 計算量
+/// 値の種類数をkとして
+/// - 構築: テーブル O(nk)、マージソート木 O(n log n)
+/// - クエリ: テーブル O(log k)、マージソート木 O(log^2 n)
+///
+/// # 使用例
+/// ```
+/// # use rust_macro::PrefixCounts;
+/// let arr = vec![3, 1, 4, 1, 5, 9, 2, 6];
+/// let pc = PrefixCounts::new(&arr);
+/// assert_eq!(pc.count_in_range(0, 4, &1), 2);
+/// assert_eq!(pc.count_leq(2, 8, &4), 3); // 4, 1, 2
+/// assert_eq!(pc.count_lt(0, 8, &1), 0);
+/// ```
+#[derive(Debug, Clone)]
+pub struct PrefixCounts<T> {
+    /// 座圧後の値（昇順、重複なし）
+    vals: Vec<T>,
+    n: usize,
+    backend: Backend,
+}
+
+#[derive(Debug, Clone)]
+enum Backend {
+    /// table[k - 1][i] = 先頭i要素のうち順位がk未満のものの個数
+    Table(Vec<Vec<u32>>),
+    /// 各ノードが担当区間の順位をソートして保持するセグメント木
+    MergeSortTree { size: usize, tree: Vec<Vec<usize>> },
+}
+
+/// テーブルを使うときの要素数の上限（値の種類数×(n+1)）
+const TABLE_LIMIT: usize = 1 << 22;
+
+impl<T: Ord + Clone> PrefixCounts<T> {
+    /// 配列から構築
+    ///
+    /// # 引数
+    /// * `arr` - 元の配列
+    ///
+    /// # 戻り値
+    /// 新しいPrefixCountsインスタンス
+    pub fn new(arr: &[T]) -> Self {
+        let mut vals = arr.to_vec();
+        vals.sort();
+        vals.dedup();
+        let n = arr.len();
+        let ranks: Vec<usize> = arr.iter().map(|x| vals.binary_search(x).unwrap()).collect();
+
+        let backend = if vals.len().saturating_mul(n + 1) <= TABLE_LIMIT {
+            Self::build_table(&ranks, vals.len())
+        } else {
+            Self::build_merge_sort_tree(&ranks)
+        };
+
+        PrefixCounts { vals, n, backend }
+    }
+
+    fn build_table(ranks: &[usize], k: usize) -> Backend {
+        let n = ranks.len();
+        let mut table = vec![vec![0u32; n + 1]; k];
+        for (i, &r) in ranks.iter().enumerate() {
+            // 順位rの要素は k > r となるすべての行でカウントされる
+            for (row, t) in table.iter_mut().enumerate() {
+                t[i + 1] = t[i] + u32::from(row >= r);
+            }
+        }
+        Backend::Table(table)
+    }
+
+    fn build_merge_sort_tree(ranks: &[usize]) -> Backend {
+        let size = ranks.len().next_power_of_two();
+        let mut tree = vec![Vec::new(); 2 * size];
+        for (i, &r) in ranks.iter().enumerate() {
+            tree[size + i].push(r);
+        }
+        for i in (1..size).rev() {
+            let (l, r) = (&tree[2 * i], &tree[2 * i + 1]);
+            let mut merged = Vec::with_capacity(l.len() + r.len());
+            let (mut a, mut b) = (0, 0);
+            while a < l.len() || b < r.len() {
+                if b == r.len() || (a < l.len() && l[a] <= r[b]) {
+                    merged.push(l[a]);
+                    a += 1;
+                } else {
+                    merged.push(r[b]);
+                    b += 1;
+                }
+            }
+            tree[i] = merged;
+        }
+        Backend::MergeSortTree { size, tree }
+    }
+
+    /// 配列の長さ
+    pub fn len(&self) -> usize {
+        self.n
+    }
+
+    /// 配列の長さが0かどうか
+    pub fn is_empty(&self) -> bool {
+        self.n == 0
+    }
+
+    /// 区間[l, r)に含まれる値valueの個数
+    ///
+    /// # パニック
+    /// `l <= r <= n` を満たさない場合panicします
+    pub fn count_in_range(&self, l: usize, r: usize, value: &T) -> usize {
+        assert!(l <= r && r <= self.n);
+        match self.vals.binary_search(value) {
+            Ok(k) => self.count_rank_lt(l, r, k + 1) - self.count_rank_lt(l, r, k),
+            Err(_) => 0,
+        }
+    }
+
+    /// 区間[l, r)に含まれるx以下の値の個数
+    pub fn count_leq(&self, l: usize, r: usize, x: &T) -> usize {
+        let k = self.vals.partition_point(|v| v <= x);
+        self.count_rank_lt(l, r, k)
+    }
+
+    /// 区間[l, r)に含まれるx未満の値の個数
+    pub fn count_lt(&self, l: usize, r: usize, x: &T) -> usize {
+        let k = self.vals.partition_point(|v| v < x);
+        self.count_rank_lt(l, r, k)
+    }
+
+    /// 区間[l, r)に含まれる順位k未満の値の個数
+    fn count_rank_lt(&self, l: usize, r: usize, k: usize) -> usize {
+        assert!(l <= r && r <= self.n);
+        if k == 0 {
+            return 0;
+        }
+        match &self.backend {
+            Backend::Table(table) => (table[k - 1][r] - table[k - 1][l]) as usize,
+            Backend::MergeSortTree { size, tree } => {
+                let (mut l, mut r) = (l + size, r + size);
+                let mut res = 0;
+                while l < r {
+                    if l & 1 == 1 {
+                        res += tree[l].partition_point(|&v| v < k);
+                        l += 1;
+                    }
+                    if r & 1 == 1 {
+                        r -= 1;
+                        res += tree[r].partition_point(|&v| v < k);
+                    }
+                    l >>= 1;
+                    r >>= 1;
+                }
+                res
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check_against_naive(pc: &PrefixCounts<i64>, arr: &[i64]) {
+        for l in 0..=arr.len() {
+            for r in l..=arr.len() {
+                for x in -1..=10 {
+                    let slice = &arr[l..r];
+                    assert_eq!(
+                        pc.count_in_range(l, r, &x),
+                        slice.iter().filter(|&&v| v == x).count()
+                    );
+                    assert_eq!(
+                        pc.count_leq(l, r, &x),
+                        slice.iter().filter(|&&v| v <= x).count()
+                    );
+                    assert_eq!(
+                        pc.count_lt(l, r, &x),
+                        slice.iter().filter(|&&v| v < x).count()
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_prefix_counts_table() {
+        let arr = vec![3, 1, 4, 1, 5, 9, 2, 6, 5, 3];
+        let pc = PrefixCounts::new(&arr);
+        assert!(matches!(pc.backend, Backend::Table(_)));
+        check_against_naive(&pc, &arr);
+    }
+
+    #[test]
+    fn test_prefix_counts_merge_sort_tree() {
+        let arr = vec![3, 1, 4, 1, 5, 9, 2, 6, 5, 3];
+        let mut pc = PrefixCounts::new(&arr);
+        let ranks: Vec<usize> = arr
+            .iter()
+            .map(|x| pc.vals.binary_search(x).unwrap())
+            .collect();
+        pc.backend = PrefixCounts::<i64>::build_merge_sort_tree(&ranks);
+        check_against_naive(&pc, &arr);
+    }
+
+    #[test]
+    fn test_prefix_counts_strings() {
+        let arr = vec!["b", "a", "c", "a"];
+        let pc = PrefixCounts::new(&arr);
+        assert_eq!(pc.count_in_range(0, 4, &"a"), 2);
+        assert_eq!(pc.count_in_range(0, 4, &"z"), 0);
+        assert_eq!(pc.count_leq(1, 3, &"b"), 1);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_prefix_counts_absent_value_out_of_range() {
+        let pc = PrefixCounts::new(&[1, 2, 3]);
+        pc.count_in_range(0, 4, &100);
+    }
+}
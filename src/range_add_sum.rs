@@ -0,0 +1,198 @@
+/// 区間加算・区間和取得を任意の順序で処理できるデータ構造
+///
+/// 2本のBIT（Fenwick木）による実装（区間加算・区間和のBIT）です。
+/// `Imos1D` は加算をすべて終えてから一度だけ構築する用途、`CumulativeSum` は
+/// 静的な配列への区間和クエリの用途に向いていますが、こちらは加算とクエリが
+/// 交互に現れる場合に使います。加算と和のみであれば遅延セグメント木より軽量です。
+///
+/// # 計算量
+/// - 構築: O(n)
+/// - 区間加算: O(log n)
+/// - 区間和: O(log n)
+///
+/// # 使用例
+/// ```
+/// # use rust_macro::RangeAddSum;
+/// let mut ras = RangeAddSum::new(5);
+/// ras.add(1, 4, 2);  // [1, 4)に2を加算
+/// assert_eq!(ras.sum(0, 5), 6);
+/// ras.add(2, 5, 3);  // [2, 5)に3を加算
+/// assert_eq!(ras.sum(2, 4), 10);
+/// assert_eq!(ras.get(4), 3);
+/// ```
+#[derive(Debug, Clone)]
+pub struct RangeAddSum {
+    n: usize,
+    /// 定数項を管理するBIT（1-indexed）
+    b0: Vec<i64>,
+    /// 1次の係数を管理するBIT（1-indexed）
+    b1: Vec<i64>,
+}
+
+impl RangeAddSum {
+    /// 長さnの0初期化された配列を作成
+    ///
+    /// # 引数
+    /// * `n` - 配列の長さ
+    ///
+    /// # 戻り値
+    /// 新しいRangeAddSumインスタンス
+    pub fn new(n: usize) -> Self {
+        RangeAddSum {
+            n,
+            b0: vec![0; n + 1],
+            b1: vec![0; n + 1],
+        }
+    }
+
+    /// 配列の初期値から構築
+    ///
+    /// # 引数
+    /// * `arr` - 元の配列
+    ///
+    /// # 戻り値
+    /// 新しいRangeAddSumインスタンス
+    pub fn from_slice(arr: &[i64]) -> Self {
+        let n = arr.len();
+        let mut b0 = vec![0; n + 1];
+        b0[1..].copy_from_slice(arr);
+        for i in 1..=n {
+            let j = i + (i & i.wrapping_neg());
+            if j <= n {
+                b0[j] += b0[i];
+            }
+        }
+        RangeAddSum {
+            n,
+            b0,
+            b1: vec![0; n + 1],
+        }
+    }
+
+    /// 配列の長さ
+    pub fn len(&self) -> usize {
+        self.n
+    }
+
+    /// 配列の長さが0かどうか
+    pub fn is_empty(&self) -> bool {
+        self.n == 0
+    }
+
+    /// 区間[l, r)にxを加算
+    ///
+    /// # 引数
+    /// * `l` - 区間の開始位置（含む）
+    /// * `r` - 区間の終了位置（含まない）
+    /// * `x` - 加算する値
+    pub fn add(&mut self, l: usize, r: usize, x: i64) {
+        assert!(l <= r && r <= self.n);
+        Self::bit_add(&mut self.b0, l, -x * l as i64);
+        Self::bit_add(&mut self.b0, r, x * r as i64);
+        Self::bit_add(&mut self.b1, l, x);
+        Self::bit_add(&mut self.b1, r, -x);
+    }
+
+    /// 範囲[l, r)の和を計算
+    ///
+    /// # 引数
+    /// * `l` - 開始位置（含む）
+    /// * `r` - 終了位置（含まない）
+    ///
+    /// # 戻り値
+    /// 範囲の和
+    pub fn sum(&self, l: usize, r: usize) -> i64 {
+        assert!(l <= r && r <= self.n);
+        self.prefix(r) - self.prefix(l)
+    }
+
+    /// i番目の要素の値を取得
+    pub fn get(&self, i: usize) -> i64 {
+        self.sum(i, i + 1)
+    }
+
+    /// 現在の配列全体を返す
+    pub fn to_vec(&self) -> Vec<i64> {
+        let mut prev = 0;
+        (1..=self.n)
+            .map(|i| {
+                let cur = self.prefix(i);
+                let v = cur - prev;
+                prev = cur;
+                v
+            })
+            .collect()
+    }
+
+    /// 先頭i要素の和
+    fn prefix(&self, i: usize) -> i64 {
+        Self::bit_sum(&self.b1, i) * i as i64 + Self::bit_sum(&self.b0, i)
+    }
+
+    /// 位置iにxを加算する（位置iは i より大きいprefixに寄与する）
+    fn bit_add(bit: &mut [i64], i: usize, x: i64) {
+        let mut i = i + 1;
+        while i < bit.len() {
+            bit[i] += x;
+            i += i & i.wrapping_neg();
+        }
+    }
+
+    /// 位置 [0, i) に加算された値の総和
+    fn bit_sum(bit: &[i64], i: usize) -> i64 {
+        let mut i = i;
+        let mut res = 0;
+        while i > 0 {
+            res += bit[i];
+            i -= i & i.wrapping_neg();
+        }
+        res
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_range_add_sum_basic() {
+        let mut ras = RangeAddSum::new(5);
+        ras.add(1, 4, 2);
+        ras.add(2, 5, 3);
+        // [0, 2, 5, 5, 3]
+        assert_eq!(ras.sum(0, 5), 15);
+        assert_eq!(ras.sum(0, 0), 0);
+        assert_eq!(ras.sum(1, 3), 7);
+        assert_eq!(ras.sum(4, 5), 3);
+        assert_eq!(
+            (0..5).map(|i| ras.get(i)).collect::<Vec<_>>(),
+            vec![0, 2, 5, 5, 3]
+        );
+        assert_eq!(ras.to_vec(), vec![0, 2, 5, 5, 3]);
+    }
+
+    #[test]
+    fn test_range_add_sum_from_slice() {
+        let arr = vec![3, -1, 4, 1, -5, 9];
+        let mut ras = RangeAddSum::from_slice(&arr);
+        let mut naive = arr.clone();
+        for &(l, r, x) in &[(0, 6, 2), (2, 4, -7), (5, 6, 10), (1, 1, 100)] {
+            ras.add(l, r, x);
+            for v in &mut naive[l..r] {
+                *v += x;
+            }
+            for l in 0..=6 {
+                for r in l..=6 {
+                    assert_eq!(ras.sum(l, r), naive[l..r].iter().sum::<i64>());
+                }
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_range_add_sum_out_of_range() {
+        let mut ras = RangeAddSum::new(3);
+        ras.add(0, 4, 1);
+    }
+}
@@ -0,0 +1,250 @@
+use crate::utils::Compress;
+
+/// 点の重みの矩形和を処理するデータ構造（ソート済み配列を持つBIT）
+///
+/// 重みを持ちうる点の座標をあらかじめ登録して構築し、登録済みの点への重みの加算と、
+/// 軸に平行な矩形に含まれる点の重みの総和をオンラインに処理します。
+/// 重みを1にすれば矩形内の点の個数を数えられます。座標の範囲が広く、
+/// 2次元の累積和やBITを密に持てない場合に使います。
+///
+/// # 計算量
+/// 点の個数をnとして
+/// - 構築: O(n log n)
+/// - 加算・矩形和: O(log² n)
+///
+/// # 使用例
+/// ```
+/// # use rust_macro::RectangleSum;
+/// let mut rs = RectangleSum::new(&[(0, 0), (3, 5), (1_000_000_000, -7)]);
+/// rs.add(0, 0, 2);
+/// rs.add(3, 5, 10);
+/// rs.add(1_000_000_000, -7, 100);
+/// // [0, 4) × [0, 10) の重みの総和
+/// assert_eq!(rs.sum(0, 4, 0, 10), 12);
+/// assert_eq!(rs.sum(1, i64::MAX, -10, 10), 110);
+/// ```
+#[derive(Debug, Clone)]
+pub struct RectangleSum {
+    xs: Compress<i64>,
+    /// BITの各ノードが担当する点のy座標（昇順・重複なし、1-indexed）
+    ys: Vec<Vec<i64>>,
+    /// 各ノードのy座標に対するBIT（1-indexed）
+    bit: Vec<Vec<i64>>,
+}
+
+impl RectangleSum {
+    /// 重みを持ちうる点の座標から、すべての重みが0の状態で構築する
+    pub fn new(points: &[(i64, i64)]) -> Self {
+        let xs = Compress::new(points.iter().map(|&(x, _)| x).collect());
+        let n = xs.size();
+        let mut ys = vec![Vec::new(); n + 1];
+        for &(x, y) in points {
+            let mut i = xs.get(&x) + 1;
+            while i <= n {
+                ys[i].push(y);
+                i += i & i.wrapping_neg();
+            }
+        }
+        for v in &mut ys {
+            v.sort_unstable();
+            v.dedup();
+        }
+        let bit = ys.iter().map(|v| vec![0; v.len() + 1]).collect();
+        RectangleSum { xs, ys, bit }
+    }
+
+    /// 重み付きの点(x, y, w)から構築する
+    pub fn from_weighted(points: &[(i64, i64, i64)]) -> Self {
+        let mut rs = Self::new(&points.iter().map(|&(x, y, _)| (x, y)).collect::<Vec<_>>());
+        for &(x, y, w) in points {
+            rs.add(x, y, w);
+        }
+        rs
+    }
+
+    /// 点(x, y)の重みにwを加える
+    ///
+    /// # パニック
+    /// (x, y)が構築時に登録されていない場合panicします
+    pub fn add(&mut self, x: i64, y: i64, w: i64) {
+        let mut i = self.xs.try_get(&x).expect("unregistered point") + 1;
+        while i < self.ys.len() {
+            let m = self.ys[i].len();
+            let mut j = self.ys[i].binary_search(&y).expect("unregistered point") + 1;
+            while j <= m {
+                self.bit[i][j] += w;
+                j += j & j.wrapping_neg();
+            }
+            i += i & i.wrapping_neg();
+        }
+    }
+
+    /// x座標が小さい方からi個の点のうち、y座標が[yl, yr)の点の重みの総和
+    fn prefix(&self, mut i: usize, yl: i64, yr: i64) -> i64 {
+        let mut res = 0;
+        while i > 0 {
+            let ys = &self.ys[i];
+            let (mut l, mut r) = (
+                ys.partition_point(|&y| y < yl),
+                ys.partition_point(|&y| y < yr),
+            );
+            while r > l {
+                res += self.bit[i][r];
+                r -= r & r.wrapping_neg();
+            }
+            while l > r {
+                res -= self.bit[i][l];
+                l -= l & l.wrapping_neg();
+            }
+            i -= i & i.wrapping_neg();
+        }
+        res
+    }
+
+    /// 矩形[xl, xr) × [yl, yr)に含まれる点の重みの総和
+    pub fn sum(&self, xl: i64, xr: i64, yl: i64, yr: i64) -> i64 {
+        if xl >= xr || yl >= yr {
+            return 0;
+        }
+        self.prefix(self.xs.lower_bound(&xr), yl, yr)
+            - self.prefix(self.xs.lower_bound(&xl), yl, yr)
+    }
+}
+
+/// 重み付きの点(x, y, w)と矩形クエリ(xl, xr, yl, yr)を受け取り、各矩形
+/// [xl, xr) × [yl, yr)に含まれる点の重みの総和をオフラインでまとめて求める
+///
+/// x座標で平面走査しながら、y座標を座標圧縮したBITに点を追加していきます。
+/// 点やクエリが先にすべて分かっている場合は`RectangleSum`より軽量です。
+///
+/// # 計算量
+/// 点の個数をn、クエリの個数をqとして O((n + q) log n)
+///
+/// # 使用例
+/// ```
+/// # use rust_macro::rectangle_sum_offline;
+/// let points = [(0, 0, 1), (2, 3, 10), (5, 1, 100)];
+/// let queries = [(0, 3, 0, 4), (1, 6, 0, 2), (6, 10, 0, 10)];
+/// assert_eq!(rectangle_sum_offline(&points, &queries), vec![11, 100, 0]);
+/// ```
+pub fn rectangle_sum_offline(
+    points: &[(i64, i64, i64)],
+    queries: &[(i64, i64, i64, i64)],
+) -> Vec<i64> {
+    let ys = Compress::new(points.iter().map(|&(_, y, _)| y).collect());
+    let m = ys.size();
+    let mut bit = vec![0i64; m + 1];
+    let prefix = |bit: &[i64], mut i: usize| {
+        let mut s = 0;
+        while i > 0 {
+            s += bit[i];
+            i -= i & i.wrapping_neg();
+        }
+        s
+    };
+
+    let mut pts: Vec<(i64, usize, i64)> =
+        points.iter().map(|&(x, y, w)| (x, ys.get(&y), w)).collect();
+    pts.sort_unstable_by_key(|&(x, _, _)| x);
+    // (x座標, クエリ番号, 符号): x座標未満の点の寄与を加減する
+    let mut events: Vec<(i64, usize, i64)> = Vec::with_capacity(2 * queries.len());
+    for (qi, &(xl, xr, yl, yr)) in queries.iter().enumerate() {
+        if xl < xr && yl < yr {
+            events.push((xl, qi, -1));
+            events.push((xr, qi, 1));
+        }
+    }
+    events.sort_unstable_by_key(|&(x, _, _)| x);
+
+    let mut res = vec![0; queries.len()];
+    let mut p = 0;
+    for (x, qi, sign) in events {
+        while p < pts.len() && pts[p].0 < x {
+            let mut i = pts[p].1 + 1;
+            while i <= m {
+                bit[i] += pts[p].2;
+                i += i & i.wrapping_neg();
+            }
+            p += 1;
+        }
+        let (_, _, yl, yr) = queries[qi];
+        let s = prefix(&bit, ys.lower_bound(&yr)) - prefix(&bit, ys.lower_bound(&yl));
+        res[qi] += sign * s;
+    }
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::XorShift64;
+
+    #[test]
+    fn test_rectangle_sum_against_naive() {
+        let mut rng = XorShift64::new(21);
+        let points: Vec<(i64, i64)> = (0..60)
+            .map(|_| (rng.gen_range_i64(-10..10), rng.gen_range_i64(-10..10)))
+            .collect();
+        let mut weights = vec![0i64; points.len()];
+        let mut rs = RectangleSum::new(&points);
+        for _ in 0..500 {
+            if rng.gen_range(0..2) == 0 {
+                let k = rng.gen_range(0..points.len() as u64) as usize;
+                let w = rng.gen_range_i64(-5..10);
+                rs.add(points[k].0, points[k].1, w);
+                weights[k] += w;
+            } else {
+                let (xl, xr) = (rng.gen_range_i64(-12..12), rng.gen_range_i64(-12..12));
+                let (yl, yr) = (rng.gen_range_i64(-12..12), rng.gen_range_i64(-12..12));
+                let expected: i64 = points
+                    .iter()
+                    .zip(&weights)
+                    .filter(|&(&(x, y), _)| xl <= x && x < xr && yl <= y && y < yr)
+                    .map(|(_, &w)| w)
+                    .sum();
+                assert_eq!(rs.sum(xl, xr, yl, yr), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_rectangle_sum_offline_matches_online() {
+        let mut rng = XorShift64::new(23);
+        let points: Vec<(i64, i64, i64)> = (0..80)
+            .map(|_| {
+                (
+                    rng.gen_range_i64(-20..20),
+                    rng.gen_range_i64(-20..20),
+                    rng.gen_range_i64(-5..10),
+                )
+            })
+            .collect();
+        let queries: Vec<(i64, i64, i64, i64)> = (0..200)
+            .map(|_| {
+                (
+                    rng.gen_range_i64(-25..25),
+                    rng.gen_range_i64(-25..25),
+                    rng.gen_range_i64(-25..25),
+                    rng.gen_range_i64(-25..25),
+                )
+            })
+            .collect();
+        let rs = RectangleSum::from_weighted(&points);
+        let expected: Vec<i64> = queries
+            .iter()
+            .map(|&(xl, xr, yl, yr)| rs.sum(xl, xr, yl, yr))
+            .collect();
+        assert_eq!(rectangle_sum_offline(&points, &queries), expected);
+        assert_eq!(rectangle_sum_offline(&[], &[(0, 1, 0, 1)]), vec![0]);
+    }
+
+    #[test]
+    fn test_rectangle_sum_count() {
+        let pts = [(1, 1, 1), (1, 1, 1), (2, 3, 1), (5, 0, 1)];
+        let rs = RectangleSum::from_weighted(&pts);
+        assert_eq!(rs.sum(0, 10, 0, 10), 4);
+        assert_eq!(rs.sum(1, 2, 1, 2), 2);
+        assert_eq!(rs.sum(2, 6, 0, 3), 1);
+        assert_eq!(rs.sum(3, 3, 0, 10), 0);
+    }
+}
@@ -0,0 +1,399 @@
+use std::marker::PhantomData;
+
+/// モノイド（結合的な二項演算と単位元を持つ集合）
+///
+/// `SegmentTree`に載せる演算を表します。`op`は結合法則
+/// `op(op(a, b), c) == op(a, op(b, c))` を満たし、`identity`はその単位元である必要があります。
+pub trait Monoid {
+    /// 要素の型
+    type S: Clone;
+    /// 単位元
+    fn identity() -> Self::S;
+    /// 二項演算
+    fn op(a: &Self::S, b: &Self::S) -> Self::S;
+}
+
+/// 和のモノイド（単位元は0）
+///
+/// # 使用例
+/// ```
+/// # use rust_macro::SegmentTree;
+/// # use rust_macro::segment_tree::{Gcd, Max, Min, Sum};
+/// let v = vec![12i64, 18, 7, 30];
+/// assert_eq!(SegmentTree::<Sum<i64>>::from(v.clone()).prod(0, 3), 37);
+/// assert_eq!(SegmentTree::<Min<i64>>::from(v.clone()).prod(0, 2), 12);
+/// assert_eq!(SegmentTree::<Max<i64>>::from(v.clone()).all_prod(), 30);
+/// assert_eq!(SegmentTree::<Gcd<i64>>::from(v).prod(0, 2), 6);
+/// ```
+pub struct Sum<T>(PhantomData<T>);
+
+/// 最小値のモノイド（単位元は型の最大値）
+pub struct Min<T>(PhantomData<T>);
+
+/// 最大値のモノイド（単位元は型の最小値）
+pub struct Max<T>(PhantomData<T>);
+
+/// 最大公約数のモノイド（単位元は0、結果は常に非負）
+pub struct Gcd<T>(PhantomData<T>);
+
+macro_rules! impl_integer_monoids {
+    ($($t:ty),*) => {$(
+        impl Monoid for Sum<$t> {
+            type S = $t;
+            fn identity() -> $t {
+                0
+            }
+            fn op(a: &$t, b: &$t) -> $t {
+                a + b
+            }
+        }
+
+        impl Monoid for Min<$t> {
+            type S = $t;
+            fn identity() -> $t {
+                <$t>::MAX
+            }
+            fn op(a: &$t, b: &$t) -> $t {
+                *a.min(b)
+            }
+        }
+
+        impl Monoid for Max<$t> {
+            type S = $t;
+            fn identity() -> $t {
+                <$t>::MIN
+            }
+            fn op(a: &$t, b: &$t) -> $t {
+                *a.max(b)
+            }
+        }
+
+        impl Monoid for Gcd<$t> {
+            type S = $t;
+            fn identity() -> $t {
+                0
+            }
+            #[allow(unused_comparisons)]
+            fn op(a: &$t, b: &$t) -> $t {
+                let (mut a, mut b) = (*a, *b);
+                while b != 0 {
+                    (a, b) = (b, a % b);
+                }
+                if a < 0 {
+                    0 - a
+                } else {
+                    a
+                }
+            }
+        }
+    )*};
+}
+impl_integer_monoids!(i32, i64, i128, isize, u32, u64, u128, usize);
+
+/// モノイドを載せたセグメント木
+///
+/// 1点更新と区間の総積（`op`による畳み込み）を処理します。
+/// `max_right`・`min_left`で、区間の総積が条件を満たす境界を二分探索できます。
+///
+/// # 計算量
+/// - 構築: O(n)
+/// - 1点更新・区間の総積・二分探索: O(log n)
+///
+/// # 使用例
+/// ```
+/// # use rust_macro::{Monoid, SegmentTree};
+/// struct MinMonoid;
+/// impl Monoid for MinMonoid {
+///     type S = i64;
+///     fn identity() -> i64 { i64::MAX }
+///     fn op(a: &i64, b: &i64) -> i64 { *a.min(b) }
+/// }
+///
+/// let mut seg = SegmentTree::<MinMonoid>::from(vec![5, 3, 7, 9, 1]);
+/// assert_eq!(seg.prod(0, 3), 3);
+/// seg.set(1, 10);
+/// assert_eq!(seg.prod(0, 3), 5);
+/// assert_eq!(seg.all_prod(), 1);
+/// // 位置1から、最小値が7以上である限り右に伸ばす
+/// assert_eq!(seg.max_right(1, |&x| x >= 7), 4);
+/// ```
+pub struct SegmentTree<M: Monoid> {
+    n: usize,
+    size: usize,
+    log: u32,
+    d: Vec<M::S>,
+}
+
+impl<M: Monoid> Clone for SegmentTree<M> {
+    fn clone(&self) -> Self {
+        SegmentTree {
+            n: self.n,
+            size: self.size,
+            log: self.log,
+            d: self.d.clone(),
+        }
+    }
+}
+
+impl<M: Monoid> SegmentTree<M> {
+    /// 長さnの、すべての要素が単位元のセグメント木を作成
+    pub fn new(n: usize) -> Self {
+        Self::from(vec![M::identity(); n])
+    }
+
+    /// 配列の長さ
+    pub fn len(&self) -> usize {
+        self.n
+    }
+
+    /// 配列の長さが0かどうか
+    pub fn is_empty(&self) -> bool {
+        self.n == 0
+    }
+
+    /// p番目の要素をxにする
+    pub fn set(&mut self, p: usize, x: M::S) {
+        assert!(p < self.n);
+        let p = p + self.size;
+        self.d[p] = x;
+        for i in 1..=self.log {
+            self.update(p >> i);
+        }
+    }
+
+    /// p番目の要素
+    pub fn get(&self, p: usize) -> M::S {
+        assert!(p < self.n);
+        self.d[p + self.size].clone()
+    }
+
+    /// 区間[l, r)の総積 op(a[l], ..., a[r-1])（空区間なら単位元）
+    pub fn prod(&self, l: usize, r: usize) -> M::S {
+        assert!(l <= r && r <= self.n);
+        let mut sml = M::identity();
+        let mut smr = M::identity();
+        let (mut l, mut r) = (l + self.size, r + self.size);
+        while l < r {
+            if l & 1 == 1 {
+                sml = M::op(&sml, &self.d[l]);
+                l += 1;
+            }
+            if r & 1 == 1 {
+                r -= 1;
+                smr = M::op(&self.d[r], &smr);
+            }
+            l >>= 1;
+            r >>= 1;
+        }
+        M::op(&sml, &smr)
+    }
+
+    /// 全体の総積
+    pub fn all_prod(&self) -> M::S {
+        self.d[1].clone()
+    }
+
+    /// `f(prod(l, r))`が真となる最大のrを返す
+    ///
+    /// fは単調（`f(prod(l, r))`が真ならより短い区間でも真）で、`f(identity)`が真である必要があります。
+    pub fn max_right<F>(&self, l: usize, f: F) -> usize
+    where
+        F: Fn(&M::S) -> bool,
+    {
+        assert!(l <= self.n);
+        assert!(f(&M::identity()));
+        if l == self.n {
+            return self.n;
+        }
+        let mut l = l + self.size;
+        let mut sm = M::identity();
+        loop {
+            while l % 2 == 0 {
+                l >>= 1;
+            }
+            if !f(&M::op(&sm, &self.d[l])) {
+                while l < self.size {
+                    l *= 2;
+                    let res = M::op(&sm, &self.d[l]);
+                    if f(&res) {
+                        sm = res;
+                        l += 1;
+                    }
+                }
+                return l - self.size;
+            }
+            sm = M::op(&sm, &self.d[l]);
+            l += 1;
+            if l & l.wrapping_neg() == l {
+                return self.n;
+            }
+        }
+    }
+
+    /// `f(prod(l, r))`が真となる最小のlを返す
+    ///
+    /// fは単調で、`f(identity)`が真である必要があります。
+    pub fn min_left<F>(&self, r: usize, f: F) -> usize
+    where
+        F: Fn(&M::S) -> bool,
+    {
+        assert!(r <= self.n);
+        assert!(f(&M::identity()));
+        if r == 0 {
+            return 0;
+        }
+        let mut r = r + self.size;
+        let mut sm = M::identity();
+        loop {
+            r -= 1;
+            while r > 1 && r % 2 == 1 {
+                r >>= 1;
+            }
+            if !f(&M::op(&self.d[r], &sm)) {
+                while r < self.size {
+                    r = 2 * r + 1;
+                    let res = M::op(&self.d[r], &sm);
+                    if f(&res) {
+                        sm = res;
+                        r -= 1;
+                    }
+                }
+                return r + 1 - self.size;
+            }
+            sm = M::op(&self.d[r], &sm);
+            if r & r.wrapping_neg() == r {
+                return 0;
+            }
+        }
+    }
+
+    fn update(&mut self, k: usize) {
+        self.d[k] = M::op(&self.d[2 * k], &self.d[2 * k + 1]);
+    }
+}
+
+impl<M: Monoid> From<Vec<M::S>> for SegmentTree<M> {
+    /// 配列から構築する
+    fn from(v: Vec<M::S>) -> Self {
+        let n = v.len();
+        let size = n.next_power_of_two();
+        let log = size.trailing_zeros();
+        let mut d = vec![M::identity(); 2 * size];
+        for (i, x) in v.into_iter().enumerate() {
+            d[size + i] = x;
+        }
+        let mut seg = SegmentTree { n, size, log, d };
+        for i in (1..size).rev() {
+            seg.update(i);
+        }
+        seg
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::XorShift64;
+
+    struct Sum;
+    impl Monoid for Sum {
+        type S = i64;
+        fn identity() -> i64 {
+            0
+        }
+        fn op(a: &i64, b: &i64) -> i64 {
+            a + b
+        }
+    }
+
+    /// 非可換なモノイド（文字列の連結）
+    struct Concat;
+    impl Monoid for Concat {
+        type S = String;
+        fn identity() -> String {
+            String::new()
+        }
+        fn op(a: &String, b: &String) -> String {
+            format!("{}{}", a, b)
+        }
+    }
+
+    #[test]
+    fn test_segment_tree_against_naive() {
+        let mut rng = XorShift64::new(3);
+        for n in [0usize, 1, 2, 5, 8, 13] {
+            let mut naive: Vec<i64> = (0..n).map(|_| rng.gen_range_i64(0..10)).collect();
+            let mut seg = SegmentTree::<Sum>::from(naive.clone());
+            for _ in 0..50 {
+                if n > 0 {
+                    let p = rng.gen_range(0..n as u64) as usize;
+                    let x = rng.gen_range_i64(0..10);
+                    seg.set(p, x);
+                    naive[p] = x;
+                    assert_eq!(seg.get(p), x);
+                }
+                for l in 0..=n {
+                    for r in l..=n {
+                        assert_eq!(seg.prod(l, r), naive[l..r].iter().sum::<i64>());
+                    }
+                    let lim = rng.gen_range_i64(0..30);
+                    let expected = (l..=n)
+                        .rev()
+                        .find(|&r| naive[l..r].iter().sum::<i64>() <= lim)
+                        .unwrap();
+                    assert_eq!(seg.max_right(l, |&s| s <= lim), expected);
+                    let r = l;
+                    let expected = (0..=r)
+                        .find(|&l| naive[l..r].iter().sum::<i64>() <= lim)
+                        .unwrap();
+                    assert_eq!(seg.min_left(r, |&s| s <= lim), expected);
+                }
+                assert_eq!(seg.all_prod(), naive.iter().sum::<i64>());
+            }
+        }
+    }
+
+    #[test]
+    fn test_segment_tree_non_commutative() {
+        let v: Vec<String> = "abcdefg".chars().map(|c| c.to_string()).collect();
+        let mut seg = SegmentTree::<Concat>::from(v);
+        assert_eq!(seg.prod(1, 5), "bcde");
+        seg.set(3, "X".to_string());
+        assert_eq!(seg.all_prod(), "abcXefg");
+        assert_eq!(seg.max_right(2, |s| s.len() <= 3), 5);
+        assert_eq!(seg.min_left(7, |s| !s.contains('X')), 4);
+        let empty = SegmentTree::<Concat>::new(3);
+        assert_eq!(empty.prod(0, 3), "");
+        assert_eq!(empty.len(), 3);
+    }
+
+    #[test]
+    fn test_prebuilt_monoids_against_naive() {
+        fn gcd(a: i64, b: i64) -> i64 {
+            if b == 0 {
+                a.abs()
+            } else {
+                gcd(b, a % b)
+            }
+        }
+        let mut rng = XorShift64::new(4271);
+        for n in [1usize, 2, 7, 16] {
+            let v: Vec<i64> = (0..n).map(|_| rng.gen_range_i64(-60..60)).collect();
+            let min = SegmentTree::<Min<i64>>::from(v.clone());
+            let max = SegmentTree::<Max<i64>>::from(v.clone());
+            let g = SegmentTree::<Gcd<i64>>::from(v.clone());
+            let s = SegmentTree::<super::Sum<i64>>::from(v.clone());
+            for l in 0..=n {
+                for r in l..=n {
+                    let w = &v[l..r];
+                    assert_eq!(min.prod(l, r), w.iter().copied().min().unwrap_or(i64::MAX));
+                    assert_eq!(max.prod(l, r), w.iter().copied().max().unwrap_or(i64::MIN));
+                    assert_eq!(g.prod(l, r), w.iter().fold(0, |a, &b| gcd(a, b)));
+                    assert_eq!(s.prod(l, r), w.iter().sum::<i64>());
+                }
+            }
+        }
+        let u = SegmentTree::<Gcd<u64>>::from(vec![0, 4, 10]);
+        assert_eq!(u.all_prod(), 2);
+    }
+}
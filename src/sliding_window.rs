@@ -0,0 +1,177 @@
+use std::collections::VecDeque;
+
+/// スライド最小値・最大値（単調デック）
+///
+/// 末尾への追加と先頭からの削除ができるキューで、含まれる要素の最小値・最大値を
+/// 常に取得できます。窓の長さが変化する尺取り法などに使えます。
+///
+/// # 計算量
+/// - 追加・削除: ならしO(1)
+/// - 最小値・最大値: O(1)
+///
+/// # 使用例
+/// ```
+/// # use rust_macro::SlidingWindowMinMax;
+/// let mut w = SlidingWindowMinMax::new();
+/// w.push(3);
+/// w.push(1);
+/// w.push(4);
+/// assert_eq!((w.min(), w.max()), (Some(&1), Some(&4)));
+/// assert_eq!(w.pop(), Some(3));
+/// assert_eq!(w.pop(), Some(1));
+/// assert_eq!(w.min(), Some(&4));
+/// ```
+#[derive(Debug, Clone)]
+pub struct SlidingWindowMinMax<T> {
+    items: VecDeque<T>,
+    /// 先頭の要素の通し番号
+    head: usize,
+    /// (通し番号, 値)が値の昇順に並ぶ
+    mins: VecDeque<(usize, T)>,
+    /// (通し番号, 値)が値の降順に並ぶ
+    maxs: VecDeque<(usize, T)>,
+}
+
+impl<T: Ord + Clone> Default for SlidingWindowMinMax<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Ord + Clone> SlidingWindowMinMax<T> {
+    /// 空の窓を作成
+    pub fn new() -> Self {
+        SlidingWindowMinMax {
+            items: VecDeque::new(),
+            head: 0,
+            mins: VecDeque::new(),
+            maxs: VecDeque::new(),
+        }
+    }
+
+    /// 窓に含まれる要素数
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// 窓が空かどうか
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// 末尾にxを追加する
+    pub fn push(&mut self, x: T) {
+        let id = self.head + self.items.len();
+        while self.mins.back().is_some_and(|(_, v)| *v >= x) {
+            self.mins.pop_back();
+        }
+        self.mins.push_back((id, x.clone()));
+        while self.maxs.back().is_some_and(|(_, v)| *v <= x) {
+            self.maxs.pop_back();
+        }
+        self.maxs.push_back((id, x.clone()));
+        self.items.push_back(x);
+    }
+
+    /// 先頭の要素を削除して返す
+    pub fn pop(&mut self) -> Option<T> {
+        let x = self.items.pop_front()?;
+        if self.mins.front().is_some_and(|&(i, _)| i == self.head) {
+            self.mins.pop_front();
+        }
+        if self.maxs.front().is_some_and(|&(i, _)| i == self.head) {
+            self.maxs.pop_front();
+        }
+        self.head += 1;
+        Some(x)
+    }
+
+    /// 窓に含まれる要素の最小値
+    pub fn min(&self) -> Option<&T> {
+        self.mins.front().map(|(_, v)| v)
+    }
+
+    /// 窓に含まれる要素の最大値
+    pub fn max(&self) -> Option<&T> {
+        self.maxs.front().map(|(_, v)| v)
+    }
+}
+
+/// 長さkのすべての窓について(最小値, 最大値)を先頭から順に列挙する
+///
+/// 要素数がn ≥ kなら、n - k + 1個の値を返します。
+///
+/// # 計算量
+/// O(n)
+///
+/// # 使用例
+/// ```
+/// # use rust_macro::sliding_window_min_max;
+/// let a = [1, 3, -1, -3, 5, 3];
+/// let mins: Vec<_> = sliding_window_min_max(a, 3).map(|(mn, _)| mn).collect();
+/// assert_eq!(mins, vec![-1, -3, -3, -3]);
+/// let maxs: Vec<_> = sliding_window_min_max(a, 3).map(|(_, mx)| mx).collect();
+/// assert_eq!(maxs, vec![3, 3, 5, 5]);
+/// ```
+///
+/// # パニック
+/// kが0の場合panicします
+pub fn sliding_window_min_max<I>(iter: I, k: usize) -> impl Iterator<Item = (I::Item, I::Item)>
+where
+    I: IntoIterator,
+    I::Item: Ord + Clone,
+{
+    assert!(k > 0, "window size must be positive");
+    let mut iter = iter.into_iter();
+    let mut w = SlidingWindowMinMax::new();
+    std::iter::from_fn(move || {
+        if w.len() == k {
+            w.pop();
+        }
+        while w.len() < k {
+            w.push(iter.next()?);
+        }
+        Some((w.min()?.clone(), w.max()?.clone()))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::XorShift64;
+
+    #[test]
+    fn test_sliding_window_against_naive() {
+        let mut rng = XorShift64::new(5);
+        let mut w = SlidingWindowMinMax::new();
+        let mut naive = VecDeque::new();
+        for _ in 0..1000 {
+            if rng.gen_range(0..3) == 0 {
+                assert_eq!(w.pop(), naive.pop_front());
+            } else {
+                let x = rng.gen_range_i64(0..20);
+                w.push(x);
+                naive.push_back(x);
+            }
+            assert_eq!(w.len(), naive.len());
+            assert_eq!(w.min(), naive.iter().min());
+            assert_eq!(w.max(), naive.iter().max());
+        }
+    }
+
+    #[test]
+    fn test_sliding_window_min_max_fixed() {
+        let a: Vec<i64> = (0..30).map(|i| (i * 7919) % 13).collect();
+        for k in 1..=a.len() {
+            let expected: Vec<_> = a
+                .windows(k)
+                .map(|s| (*s.iter().min().unwrap(), *s.iter().max().unwrap()))
+                .collect();
+            assert_eq!(
+                sliding_window_min_max(a.iter().copied(), k).collect::<Vec<_>>(),
+                expected
+            );
+        }
+        assert_eq!(sliding_window_min_max([1, 2], 3).count(), 0);
+    }
+}
@@ -0,0 +1,128 @@
+/// 静的な配列に対する冪等な区間演算を前計算するスパーステーブル
+///
+/// `f`は結合的かつ冪等（`f(a, a) == a`）である必要があります。min・max・gcd・bitwise and/orなどが該当します。
+/// 重なりのある2区間の結果を合成するため、和のような冪等でない演算には使えません。
+///
+/// # 計算量
+/// - 構築: O(n log n)
+/// - 区間クエリ: O(1)
+///
+/// # 使用例
+/// ```
+/// # use rust_macro::SparseTable;
+/// let st = SparseTable::new(vec![5, 3, 7, 9, 1, 4], |a: &i32, b: &i32| *a.min(b));
+/// assert_eq!(st.query(0, 3), 3);
+/// assert_eq!(st.query(2, 4), 7);
+/// assert_eq!(st.query(0, 6), 1);
+/// ```
+#[derive(Debug, Clone)]
+pub struct SparseTable<T, F> {
+    /// table[k][i]: 区間[i, i + 2^k)の演算結果
+    table: Vec<Vec<T>>,
+    f: F,
+}
+
+impl<T: Clone, F: Fn(&T, &T) -> T> SparseTable<T, F> {
+    /// 配列vと演算fから構築する
+    pub fn new(v: Vec<T>, f: F) -> Self {
+        let n = v.len();
+        let mut table = vec![v];
+        let mut k = 1;
+        while 2 * k <= n {
+            let prev = table.last().unwrap();
+            let next = (0..=n - 2 * k).map(|i| f(&prev[i], &prev[i + k])).collect();
+            table.push(next);
+            k *= 2;
+        }
+        SparseTable { table, f }
+    }
+
+    /// 配列の長さ
+    pub fn len(&self) -> usize {
+        self.table[0].len()
+    }
+
+    /// 配列の長さが0かどうか
+    pub fn is_empty(&self) -> bool {
+        self.table[0].is_empty()
+    }
+
+    /// 区間[l, r)の演算結果
+    ///
+    /// # パニック
+    /// 区間が空（l >= r）または範囲外の場合panicします
+    pub fn query(&self, l: usize, r: usize) -> T {
+        assert!(l < r && r <= self.len(), "invalid range");
+        let k = (usize::BITS - 1 - (r - l).leading_zeros()) as usize;
+        (self.f)(&self.table[k][l], &self.table[k][r - (1 << k)])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::XorShift64;
+
+    fn gcd(a: u64, b: u64) -> u64 {
+        if b == 0 {
+            a
+        } else {
+            gcd(b, a % b)
+        }
+    }
+
+    #[test]
+    fn test_sparse_table_against_naive() {
+        let mut rng = XorShift64::new(4274);
+        for n in [1usize, 2, 3, 8, 13, 32] {
+            let v: Vec<u64> = (0..n).map(|_| rng.gen_range(0..50) * 6).collect();
+            let min = SparseTable::new(v.clone(), |a: &u64, b: &u64| *a.min(b));
+            let max = SparseTable::new(v.clone(), |a: &u64, b: &u64| *a.max(b));
+            let g = SparseTable::new(v.clone(), |a: &u64, b: &u64| gcd(*a, *b));
+            assert_eq!(min.len(), n);
+            for l in 0..n {
+                for r in l + 1..=n {
+                    let w = &v[l..r];
+                    assert_eq!(min.query(l, r), *w.iter().min().unwrap());
+                    assert_eq!(max.query(l, r), *w.iter().max().unwrap());
+                    assert_eq!(g.query(l, r), w.iter().fold(0, |a, &b| gcd(a, b)));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_sparse_table_with_euler_tour_lca() {
+        // 0 - 1 - 3
+        //  \   \
+        //   2   4
+        let children = [vec![1, 2], vec![3, 4], vec![], vec![], vec![]];
+        let mut tour = vec![];
+        let mut first = [0; 5];
+        fn dfs(
+            u: usize,
+            d: usize,
+            ch: &[Vec<usize>],
+            tour: &mut Vec<(usize, usize)>,
+            first: &mut [usize],
+        ) {
+            first[u] = tour.len();
+            tour.push((d, u));
+            for &v in &ch[u] {
+                dfs(v, d + 1, ch, tour, first);
+                tour.push((d, u));
+            }
+        }
+        dfs(0, 0, &children, &mut tour, &mut first);
+        // (深さ, 頂点)の最小値がLCA
+        let st = SparseTable::new(tour, |a: &(usize, usize), b: &(usize, usize)| *a.min(b));
+        let lca = |u: usize, v: usize| {
+            let (a, b) = (first[u].min(first[v]), first[u].max(first[v]));
+            st.query(a, b + 1).1
+        };
+        assert_eq!(lca(3, 4), 1);
+        assert_eq!(lca(3, 2), 0);
+        assert_eq!(lca(4, 1), 1);
+        assert_eq!(lca(2, 2), 2);
+    }
+}
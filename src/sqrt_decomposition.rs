@@ -0,0 +1,245 @@
+/// 平方分割で扱う処理のルール
+///
+/// 配列を長さ約√nのブロックに分け、各ブロックの要約（遅延している更新も含む）を
+/// ユーザーが定義します。区間に完全に含まれるブロックは要約に対して、
+/// 一部だけ含まれるブロックは要素に対して直接処理したうえで要約を作り直します。
+pub trait SqrtRules {
+    /// 要素
+    type T: Clone;
+    /// ブロックの要約（遅延している更新を含む）
+    type Block;
+    /// 更新操作
+    type U;
+    /// クエリのパラメータ
+    type P;
+    /// クエリの結果
+    type Q;
+
+    /// ブロック内の要素から要約を作る（遅延している更新はない状態）
+    fn build(elems: &[Self::T]) -> Self::Block;
+    /// 遅延している更新をブロック内の要素に反映する
+    ///
+    /// この後`build`で要約が作り直されます。
+    fn push(block: &Self::Block, elems: &mut [Self::T]);
+    /// ブロック全体に更新を適用する
+    fn update_block(block: &mut Self::Block, u: &Self::U);
+    /// 1要素に更新を適用する
+    fn update_elem(elem: &mut Self::T, u: &Self::U);
+    /// ブロック全体に対するクエリの結果
+    fn query_block(block: &Self::Block, p: &Self::P) -> Self::Q;
+    /// 1要素に対するクエリの結果
+    fn query_elem(elem: &Self::T, p: &Self::P) -> Self::Q;
+    /// クエリの結果の単位元
+    fn identity() -> Self::Q;
+    /// クエリの結果を（左から順に）併合する
+    fn merge(a: Self::Q, b: Self::Q) -> Self::Q;
+}
+
+/// 平方分割による区間更新・区間クエリ
+///
+/// 遅延セグメント木のように更新の合成ができない場合でも、ブロックごとの要約の
+/// 作り方さえ定義すれば区間更新と区間クエリを処理できます。
+///
+/// # 計算量
+/// ブロックの要約の構築をO(B)、ブロックへの更新・クエリをO(1)として
+/// - 区間更新・区間クエリ: O(√n)
+///
+/// # 使用例
+/// ```
+/// # use rust_macro::{SqrtDecomposition, SqrtRules};
+/// // 区間加算・区間和
+/// struct AddSum;
+/// impl SqrtRules for AddSum {
+///     type T = i64;
+///     type Block = (i64, i64, usize); // (和, 遅延している加算, 要素数)
+///     type U = i64;
+///     type P = ();
+///     type Q = i64;
+///     fn build(e: &[i64]) -> Self::Block { (e.iter().sum(), 0, e.len()) }
+///     fn push(b: &Self::Block, e: &mut [i64]) { e.iter_mut().for_each(|x| *x += b.1) }
+///     fn update_block(b: &mut Self::Block, u: &i64) { b.0 += u * b.2 as i64; b.1 += u; }
+///     fn update_elem(e: &mut i64, u: &i64) { *e += u }
+///     fn query_block(b: &Self::Block, _: &()) -> i64 { b.0 }
+///     fn query_elem(e: &i64, _: &()) -> i64 { *e }
+///     fn identity() -> i64 { 0 }
+///     fn merge(a: i64, b: i64) -> i64 { a + b }
+/// }
+///
+/// let mut sd = SqrtDecomposition::<AddSum>::new(vec![1, 2, 3, 4, 5]);
+/// sd.update(1, 4, &10);
+/// assert_eq!(sd.query(0, 5, &()), 45);
+/// assert_eq!(sd.query(3, 5, &()), 19);
+/// ```
+pub struct SqrtDecomposition<R: SqrtRules> {
+    elems: Vec<R::T>,
+    blocks: Vec<R::Block>,
+    block_size: usize,
+}
+
+impl<R: SqrtRules> SqrtDecomposition<R> {
+    /// 配列から構築する（ブロックの長さは√n）
+    pub fn new(elems: Vec<R::T>) -> Self {
+        let mut b = 1;
+        while b * b < elems.len() {
+            b += 1;
+        }
+        Self::with_block_size(elems, b)
+    }
+
+    /// ブロックの長さを指定して配列から構築する
+    pub fn with_block_size(elems: Vec<R::T>, block_size: usize) -> Self {
+        assert!(block_size > 0);
+        let blocks = elems.chunks(block_size).map(R::build).collect();
+        SqrtDecomposition {
+            elems,
+            blocks,
+            block_size,
+        }
+    }
+
+    /// 配列の長さ
+    pub fn len(&self) -> usize {
+        self.elems.len()
+    }
+
+    /// 配列の長さが0かどうか
+    pub fn is_empty(&self) -> bool {
+        self.elems.is_empty()
+    }
+
+    /// ブロックkの要素の範囲
+    fn block_range(&self, k: usize) -> (usize, usize) {
+        let l = k * self.block_size;
+        (l, (l + self.block_size).min(self.elems.len()))
+    }
+
+    /// ブロックkの遅延している更新を要素に反映する
+    fn push(&mut self, k: usize) {
+        let (l, r) = self.block_range(k);
+        R::push(&self.blocks[k], &mut self.elems[l..r]);
+        self.blocks[k] = R::build(&self.elems[l..r]);
+    }
+
+    /// 区間[l, r)に更新uを適用する
+    pub fn update(&mut self, l: usize, r: usize, u: &R::U) {
+        assert!(l <= r && r <= self.elems.len());
+        if l == r {
+            return;
+        }
+        let (bl, br) = (l / self.block_size, (r - 1) / self.block_size);
+        for k in bl..=br {
+            let (s, t) = self.block_range(k);
+            if l <= s && t <= r {
+                R::update_block(&mut self.blocks[k], u);
+            } else {
+                self.push(k);
+                for e in &mut self.elems[l.max(s)..r.min(t)] {
+                    R::update_elem(e, u);
+                }
+                self.blocks[k] = R::build(&self.elems[s..t]);
+            }
+        }
+    }
+
+    /// 区間[l, r)に対するパラメータpのクエリの結果
+    pub fn query(&mut self, l: usize, r: usize, p: &R::P) -> R::Q {
+        assert!(l <= r && r <= self.elems.len());
+        let mut res = R::identity();
+        if l == r {
+            return res;
+        }
+        let (bl, br) = (l / self.block_size, (r - 1) / self.block_size);
+        for k in bl..=br {
+            let (s, t) = self.block_range(k);
+            if l <= s && t <= r {
+                res = R::merge(res, R::query_block(&self.blocks[k], p));
+            } else {
+                self.push(k);
+                for e in &self.elems[l.max(s)..r.min(t)] {
+                    res = R::merge(res, R::query_elem(e, p));
+                }
+            }
+        }
+        res
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::XorShift64;
+
+    /// 区間加算・区間内のx以上の要素の個数（ブロックはソート済みの要素と遅延加算を持つ）
+    struct AddCountGe;
+    impl SqrtRules for AddCountGe {
+        type T = i64;
+        type Block = (Vec<i64>, i64);
+        type U = i64;
+        type P = i64;
+        type Q = usize;
+        fn build(elems: &[i64]) -> Self::Block {
+            let mut v = elems.to_vec();
+            v.sort();
+            (v, 0)
+        }
+        fn push(block: &Self::Block, elems: &mut [i64]) {
+            for e in elems {
+                *e += block.1;
+            }
+        }
+        fn update_block(block: &mut Self::Block, u: &i64) {
+            block.1 += u;
+        }
+        fn update_elem(elem: &mut i64, u: &i64) {
+            *elem += u;
+        }
+        fn query_block(block: &Self::Block, x: &i64) -> usize {
+            let t = x - block.1;
+            block.0.len() - block.0.partition_point(|&v| v < t)
+        }
+        fn query_elem(elem: &i64, x: &i64) -> usize {
+            usize::from(elem >= x)
+        }
+        fn identity() -> usize {
+            0
+        }
+        fn merge(a: usize, b: usize) -> usize {
+            a + b
+        }
+    }
+
+    #[test]
+    fn test_sqrt_decomposition_against_naive() {
+        let mut rng = XorShift64::new(8);
+        for n in [1usize, 2, 7, 30] {
+            let mut naive: Vec<i64> = (0..n).map(|_| rng.gen_range_i64(-5..5)).collect();
+            let mut sd = SqrtDecomposition::<AddCountGe>::new(naive.clone());
+            assert_eq!(sd.len(), n);
+            for _ in 0..300 {
+                let l = rng.gen_range(0..n as u64 + 1) as usize;
+                let r = rng.gen_range(l as u64..n as u64 + 1) as usize;
+                let x = rng.gen_range_i64(-6..6);
+                if rng.gen_range(0..2) == 0 {
+                    sd.update(l, r, &x);
+                    for v in &mut naive[l..r] {
+                        *v += x;
+                    }
+                } else {
+                    let expected = naive[l..r].iter().filter(|&&v| v >= x).count();
+                    assert_eq!(sd.query(l, r, &x), expected);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_sqrt_decomposition_block_size() {
+        let mut sd = SqrtDecomposition::<AddCountGe>::with_block_size(vec![0; 10], 3);
+        sd.update(2, 9, &5);
+        assert_eq!(sd.query(0, 10, &5), 7);
+        sd.update(0, 10, &-5);
+        assert_eq!(sd.query(0, 10, &0), 7);
+        assert_eq!(sd.query(0, 10, &-5), 10);
+        assert_eq!(sd.query(4, 4, &0), 0);
+    }
+}
@@ -0,0 +1,86 @@
+use super::SuffixArray;
+
+/// aとbの最長共通部分文字列を求める
+///
+/// aとbを区切り文字を挟んで連結した列の接尾辞配列で、辞書順で隣り合う
+/// aの接尾辞とbの接尾辞の最長共通接頭辞の最大値を求めます。
+///
+/// # 戻り値
+/// (長さ, aでの開始位置, bでの開始位置)。共通部分文字列がなければ(0, 0, 0)
+///
+/// # 計算量
+/// O((n + m) log (n + m))
+///
+/// # 使用例
+/// ```
+/// # use rust_macro::strings::longest_common_substring;
+/// let (a, b) = (b"xabcdey", b"zzbcdabc");
+/// let (len, i, j) = longest_common_substring(a, b);
+/// assert_eq!(len, 3);
+/// assert_eq!(&a[i..i + len], &b[j..j + len]);
+/// ```
+pub fn longest_common_substring<T: Ord>(a: &[T], b: &[T]) -> (usize, usize, usize) {
+    let n = a.len();
+    // Noneは区切り文字で、どの文字とも一致しない
+    let s: Vec<Option<&T>> = a
+        .iter()
+        .map(Some)
+        .chain(std::iter::once(None))
+        .chain(b.iter().map(Some))
+        .collect();
+    let sa = SuffixArray::new(&s);
+    let mut best = (0, 0, 0);
+    for (k, &h) in sa.lcp_array().iter().enumerate() {
+        let (x, y) = (sa.sa()[k], sa.sa()[k + 1]);
+        let (i, j) = match (x < n, y < n) {
+            (true, false) => (x, y),
+            (false, true) => (y, x),
+            _ => continue,
+        };
+        if h > best.0 {
+            best = (h, i, j - n - 1);
+        }
+    }
+    best
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::XorShift64;
+
+    #[test]
+    fn test_longest_common_substring_against_naive() {
+        let mut rng = XorShift64::new(71);
+        for _ in 0..200 {
+            let gen = |rng: &mut XorShift64| -> Vec<u8> {
+                let n = rng.gen_range(0..12) as usize;
+                (0..n).map(|_| b'a' + rng.gen_range(0..3) as u8).collect()
+            };
+            let (a, b) = (gen(&mut rng), gen(&mut rng));
+            let mut expected = 0;
+            for i in 0..a.len() {
+                for j in 0..b.len() {
+                    let l = a[i..]
+                        .iter()
+                        .zip(&b[j..])
+                        .take_while(|(x, y)| x == y)
+                        .count();
+                    expected = expected.max(l);
+                }
+            }
+            let (len, i, j) = longest_common_substring(&a, &b);
+            assert_eq!(len, expected);
+            assert_eq!(a[i..i + len], b[j..j + len]);
+        }
+    }
+
+    #[test]
+    fn test_longest_common_substring_disjoint() {
+        assert_eq!(longest_common_substring(b"aaa", b"bbb"), (0, 0, 0));
+        assert_eq!(longest_common_substring::<u8>(&[], b"abc"), (0, 0, 0));
+        let a: Vec<char> = "東京都".chars().collect();
+        let b: Vec<char> = "京都府".chars().collect();
+        assert_eq!(longest_common_substring(&a, &b), (2, 1, 0));
+    }
+}
@@ -0,0 +1,15 @@
+pub mod common_substring;
+pub mod palindrome;
+pub mod pattern;
+pub mod rolling_hash;
+pub mod subsequence;
+pub mod suffix_array;
+pub mod trie;
+
+pub use common_substring::longest_common_substring;
+pub use palindrome::{longest_palindromic_substring, manacher};
+pub use pattern::{find_all_occurrences, kmp_failure, z_algorithm};
+pub use rolling_hash::{RollingHash, StrHash};
+pub use subsequence::SubsequenceAutomaton;
+pub use suffix_array::SuffixArray;
+pub use trie::{BinaryTrie, Trie};
@@ -0,0 +1,111 @@
+/// Manacher法で、すべての中心についての最長回文の長さを求める
+///
+/// 長さnの列に対し長さ`2n - 1`の配列を返します（nが0なら空）。
+/// `res[2i]`はs[i]を中心とする最長回文（奇数長）の長さ、
+/// `res[2i + 1]`はs[i]とs[i + 1]の間を中心とする最長回文（偶数長）の長さです。
+/// s[l..r]が回文であることは`res[l + r - 1] >= r - l`と同値です。
+///
+/// # 計算量
+/// O(n)
+///
+/// # 使用例
+/// ```
+/// # use rust_macro::strings::manacher;
+/// assert_eq!(manacher(b"abaab"), vec![1, 0, 3, 0, 1, 4, 1, 0, 1]);
+/// ```
+pub fn manacher<T: PartialEq>(s: &[T]) -> Vec<usize> {
+    let n = s.len();
+    if n == 0 {
+        return vec![];
+    }
+    let m = 2 * n - 1;
+    // 文字の間に仮想的な区切りを入れた列tで、中心kからの距離dの2文字が等しいか
+    let matches = |k: usize, d: usize| (k - d) % 2 == 1 || s[(k - d) / 2] == s[(k + d) / 2];
+    // rad[k]: t[k - rad + 1..k + rad]が回文となる最大のrad
+    let mut rad = vec![0; m];
+    let (mut k, mut r) = (0, 0);
+    while k < m {
+        while k >= r && k + r < m && matches(k, r) {
+            r += 1;
+        }
+        rad[k] = r;
+        let mut d = 1;
+        while d <= k && d < r && d + rad[k - d] < r {
+            rad[k + d] = rad[k - d];
+            d += 1;
+        }
+        k += d;
+        r -= d;
+    }
+    // 区間[k - rad + 1, k + rad - 1]に含まれる偶数の位置（元の文字）の個数が回文の長さ
+    let evens = |a: usize, b: usize| b / 2 + 1 - (a + 1) / 2;
+    (0..m)
+        .map(|k| evens(k + 1 - rad[k], k + rad[k] - 1))
+        .collect()
+}
+
+/// 最長の回文である部分列（連続）の区間[l, r)を返す
+///
+/// 複数ある場合は最も左のものを返します。空の列に対しては(0, 0)を返します。
+///
+/// # 計算量
+/// O(n)
+///
+/// # 使用例
+/// ```
+/// # use rust_macro::strings::longest_palindromic_substring;
+/// let s = b"xabacabay";
+/// assert_eq!(longest_palindromic_substring(s), (1, 8));
+/// assert_eq!(longest_palindromic_substring(b"ab"), (0, 1));
+/// ```
+pub fn longest_palindromic_substring<T: PartialEq>(s: &[T]) -> (usize, usize) {
+    let mut best = (0, 0);
+    for (k, len) in manacher(s).into_iter().enumerate() {
+        // 中心が k / 2 の位置なので、左端は (k + 1 - len) / 2
+        let l = (k + 1 - len) / 2;
+        if len > best.1 - best.0 || (len == best.1 - best.0 && l < best.0) {
+            best = (l, l + len);
+        }
+    }
+    best
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::is_palindrome_slice;
+    use crate::XorShift64;
+
+    #[test]
+    fn test_manacher_against_naive() {
+        let mut rng = XorShift64::new(4289);
+        for _ in 0..300 {
+            let n = rng.gen_range(0..20) as usize;
+            let s: Vec<u8> = (0..n).map(|_| rng.gen_range(0..2) as u8).collect();
+            let rad = manacher(&s);
+            assert_eq!(rad.len(), (2 * n).saturating_sub(1));
+            for l in 0..n {
+                for r in l + 1..=n {
+                    assert_eq!(rad[l + r - 1] >= r - l, is_palindrome_slice(&s[l..r]));
+                }
+            }
+            let (l, r) = longest_palindromic_substring(&s);
+            assert!(is_palindrome_slice(&s[l..r]));
+            let best = (0..=n)
+                .flat_map(|l| (l..=n).map(move |r| (l, r)))
+                .filter(|&(l, r)| is_palindrome_slice(&s[l..r]))
+                .map(|(l, r)| r - l)
+                .max()
+                .unwrap();
+            assert_eq!(r - l, best);
+        }
+    }
+
+    #[test]
+    fn test_longest_palindromic_substring_chars() {
+        let s: Vec<char> = "たけやぶやけた!".chars().collect();
+        assert_eq!(longest_palindromic_substring(&s), (0, 7));
+        assert_eq!(longest_palindromic_substring::<char>(&[]), (0, 0));
+        assert_eq!(longest_palindromic_substring(b"abcbbcbd"), (1, 7));
+    }
+}
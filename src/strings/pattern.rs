@@ -0,0 +1,142 @@
+/// Z-algorithm
+///
+/// `z[i]`はsとs[i..]の最長共通接頭辞の長さです（`z[0] = n`）。
+///
+/// # 計算量
+/// O(n)
+///
+/// # 使用例
+/// ```
+/// # use rust_macro::strings::z_algorithm;
+/// assert_eq!(z_algorithm(b"aabcaab"), vec![7, 1, 0, 0, 3, 1, 0]);
+/// assert_eq!(z_algorithm::<u8>(&[]), vec![]);
+/// ```
+pub fn z_algorithm<T: PartialEq>(s: &[T]) -> Vec<usize> {
+    let n = s.len();
+    if n == 0 {
+        return vec![];
+    }
+    let mut z = vec![0; n];
+    z[0] = n;
+    // [l, r)はこれまでに見つかった、sの接頭辞と一致する区間のうちrが最大のもの
+    let (mut l, mut r) = (0, 0);
+    for i in 1..n {
+        let mut k = if i < r { z[i - l].min(r - i) } else { 0 };
+        while i + k < n && s[k] == s[i + k] {
+            k += 1;
+        }
+        z[i] = k;
+        if i + k > r {
+            (l, r) = (i, i + k);
+        }
+    }
+    z
+}
+
+/// KMP法の失敗関数（prefix function）
+///
+/// `pi[i]`はs[..=i]の真の接頭辞かつ接尾辞である最長の長さです。
+/// s[..i]の周期は`i - pi[i - 1]`で求まります。
+///
+/// # 計算量
+/// O(n)
+///
+/// # 使用例
+/// ```
+/// # use rust_macro::strings::kmp_failure;
+/// assert_eq!(kmp_failure(b"abacaba"), vec![0, 0, 1, 0, 1, 2, 3]);
+/// ```
+pub fn kmp_failure<T: PartialEq>(s: &[T]) -> Vec<usize> {
+    let n = s.len();
+    let mut pi = vec![0; n];
+    for i in 1..n {
+        let mut k = pi[i - 1];
+        while k > 0 && s[i] != s[k] {
+            k = pi[k - 1];
+        }
+        if s[i] == s[k] {
+            k += 1;
+        }
+        pi[i] = k;
+    }
+    pi
+}
+
+/// text中でpatternが現れる開始位置をすべて昇順で返す（重なりも含む）
+///
+/// patternが空の場合は`0..=text.len()`のすべての位置を返します。
+///
+/// # 計算量
+/// O(|text| + |pattern|)
+///
+/// # 使用例
+/// ```
+/// # use rust_macro::strings::find_all_occurrences;
+/// assert_eq!(find_all_occurrences(b"abababa", b"aba"), vec![0, 2, 4]);
+/// assert_eq!(find_all_occurrences(b"abc", b"d"), vec![]);
+/// ```
+pub fn find_all_occurrences<T: PartialEq>(text: &[T], pattern: &[T]) -> Vec<usize> {
+    let m = pattern.len();
+    if m == 0 {
+        return (0..=text.len()).collect();
+    }
+    let pi = kmp_failure(pattern);
+    let mut res = vec![];
+    let mut k = 0;
+    for (i, c) in text.iter().enumerate() {
+        while k > 0 && (k == m || pattern[k] != *c) {
+            k = pi[k - 1];
+        }
+        if pattern[k] == *c {
+            k += 1;
+        }
+        if k == m {
+            res.push(i + 1 - m);
+        }
+    }
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::XorShift64;
+
+    fn gen(rng: &mut XorShift64, max_len: u64) -> Vec<u8> {
+        let n = rng.gen_range(0..max_len + 1) as usize;
+        (0..n).map(|_| b'a' + rng.gen_range(0..2) as u8).collect()
+    }
+
+    #[test]
+    fn test_z_and_failure_against_naive() {
+        let mut rng = XorShift64::new(4285);
+        for _ in 0..300 {
+            let s = gen(&mut rng, 20);
+            let n = s.len();
+            let z: Vec<usize> = (0..n)
+                .map(|i| s.iter().zip(&s[i..]).take_while(|(a, b)| a == b).count())
+                .collect();
+            assert_eq!(z_algorithm(&s), z);
+            let pi: Vec<usize> = (0..n)
+                .map(|i| (0..=i).rev().find(|&k| s[..k] == s[i + 1 - k..=i]).unwrap())
+                .collect();
+            assert_eq!(kmp_failure(&s), pi);
+        }
+    }
+
+    #[test]
+    fn test_find_all_occurrences_against_naive() {
+        let mut rng = XorShift64::new(42851);
+        for _ in 0..300 {
+            let text = gen(&mut rng, 20);
+            let pattern = gen(&mut rng, 4);
+            let naive: Vec<usize> = (0..=text.len())
+                .filter(|&i| text[i..].starts_with(&pattern))
+                .collect();
+            assert_eq!(find_all_occurrences(&text, &pattern), naive);
+        }
+        let text: Vec<char> = "いろはにほへといろは".chars().collect();
+        let pattern: Vec<char> = "いろ".chars().collect();
+        assert_eq!(find_all_occurrences(&text, &pattern), vec![0, 7]);
+    }
+}
@@ -0,0 +1,205 @@
+use crate::math::pow_mod;
+use crate::XorShift64;
+use std::sync::OnceLock;
+
+const MODS: [u64; 2] = [1_000_000_007, 998_244_353];
+
+/// プロセス内で共通の基数（実行ごとにランダム）
+///
+/// 基数を共有することで、異なる文字列の部分文字列のハッシュ値を比較できます。
+fn bases() -> [u64; 2] {
+    static BASES: OnceLock<[u64; 2]> = OnceLock::new();
+    *BASES.get_or_init(|| {
+        let seed = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_or(0, |d| d.as_nanos() as u64);
+        let mut rng = XorShift64::new(seed);
+        MODS.map(|m| rng.gen_range(1 << 16..m - 1))
+    })
+}
+
+/// 文字列（の部分文字列）のハッシュ値
+///
+/// 2つの法でのハッシュ値と長さの組です。`==`で比較でき、`RollingHash::concat`で連結できます。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct StrHash {
+    hash: [u64; 2],
+    len: usize,
+}
+
+impl StrHash {
+    /// 元の文字列の長さ
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// 空文字列のハッシュ値かどうか
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+/// ローリングハッシュ（2つの法）
+///
+/// 前計算した文字列の任意の部分文字列のハッシュ値をO(1)で求めます。
+/// 基数は実行ごとにランダムに選ばれ、同じプロセス内のすべての`RollingHash`で共通です。
+///
+/// # 計算量
+/// - 構築: O(n)
+/// - 部分文字列のハッシュ値・連結: O(1)
+/// - 最長共通接頭辞: O(log n)
+///
+/// # 使用例
+/// ```
+/// # use rust_macro::strings::RollingHash;
+/// let a = RollingHash::new(b"abracadabra");
+/// let b = RollingHash::new(b"cadabra");
+/// assert_eq!(a.hash(4, 11), b.hash(0, 7));
+/// assert_eq!(a.hash(0, 4), a.hash(7, 11)); // "abra"
+/// assert_ne!(a.hash(0, 4), a.hash(1, 5));
+/// assert_eq!(a.concat(a.hash(0, 4), a.hash(4, 11)), a.hash(0, 11));
+/// assert_eq!(a.lcp(0, &b, 2), 0);
+/// assert_eq!(a.lcp(7, &b, 3), 4);
+/// ```
+#[derive(Debug, Clone)]
+pub struct RollingHash {
+    /// prefix[i]: 先頭i文字のハッシュ値
+    prefix: Vec<[u64; 2]>,
+    /// pow[i]: 基数のi乗
+    pow: Vec<[u64; 2]>,
+}
+
+impl RollingHash {
+    /// バイト列や文字の列から構築する
+    pub fn new<T: Copy + Into<u64>>(s: &[T]) -> Self {
+        let b = bases();
+        let n = s.len();
+        let mut prefix = vec![[0; 2]; n + 1];
+        let mut pow = vec![[1; 2]; n + 1];
+        for (i, &c) in s.iter().enumerate() {
+            let c: u64 = c.into();
+            for k in 0..2 {
+                prefix[i + 1][k] = (prefix[i][k] * b[k] + c % MODS[k] + 1) % MODS[k];
+                pow[i + 1][k] = pow[i][k] * b[k] % MODS[k];
+            }
+        }
+        RollingHash { prefix, pow }
+    }
+
+    /// 文字列の長さ
+    pub fn len(&self) -> usize {
+        self.prefix.len() - 1
+    }
+
+    /// 空文字列かどうか
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// 部分文字列s[l..r]のハッシュ値
+    pub fn hash(&self, l: usize, r: usize) -> StrHash {
+        assert!(l <= r && r <= self.len());
+        let mut hash = [0; 2];
+        for (k, h) in hash.iter_mut().enumerate() {
+            let m = MODS[k];
+            *h = (self.prefix[r][k] + m * m - self.prefix[l][k] * self.pow[r - l][k]) % m;
+        }
+        StrHash { hash, len: r - l }
+    }
+
+    /// 文字列全体のハッシュ値
+    pub fn full(&self) -> StrHash {
+        self.hash(0, self.len())
+    }
+
+    /// 文字列aの後ろにbを連結した文字列のハッシュ値
+    pub fn concat(&self, a: StrHash, b: StrHash) -> StrHash {
+        let mut hash = [0; 2];
+        for (k, h) in hash.iter_mut().enumerate() {
+            let p = match self.pow.get(b.len) {
+                Some(p) => p[k],
+                None => pow_mod(bases()[k], b.len as u64, MODS[k]),
+            };
+            *h = (a.hash[k] * p + b.hash[k]) % MODS[k];
+        }
+        StrHash {
+            hash,
+            len: a.len + b.len,
+        }
+    }
+
+    /// selfのi文字目以降とotherのj文字目以降の最長共通接頭辞の長さ
+    pub fn lcp(&self, i: usize, other: &RollingHash, j: usize) -> usize {
+        let (mut ok, mut ng) = (0, (self.len() - i).min(other.len() - j) + 1);
+        while ng - ok > 1 {
+            let mid = (ok + ng) / 2;
+            if self.hash(i, i + mid) == other.hash(j, j + mid) {
+                ok = mid;
+            } else {
+                ng = mid;
+            }
+        }
+        ok
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::XorShift64;
+
+    #[test]
+    fn test_rolling_hash_substrings() {
+        let s: Vec<char> = "mississippi".chars().collect();
+        let rh = RollingHash::new(&s);
+        for l1 in 0..=s.len() {
+            for r1 in l1..=s.len() {
+                for l2 in 0..=s.len() {
+                    for r2 in l2..=s.len() {
+                        assert_eq!(rh.hash(l1, r1) == rh.hash(l2, r2), s[l1..r1] == s[l2..r2]);
+                    }
+                }
+            }
+        }
+        assert_eq!(rh.lcp(1, &rh, 4), 4);
+        assert_eq!(rh.full().len(), 11);
+    }
+
+    #[test]
+    fn test_rolling_hash_concat_across_strings() {
+        let a = RollingHash::new(b"ab");
+        let b = RollingHash::new(b"xyzxyz");
+        let c = RollingHash::new(b"abxyzxyz");
+        assert_eq!(a.concat(a.full(), b.full()), c.full());
+        assert_eq!(
+            b.concat(b.hash(0, 3), a.full()),
+            RollingHash::new(b"xyzab").full()
+        );
+        assert_eq!(a.concat(StrHash::default(), a.full()), a.full());
+        // 長さが異なれば区別される（"\0"は1文字として扱う）
+        assert_ne!(RollingHash::new(&[0u8]).full(), StrHash::default());
+    }
+
+    #[test]
+    fn test_rolling_hash_lcp_against_naive() {
+        let mut rng = XorShift64::new(4284);
+        for _ in 0..100 {
+            let mut gen = || -> Vec<u8> {
+                let n = rng.gen_range(0..15) as usize;
+                (0..n).map(|_| b'a' + rng.gen_range(0..2) as u8).collect()
+            };
+            let (a, b) = (gen(), gen());
+            let (ra, rb) = (RollingHash::new(&a), RollingHash::new(&b));
+            for i in 0..=a.len() {
+                for j in 0..=b.len() {
+                    let naive = a[i..]
+                        .iter()
+                        .zip(&b[j..])
+                        .take_while(|(x, y)| x == y)
+                        .count();
+                    assert_eq!(ra.lcp(i, &rb, j), naive);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,199 @@
+/// 部分列オートマトン（各位置以降で各文字が次に現れる位置の表）
+///
+/// 文字は0以上sigma未満の整数で表します。小文字の英字列なら`from_lowercase`で作れます。
+/// 部分列の判定や、貪欲に部分列をとる問題で使います。
+///
+/// # 計算量
+/// - 構築: O(n σ)
+/// - 次の出現位置: O(1)
+/// - 部分列判定・必要な繰り返し回数: O(|t|)
+///
+/// # 使用例
+/// ```
+/// # use rust_macro::strings::SubsequenceAutomaton;
+/// let sa = SubsequenceAutomaton::from_lowercase("abcab");
+/// assert_eq!(sa.next(2, 1), Some(4)); // 2文字目以降で最初の'b'
+/// assert!(sa.is_subsequence(&[0, 2, 1])); // "acb"
+/// assert!(!sa.is_subsequence(&[2, 2])); // "cc"
+/// // "cc"を部分列に含むには"abcab"を2回つなげる必要がある
+/// assert_eq!(sa.min_copies(&[2, 2]), Some(2));
+/// assert_eq!(sa.min_copies(&[3]), None);
+/// // 問い合わせも英小文字のまま渡せる
+/// assert!(sa.is_subsequence_lowercase("acb"));
+/// assert_eq!(sa.min_copies_lowercase("cc"), Some(2));
+/// ```
+#[derive(Debug, Clone)]
+pub struct SubsequenceAutomaton {
+    n: usize,
+    sigma: usize,
+    /// table[i * sigma + c]: i以上でcが現れる最小の位置（なければn）
+    table: Vec<u32>,
+}
+
+impl SubsequenceAutomaton {
+    /// 各文字が0以上sigma未満の列から構築する
+    ///
+    /// # パニック
+    /// sigma以上の文字を含む場合panicします
+    pub fn new<T: Copy + Into<usize>>(s: &[T], sigma: usize) -> Self {
+        let n = s.len();
+        let mut table = vec![n as u32; (n + 1) * sigma];
+        for i in (0..n).rev() {
+            let c: usize = s[i].into();
+            assert!(c < sigma, "character out of range");
+            let (cur, next) = table.split_at_mut((i + 1) * sigma);
+            cur[i * sigma..].copy_from_slice(&next[..sigma]);
+            cur[i * sigma + c] = i as u32;
+        }
+        SubsequenceAutomaton { n, sigma, table }
+    }
+
+    /// 英小文字からなる文字列から構築する（'a'を0、'z'を25とする）
+    ///
+    /// # パニック
+    /// 英小文字以外の文字を含む場合panicします
+    pub fn from_lowercase(s: &str) -> Self {
+        Self::new(&lowercase_to_indices(s), 26)
+    }
+
+    /// 元の列の長さ
+    pub fn len(&self) -> usize {
+        self.n
+    }
+
+    /// 元の列が空かどうか
+    pub fn is_empty(&self) -> bool {
+        self.n == 0
+    }
+
+    /// i以上でcが現れる最小の位置
+    pub fn next(&self, i: usize, c: usize) -> Option<usize> {
+        if i >= self.n || c >= self.sigma {
+            return None;
+        }
+        let j = self.table[i * self.sigma + c] as usize;
+        (j < self.n).then_some(j)
+    }
+
+    /// tが部分列かどうか
+    pub fn is_subsequence(&self, t: &[usize]) -> bool {
+        let mut pos = 0;
+        for &c in t {
+            match self.next(pos, c) {
+                Some(j) => pos = j + 1,
+                None => return false,
+            }
+        }
+        true
+    }
+
+    /// 英小文字からなる文字列tが部分列かどうか（`from_lowercase`と同じ対応で変換する）
+    ///
+    /// # パニック
+    /// 英小文字以外の文字を含む場合panicします
+    pub fn is_subsequence_lowercase(&self, t: &str) -> bool {
+        self.is_subsequence(&lowercase_to_indices(t))
+    }
+
+    /// 英小文字からなる文字列tについての`min_copies`
+    ///
+    /// # パニック
+    /// 英小文字以外の文字を含む場合panicします
+    pub fn min_copies_lowercase(&self, t: &str) -> Option<usize> {
+        self.min_copies(&lowercase_to_indices(t))
+    }
+
+    /// 元の列を何回つなげればtを部分列として含むか（含められなければ`None`）
+    pub fn min_copies(&self, t: &[usize]) -> Option<usize> {
+        if t.is_empty() {
+            return Some(0);
+        }
+        let (mut copies, mut pos) = (1, 0);
+        for &c in t {
+            let j = match self.next(pos, c) {
+                Some(j) => j,
+                None => {
+                    copies += 1;
+                    self.next(0, c)?
+                }
+            };
+            pos = j + 1;
+        }
+        Some(copies)
+    }
+}
+
+/// 英小文字を'a'を0、'z'を25とする番号に変換する
+fn lowercase_to_indices(s: &str) -> Vec<usize> {
+    s.bytes()
+        .map(|c| {
+            assert!(c.is_ascii_lowercase(), "non-lowercase character");
+            (c - b'a') as usize
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::XorShift64;
+
+    fn naive_is_subsequence(s: &[usize], t: &[usize]) -> bool {
+        let mut it = s.iter();
+        t.iter().all(|c| it.any(|x| x == c))
+    }
+
+    #[test]
+    fn test_subsequence_automaton_against_naive() {
+        let mut rng = XorShift64::new(81);
+        for _ in 0..200 {
+            let n = rng.gen_range(0..10) as usize;
+            let s: Vec<usize> = (0..n).map(|_| rng.gen_range(0..3) as usize).collect();
+            let sa = SubsequenceAutomaton::new(&s, 3);
+            for i in 0..=n {
+                for c in 0..3 {
+                    assert_eq!(sa.next(i, c), (i..n).find(|&j| s[j] == c));
+                }
+            }
+            let t: Vec<usize> = (0..rng.gen_range(0..6))
+                .map(|_| rng.gen_range(0..3) as usize)
+                .collect();
+            assert_eq!(sa.is_subsequence(&t), naive_is_subsequence(&s, &t));
+            let expected = (0..=t.len()).find(|&k| naive_is_subsequence(&s.repeat(k), &t));
+            assert_eq!(sa.min_copies(&t), expected);
+        }
+    }
+
+    #[test]
+    fn test_subsequence_automaton_empty() {
+        let sa = SubsequenceAutomaton::from_lowercase("");
+        assert!(sa.is_empty());
+        assert!(sa.is_subsequence(&[]));
+        assert_eq!(sa.min_copies(&[]), Some(0));
+        assert_eq!(sa.min_copies(&[0]), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "non-lowercase character")]
+    fn test_from_lowercase_rejects_other_characters() {
+        SubsequenceAutomaton::from_lowercase("abC");
+    }
+
+    #[test]
+    fn test_lowercase_queries() {
+        let sa = SubsequenceAutomaton::from_lowercase("banana");
+        for t in ["", "bnn", "aaa", "nab", "ba", "z"] {
+            let v: Vec<usize> = t.bytes().map(|c| (c - b'a') as usize).collect();
+            assert_eq!(sa.is_subsequence_lowercase(t), sa.is_subsequence(&v));
+            assert_eq!(sa.min_copies_lowercase(t), sa.min_copies(&v));
+        }
+        assert_eq!(sa.min_copies_lowercase("nab"), Some(2));
+        assert_eq!(sa.min_copies_lowercase("z"), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "non-lowercase character")]
+    fn test_lowercase_queries_reject_other_characters() {
+        SubsequenceAutomaton::from_lowercase("banana").is_subsequence_lowercase("Ban");
+    }
+}
@@ -0,0 +1,290 @@
+use crate::SparseTable;
+
+const NONE: usize = usize::MAX;
+
+/// SA-ISで接尾辞配列を構築する（sの各値は0以上upper以下）
+fn sa_is(s: &[usize], upper: usize) -> Vec<usize> {
+    let n = s.len();
+    match n {
+        0 => return vec![],
+        1 => return vec![0],
+        2 => return if s[0] < s[1] { vec![0, 1] } else { vec![1, 0] },
+        _ => {}
+    }
+    // ls[i]: i文字目以降の接尾辞がS型（次の接尾辞より小さい）かどうか
+    let mut ls = vec![false; n];
+    for i in (0..n - 1).rev() {
+        ls[i] = if s[i] == s[i + 1] {
+            ls[i + 1]
+        } else {
+            s[i] < s[i + 1]
+        };
+    }
+    // 各文字のバケットの中で、L型・S型の接尾辞が始まる位置
+    let mut sum_l = vec![0; upper + 1];
+    let mut sum_s = vec![0; upper + 1];
+    for i in 0..n {
+        if ls[i] {
+            sum_l[s[i] + 1] += 1;
+        } else {
+            sum_s[s[i]] += 1;
+        }
+    }
+    for i in 0..=upper {
+        sum_s[i] += sum_l[i];
+        if i < upper {
+            sum_l[i + 1] += sum_s[i];
+        }
+    }
+
+    let induce = |sa: &mut [usize], lms: &[usize]| {
+        sa.iter_mut().for_each(|x| *x = NONE);
+        let mut buf = sum_s.clone();
+        for &d in lms {
+            if d != n {
+                sa[buf[s[d]]] = d;
+                buf[s[d]] += 1;
+            }
+        }
+        let mut buf = sum_l.clone();
+        sa[buf[s[n - 1]]] = n - 1;
+        buf[s[n - 1]] += 1;
+        for i in 0..n {
+            let v = sa[i];
+            if v != NONE && v >= 1 && !ls[v - 1] {
+                sa[buf[s[v - 1]]] = v - 1;
+                buf[s[v - 1]] += 1;
+            }
+        }
+        let mut buf = sum_l.clone();
+        for i in (0..n).rev() {
+            let v = sa[i];
+            if v != NONE && v >= 1 && ls[v - 1] {
+                buf[s[v - 1] + 1] -= 1;
+                sa[buf[s[v - 1] + 1]] = v - 1;
+            }
+        }
+    };
+
+    // LMS（左がL型である最左のS型）の位置を集めて誘導ソートする
+    let mut lms_map = vec![NONE; n + 1];
+    let mut lms = Vec::new();
+    for i in 1..n {
+        if !ls[i - 1] && ls[i] {
+            lms_map[i] = lms.len();
+            lms.push(i);
+        }
+    }
+    let m = lms.len();
+    let mut sa = vec![NONE; n];
+    induce(&mut sa, &lms);
+
+    if m > 0 {
+        // LMS部分文字列に番号を付け、その列の接尾辞配列を再帰的に求める
+        let mut sorted_lms: Vec<usize> =
+            sa.iter().copied().filter(|&v| lms_map[v] != NONE).collect();
+        let mut rec_s = vec![0; m];
+        let mut rec_upper = 0;
+        for i in 1..m {
+            let (mut l, mut r) = (sorted_lms[i - 1], sorted_lms[i]);
+            let end_l = lms.get(lms_map[l] + 1).copied().unwrap_or(n);
+            let end_r = lms.get(lms_map[r] + 1).copied().unwrap_or(n);
+            let mut same = true;
+            if end_l - l != end_r - r {
+                same = false;
+            } else {
+                while l < end_l && s[l] == s[r] {
+                    l += 1;
+                    r += 1;
+                }
+                if l == n || s[l] != s[r] {
+                    same = false;
+                }
+            }
+            if !same {
+                rec_upper += 1;
+            }
+            rec_s[lms_map[sorted_lms[i]]] = rec_upper;
+        }
+        let rec_sa = sa_is(&rec_s, rec_upper);
+        for (x, &i) in sorted_lms.iter_mut().zip(&rec_sa) {
+            *x = lms[i];
+        }
+        induce(&mut sa, &sorted_lms);
+    }
+    sa
+}
+
+/// 接尾辞配列とLCP配列
+///
+/// `sa()[k]`は辞書順でk番目の接尾辞の開始位置、`lcp_array()[k]`は辞書順でk番目と
+/// k+1番目の接尾辞の最長共通接頭辞の長さです。任意の2つの接尾辞の最長共通接頭辞も
+/// スパーステーブルによりO(1)で求められます。
+///
+/// # 計算量
+/// - 構築: O(n log n)（接尾辞配列とLCP配列自体はSA-IS・Kasaiの方法でO(n)）
+/// - 2つの接尾辞の最長共通接頭辞: O(1)
+///
+/// # 使用例
+/// ```
+/// # use rust_macro::strings::SuffixArray;
+/// let sa = SuffixArray::new(b"abracadabra");
+/// assert_eq!(sa.sa(), &[10, 7, 0, 3, 5, 8, 1, 4, 6, 9, 2]);
+/// assert_eq!(sa.lcp_array(), &[1, 4, 1, 1, 0, 3, 0, 0, 0, 2]);
+/// // "abra" と "abracadabra"
+/// assert_eq!(sa.lcp(7, 0), 4);
+/// assert_eq!(sa.count_distinct_substrings(), 54);
+/// ```
+#[derive(Debug, Clone)]
+pub struct SuffixArray {
+    sa: Vec<usize>,
+    rank: Vec<usize>,
+    lcp: Vec<usize>,
+    /// LCP配列の区間最小値
+    table: SparseTable<usize, fn(&usize, &usize) -> usize>,
+}
+
+impl SuffixArray {
+    /// 列sから構築する
+    pub fn new<T: Ord>(s: &[T]) -> Self {
+        let n = s.len();
+        // 値を0から始まる順位に圧縮する
+        let mut idx: Vec<usize> = (0..n).collect();
+        idx.sort_by(|&a, &b| s[a].cmp(&s[b]));
+        let mut t = vec![0; n];
+        let mut upper = 0;
+        for k in 1..n {
+            if s[idx[k - 1]] != s[idx[k]] {
+                upper += 1;
+            }
+            t[idx[k]] = upper;
+        }
+        let sa = sa_is(&t, upper);
+
+        // Kasaiの方法でLCP配列を求める
+        let mut rank = vec![0; n];
+        for (k, &i) in sa.iter().enumerate() {
+            rank[i] = k;
+        }
+        let mut lcp = vec![0; n.saturating_sub(1)];
+        let mut h: usize = 0;
+        for i in 0..n {
+            h = h.saturating_sub(1);
+            if rank[i] == 0 {
+                continue;
+            }
+            let j = sa[rank[i] - 1];
+            while i + h < n && j + h < n && t[i + h] == t[j + h] {
+                h += 1;
+            }
+            lcp[rank[i] - 1] = h;
+        }
+
+        let min: fn(&usize, &usize) -> usize = |a, b| *a.min(b);
+        let table = SparseTable::new(lcp.clone(), min);
+        SuffixArray {
+            sa,
+            rank,
+            lcp,
+            table,
+        }
+    }
+
+    /// 列の長さ
+    pub fn len(&self) -> usize {
+        self.sa.len()
+    }
+
+    /// 空列かどうか
+    pub fn is_empty(&self) -> bool {
+        self.sa.is_empty()
+    }
+
+    /// 接尾辞配列
+    pub fn sa(&self) -> &[usize] {
+        &self.sa
+    }
+
+    /// i文字目から始まる接尾辞の辞書順での順位（0始まり）
+    pub fn rank(&self, i: usize) -> usize {
+        self.rank[i]
+    }
+
+    /// LCP配列（長さn - 1）
+    pub fn lcp_array(&self) -> &[usize] {
+        &self.lcp
+    }
+
+    /// i文字目から始まる接尾辞とj文字目から始まる接尾辞の最長共通接頭辞の長さ
+    pub fn lcp(&self, i: usize, j: usize) -> usize {
+        if i == j {
+            return self.len() - i;
+        }
+        let (a, b) = {
+            let (x, y) = (self.rank[i], self.rank[j]);
+            (x.min(y), x.max(y))
+        };
+        self.table.query(a, b)
+    }
+
+    /// 異なる部分文字列（空文字列を除く）の個数
+    pub fn count_distinct_substrings(&self) -> u64 {
+        let n = self.len() as u64;
+        n * (n + 1) / 2 - self.lcp.iter().map(|&x| x as u64).sum::<u64>()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::XorShift64;
+    use std::collections::HashSet;
+
+    fn naive_sa(s: &[u8]) -> Vec<usize> {
+        let mut sa: Vec<usize> = (0..s.len()).collect();
+        sa.sort_by(|&a, &b| s[a..].cmp(&s[b..]));
+        sa
+    }
+
+    fn naive_lcp(a: &[u8], b: &[u8]) -> usize {
+        a.iter().zip(b).take_while(|(x, y)| x == y).count()
+    }
+
+    #[test]
+    fn test_suffix_array_against_naive() {
+        let mut rng = XorShift64::new(61);
+        for n in 0..60usize {
+            for alphabet in [1u64, 2, 3, 26] {
+                let s: Vec<u8> = (0..n)
+                    .map(|_| b'a' + rng.gen_range(0..alphabet) as u8)
+                    .collect();
+                let sa = SuffixArray::new(&s);
+                assert_eq!(sa.sa(), &naive_sa(&s)[..]);
+                for k in 0..n.saturating_sub(1) {
+                    let (i, j) = (sa.sa()[k], sa.sa()[k + 1]);
+                    assert_eq!(sa.lcp_array()[k], naive_lcp(&s[i..], &s[j..]));
+                }
+                for i in 0..n {
+                    for j in 0..n {
+                        assert_eq!(sa.lcp(i, j), naive_lcp(&s[i..], &s[j..]));
+                    }
+                }
+                let distinct: HashSet<&[u8]> = (0..n)
+                    .flat_map(|l| (l + 1..=n).map(move |r| (l, r)))
+                    .map(|(l, r)| &s[l..r])
+                    .collect();
+                assert_eq!(sa.count_distinct_substrings(), distinct.len() as u64);
+            }
+        }
+    }
+
+    #[test]
+    fn test_suffix_array_generic_values() {
+        let v = [5i64, -3, 5, -3, 5];
+        let sa = SuffixArray::new(&v);
+        assert_eq!(sa.sa(), &[3, 1, 4, 2, 0]);
+        assert_eq!(sa.rank(0), 4);
+        assert_eq!(sa.lcp(0, 2), 3);
+        assert!(SuffixArray::new::<u8>(&[]).is_empty());
+    }
+}
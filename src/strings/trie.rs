@@ -0,0 +1,396 @@
+use rustc_hash::FxHasher;
+use std::collections::HashMap;
+use std::hash::{BuildHasherDefault, Hash};
+
+#[derive(Debug, Clone)]
+struct TrieNode<K, P> {
+    children: HashMap<K, usize, BuildHasherDefault<FxHasher>>,
+    /// このノードを通る（このノードを接頭辞に持つ）文字列の個数
+    prefix_count: usize,
+    /// このノードで終わる文字列の個数
+    count: usize,
+    payload: P,
+}
+
+/// 各ノードに任意のデータを持たせられるトライ木
+///
+/// キーは`u8`や`char`など任意の文字の列で、同じ文字列を複数回挿入できます（多重集合）。
+/// ノードは番号で表し、根は`Trie::ROOT`です。`insert_with`で挿入時に通ったノードの
+/// データを更新すれば、接頭辞ごとの集約値（最大値や合計など）を持てます。
+///
+/// # 計算量
+/// 文字列の長さをLとして、挿入・削除・検索はいずれも期待O(L)
+///
+/// # 使用例
+/// ```
+/// # use rust_macro::strings::Trie;
+/// // 各接頭辞について、それを持つ単語のスコアの最大値を持つ
+/// let mut trie = Trie::<u8, i64>::new();
+/// for (word, score) in [("apple", 3), ("app", 5), ("apricot", 1), ("banana", 4)] {
+///     trie.insert_with(word.bytes(), |best| *best = (*best).max(score));
+/// }
+/// assert_eq!(trie.count_prefix("ap".bytes()), 3);
+/// assert_eq!(trie.count("app".bytes()), 1);
+/// assert_eq!(trie.count("ap".bytes()), 0);
+/// let node = trie.find("apr".bytes()).unwrap();
+/// assert_eq!(*trie.payload(node), 1);
+/// assert_eq!(*trie.payload(trie.find("ap".bytes()).unwrap()), 5);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Trie<K, P = ()> {
+    nodes: Vec<TrieNode<K, P>>,
+}
+
+impl<K: Hash + Eq, P: Default> Default for Trie<K, P> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Hash + Eq, P: Default> Trie<K, P> {
+    /// 空のトライ木を作成
+    pub fn new() -> Self {
+        Trie {
+            nodes: vec![Self::new_node()],
+        }
+    }
+
+    fn new_node() -> TrieNode<K, P> {
+        TrieNode {
+            children: HashMap::default(),
+            prefix_count: 0,
+            count: 0,
+            payload: P::default(),
+        }
+    }
+
+    /// 文字列を挿入し、終端のノード番号を返す
+    pub fn insert<I: IntoIterator<Item = K>>(&mut self, key: I) -> usize {
+        self.insert_with(key, |_| {})
+    }
+
+    /// 文字列を挿入し、終端のノード番号を返す
+    ///
+    /// 根から終端までの通ったノードすべて（根と終端を含む）のデータに対して、根に近い順にfを呼びます。
+    pub fn insert_with<I, F>(&mut self, key: I, mut f: F) -> usize
+    where
+        I: IntoIterator<Item = K>,
+        F: FnMut(&mut P),
+    {
+        let mut v = Self::ROOT;
+        self.nodes[v].prefix_count += 1;
+        f(&mut self.nodes[v].payload);
+        for c in key {
+            v = match self.nodes[v].children.get(&c) {
+                Some(&u) => u,
+                None => {
+                    let u = self.nodes.len();
+                    self.nodes.push(Self::new_node());
+                    self.nodes[v].children.insert(c, u);
+                    u
+                }
+            };
+            self.nodes[v].prefix_count += 1;
+            f(&mut self.nodes[v].payload);
+        }
+        self.nodes[v].count += 1;
+        v
+    }
+}
+
+impl<K: Hash + Eq, P> Trie<K, P> {
+    /// 根のノード番号
+    pub const ROOT: usize = 0;
+
+    /// 挿入されている文字列の個数（重複を含む）
+    pub fn len(&self) -> usize {
+        self.nodes[0].prefix_count
+    }
+
+    /// 文字列が1つも挿入されていないかどうか
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// 文字列keyに対応するノード番号。keyを接頭辞に持つ文字列が挿入されたことがなければ`None`
+    pub fn find<I: IntoIterator<Item = K>>(&self, key: I) -> Option<usize> {
+        key.into_iter()
+            .try_fold(Self::ROOT, |v, c| self.child(v, &c))
+    }
+
+    /// ノードvから文字cで進んだ先のノード番号
+    pub fn child(&self, v: usize, c: &K) -> Option<usize> {
+        self.nodes[v].children.get(c).copied()
+    }
+
+    /// ノードvの子の(文字, ノード番号)の列（順不同）
+    pub fn children(&self, v: usize) -> impl Iterator<Item = (&K, usize)> {
+        self.nodes[v].children.iter().map(|(c, &u)| (c, u))
+    }
+
+    /// 文字列keyがちょうど挿入されている個数
+    pub fn count<I: IntoIterator<Item = K>>(&self, key: I) -> usize {
+        self.find(key).map_or(0, |v| self.nodes[v].count)
+    }
+
+    /// prefixを接頭辞に持つ文字列の個数
+    pub fn count_prefix<I: IntoIterator<Item = K>>(&self, prefix: I) -> usize {
+        self.find(prefix).map_or(0, |v| self.nodes[v].prefix_count)
+    }
+
+    /// ノードvで終わる文字列の個数
+    pub fn count_at(&self, v: usize) -> usize {
+        self.nodes[v].count
+    }
+
+    /// ノードvを接頭辞に持つ文字列の個数
+    pub fn count_prefix_at(&self, v: usize) -> usize {
+        self.nodes[v].prefix_count
+    }
+
+    /// 文字列keyを1つ削除する。存在しなかった場合は何もせず`false`を返す
+    ///
+    /// ノードのデータは変更しません。削除に合わせて集約値を戻す必要がある場合は、
+    /// `path`で得たノードを自分で更新してください。
+    pub fn remove<I: IntoIterator<Item = K>>(&mut self, key: I) -> bool {
+        let path = self.path(key);
+        match path.last() {
+            Some(&end) if self.nodes[end].count > 0 => self.nodes[end].count -= 1,
+            _ => return false,
+        }
+        for v in path {
+            self.nodes[v].prefix_count -= 1;
+        }
+        true
+    }
+
+    /// 根から文字列keyをたどったノード番号の列（根を含む）。途中で途切れた場合は空
+    pub fn path<I: IntoIterator<Item = K>>(&self, key: I) -> Vec<usize> {
+        let mut path = vec![Self::ROOT];
+        for c in key {
+            match self.nodes[*path.last().unwrap()].children.get(&c) {
+                Some(&u) => path.push(u),
+                None => return vec![],
+            }
+        }
+        path
+    }
+
+    /// ノードvのデータ
+    pub fn payload(&self, v: usize) -> &P {
+        &self.nodes[v].payload
+    }
+
+    /// ノードvのデータ（可変）
+    pub fn payload_mut(&mut self, v: usize) -> &mut P {
+        &mut self.nodes[v].payload
+    }
+}
+
+/// 非負整数の多重集合を2進数のトライ木で持つ構造体
+///
+/// 集合内の値とxとのxorの最大値・最小値を求められます。
+///
+/// # 計算量
+/// ビット数をBとして、挿入・削除・クエリはいずれもO(B)
+///
+/// # 使用例
+/// ```
+/// # use rust_macro::strings::BinaryTrie;
+/// let mut bt = BinaryTrie::new(30);
+/// for x in [3, 10, 5, 25] {
+///     bt.insert(x);
+/// }
+/// assert_eq!(bt.max_xor(2), Some(25 ^ 2));
+/// assert_eq!(bt.min_xor(8), Some(10 ^ 8));
+/// bt.remove(25);
+/// assert_eq!(bt.max_xor(2), Some(10 ^ 2));
+/// ```
+#[derive(Debug, Clone)]
+pub struct BinaryTrie {
+    bits: u32,
+    /// children[v][b]: ノードvからビットbで進んだ先（0はなし。根は子にならない）
+    children: Vec<[usize; 2]>,
+    /// ノードvを通る値の個数
+    count: Vec<usize>,
+}
+
+impl BinaryTrie {
+    /// 下位bitsビットの値を扱うトライ木を作成
+    ///
+    /// # パニック
+    /// bitsが64を超える場合panicします
+    pub fn new(bits: u32) -> Self {
+        assert!(bits <= 64);
+        BinaryTrie {
+            bits,
+            children: vec![[0; 2]],
+            count: vec![0],
+        }
+    }
+
+    /// 値の個数（重複を含む）
+    pub fn len(&self) -> usize {
+        self.count[0]
+    }
+
+    /// 空かどうか
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn bit(&self, x: u64, i: u32) -> usize {
+        (x >> i & 1) as usize
+    }
+
+    /// xを1つ追加する
+    ///
+    /// # パニック
+    /// xがbitsビットに収まらない場合panicします
+    pub fn insert(&mut self, x: u64) {
+        assert!(self.bits == 64 || x >> self.bits == 0, "value out of range");
+        let mut v = 0;
+        self.count[v] += 1;
+        for i in (0..self.bits).rev() {
+            let b = self.bit(x, i);
+            if self.children[v][b] == 0 {
+                self.children[v][b] = self.children.len();
+                self.children.push([0; 2]);
+                self.count.push(0);
+            }
+            v = self.children[v][b];
+            self.count[v] += 1;
+        }
+    }
+
+    /// xの個数
+    pub fn count(&self, x: u64) -> usize {
+        if self.bits < 64 && x >> self.bits != 0 {
+            return 0;
+        }
+        let mut v = 0;
+        for i in (0..self.bits).rev() {
+            v = self.children[v][self.bit(x, i)];
+            if v == 0 {
+                return 0;
+            }
+        }
+        self.count[v]
+    }
+
+    /// xを1つ削除する。存在しなかった場合は何もせず`false`を返す
+    pub fn remove(&mut self, x: u64) -> bool {
+        if self.count(x) == 0 {
+            return false;
+        }
+        let mut v = 0;
+        self.count[v] -= 1;
+        for i in (0..self.bits).rev() {
+            v = self.children[v][self.bit(x, i)];
+            self.count[v] -= 1;
+        }
+        true
+    }
+
+    /// 集合内の値yについてのx ^ yの最小値。空の場合は`None`
+    pub fn min_xor(&self, x: u64) -> Option<u64> {
+        if self.is_empty() {
+            return None;
+        }
+        let mut v = 0;
+        let mut res = 0;
+        for i in (0..self.bits).rev() {
+            let b = self.bit(x, i);
+            let same = self.children[v][b];
+            if same != 0 && self.count[same] > 0 {
+                v = same;
+            } else {
+                v = self.children[v][b ^ 1];
+                res |= 1 << i;
+            }
+        }
+        Some(res)
+    }
+
+    /// 集合内の値yについてのx ^ yの最大値。空の場合は`None`
+    pub fn max_xor(&self, x: u64) -> Option<u64> {
+        let mask = if self.bits == 64 {
+            u64::MAX
+        } else {
+            (1 << self.bits) - 1
+        };
+        self.min_xor(!x & mask).map(|r| !r & mask)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::XorShift64;
+
+    #[test]
+    fn test_trie_against_naive() {
+        let mut rng = XorShift64::new(4287);
+        let mut trie = Trie::<char, usize>::new();
+        let mut naive: Vec<String> = vec![];
+        for _ in 0..500 {
+            let n = rng.gen_range(0..5) as usize;
+            let s: String = (0..n)
+                .map(|_| (b'a' + rng.gen_range(0..3) as u8) as char)
+                .collect();
+            if rng.gen_range(0..3) == 0 {
+                let pos = naive.iter().position(|t| *t == s);
+                assert_eq!(trie.remove(s.chars()), pos.is_some());
+                if let Some(i) = pos {
+                    naive.swap_remove(i);
+                }
+            } else {
+                trie.insert_with(s.chars(), |p| *p += 1);
+                naive.push(s.clone());
+            }
+            assert_eq!(trie.len(), naive.len());
+            assert_eq!(
+                trie.count(s.chars()),
+                naive.iter().filter(|t| **t == s).count()
+            );
+            assert_eq!(
+                trie.count_prefix(s.chars()),
+                naive.iter().filter(|t| t.starts_with(&s)).count()
+            );
+        }
+        // 削除してもデータは変わらない
+        let v = trie.find("".chars()).unwrap();
+        assert_eq!(v, Trie::<char, usize>::ROOT);
+        assert!(*trie.payload(v) >= trie.len());
+        assert_eq!(trie.path("abcabc".chars()).len(), 0);
+        assert_eq!(trie.find("d".chars()), None);
+    }
+
+    #[test]
+    fn test_binary_trie_against_naive() {
+        let mut rng = XorShift64::new(42871);
+        for bits in [1, 5, 64] {
+            let mut bt = BinaryTrie::new(bits);
+            let mut naive: Vec<u64> = vec![];
+            let max = if bits == 64 { u64::MAX } else { 1 << bits };
+            for _ in 0..300 {
+                let x = rng.gen_range(0..max);
+                if rng.gen_range(0..3) == 0 {
+                    let pos = naive.iter().position(|&y| y == x);
+                    assert_eq!(bt.remove(x), pos.is_some());
+                    if let Some(i) = pos {
+                        naive.swap_remove(i);
+                    }
+                } else {
+                    bt.insert(x);
+                    naive.push(x);
+                }
+                let q = rng.gen_range(0..max);
+                assert_eq!(bt.len(), naive.len());
+                assert_eq!(bt.count(x), naive.iter().filter(|&&y| y == x).count());
+                assert_eq!(bt.max_xor(q), naive.iter().map(|&y| y ^ q).max());
+                assert_eq!(bt.min_xor(q), naive.iter().map(|&y| y ^ q).min());
+            }
+        }
+    }
+}
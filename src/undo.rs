@@ -0,0 +1,264 @@
+use rustc_hash::FxHasher;
+use std::collections::HashMap;
+use std::hash::{BuildHasherDefault, Hash};
+use std::ops::Index;
+
+#[derive(Debug, Clone)]
+enum VecOp<T> {
+    Set(usize, T),
+    Push,
+    Pop(T),
+}
+
+/// 変更を巻き戻せる配列
+///
+/// すべての変更を履歴に記録し、`snapshot`で取得した時点まで`rollback`で戻せます。
+/// バックトラックを伴う探索や、オフラインの分割統治で状態を共有する場合に使います。
+///
+/// # 計算量
+/// - 変更: O(1)
+/// - 巻き戻し: 取り消す変更の個数に比例
+///
+/// # 使用例
+/// ```
+/// # use rust_macro::UndoVec;
+/// let mut v = UndoVec::new(vec![0; 3]);
+/// v.set(0, 5);
+/// let s = v.snapshot();
+/// v.set(1, 7);
+/// v.push(9);
+/// assert_eq!(v.as_slice(), &[5, 7, 0, 9]);
+/// v.rollback(s);
+/// assert_eq!(v.as_slice(), &[5, 0, 0]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct UndoVec<T> {
+    data: Vec<T>,
+    history: Vec<VecOp<T>>,
+}
+
+impl<T: Clone> UndoVec<T> {
+    /// 初期値から作成
+    pub fn new(data: Vec<T>) -> Self {
+        UndoVec {
+            data,
+            history: Vec::new(),
+        }
+    }
+
+    /// 長さ
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    /// 空かどうか
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// 現在の内容
+    pub fn as_slice(&self) -> &[T] {
+        &self.data
+    }
+
+    /// i番目の要素をxにする
+    pub fn set(&mut self, i: usize, x: T) {
+        let old = std::mem::replace(&mut self.data[i], x);
+        self.history.push(VecOp::Set(i, old));
+    }
+
+    /// 末尾にxを追加する
+    pub fn push(&mut self, x: T) {
+        self.data.push(x);
+        self.history.push(VecOp::Push);
+    }
+
+    /// 末尾の要素を削除して返す
+    pub fn pop(&mut self) -> Option<T> {
+        let x = self.data.pop()?;
+        self.history.push(VecOp::Pop(x.clone()));
+        Some(x)
+    }
+
+    /// 現在の状態を表す値（`rollback`に渡す）
+    pub fn snapshot(&self) -> usize {
+        self.history.len()
+    }
+
+    /// `snapshot`を取得した時点の状態に戻す
+    pub fn rollback(&mut self, snapshot: usize) {
+        assert!(snapshot <= self.history.len(), "invalid snapshot");
+        while self.history.len() > snapshot {
+            match self.history.pop().unwrap() {
+                VecOp::Set(i, old) => self.data[i] = old,
+                VecOp::Push => {
+                    self.data.pop();
+                }
+                VecOp::Pop(x) => self.data.push(x),
+            }
+        }
+    }
+}
+
+impl<T> Index<usize> for UndoVec<T> {
+    type Output = T;
+    fn index(&self, i: usize) -> &T {
+        &self.data[i]
+    }
+}
+
+/// 変更を巻き戻せるハッシュマップ
+///
+/// `UndoVec`と同様に、`snapshot`で取得した時点まで`rollback`で戻せます。
+///
+/// # 計算量
+/// - 変更・参照: 期待O(1)
+/// - 巻き戻し: 取り消す変更の個数に比例
+///
+/// # 使用例
+/// ```
+/// # use rust_macro::UndoMap;
+/// let mut m = UndoMap::new();
+/// m.insert("a", 1);
+/// let s = m.snapshot();
+/// m.insert("a", 2);
+/// m.insert("b", 3);
+/// m.remove(&"a");
+/// assert_eq!((m.get(&"a"), m.get(&"b")), (None, Some(&3)));
+/// m.rollback(s);
+/// assert_eq!((m.get(&"a"), m.get(&"b")), (Some(&1), None));
+/// ```
+#[derive(Debug, Clone)]
+pub struct UndoMap<K, V> {
+    map: HashMap<K, V, BuildHasherDefault<FxHasher>>,
+    /// (キー, 変更前の値)
+    history: Vec<(K, Option<V>)>,
+}
+
+impl<K: Hash + Eq + Clone, V: Clone> Default for UndoMap<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Hash + Eq + Clone, V: Clone> UndoMap<K, V> {
+    /// 空のマップを作成
+    pub fn new() -> Self {
+        UndoMap {
+            map: HashMap::default(),
+            history: Vec::new(),
+        }
+    }
+
+    /// 要素数
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    /// 空かどうか
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    /// キーkの値
+    pub fn get(&self, k: &K) -> Option<&V> {
+        self.map.get(k)
+    }
+
+    /// キーkを含むかどうか
+    pub fn contains_key(&self, k: &K) -> bool {
+        self.map.contains_key(k)
+    }
+
+    /// キーkの値をvにし、変更前の値を返す
+    pub fn insert(&mut self, k: K, v: V) -> Option<V> {
+        let old = self.map.insert(k.clone(), v);
+        self.history.push((k, old.clone()));
+        old
+    }
+
+    /// キーkを削除し、削除した値を返す
+    pub fn remove(&mut self, k: &K) -> Option<V> {
+        let old = self.map.remove(k)?;
+        self.history.push((k.clone(), Some(old.clone())));
+        Some(old)
+    }
+
+    /// 現在の状態を表す値（`rollback`に渡す）
+    pub fn snapshot(&self) -> usize {
+        self.history.len()
+    }
+
+    /// `snapshot`を取得した時点の状態に戻す
+    pub fn rollback(&mut self, snapshot: usize) {
+        assert!(snapshot <= self.history.len(), "invalid snapshot");
+        while self.history.len() > snapshot {
+            let (k, old) = self.history.pop().unwrap();
+            match old {
+                Some(v) => self.map.insert(k, v),
+                None => self.map.remove(&k),
+            };
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::XorShift64;
+
+    #[test]
+    fn test_undo_vec_nested_rollback() {
+        let mut rng = XorShift64::new(41);
+        let mut v = UndoVec::new(vec![0u64; 5]);
+        let mut saved = Vec::new();
+        for _ in 0..300 {
+            match rng.gen_range(0..5) {
+                0 => saved.push((v.snapshot(), v.as_slice().to_vec())),
+                1 => {
+                    if let Some((s, data)) = saved.pop() {
+                        v.rollback(s);
+                        assert_eq!(v.as_slice(), &data[..]);
+                    }
+                }
+                2 => v.push(rng.gen_range(0..100)),
+                3 => {
+                    v.pop();
+                }
+                _ => {
+                    if !v.is_empty() {
+                        let i = rng.gen_range(0..v.len() as u64) as usize;
+                        v.set(i, rng.gen_range(0..100));
+                    }
+                }
+            }
+        }
+        v.rollback(0);
+        assert_eq!(v.as_slice(), &[0; 5]);
+        assert_eq!(v[4], 0);
+    }
+
+    #[test]
+    fn test_undo_map_rollback() {
+        let mut m = UndoMap::new();
+        for i in 0..10 {
+            m.insert(i, i * i);
+        }
+        let s = m.snapshot();
+        for i in 0..10 {
+            if i % 2 == 0 {
+                m.remove(&i);
+            } else {
+                m.insert(i, 0);
+            }
+        }
+        m.insert(100, 1);
+        assert_eq!(m.len(), 6);
+        m.rollback(s);
+        assert_eq!(m.len(), 10);
+        assert!((0..10).all(|i| m.get(&i) == Some(&(i * i))));
+        assert!(!m.contains_key(&100));
+        m.rollback(0);
+        assert!(m.is_empty());
+    }
+}
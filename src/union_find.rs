@@ -1,11 +1,15 @@
 //! Union-Find data structure implementation
 
 use im_rc::Vector;
+use rustc_hash::FxHasher;
+use std::collections::HashMap;
+use std::hash::{BuildHasherDefault, Hash};
 
 #[derive(Debug)]
 pub struct UnionFind {
     parent: Vec<usize>,
     size: Vec<usize>,
+    components: usize,
 }
 
 /// Persistent Union-Find (Disjoint Set Union) data structure
@@ -16,10 +20,10 @@ pub struct UnionFind {
 ///
 /// # Key Features
 ///
-/// - **Path Compression**: Flattens tree structure for better amortized performance
-/// - **Union by Size**: Attaches smaller trees to larger ones to maintain balance
+/// - **Union by Size**: Attaches smaller trees to larger ones, keeping every tree O(log n) deep
+/// - **Read-only Queries**: `find`, `same` and `size` take `&self` and never copy the structure
 /// - **Persistent**: Supports efficient cloning and maintains immutable history
-/// - **Same API**: Identical interface to the standard UnionFind structure
+/// - **Familiar API**: Same method names as `UnionFind`, but the queries need no `&mut self`
 ///
 /// # Use Cases
 ///
@@ -32,18 +36,19 @@ pub struct UnionFind {
 /// # Time Complexity
 ///
 /// - **Construction**: O(n)
-/// - **Find**: Amortized O(α(n)), worst case O(log n)
-/// - **Unite**: Amortized O(α(n)), worst case O(log n)
-/// - **Same**: Amortized O(α(n)), worst case O(log n)
-/// - **Size**: Amortized O(α(n)), worst case O(log n)
+/// - **Find**: O(log n) parent lookups
+/// - **Unite**: O(log n) parent lookups plus two vector updates
+/// - **Same**: O(log n) parent lookups
+/// - **Size**: O(log n) parent lookups
 /// - **Clone**: O(1) (shallow copy due to immutable structures)
 ///
-/// Where α is the inverse Ackermann function (practically constant for all realistic inputs).
+/// Each lookup or update of an `im_rc::Vector` is O(log₆₄ n), which is effectively constant.
+/// Path compression is not used because it would require writing a new version on every query.
 ///
 /// # Space Complexity
 ///
 /// - **Overall**: O(n) for the data structure
-/// - **Per Operation**: O(log n) due to immutable vector updates
+/// - **Per Unite**: O(log n) due to immutable vector updates
 /// - **Cloning**: O(1) due to structural sharing
 ///
 /// # Examples
@@ -76,7 +81,7 @@ pub struct UnionFind {
 /// uf1.unite(0, 1);
 ///
 /// // Create a snapshot
-/// let mut uf2 = uf1.clone();
+/// let uf2 = uf1.clone();
 ///
 /// // Modify the original
 /// uf1.unite(2, 3);
@@ -97,6 +102,7 @@ impl UnionFind {
         UnionFind {
             parent: (0..n).collect(),
             size: vec![1; n],
+            components: n,
         }
     }
 
@@ -128,6 +134,7 @@ impl UnionFind {
             self.parent[y_root] = x_root;
             self.size[x_root] += self.size[y_root];
         }
+        self.components -= 1;
     }
 
     /// Checks if `x` and `y` are in the same set
@@ -140,6 +147,48 @@ impl UnionFind {
         let root = self.find(x);
         self.size[root]
     }
+
+    /// Returns the number of disjoint sets
+    pub fn count_components(&self) -> usize {
+        self.components
+    }
+
+    /// Returns the roots of all sets in ascending order
+    pub fn roots(&mut self) -> Vec<usize> {
+        (0..self.parent.len())
+            .filter(|&x| self.find(x) == x)
+            .collect()
+    }
+
+    /// Returns the elements of each set
+    ///
+    /// Each group is sorted, and groups are ordered by their smallest element.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use rust_macro::UnionFind;
+    /// let mut uf = UnionFind::new(5);
+    /// uf.unite(3, 1);
+    /// uf.unite(4, 0);
+    /// assert_eq!(uf.count_components(), 3);
+    /// assert_eq!(uf.groups(), vec![vec![0, 4], vec![1, 3], vec![2]]);
+    /// ```
+    pub fn groups(&mut self) -> Vec<Vec<usize>> {
+        let n = self.parent.len();
+        // index[root]: position of the root's group in the result
+        let mut index = vec![usize::MAX; n];
+        let mut groups: Vec<Vec<usize>> = Vec::with_capacity(self.components);
+        for x in 0..n {
+            let root = self.find(x);
+            if index[root] == usize::MAX {
+                index[root] = groups.len();
+                groups.push(Vec::with_capacity(self.size[root]));
+            }
+            groups[index[root]].push(x);
+        }
+        groups
+    }
 }
 
 impl PersistentUnionFind {
@@ -177,9 +226,8 @@ impl PersistentUnionFind {
 
     /// Finds the root of element `x`
     ///
-    /// Finds the root (representative) of the set containing element `x` with path compression.
-    /// Path compression flattens the tree structure to improve future query performance.
-    /// Uses immutable data structures, so path compression creates a new persistent state.
+    /// Finds the root (representative) of the set containing element `x` by following parent
+    /// links. The structure is not modified, so this can be called on any shared version.
     ///
     /// # Arguments
     ///
@@ -191,12 +239,11 @@ impl PersistentUnionFind {
     ///
     /// # Time Complexity
     ///
-    /// * **Amortized O(α(n))** - Where α is the inverse Ackermann function (practically constant)
-    /// * **Worst case O(log n)** - For a single operation without prior compression
+    /// * **O(log n)** - Union by size bounds the depth of every tree
     ///
     /// # Space Complexity
     ///
-    /// * **O(log n)** - Due to immutable vector updates during path compression
+    /// * **O(1)** - No new version is created
     ///
     /// # Examples
     ///
@@ -210,23 +257,11 @@ impl PersistentUnionFind {
     /// let root3 = uf.find(3);
     /// assert_eq!(root1, root3); // Same root means same set
     /// ```
-    pub fn find(&mut self, x: usize) -> usize {
-        if self.parent[x] == x {
-            x
-        } else {
-            let p = self.clone().find_immut(self.parent[x]);
-            self.parent = self.parent.update(x, p);
-            p
-        }
-    }
-
-    /// Helper function for immutable find operations
-    fn find_immut(&self, x: usize) -> usize {
-        if self.parent[x] == x {
-            x
-        } else {
-            self.find_immut(self.parent[x])
+    pub fn find(&self, mut x: usize) -> usize {
+        while self.parent[x] != x {
+            x = self.parent[x];
         }
+        x
     }
 
     /// Unites two sets containing `x` and `y`
@@ -242,8 +277,7 @@ impl PersistentUnionFind {
     ///
     /// # Time Complexity
     ///
-    /// * **Amortized O(α(n))** - Where α is the inverse Ackermann function
-    /// * **Worst case O(log n)** - Due to find operations
+    /// * **O(log n)** - Due to find operations
     ///
     /// # Space Complexity
     ///
@@ -288,7 +322,7 @@ impl PersistentUnionFind {
     /// Checks if `x` and `y` are in the same set
     ///
     /// Determines whether two elements belong to the same connected component.
-    /// This operation may trigger path compression through the find operations.
+    /// This operation does not modify the structure.
     ///
     /// # Arguments
     ///
@@ -301,12 +335,11 @@ impl PersistentUnionFind {
     ///
     /// # Time Complexity
     ///
-    /// * **Amortized O(α(n))** - Where α is the inverse Ackermann function
-    /// * **Worst case O(log n)** - Due to two find operations
+    /// * **O(log n)** - Due to two find operations
     ///
     /// # Space Complexity
     ///
-    /// * **O(log n)** - Due to immutable vector updates during path compression
+    /// * **O(1)** - No new version is created
     ///
     /// # Examples
     ///
@@ -319,14 +352,14 @@ impl PersistentUnionFind {
     /// assert!(uf.same(0, 1)); // Now connected
     /// assert!(!uf.same(0, 2)); // Still separate from 2
     /// ```
-    pub fn same(&mut self, x: usize, y: usize) -> bool {
+    pub fn same(&self, x: usize, y: usize) -> bool {
         self.find(x) == self.find(y)
     }
 
     /// Returns the size of the set containing `x`
     ///
     /// Gets the number of elements in the connected component containing element `x`.
-    /// This operation may trigger path compression through the find operation.
+    /// This operation does not modify the structure.
     ///
     /// # Arguments
     ///
@@ -338,12 +371,11 @@ impl PersistentUnionFind {
     ///
     /// # Time Complexity
     ///
-    /// * **Amortized O(α(n))** - Where α is the inverse Ackermann function
-    /// * **Worst case O(log n)** - Due to find operation
+    /// * **O(log n)** - Due to find operation
     ///
     /// # Space Complexity
     ///
-    /// * **O(log n)** - Due to immutable vector updates during path compression
+    /// * **O(1)** - No new version is created
     ///
     /// # Examples
     ///
@@ -357,15 +389,260 @@ impl PersistentUnionFind {
     /// uf.unite(0, 2);
     /// assert_eq!(uf.size(1), 3); // All connected elements have size 3
     /// ```
-    pub fn size(&mut self, x: usize) -> usize {
-        let root = self.find(x);
-        self.size[root]
+    pub fn size(&self, x: usize) -> usize {
+        self.size[self.find(x)]
+    }
+}
+
+/// Union-Find over arbitrary hashable keys
+///
+/// Keys are assigned consecutive ids on first use (the same way `Graph` does with
+/// `coord_map` / `reverse_map`), so strings, tuples or large coordinates can be
+/// united directly. Keys that have never been seen behave as singleton sets.
+///
+/// # Time Complexity
+///
+/// Same as `UnionFind`, plus expected O(1) hashing per key.
+///
+/// # Examples
+///
+/// ```rust
+/// # use rust_macro::MappedUnionFind;
+/// let mut uf = MappedUnionFind::new();
+/// uf.unite("alice", "bob");
+/// uf.unite("carol", "dave");
+/// uf.unite("bob", "dave");
+/// assert!(uf.same(&"alice", &"carol"));
+/// assert!(!uf.same(&"alice", &"eve"));
+/// assert_eq!(uf.size(&"dave"), 4);
+/// assert_eq!(uf.size(&"eve"), 1);
+/// assert_eq!(uf.count_components(), 1);
+/// ```
+#[derive(Debug)]
+pub struct MappedUnionFind<I: Hash + Eq + Clone> {
+    uf: UnionFind,
+    coord_map: HashMap<I, usize, BuildHasherDefault<FxHasher>>,
+    reverse_map: Vec<I>,
+}
+
+impl<I: Hash + Eq + Clone> Default for MappedUnionFind<I> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<I: Hash + Eq + Clone> MappedUnionFind<I> {
+    /// Creates an empty structure
+    pub fn new() -> Self {
+        MappedUnionFind {
+            uf: UnionFind::new(0),
+            coord_map: HashMap::default(),
+            reverse_map: Vec::new(),
+        }
+    }
+
+    /// Returns the number of registered keys
+    pub fn len(&self) -> usize {
+        self.reverse_map.len()
+    }
+
+    /// Returns `true` if no key has been registered
+    pub fn is_empty(&self) -> bool {
+        self.reverse_map.is_empty()
+    }
+
+    /// Returns the id of `key`, if it has been registered
+    pub fn get_id(&self, key: &I) -> Option<usize> {
+        self.coord_map.get(key).copied()
+    }
+
+    /// Returns the id of `key`, registering it as a new singleton set if needed
+    pub fn get_or_create_id(&mut self, key: I) -> usize {
+        if let Some(id) = self.get_id(&key) {
+            return id;
+        }
+        let id = self.reverse_map.len();
+        self.coord_map.insert(key.clone(), id);
+        self.reverse_map.push(key);
+        self.uf.parent.push(id);
+        self.uf.size.push(1);
+        self.uf.components += 1;
+        id
+    }
+
+    /// Returns the key with the given id
+    pub fn key(&self, id: usize) -> &I {
+        &self.reverse_map[id]
+    }
+
+    /// Finds the representative key of the set containing `key`
+    ///
+    /// Unlike `same` and `size`, an unseen `key` is registered as a new singleton set,
+    /// so `len` and `count_components` both grow by one. Use `get_id` to look a key up
+    /// without registering it.
+    pub fn find(&mut self, key: I) -> &I {
+        let id = self.get_or_create_id(key);
+        let root = self.uf.find(id);
+        &self.reverse_map[root]
+    }
+
+    /// Unites two sets containing `x` and `y`
+    pub fn unite(&mut self, x: I, y: I) {
+        let x = self.get_or_create_id(x);
+        let y = self.get_or_create_id(y);
+        self.uf.unite(x, y);
+    }
+
+    /// Checks if `x` and `y` are in the same set
+    pub fn same(&mut self, x: &I, y: &I) -> bool {
+        match (self.get_id(x), self.get_id(y)) {
+            (Some(x), Some(y)) => self.uf.same(x, y),
+            _ => x == y,
+        }
+    }
+
+    /// Returns the size of the set containing `x`
+    pub fn size(&mut self, x: &I) -> usize {
+        self.get_id(x).map_or(1, |id| self.uf.size(id))
+    }
+
+    /// Returns the number of disjoint sets among the registered keys
+    pub fn count_components(&self) -> usize {
+        self.uf.count_components()
+    }
+
+    /// Returns the keys of each set
+    ///
+    /// Groups and the keys in them are ordered by registration order.
+    pub fn groups(&mut self) -> Vec<Vec<I>> {
+        self.uf
+            .groups()
+            .into_iter()
+            .map(|g| {
+                g.into_iter()
+                    .map(|id| self.reverse_map[id].clone())
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+/// Union-Find that can undo `unite` operations
+///
+/// Uses union by size without path compression, so every operation is O(log n) and each
+/// `unite` can be reverted in O(1). Useful for offline dynamic connectivity
+/// (segment tree over time) and for backtracking searches.
+///
+/// # Time Complexity
+///
+/// - **Find / Unite / Same / Size**: O(log n)
+/// - **Undo**: O(1)
+///
+/// # Examples
+///
+/// ```rust
+/// # use rust_macro::RollbackUnionFind;
+/// let mut uf = RollbackUnionFind::new(4);
+/// uf.unite(0, 1);
+/// let snap = uf.snapshot();
+/// uf.unite(1, 2);
+/// uf.unite(2, 3);
+/// assert!(uf.same(0, 3));
+///
+/// uf.rollback(snap);
+/// assert!(uf.same(0, 1));
+/// assert!(!uf.same(0, 2));
+/// ```
+#[derive(Debug, Clone)]
+pub struct RollbackUnionFind {
+    parent: Vec<usize>,
+    size: Vec<usize>,
+    /// One entry per `unite` call: the root that was attached to another root, if any
+    history: Vec<Option<usize>>,
+}
+
+impl RollbackUnionFind {
+    /// Creates a new Union-Find structure with `n` elements
+    pub fn new(n: usize) -> Self {
+        RollbackUnionFind {
+            parent: (0..n).collect(),
+            size: vec![1; n],
+            history: Vec::new(),
+        }
+    }
+
+    /// Finds the root of element `x`
+    pub fn find(&self, mut x: usize) -> usize {
+        while self.parent[x] != x {
+            x = self.parent[x];
+        }
+        x
+    }
+
+    /// Unites two sets containing `x` and `y`
+    ///
+    /// Returns `true` if they were in different sets. Every call, including one that
+    /// merges nothing, is recorded and can be reverted by `undo`.
+    pub fn unite(&mut self, x: usize, y: usize) -> bool {
+        let (mut x_root, mut y_root) = (self.find(x), self.find(y));
+        if x_root == y_root {
+            self.history.push(None);
+            return false;
+        }
+        if self.size[x_root] < self.size[y_root] {
+            std::mem::swap(&mut x_root, &mut y_root);
+        }
+        self.parent[y_root] = x_root;
+        self.size[x_root] += self.size[y_root];
+        self.history.push(Some(y_root));
+        true
+    }
+
+    /// Reverts the most recent `unite` call. Returns `false` if there is nothing to undo
+    pub fn undo(&mut self) -> bool {
+        let Some(entry) = self.history.pop() else {
+            return false;
+        };
+        if let Some(child) = entry {
+            let root = self.parent[child];
+            self.parent[child] = child;
+            self.size[root] -= self.size[child];
+        }
+        true
+    }
+
+    /// Returns a token describing the current state, to be passed to `rollback`
+    pub fn snapshot(&self) -> usize {
+        self.history.len()
+    }
+
+    /// Reverts all `unite` calls made after `snapshot` was taken
+    ///
+    /// # Panics
+    ///
+    /// Panics if the state has already been rolled back past `snapshot`.
+    pub fn rollback(&mut self, snapshot: usize) {
+        assert!(snapshot <= self.history.len(), "snapshot is in the future");
+        while self.history.len() > snapshot {
+            self.undo();
+        }
+    }
+
+    /// Checks if `x` and `y` are in the same set
+    pub fn same(&self, x: usize, y: usize) -> bool {
+        self.find(x) == self.find(y)
+    }
+
+    /// Returns the size of the set containing `x`
+    pub fn size(&self, x: usize) -> usize {
+        self.size[self.find(x)]
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::XorShift64;
 
     #[test]
     fn test_union_find() {
@@ -390,6 +667,68 @@ mod tests {
         assert_eq!(uf.size(0), 4);
     }
 
+    #[test]
+    fn test_union_find_groups() {
+        let mut rng = XorShift64::new(4294);
+        for n in [0usize, 1, 10] {
+            let mut uf = UnionFind::new(n);
+            let mut label: Vec<usize> = (0..n).collect();
+            for _ in 0..20 {
+                if n > 0 {
+                    let (x, y) = (
+                        rng.gen_range(0..n as u64) as usize,
+                        rng.gen_range(0..n as u64) as usize,
+                    );
+                    uf.unite(x, y);
+                    let (from, to) = (label[y], label[x]);
+                    label
+                        .iter_mut()
+                        .filter(|l| **l == from)
+                        .for_each(|l| *l = to);
+                }
+                let groups = uf.groups();
+                let mut expected: Vec<Vec<usize>> = vec![];
+                for x in 0..n {
+                    if (0..x).all(|y| label[y] != label[x]) {
+                        expected.push((x..n).filter(|&y| label[y] == label[x]).collect());
+                    }
+                }
+                assert_eq!(groups, expected);
+                assert_eq!(uf.count_components(), expected.len());
+                let roots = uf.roots();
+                assert_eq!(roots.len(), expected.len());
+                assert!(roots.iter().all(|&r| uf.find(r) == r));
+            }
+        }
+    }
+
+    #[test]
+    fn test_mapped_union_find() {
+        let mut rng = XorShift64::new(4295);
+        let mut uf = MappedUnionFind::new();
+        let mut naive = UnionFind::new(20);
+        let key = |x: usize| (x % 4, format!("k{}", x));
+        for _ in 0..100 {
+            let (x, y) = (rng.gen_range(0..20) as usize, rng.gen_range(0..20) as usize);
+            uf.unite(key(x), key(y));
+            naive.unite(x, y);
+            let (a, b) = (rng.gen_range(0..20) as usize, rng.gen_range(0..20) as usize);
+            let registered = |v: usize| uf.get_id(&key(v)).is_some();
+            if registered(a) && registered(b) {
+                assert_eq!(uf.same(&key(a), &key(b)), naive.same(a, b));
+                assert_eq!(uf.size(&key(a)), naive.size(a));
+            }
+        }
+        let (before, components) = (uf.len(), uf.count_components());
+        let rep = uf.find((9, "new".to_string())).clone();
+        assert_eq!(rep, (9, "new".to_string()));
+        assert_eq!(uf.len(), before + 1);
+        assert_eq!(uf.count_components(), components + 1);
+        assert_eq!(uf.groups().iter().map(Vec::len).sum::<usize>(), uf.len());
+        assert_eq!(uf.groups().len(), uf.count_components());
+        assert!(!uf.same(&(0, "x".to_string()), &(0, "y".to_string())));
+    }
+
     #[test]
     fn test_persistent_union_find() {
         let mut uf = PersistentUnionFind::new(5);
@@ -419,7 +758,7 @@ mod tests {
         uf1.unite(0, 1);
 
         // Clone at this state
-        let mut uf2 = uf1.clone();
+        let uf2 = uf1.clone();
 
         // Modify original
         uf1.unite(2, 3);
@@ -429,4 +768,77 @@ mod tests {
         assert!(!uf2.same(2, 3));
         assert!(uf2.same(0, 1));
     }
+
+    #[test]
+    fn test_persistent_versions_against_naive() {
+        let mut rng = XorShift64::new(4296);
+        let n = 50;
+        // Branch new versions off random older ones and check every version at the end
+        let mut versions = vec![PersistentUnionFind::new(n)];
+        let mut edges: Vec<Vec<(usize, usize)>> = vec![vec![]];
+        for _ in 0..200 {
+            let k = rng.gen_range(0..versions.len() as u64) as usize;
+            let (x, y) = (
+                rng.gen_range(0..n as u64) as usize,
+                rng.gen_range(0..n as u64) as usize,
+            );
+            let mut uf = versions[k].clone();
+            uf.unite(x, y);
+            versions.push(uf);
+            let mut e = edges[k].clone();
+            e.push((x, y));
+            edges.push(e);
+        }
+        for (uf, e) in versions.iter().zip(&edges) {
+            let mut naive = UnionFind::new(n);
+            for &(x, y) in e {
+                naive.unite(x, y);
+            }
+            for x in 0..n {
+                assert_eq!(uf.size(x), naive.size(x));
+                for y in 0..n {
+                    assert_eq!(uf.same(x, y), naive.same(x, y));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_rollback_union_find_against_naive() {
+        let mut rng = XorShift64::new(4293);
+        let n = 8;
+        let mut uf = RollbackUnionFind::new(n);
+        // Naive labels after each unite call (same label means same set)
+        let mut states = vec![(0..n).collect::<Vec<usize>>()];
+        for _ in 0..500 {
+            if rng.gen_range(0..3) == 0 && states.len() > 1 {
+                let k = rng.gen_range(1..states.len() as u64) as usize;
+                uf.rollback(k - 1);
+                states.truncate(k);
+            } else {
+                let (x, y) = (
+                    rng.gen_range(0..n as u64) as usize,
+                    rng.gen_range(0..n as u64) as usize,
+                );
+                let mut label = states.last().unwrap().clone();
+                assert_eq!(uf.unite(x, y), label[x] != label[y]);
+                let (from, to) = (label[y], label[x]);
+                label
+                    .iter_mut()
+                    .filter(|l| **l == from)
+                    .for_each(|l| *l = to);
+                states.push(label);
+            }
+            let label = states.last().unwrap();
+            assert_eq!(uf.snapshot(), states.len() - 1);
+            for x in 0..n {
+                assert_eq!(uf.size(x), label.iter().filter(|&&l| l == label[x]).count());
+                for y in 0..n {
+                    assert_eq!(uf.same(x, y), label[x] == label[y]);
+                }
+            }
+        }
+        uf.rollback(0);
+        assert!(!uf.undo());
+    }
 }
@@ -0,0 +1,181 @@
+/// 上下左右の4方向（右・下・左・上の順）
+pub const DIR4: [(isize, isize); 4] = [(0, 1), (1, 0), (0, -1), (-1, 0)];
+
+/// 斜めを含む8方向（右から時計回り）
+pub const DIR8: [(isize, isize); 8] = [
+    (0, 1),
+    (1, 1),
+    (1, 0),
+    (1, -1),
+    (0, -1),
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+];
+
+/// 位置(i, j)を(di, dj)だけ移動した位置がh×wのグリッド内にあれば返す
+pub fn step(
+    i: usize,
+    j: usize,
+    (di, dj): (isize, isize),
+    h: usize,
+    w: usize,
+) -> Option<(usize, usize)> {
+    let ni = i.checked_add_signed(di)?;
+    let nj = j.checked_add_signed(dj)?;
+    (ni < h && nj < w).then_some((ni, nj))
+}
+
+/// h×wのグリッドで(i, j)に上下左右で隣接するマスを列挙する
+///
+/// # 使用例
+/// ```
+/// # use rust_macro::utils::grid::neighbors4;
+/// let v: Vec<_> = neighbors4(0, 0, 2, 3).collect();
+/// assert_eq!(v, vec![(0, 1), (1, 0)]);
+/// ```
+pub fn neighbors4(i: usize, j: usize, h: usize, w: usize) -> impl Iterator<Item = (usize, usize)> {
+    DIR4.iter().filter_map(move |&d| step(i, j, d, h, w))
+}
+
+/// h×wのグリッドで(i, j)に斜めを含めて隣接するマスを列挙する
+pub fn neighbors8(i: usize, j: usize, h: usize, w: usize) -> impl Iterator<Item = (usize, usize)> {
+    DIR8.iter().filter_map(move |&d| step(i, j, d, h, w))
+}
+
+/// グリッドを時計回りに90度回転する
+///
+/// h×wのグリッドはw×hになります。
+///
+/// # 使用例
+/// ```
+/// # use rust_macro::utils::grid::rotate90;
+/// let g = vec![vec![1, 2, 3], vec![4, 5, 6]];
+/// assert_eq!(rotate90(&g), vec![vec![4, 1], vec![5, 2], vec![6, 3]]);
+/// ```
+pub fn rotate90<T: Clone>(grid: &[Vec<T>]) -> Vec<Vec<T>> {
+    let h = grid.len();
+    let w = grid.first().map_or(0, |row| row.len());
+    (0..w)
+        .map(|j| (0..h).rev().map(|i| grid[i][j].clone()).collect())
+        .collect()
+}
+
+/// グリッドを転置する
+pub fn transpose<T: Clone>(grid: &[Vec<T>]) -> Vec<Vec<T>> {
+    let h = grid.len();
+    let w = grid.first().map_or(0, |row| row.len());
+    (0..w)
+        .map(|j| (0..h).map(|i| grid[i][j].clone()).collect())
+        .collect()
+}
+
+/// グリッドを左右反転する
+pub fn flip_horizontal<T: Clone>(grid: &[Vec<T>]) -> Vec<Vec<T>> {
+    grid.iter()
+        .map(|row| row.iter().rev().cloned().collect())
+        .collect()
+}
+
+/// グリッドを上下反転する
+pub fn flip_vertical<T: Clone>(grid: &[Vec<T>]) -> Vec<Vec<T>> {
+    grid.iter().rev().cloned().collect()
+}
+
+/// 文字列の各行を文字のグリッドに変換する
+///
+/// # 使用例
+/// ```
+/// # use rust_macro::utils::grid::parse_grid;
+/// let lines = vec!["#.".to_string(), ".#".to_string()];
+/// assert_eq!(parse_grid(&lines), vec![vec!['#', '.'], vec!['.', '#']]);
+/// ```
+pub fn parse_grid<S: AsRef<str>>(lines: &[S]) -> Vec<Vec<char>> {
+    lines.iter().map(|s| s.as_ref().chars().collect()).collect()
+}
+
+/// グリッドの周囲を番兵sentinelで1マス分囲む
+///
+/// h×wのグリッドは(h+2)×(w+2)になり、元の(i, j)は(i+1, j+1)に移ります。
+/// 探索やシミュレーションで番兵を壁として扱えば、境界のチェックが不要になります。
+///
+/// # 使用例
+/// ```
+/// # use rust_macro::utils::grid::pad_grid;
+/// let g = vec![vec!['.']];
+/// assert_eq!(
+///     pad_grid(&g, '#'),
+///     vec![vec!['#', '#', '#'], vec!['#', '.', '#'], vec!['#', '#', '#']]
+/// );
+/// ```
+pub fn pad_grid<T: Clone>(grid: &[Vec<T>], sentinel: T) -> Vec<Vec<T>> {
+    let w = grid.first().map_or(0, |row| row.len());
+    let mut res = Vec::with_capacity(grid.len() + 2);
+    res.push(vec![sentinel.clone(); w + 2]);
+    for row in grid {
+        let mut r = Vec::with_capacity(w + 2);
+        r.push(sentinel.clone());
+        r.extend(row.iter().cloned());
+        r.push(sentinel.clone());
+        res.push(r);
+    }
+    res.push(vec![sentinel; w + 2]);
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_neighbors() {
+        let mut v: Vec<_> = neighbors4(1, 1, 3, 3).collect();
+        v.sort();
+        assert_eq!(v, vec![(0, 1), (1, 0), (1, 2), (2, 1)]);
+        assert_eq!(neighbors8(1, 1, 3, 3).count(), 8);
+        assert_eq!(neighbors8(0, 2, 3, 3).count(), 3);
+        assert_eq!(neighbors4(0, 0, 1, 1).count(), 0);
+        assert_eq!(step(0, 0, (-1, 0), 5, 5), None);
+        assert_eq!(step(4, 3, (0, 1), 5, 5), Some((4, 4)));
+    }
+
+    #[test]
+    fn test_transformations() {
+        let g = vec![vec!['a', 'b', 'c'], vec!['d', 'e', 'f']];
+        let r = rotate90(&g);
+        assert_eq!(r, vec![vec!['d', 'a'], vec!['e', 'b'], vec!['f', 'c']]);
+        assert_eq!(rotate90(&rotate90(&rotate90(&r))), g);
+        assert_eq!(
+            transpose(&g),
+            vec![vec!['a', 'd'], vec!['b', 'e'], vec!['c', 'f']]
+        );
+        assert_eq!(
+            flip_horizontal(&g),
+            vec![vec!['c', 'b', 'a'], vec!['f', 'e', 'd']]
+        );
+        assert_eq!(flip_vertical(&g), vec![g[1].clone(), g[0].clone()]);
+        assert!(rotate90::<i32>(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_parse_and_pad_grid() {
+        let g = parse_grid(&["ab", "cd"]);
+        let p = pad_grid(&g, '#');
+        assert_eq!(p.len(), 4);
+        assert!(p.iter().all(|row| row.len() == 4));
+        assert_eq!(p[1][1..3], ['a', 'b']);
+        assert_eq!(p[2][1..3], ['c', 'd']);
+        assert_eq!(p[3], vec!['#'; 4]);
+        // 番兵に囲まれた内側では境界チェックなしに隣接マスを参照できる
+        let open = (1..3)
+            .flat_map(|i| (1..3).map(move |j| (i, j)))
+            .filter(|&(i, j)| {
+                DIR4.iter().any(|&(di, dj)| {
+                    p[(i as isize + di) as usize][(j as isize + dj) as usize] == '#'
+                })
+            })
+            .count();
+        assert_eq!(open, 4);
+        assert_eq!(pad_grid::<u8>(&[], 0), vec![vec![0, 0], vec![0, 0]]);
+    }
+}
@@ -0,0 +1,669 @@
+pub mod grid;
+pub mod permutation;
+pub mod random;
+pub mod runner;
+pub mod scanner;
+pub mod search;
+pub mod timer;
+pub mod writer;
+
+use bitvec::prelude::*;
+use std::collections::BTreeMap;
+use std::ops::Sub;
+
+pub use permutation::{next_permutation, permutations, prev_permutation, Permutations};
+pub use random::XorShift64;
+pub use runner::{run_cases, run_with_stack};
+pub use scanner::Scanner;
+pub use timer::TimeKeeper;
+pub use writer::OutputWriter;
+
+/// 値の座圧（座標圧縮）を行う構造体
+#[derive(Debug, Clone)]
+pub struct Compress<T> {
+    /// 値から圧縮後のインデックスへのマッピング
+    mapping: BTreeMap<T, usize>,
+    /// 圧縮後のインデックスから元の値への逆変換テーブル
+    rev: Vec<T>,
+}
+
+impl<T: Ord + Clone> Compress<T> {
+    /// 値のリストから座圧構造体を生成する
+    ///
+    /// # 引数
+    /// * `values` - 圧縮したい値のベクタ
+    ///
+    /// # 例
+    /// ```
+    /// use rust_macro::utils::Compress;
+    /// let c = Compress::new(vec![100, 200, 100, 300]);
+    /// assert_eq!(c.get(&100), 0);
+    /// assert_eq!(c.get(&200), 1);
+    /// assert_eq!(c.get(&300), 2);
+    /// ```
+    pub fn new(mut values: Vec<T>) -> Self {
+        values.sort();
+        values.dedup();
+        let mapping = values
+            .iter()
+            .enumerate()
+            .map(|(i, v)| (v.clone(), i))
+            .collect();
+        Compress {
+            mapping,
+            rev: values,
+        }
+    }
+
+    /// 複数のイテレータの値をまとめて座圧する
+    ///
+    /// 区間の両端など、別々に持っている値の列を一時的なベクタに集めずに渡せます。
+    ///
+    /// # 例
+    /// ```
+    /// use rust_macro::utils::Compress;
+    /// let intervals = vec![(1, 5), (3, 10)];
+    /// let c = Compress::from_iters(intervals.iter().map(|&(l, r)| [l, r]));
+    /// assert_eq!(c.size(), 4);
+    /// assert_eq!(c.get(&5), 2);
+    /// ```
+    pub fn from_iters<I, J>(iters: I) -> Self
+    where
+        I: IntoIterator<Item = J>,
+        J: IntoIterator<Item = T>,
+    {
+        Self::new(iters.into_iter().flatten().collect())
+    }
+
+    /// スライスの各値を圧縮後のインデックスに変換する
+    ///
+    /// # パニック
+    /// 含まれない値がある場合panicします
+    pub fn compress_slice(&self, xs: &[T]) -> Vec<usize> {
+        xs.iter().map(|x| self.get(x)).collect()
+    }
+
+    /// スライスの各値を圧縮後のインデックスに変換する（`compress_slice`の別名）
+    pub fn compress_vec(&self, values: &[T]) -> Vec<usize> {
+        self.compress_slice(values)
+    }
+
+    /// 値xの圧縮後のインデックスを取得する
+    ///
+    /// # パニック
+    /// xが存在しない場合panicします
+    pub fn get(&self, x: &T) -> usize {
+        self.mapping[x]
+    }
+
+    /// 値xの圧縮後のインデックスを取得する。xが存在しない場合は`None`
+    pub fn try_get(&self, x: &T) -> Option<usize> {
+        self.mapping.get(x).copied()
+    }
+
+    /// 値xの圧縮後のインデックスを取得する（`try_get`の別名）
+    pub fn get_checked(&self, x: &T) -> Option<usize> {
+        self.try_get(x)
+    }
+
+    /// x以上の値のうち最小のものの圧縮後のインデックスを返す
+    ///
+    /// xより小さい値の種類数と等しく、該当する値がない場合は`size()`を返します。
+    ///
+    /// # 例
+    /// ```
+    /// use rust_macro::utils::Compress;
+    /// let c = Compress::new(vec![10, 20, 30]);
+    /// assert_eq!(c.lower_bound(&20), 1);
+    /// assert_eq!(c.lower_bound(&25), 2);
+    /// assert_eq!(c.upper_bound(&20), 2);
+    /// assert_eq!(c.lower_bound(&40), 3);
+    /// ```
+    pub fn lower_bound(&self, x: &T) -> usize {
+        self.rev.partition_point(|v| v < x)
+    }
+
+    /// xより大きい値のうち最小のものの圧縮後のインデックスを返す
+    ///
+    /// x以下の値の種類数と等しく、該当する値がない場合は`size()`を返します。
+    pub fn upper_bound(&self, x: &T) -> usize {
+        self.rev.partition_point(|v| v <= x)
+    }
+
+    /// 圧縮後の値の種類数を返す
+    pub fn size(&self) -> usize {
+        self.rev.len()
+    }
+
+    /// 圧縮後のインデックスiに対応する元の値を返す
+    pub fn rev(&self, i: usize) -> &T {
+        &self.rev[i]
+    }
+}
+
+/// x軸・y軸をまとめて座圧する構造体
+///
+/// 点や長方形の集合から両軸の座標を集めて圧縮し、長方形を圧縮後のグリッドのセルの範囲に
+/// 変換します。圧縮後のセル(i, j)は [xs[i], xs[i+1]) × [ys[j], ys[j+1]) の領域を表し、
+/// その大きさは`cell_size`で取得できます。`Imos2D`や`CumulativeSum2D`と組み合わせて使います。
+///
+/// # 例
+/// ```
+/// use rust_macro::{Compress2D, Imos2D};
+/// // 長方形の和集合の面積
+/// let rects = vec![(0, 0, 2, 2), (1, 1, 3, 3)];
+/// let c = Compress2D::from_rects(rects.iter().copied());
+/// let (h, w) = c.size();
+/// let mut imos = Imos2D::new(h, w);
+/// for &(x1, y1, x2, y2) in &rects {
+///     let (i1, j1, i2, j2) = c.rect(&x1, &y1, &x2, &y2);
+///     imos.add(i1, j1, i2, j2, 1);
+/// }
+/// let grid = imos.build();
+/// let mut area = 0;
+/// for i in 0..h - 1 {
+///     for j in 0..w - 1 {
+///         if grid[i][j] > 0 {
+///             let (dx, dy) = c.cell_size(i, j);
+///             area += dx * dy;
+///         }
+///     }
+/// }
+/// assert_eq!(area, 7);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Compress2D<T> {
+    xs: Compress<T>,
+    ys: Compress<T>,
+}
+
+impl<T: Ord + Clone> Compress2D<T> {
+    /// 点(x, y)の集合から構築する
+    pub fn from_points<I>(points: I) -> Self
+    where
+        I: IntoIterator<Item = (T, T)>,
+    {
+        let (xs, ys) = points.into_iter().unzip();
+        Compress2D {
+            xs: Compress::new(xs),
+            ys: Compress::new(ys),
+        }
+    }
+
+    /// 長方形(x1, y1, x2, y2)の集合から構築する（各長方形の四隅の座標を圧縮する）
+    pub fn from_rects<I>(rects: I) -> Self
+    where
+        I: IntoIterator<Item = (T, T, T, T)>,
+    {
+        let mut xs = Vec::new();
+        let mut ys = Vec::new();
+        for (x1, y1, x2, y2) in rects {
+            xs.push(x1);
+            xs.push(x2);
+            ys.push(y1);
+            ys.push(y2);
+        }
+        Compress2D {
+            xs: Compress::new(xs),
+            ys: Compress::new(ys),
+        }
+    }
+
+    /// x軸の座圧
+    pub fn xs(&self) -> &Compress<T> {
+        &self.xs
+    }
+
+    /// y軸の座圧
+    pub fn ys(&self) -> &Compress<T> {
+        &self.ys
+    }
+
+    /// 各軸の座標の種類数 (x軸, y軸)
+    pub fn size(&self) -> (usize, usize) {
+        (self.xs.size(), self.ys.size())
+    }
+
+    /// 点(x, y)の圧縮後の座標を取得する
+    ///
+    /// # パニック
+    /// x, yのいずれかが存在しない場合panicします
+    pub fn get(&self, x: &T, y: &T) -> (usize, usize) {
+        (self.xs.get(x), self.ys.get(y))
+    }
+
+    /// 長方形[x1, x2) × [y1, y2)を圧縮後のセルの範囲 (i1, j1, i2, j2) に変換する
+    ///
+    /// 座標が圧縮に含まれていない場合も`lower_bound`で位置づけるため、
+    /// 返り値の範囲は元の長方形に完全に含まれるセルの集合になります。
+    pub fn rect(&self, x1: &T, y1: &T, x2: &T, y2: &T) -> (usize, usize, usize, usize) {
+        let (i1, i2) = (self.xs.lower_bound(x1), self.xs.lower_bound(x2));
+        let (j1, j2) = (self.ys.lower_bound(y1), self.ys.lower_bound(y2));
+        (i1, j1, i2.max(i1), j2.max(j1))
+    }
+}
+
+impl<T: Ord + Clone + Sub<Output = T>> Compress2D<T> {
+    /// セル(i, j)の各軸方向の幅 (xs[i+1] - xs[i], ys[j+1] - ys[j])
+    ///
+    /// # パニック
+    /// `i + 1 < size().0` かつ `j + 1 < size().1` を満たさない場合panicします
+    pub fn cell_size(&self, i: usize, j: usize) -> (T, T) {
+        (
+            self.xs.rev(i + 1).clone() - self.xs.rev(i).clone(),
+            self.ys.rev(j + 1).clone() - self.ys.rev(j).clone(),
+        )
+    }
+}
+
+/// 真偽値に応じて"Yes"/"No"を返すユーティリティ関数
+///
+/// 出力はしないので、`println!`や`OutputWriter`と組み合わせて使います。
+/// 直接出力したい場合は`print_yesno`を使ってください。
+///
+/// # 使用例
+/// ```
+/// # use rust_macro::utils::{yesno, yesno_custom, YESNO};
+/// assert_eq!(yesno(true), "Yes");
+/// assert_eq!(format!("{}", YESNO(false)), "NO");
+/// assert_eq!(yesno_custom(false, "First", "Second"), "Second");
+/// ```
+#[must_use]
+pub fn yesno(b: bool) -> &'static str {
+    yesno_custom(b, "Yes", "No")
+}
+
+/// 真偽値に応じて"YES"/"NO"を返す
+#[must_use]
+#[allow(non_snake_case)]
+pub fn YESNO(b: bool) -> &'static str {
+    yesno_custom(b, "YES", "NO")
+}
+
+/// 真偽値に応じてyes/noのいずれかを返す
+#[must_use]
+pub fn yesno_custom<'a>(b: bool, yes: &'a str, no: &'a str) -> &'a str {
+    if b {
+        yes
+    } else {
+        no
+    }
+}
+
+/// 真偽値に応じて"Yes"/"No"を出力する
+pub fn print_yesno(b: bool) {
+    println!("{}", yesno(b));
+}
+
+pub fn fmt_bitvec(bits: &BitVec<usize, Msb0>) -> String {
+    bits.iter().map(|b| if *b { '1' } else { '0' }).collect()
+}
+
+pub fn fmt_u2bit(bits: usize) -> String {
+    let mut s = String::new();
+    for i in (0..30).rev() {
+        s.push(if (bits >> i) & 1 == 1 { '1' } else { '0' });
+    }
+    s
+}
+
+/// イテレータを受け取って回文であるか判定する
+pub fn is_palindrome<I, T>(iter: I) -> bool
+where
+    I: IntoIterator<Item = T>,
+    T: PartialEq,
+{
+    let items: Vec<T> = iter.into_iter().collect();
+    items.iter().eq(items.iter().rev())
+}
+
+/// スライスが回文であるか判定する（追加のメモリを使わない）
+pub fn is_palindrome_slice<T: PartialEq>(s: &[T]) -> bool {
+    let n = s.len();
+    (0..n / 2).all(|i| s[i] == s[n - 1 - i])
+}
+
+/// スライスの区間[l, r)が回文であるか判定する
+///
+/// # 使用例
+/// ```
+/// # use rust_macro::utils::is_palindrome_range;
+/// let s = b"xabbay";
+/// assert!(is_palindrome_range(s, 1, 5));
+/// assert!(!is_palindrome_range(s, 0, 5));
+/// assert!(is_palindrome_range(s, 3, 3));
+/// ```
+pub fn is_palindrome_range<T: PartialEq>(s: &[T], l: usize, r: usize) -> bool {
+    is_palindrome_slice(&s[l..r])
+}
+
+/// 連続する等しい要素をまとめて(要素, 個数)の列にする（ランレングス圧縮）
+///
+/// # 計算量
+/// O(n)
+///
+/// # 使用例
+/// ```
+/// # use rust_macro::utils::{run_length_decode, run_length_encode};
+/// let rle = run_length_encode("aaabccaa".chars());
+/// assert_eq!(rle, vec![('a', 3), ('b', 1), ('c', 2), ('a', 2)]);
+/// assert_eq!(run_length_decode(&rle).into_iter().collect::<String>(), "aaabccaa");
+/// ```
+pub fn run_length_encode<I, T>(iter: I) -> Vec<(T, usize)>
+where
+    I: IntoIterator<Item = T>,
+    T: PartialEq,
+{
+    let mut res: Vec<(T, usize)> = Vec::new();
+    for x in iter {
+        match res.last_mut() {
+            Some((y, cnt)) if *y == x => *cnt += 1,
+            _ => res.push((x, 1)),
+        }
+    }
+    res
+}
+
+/// `run_length_encode`の逆変換
+pub fn run_length_decode<T: Clone>(runs: &[(T, usize)]) -> Vec<T> {
+    runs.iter()
+        .flat_map(|(x, cnt)| std::iter::repeat(x.clone()).take(*cnt))
+        .collect()
+}
+
+/// 回文である最長の接頭辞の長さを返す
+///
+/// s、区切り、sの逆順をつなげた列のKMPの失敗関数を求め、末尾の値を答えとします。
+///
+/// # 計算量
+/// O(n)
+///
+/// # 使用例
+/// ```
+/// # use rust_macro::utils::{longest_palindromic_prefix, longest_palindromic_suffix};
+/// let s: Vec<char> = "abacaba_xyz".chars().collect();
+/// assert_eq!(longest_palindromic_prefix(&s), 7);
+/// assert_eq!(longest_palindromic_suffix(&s), 1);
+/// ```
+pub fn longest_palindromic_prefix<T: PartialEq>(s: &[T]) -> usize {
+    match_prefix_at_end(s.iter(), s.iter().rev())
+}
+
+/// 回文である最長の接尾辞の長さを返す
+///
+/// # 計算量
+/// O(n)
+pub fn longest_palindromic_suffix<T: PartialEq>(s: &[T]) -> usize {
+    match_prefix_at_end(s.iter().rev(), s.iter())
+}
+
+/// textを読み終えた時点で、patternの接頭辞とtextの接尾辞が一致する最大の長さ
+///
+/// 区切りの`None`はどの要素とも一致しないため、答えはpatternの長さを超えません。
+fn match_prefix_at_end<'a, T: PartialEq + 'a>(
+    pattern: impl Iterator<Item = &'a T>,
+    text: impl Iterator<Item = &'a T>,
+) -> usize {
+    let joined: Vec<Option<&T>> = pattern
+        .map(Some)
+        .chain(std::iter::once(None))
+        .chain(text.map(Some))
+        .collect();
+    *crate::strings::kmp_failure(&joined).last().unwrap()
+}
+
+/// 10進数をb進数に変換して返す
+pub fn to_base(mut n: usize, base: usize) -> Vec<usize> {
+    if n == 0 {
+        return vec![0];
+    }
+    let mut digits = Vec::new();
+    while n > 0 {
+        digits.push(n % base);
+        n /= base;
+    }
+    digits.reverse();
+    digits
+}
+
+/// b進数の桁の列（上位桁から）を10進数に変換して返す。`to_base`の逆変換
+pub fn from_base(digits: &[usize], base: usize) -> usize {
+    digits.iter().fold(0, |acc, &d| acc * base + d)
+}
+
+/// 整数nを負の基数-baseで表した桁の列（上位桁から）を返す
+///
+/// 例えば-2進数では 3 = 1×4 + 1×(-2) + 1 なので `[1, 1, 1]` になります。
+///
+/// # 使用例
+/// ```
+/// # use rust_macro::utils::{to_negative_base, from_negative_base};
+/// assert_eq!(to_negative_base(3, 2), vec![1, 1, 1]);
+/// assert_eq!(to_negative_base(-2, 2), vec![1, 0]);
+/// assert_eq!(from_negative_base(&[1, 1, 1], 2), 3);
+/// ```
+pub fn to_negative_base(mut n: i64, base: usize) -> Vec<usize> {
+    assert!(base >= 2);
+    let b = -(base as i64);
+    if n == 0 {
+        return vec![0];
+    }
+    let mut digits = Vec::new();
+    while n != 0 {
+        let r = n.rem_euclid(base as i64);
+        digits.push(r as usize);
+        n = (n - r) / b;
+    }
+    digits.reverse();
+    digits
+}
+
+/// 負の基数-baseで表された桁の列（上位桁から）を整数に変換して返す
+pub fn from_negative_base(digits: &[usize], base: usize) -> i64 {
+    let b = -(base as i64);
+    digits.iter().fold(0, |acc, &d| acc * b + d as i64)
+}
+
+/// 文字列で表されたfrom進数の非負整数をto進数の文字列に変換する
+///
+/// 桁の列のまま筆算で割り算を行うため、u64に収まらない大きさの数も扱えます。
+/// 桁には`0-9`, `a-z`（大文字も可）を使い、基数は2以上36以下です。
+///
+/// # 計算量
+/// 桁数をnとしてO(n^2)
+///
+/// # 使用例
+/// ```
+/// # use rust_macro::utils::convert_base;
+/// assert_eq!(convert_base("255", 10, 16), "ff");
+/// assert_eq!(
+///     convert_base("100000000000000000000000000000", 10, 2).len(),
+///     97
+/// );
+/// ```
+///
+/// # パニック
+/// 基数が範囲外の場合や、from進数として不正な文字を含む場合panicします
+pub fn convert_base(s: &str, from: u32, to: u32) -> String {
+    assert!((2..=36).contains(&from) && (2..=36).contains(&to));
+    let mut digits: Vec<u32> = s
+        .chars()
+        .map(|c| c.to_digit(from).expect("invalid digit"))
+        .skip_while(|&d| d == 0)
+        .collect();
+    if digits.is_empty() {
+        return "0".to_string();
+    }
+    let mut res = Vec::new();
+    while !digits.is_empty() {
+        // digitsをtoで割った商と余りを求める
+        let mut rem = 0;
+        let mut quot = Vec::with_capacity(digits.len());
+        for &d in &digits {
+            let cur = rem * from + d;
+            if !quot.is_empty() || cur / to > 0 {
+                quot.push(cur / to);
+            }
+            rem = cur % to;
+        }
+        res.push(std::char::from_digit(rem, to).unwrap());
+        digits = quot;
+    }
+    res.iter().rev().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compress_bounds() {
+        let c = Compress::new(vec![5, 1, 9, 5]);
+        assert_eq!(c.try_get(&5), Some(1));
+        assert_eq!(c.try_get(&4), None);
+        for x in 0..11 {
+            let naive_lb = c.rev.iter().filter(|&&v| v < x).count();
+            let naive_ub = c.rev.iter().filter(|&&v| v <= x).count();
+            assert_eq!(c.lower_bound(&x), naive_lb);
+            assert_eq!(c.upper_bound(&x), naive_ub);
+        }
+    }
+
+    #[test]
+    fn test_compress_bulk() {
+        let a = vec![30, 10, 20];
+        let b = vec![20, 40];
+        let c = Compress::from_iters([a.clone(), b.clone()]);
+        assert_eq!(c.size(), 4);
+        assert_eq!(c.compress_slice(&a), vec![2, 0, 1]);
+        assert_eq!(c.compress_slice(&b), vec![1, 3]);
+        assert_eq!(c.compress_vec(&a), c.compress_slice(&a));
+        assert_eq!(c.get_checked(&40), Some(3));
+        assert_eq!(c.get_checked(&25), None);
+        let empty = Compress::from_iters(Vec::<Vec<i32>>::new());
+        assert_eq!(empty.size(), 0);
+    }
+
+    #[test]
+    fn test_compress2d() {
+        let c = Compress2D::from_points(vec![(5, -1), (2, 7), (5, 3)]);
+        assert_eq!(c.size(), (2, 3));
+        assert_eq!(c.get(&5, &3), (1, 1));
+        assert_eq!(c.rect(&0, &0, &6, &4), (0, 1, 2, 2));
+        assert_eq!(c.rect(&3, &8, &4, &9), (1, 3, 1, 3));
+
+        let c = Compress2D::from_rects(vec![(0, 0, 10, 4), (3, 1, 5, 6)]);
+        assert_eq!(c.size(), (4, 4));
+        assert_eq!(c.cell_size(0, 0), (3, 1));
+        assert_eq!(c.cell_size(2, 2), (5, 2));
+    }
+
+    #[test]
+    fn test_is_palindrome() {
+        assert!(is_palindrome(vec![1, 2, 3, 2, 1]));
+        assert!(is_palindrome(vec![1, 2, 2, 1]));
+        assert!(is_palindrome(vec![5]));
+        assert!(is_palindrome(Vec::<i32>::new()));
+        assert!(!is_palindrome(vec![1, 2, 3, 4]));
+        assert!(is_palindrome("racecar".chars()));
+        assert!(!is_palindrome("hello".chars()));
+    }
+
+    #[test]
+    fn test_palindrome_helpers() {
+        let s: Vec<u8> = b"abaabcbaa".to_vec();
+        for l in 0..=s.len() {
+            for r in l..=s.len() {
+                assert_eq!(is_palindrome_range(&s, l, r), is_palindrome(s[l..r].iter()));
+            }
+        }
+        for t in [
+            "", "a", "aa", "ab", "abaab", "aabaa", "abcd", "baab_baa", "zzzzy",
+        ] {
+            let t: Vec<char> = t.chars().collect();
+            let naive_pre = (0..=t.len())
+                .rev()
+                .find(|&k| is_palindrome_slice(&t[..k]))
+                .unwrap();
+            let naive_suf = (0..=t.len())
+                .rev()
+                .find(|&k| is_palindrome_slice(&t[t.len() - k..]))
+                .unwrap();
+            assert_eq!(longest_palindromic_prefix(&t), naive_pre);
+            assert_eq!(longest_palindromic_suffix(&t), naive_suf);
+        }
+    }
+
+    #[test]
+    fn test_run_length() {
+        assert_eq!(run_length_encode(Vec::<i32>::new()), vec![]);
+        assert_eq!(run_length_encode([5]), vec![(5, 1)]);
+        assert_eq!(
+            run_length_encode(vec![1, 1, 2, 1, 3, 3, 3]),
+            vec![(1, 2), (2, 1), (1, 1), (3, 3)]
+        );
+        for bits in 0..256u32 {
+            let v: Vec<u32> = (0..8).map(|i| bits >> i & 1).collect();
+            let rle = run_length_encode(v.iter().copied());
+            assert!(rle.windows(2).all(|w| w[0].0 != w[1].0));
+            assert_eq!(run_length_decode(&rle), v);
+        }
+    }
+
+    #[test]
+    fn test_from_base() {
+        for n in 0..200 {
+            for base in 2..12 {
+                assert_eq!(from_base(&to_base(n, base), base), n);
+            }
+        }
+    }
+
+    #[test]
+    fn test_negative_base() {
+        for n in -200..200 {
+            for base in 2..6 {
+                let d = to_negative_base(n, base);
+                assert!(d.iter().all(|&x| x < base));
+                assert!(d[0] != 0 || d.len() == 1);
+                assert_eq!(from_negative_base(&d, base), n);
+            }
+        }
+        assert_eq!(to_negative_base(0, 2), vec![0]);
+        assert_eq!(to_negative_base(6, 2), vec![1, 1, 0, 1, 0]);
+    }
+
+    #[test]
+    fn test_convert_base() {
+        assert_eq!(convert_base("0", 10, 2), "0");
+        assert_eq!(convert_base("000", 7, 3), "0");
+        assert_eq!(convert_base("FF", 16, 10), "255");
+        assert_eq!(convert_base("zz", 36, 10), "1295");
+        for n in 0u64..300 {
+            let s = convert_base(&n.to_string(), 10, 3);
+            assert_eq!(u64::from_str_radix(&s, 3).unwrap(), n);
+        }
+        let big = "123456789012345678901234567890";
+        assert_eq!(convert_base(&convert_base(big, 10, 7), 7, 10), big);
+    }
+
+    #[test]
+    fn test_yesno() {
+        assert_eq!(yesno(true), "Yes");
+        assert_eq!(yesno(false), "No");
+        assert_eq!(YESNO(true), "YES");
+        assert_eq!(YESNO(false), "NO");
+        assert_eq!(yesno_custom(true, "Alice", "Bob"), "Alice");
+        assert_eq!(format!("{} {}", yesno(true), yesno(false)), "Yes No");
+    }
+
+    #[test]
+    fn test_to_base() {
+        assert_eq!(to_base(0, 2), vec![0]);
+        assert_eq!(to_base(10, 2), vec![1, 0, 1, 0]);
+        assert_eq!(to_base(255, 16), vec![15, 15]);
+        assert_eq!(to_base(8, 3), vec![2, 2]);
+        assert_eq!(to_base(27, 3), vec![1, 0, 0, 0]);
+        assert_eq!(to_base(123, 10), vec![1, 2, 3]);
+    }
+}
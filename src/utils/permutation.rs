@@ -0,0 +1,118 @@
+/// 配列を辞書順で次の順列に並べ替える
+///
+/// 次の順列が存在しない（降順に並んでいる）場合は配列を変更せず`false`を返します。
+/// 重複する要素を含む場合は、重複を除いた順列を列挙します。
+///
+/// # 計算量
+/// O(n)
+///
+/// # 使用例
+/// ```
+/// # use rust_macro::next_permutation;
+/// let mut a = vec![1, 2, 3];
+/// let mut all = vec![a.clone()];
+/// while next_permutation(&mut a) {
+///     all.push(a.clone());
+/// }
+/// assert_eq!(all.len(), 6);
+/// assert_eq!(all[1], vec![1, 3, 2]);
+/// assert_eq!(a, vec![3, 2, 1]);
+/// ```
+pub fn next_permutation<T: Ord>(a: &mut [T]) -> bool {
+    // a[i] < a[i + 1] となる最大のiを探す
+    let i = match (1..a.len()).rev().find(|&i| a[i - 1] < a[i]) {
+        Some(i) => i - 1,
+        None => return false,
+    };
+    // a[i] < a[j] となる最大のjと交換し、i+1以降を昇順にする
+    let j = (i + 1..a.len()).rev().find(|&j| a[i] < a[j]).unwrap();
+    a.swap(i, j);
+    a[i + 1..].reverse();
+    true
+}
+
+/// 配列を辞書順で前の順列に並べ替える
+///
+/// 前の順列が存在しない（昇順に並んでいる）場合は配列を変更せず`false`を返します。
+///
+/// # 計算量
+/// O(n)
+pub fn prev_permutation<T: Ord>(a: &mut [T]) -> bool {
+    let i = match (1..a.len()).rev().find(|&i| a[i - 1] > a[i]) {
+        Some(i) => i - 1,
+        None => return false,
+    };
+    let j = (i + 1..a.len()).rev().find(|&j| a[i] > a[j]).unwrap();
+    a.swap(i, j);
+    a[i + 1..].reverse();
+    true
+}
+
+/// 0..nの順列を辞書順に列挙するイテレータを返す
+///
+/// # 使用例
+/// ```
+/// # use rust_macro::permutations;
+/// let perms: Vec<Vec<usize>> = permutations(3).collect();
+/// assert_eq!(perms.len(), 6);
+/// assert_eq!(perms[0], vec![0, 1, 2]);
+/// assert_eq!(perms[5], vec![2, 1, 0]);
+/// ```
+pub fn permutations(n: usize) -> Permutations {
+    Permutations {
+        next: Some((0..n).collect()),
+    }
+}
+
+/// `permutations`が返すイテレータ
+#[derive(Debug, Clone)]
+pub struct Permutations {
+    next: Option<Vec<usize>>,
+}
+
+impl Iterator for Permutations {
+    type Item = Vec<usize>;
+
+    fn next(&mut self) -> Option<Vec<usize>> {
+        let cur = self.next.take()?;
+        let mut succ = cur.clone();
+        if next_permutation(&mut succ) {
+            self.next = Some(succ);
+        }
+        Some(cur)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_next_prev_permutation() {
+        let mut a = vec![1, 1, 2];
+        let mut all = vec![a.clone()];
+        while next_permutation(&mut a) {
+            all.push(a.clone());
+        }
+        assert_eq!(all, vec![vec![1, 1, 2], vec![1, 2, 1], vec![2, 1, 1]]);
+
+        let mut back = vec![a.clone()];
+        while prev_permutation(&mut a) {
+            back.push(a.clone());
+        }
+        back.reverse();
+        assert_eq!(back, all);
+
+        let mut empty: Vec<i32> = vec![];
+        assert!(!next_permutation(&mut empty));
+        assert!(!prev_permutation(&mut empty));
+    }
+
+    #[test]
+    fn test_permutations() {
+        assert_eq!(permutations(0).count(), 1);
+        assert_eq!(permutations(4).count(), 24);
+        let perms: Vec<_> = permutations(4).collect();
+        assert!(perms.windows(2).all(|w| w[0] < w[1]));
+    }
+}
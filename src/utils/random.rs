@@ -0,0 +1,129 @@
+use std::ops::Range;
+
+/// 外部クレートに依存しない軽量な疑似乱数生成器（xorshift64）
+///
+/// 同じシードからは常に同じ乱数列を生成するため、ローカルのランダムテストと
+/// ジャッジ上で挙動が一致します。暗号用途には使えません。
+///
+/// # 使用例
+/// ```
+/// # use rust_macro::XorShift64;
+/// let mut rng = XorShift64::new(42);
+/// let x = rng.gen_range(10..20);
+/// assert!((10..20).contains(&x));
+///
+/// let mut a: Vec<u32> = (0..10).collect();
+/// rng.shuffle(&mut a);
+/// a.sort();
+/// assert_eq!(a, (0..10).collect::<Vec<_>>());
+/// ```
+#[derive(Debug, Clone)]
+pub struct XorShift64 {
+    state: u64,
+}
+
+impl XorShift64 {
+    /// シードを指定して作成
+    ///
+    /// xorshiftは状態0から抜け出せないため、シードはsplitmix64で撹拌してから使います。
+    pub fn new(seed: u64) -> Self {
+        let mut z = seed.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^= z >> 31;
+        XorShift64 {
+            state: if z == 0 { 0x2545_f491_4f6c_dd1d } else { z },
+        }
+    }
+
+    /// 次の64bitの乱数を返す
+    pub fn next_u64(&mut self) -> u64 {
+        let mut x = self.state;
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+        self.state = x;
+        x
+    }
+
+    /// [0, 1)の一様な実数を返す
+    pub fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// 区間[start, end)の一様な整数を返す
+    ///
+    /// # パニック
+    /// 区間が空の場合panicします
+    pub fn gen_range(&mut self, range: Range<u64>) -> u64 {
+        assert!(range.start < range.end, "empty range");
+        let width = range.end - range.start;
+        // 剰余による偏りを避けるため、widthの倍数に収まらない値は捨てる
+        let zone = u64::MAX - u64::MAX % width;
+        loop {
+            let x = self.next_u64();
+            if x < zone {
+                return range.start + x % width;
+            }
+        }
+    }
+
+    /// 区間[start, end)の一様な整数を返す（i64版）
+    ///
+    /// # パニック
+    /// 区間が空の場合panicします
+    pub fn gen_range_i64(&mut self, range: Range<i64>) -> i64 {
+        assert!(range.start < range.end, "empty range");
+        let width = range.end.wrapping_sub(range.start) as u64;
+        range.start.wrapping_add(self.gen_range(0..width) as i64)
+    }
+
+    /// スライスをFisher–Yatesのアルゴリズムでシャッフルする
+    pub fn shuffle<T>(&mut self, a: &mut [T]) {
+        for i in (1..a.len()).rev() {
+            let j = self.gen_range(0..i as u64 + 1) as usize;
+            a.swap(i, j);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_xorshift_deterministic() {
+        let mut a = XorShift64::new(0);
+        let mut b = XorShift64::new(0);
+        for _ in 0..100 {
+            assert_eq!(a.next_u64(), b.next_u64());
+        }
+        assert_ne!(XorShift64::new(1).next_u64(), XorShift64::new(2).next_u64());
+    }
+
+    #[test]
+    fn test_xorshift_ranges() {
+        let mut rng = XorShift64::new(12345);
+        let mut seen = [false; 5];
+        for _ in 0..1000 {
+            let x = rng.gen_range_i64(-2..3);
+            assert!((-2..3).contains(&x));
+            seen[(x + 2) as usize] = true;
+            let f = rng.next_f64();
+            assert!((0.0..1.0).contains(&f));
+        }
+        assert!(seen.iter().all(|&b| b));
+        assert_eq!(rng.gen_range(7..8), 7);
+        assert!(rng.gen_range_i64(i64::MIN..i64::MAX) < i64::MAX);
+    }
+
+    #[test]
+    fn test_shuffle() {
+        let mut rng = XorShift64::new(7);
+        let mut a: Vec<usize> = (0..50).collect();
+        rng.shuffle(&mut a);
+        assert_ne!(a, (0..50).collect::<Vec<_>>());
+        a.sort();
+        assert_eq!(a, (0..50).collect::<Vec<_>>());
+    }
+}
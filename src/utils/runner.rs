@@ -0,0 +1,107 @@
+use std::io::{Read, Write};
+
+use super::{OutputWriter, Scanner};
+
+/// 先頭のテストケース数Tを読み、各ケースについて`solve`を呼び出す
+///
+/// 出力は1つのバッファにまとめられ、最後に一度だけ書き出されます。
+///
+/// # 引数
+/// * `input` - 入力元
+/// * `output` - 出力先
+/// * `solve` - 1ケース分を処理する関数
+///
+/// # 戻り値
+/// 書き出し後の出力先
+///
+/// # 使用例
+/// ```
+/// # use rust_macro::{run_cases, OutputWriter, Scanner};
+/// use std::io::Write;
+///
+/// fn solve<W: Write>(sc: &mut Scanner, out: &mut OutputWriter<W>) {
+///     let a: i64 = sc.read();
+///     let b: i64 = sc.read();
+///     writeln!(out, "{}", a + b).unwrap();
+/// }
+///
+/// let res = run_cases("2\n1 2\n3 4\n".as_bytes(), Vec::new(), solve);
+/// assert_eq!(res, b"3\n7\n");
+/// ```
+pub fn run_cases<R, W, F>(input: R, output: W, mut solve: F) -> W
+where
+    R: Read,
+    W: Write,
+    F: FnMut(&mut Scanner, &mut OutputWriter<W>),
+{
+    let mut sc = Scanner::new(input);
+    let mut out = OutputWriter::new(output);
+    let t: usize = sc.read();
+    for _ in 0..t {
+        solve(&mut sc, &mut out);
+    }
+    out.into_inner()
+}
+
+/// スタックサイズを`size_mb`MiBに広げたスレッドで`f`を実行し、その結果を返す
+///
+/// 深さ10^5を超える再帰（木DPや`Graph::to_dsu`のDFSなど）は、既定のスタック
+/// （メインスレッドで8MiB程度、ジャッジによってはもっと小さい）ではスタックオーバーフローします。
+/// `main`の処理全体をこの関数で包むのが簡単です。`f`内でのpanicは呼び出し元に伝播します。
+///
+/// # 使用例
+/// ```
+/// # use rust_macro::run_with_stack;
+/// fn depth(n: u64) -> u64 {
+///     if n == 0 { 0 } else { depth(n - 1) + 1 }
+/// }
+/// assert_eq!(run_with_stack(256, || depth(200_000)), 200_000);
+/// ```
+pub fn run_with_stack<F, R>(size_mb: usize, f: F) -> R
+where
+    F: FnOnce() -> R + Send + 'static,
+    R: Send + 'static,
+{
+    let handle = std::thread::Builder::new()
+        .stack_size(size_mb << 20)
+        .spawn(f)
+        .expect("failed to spawn thread");
+    match handle.join() {
+        Ok(res) => res,
+        Err(e) => std::panic::resume_unwind(e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_run_with_stack_deep_recursion_and_panic() {
+        // 各フレームに配列を置いて、既定のスタックでは足りない深さまで再帰する
+        fn deep(n: usize) -> usize {
+            let buf = std::hint::black_box([0u8; 64]);
+            if n == 0 {
+                0
+            } else {
+                deep(n - 1) + 1 + buf[0] as usize
+            }
+        }
+        assert_eq!(run_with_stack(512, || deep(200_000)), 200_000);
+        let res = std::panic::catch_unwind(|| run_with_stack(1, || panic!("boom")));
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn test_run_cases() {
+        let mut count = 0;
+        let res = run_cases("3\n2 a b\n0\n1 c\n".as_bytes(), Vec::new(), |sc, out| {
+            let n: usize = sc.read();
+            let v: Vec<String> = sc.read_vec(n);
+            out.writeln_iter(v, ",");
+            count += 1;
+        });
+        assert_eq!(count, 3);
+        assert_eq!(res, b"a,b\n\nc\n");
+    }
+}
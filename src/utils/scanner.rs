@@ -0,0 +1,150 @@
+use std::cell::RefCell;
+use std::fmt::Debug;
+use std::io::{self, Read};
+use std::str::FromStr;
+
+/// 高速な入力読み込みを行う構造体
+///
+/// 入力全体を最初に一度だけバッファへ読み込み、空白区切りのトークンとして取り出します。
+///
+/// # 使用例
+/// ```
+/// # use rust_macro::Scanner;
+/// let input = "3 2\n1 2 3\n#.\n.#\n";
+/// let mut sc = Scanner::new(input.as_bytes());
+/// let n: usize = sc.read();
+/// let m: usize = sc.read();
+/// let a: Vec<i64> = sc.read_vec(n);
+/// let grid = sc.read_grid(m, 2);
+/// assert_eq!(a, vec![1, 2, 3]);
+/// assert_eq!(grid, vec![vec!['#', '.'], vec!['.', '#']]);
+/// ```
+///
+/// 標準入力から読む場合は `Scanner::new(std::io::stdin())` とします。
+#[derive(Debug, Clone)]
+pub struct Scanner {
+    buf: Vec<u8>,
+    pos: usize,
+}
+
+impl Scanner {
+    /// 入力全体を読み込んでScannerを作成
+    ///
+    /// # 引数
+    /// * `reader` - 入力元（`std::io::stdin()` など）
+    pub fn new<R: Read>(mut reader: R) -> Self {
+        let mut buf = Vec::new();
+        reader.read_to_end(&mut buf).expect("failed to read input");
+        Scanner { buf, pos: 0 }
+    }
+
+    /// 次のトークンを返す。入力の終端に達した場合は`None`
+    pub fn token(&mut self) -> Option<&str> {
+        while self.pos < self.buf.len() && self.buf[self.pos].is_ascii_whitespace() {
+            self.pos += 1;
+        }
+        if self.pos == self.buf.len() {
+            return None;
+        }
+        let start = self.pos;
+        while self.pos < self.buf.len() && !self.buf[self.pos].is_ascii_whitespace() {
+            self.pos += 1;
+        }
+        Some(std::str::from_utf8(&self.buf[start..self.pos]).expect("input is not valid UTF-8"))
+    }
+
+    /// 次のトークンを型`T`として読み込む
+    ///
+    /// # パニック
+    /// 入力の終端に達した場合や、トークンを`T`として解釈できない場合panicします
+    pub fn read<T>(&mut self) -> T
+    where
+        T: FromStr,
+        T::Err: Debug,
+    {
+        let token = self.token().expect("unexpected end of input");
+        token
+            .parse()
+            .unwrap_or_else(|e| panic!("failed to parse {:?}: {:?}", token, e))
+    }
+
+    /// n個のトークンを型`T`として読み込む
+    pub fn read_vec<T>(&mut self, n: usize) -> Vec<T>
+    where
+        T: FromStr,
+        T::Err: Debug,
+    {
+        (0..n).map(|_| self.read()).collect()
+    }
+
+    /// 次のトークンを文字のベクタとして読み込む
+    pub fn read_chars(&mut self) -> Vec<char> {
+        self.token()
+            .expect("unexpected end of input")
+            .chars()
+            .collect()
+    }
+
+    /// h行w列の文字のグリッドを読み込む（各行は空白を含まない1トークン）
+    ///
+    /// # パニック
+    /// 行の長さがwと異なる場合panicします
+    pub fn read_grid(&mut self, h: usize, w: usize) -> Vec<Vec<char>> {
+        (0..h)
+            .map(|_| {
+                let row = self.read_chars();
+                assert_eq!(row.len(), w, "grid row has unexpected length");
+                row
+            })
+            .collect()
+    }
+}
+
+thread_local! {
+    static STDIN: RefCell<Option<Scanner>> = const { RefCell::new(None) };
+}
+
+/// 標準入力を読み込む共有のScannerを使って処理を行う
+///
+/// 最初の呼び出し時に標準入力全体を読み込みます。`input!`マクロの内部で使われます。
+pub fn with_stdin<F, R>(f: F) -> R
+where
+    F: FnOnce(&mut Scanner) -> R,
+{
+    STDIN.with(|sc| {
+        let mut sc = sc.borrow_mut();
+        f(sc.get_or_insert_with(|| Scanner::new(io::stdin())))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scanner_read() {
+        let mut sc = Scanner::new("  42\n-7 3.5\r\nhello\n".as_bytes());
+        assert_eq!(sc.read::<usize>(), 42);
+        assert_eq!(sc.read::<i64>(), -7);
+        assert_eq!(sc.read::<f64>(), 3.5);
+        assert_eq!(sc.read::<String>(), "hello");
+        assert_eq!(sc.token(), None);
+    }
+
+    #[test]
+    fn test_scanner_vec_and_grid() {
+        let mut sc = Scanner::new("3\n1 2 3\nab\ncd\nxyz".as_bytes());
+        let n = sc.read();
+        assert_eq!(sc.read_vec::<u32>(n), vec![1, 2, 3]);
+        assert_eq!(sc.read_grid(2, 2), vec![vec!['a', 'b'], vec!['c', 'd']]);
+        assert_eq!(sc.read_chars(), vec!['x', 'y', 'z']);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_scanner_eof() {
+        let mut sc = Scanner::new("1".as_bytes());
+        sc.read::<i32>();
+        sc.read::<i32>();
+    }
+}
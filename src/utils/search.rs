@@ -0,0 +1,186 @@
+/// 単調な述語について、条件を満たす境界を二分探索で求める
+///
+/// `f(ok)`が真、`f(ng)`が偽であるとき、`ok`と`ng`の間で`f`が真となる`ng`側の端を返します。
+/// `ok < ng`と`ok > ng`のどちらでも使えます。
+///
+/// # 計算量
+/// O(log |ng - ok|)
+///
+/// # 使用例
+/// ```
+/// # use rust_macro::utils::search::binary_search;
+/// // x * x <= 50 を満たす最大のx
+/// assert_eq!(binary_search(100, 0, |x| x * x <= 50), 7);
+/// // x * x >= 50 を満たす最小のx
+/// assert_eq!(binary_search(0, 100, |x| x * x >= 50), 8);
+/// ```
+pub fn binary_search<F>(mut ng: isize, mut ok: isize, f: F) -> isize
+where
+    F: Fn(isize) -> bool,
+{
+    while (ng - ok).abs() > 1 {
+        let mid = ok + (ng - ok) / 2;
+        if f(mid) {
+            ok = mid;
+        } else {
+            ng = mid;
+        }
+    }
+    ok
+}
+
+/// 実数上の単調な述語について、境界を固定回数の二分探索で求める
+///
+/// 誤差による無限ループを避けるため、区間の幅ではなく反復回数で打ち切ります。
+/// 100回程度で倍精度の限界まで収束します。
+///
+/// # 使用例
+/// ```
+/// # use rust_macro::utils::search::binary_search_f64;
+/// let r = binary_search_f64(0.0, 2.0, 100, |x| x * x <= 2.0);
+/// assert!((r - 2f64.sqrt()).abs() < 1e-9);
+/// ```
+pub fn binary_search_f64<F>(mut ok: f64, mut ng: f64, iterations: usize, f: F) -> f64
+where
+    F: Fn(f64) -> bool,
+{
+    for _ in 0..iterations {
+        let mid = (ok + ng) / 2.0;
+        if f(mid) {
+            ok = mid;
+        } else {
+            ng = mid;
+        }
+    }
+    ok
+}
+
+/// スライスの先頭から`f`が真となる要素の個数（最初に偽となる位置）を返す
+///
+/// `f`は先頭側で真、末尾側で偽となる（区分された）述語である必要があります。
+///
+/// # 使用例
+/// ```
+/// # use rust_macro::utils::search::partition_point;
+/// let a = [1, 3, 3, 5, 8];
+/// assert_eq!(partition_point(&a, |&x| x < 3), 1);
+/// assert_eq!(partition_point(&a, |&x| x <= 3), 3);
+/// assert_eq!(partition_point(&a, |&x| x < 100), 5);
+/// ```
+pub fn partition_point<T, F>(a: &[T], f: F) -> usize
+where
+    F: Fn(&T) -> bool,
+{
+    let (mut ok, mut ng) = (0, a.len());
+    while ok < ng {
+        let mid = ok + (ng - ok) / 2;
+        if f(&a[mid]) {
+            ok = mid + 1;
+        } else {
+            ng = mid;
+        }
+    }
+    ok
+}
+
+/// 整数上の下に凸な関数について、[lo, hi]での最小値をとる点を三分探索で求める
+///
+/// 関数値が平らな区間を含む場合は、その中のいずれかの点を返します（狭義に凸である必要はありませんが、
+/// 最小値以外で平らな区間を持たないことが必要です）。
+///
+/// # 戻り値
+/// (最小値をとる点, 最小値)
+///
+/// # 使用例
+/// ```
+/// # use rust_macro::utils::search::ternary_search;
+/// assert_eq!(ternary_search(-100, 100, |x| (x - 7) * (x - 7) + 3), (7, 3));
+/// ```
+pub fn ternary_search<T, F>(mut lo: i64, mut hi: i64, f: F) -> (i64, T)
+where
+    T: PartialOrd,
+    F: Fn(i64) -> T,
+{
+    assert!(lo <= hi);
+    while hi - lo > 2 {
+        let m1 = lo + (hi - lo) / 3;
+        let m2 = hi - (hi - lo) / 3;
+        if f(m1) > f(m2) {
+            lo = m1 + 1;
+        } else {
+            hi = m2;
+        }
+    }
+    let mut best = (lo, f(lo));
+    for x in lo + 1..=hi {
+        let v = f(x);
+        if v < best.1 {
+            best = (x, v);
+        }
+    }
+    best
+}
+
+/// 実数上の下に凸な関数について、[lo, hi]での最小値をとる点を固定回数の三分探索で求める
+///
+/// 最大値を求めたい場合は符号を反転した関数を渡してください。
+///
+/// # 使用例
+/// ```
+/// # use rust_macro::utils::search::ternary_search_f64;
+/// let x = ternary_search_f64(-10.0, 10.0, 200, |x| (x - 1.5).powi(2));
+/// assert!((x - 1.5).abs() < 1e-6);
+/// ```
+pub fn ternary_search_f64<F>(mut lo: f64, mut hi: f64, iterations: usize, f: F) -> f64
+where
+    F: Fn(f64) -> f64,
+{
+    for _ in 0..iterations {
+        let m1 = (lo * 2.0 + hi) / 3.0;
+        let m2 = (lo + hi * 2.0) / 3.0;
+        if f(m1) > f(m2) {
+            lo = m1;
+        } else {
+            hi = m2;
+        }
+    }
+    (lo + hi) / 2.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_binary_search() {
+        assert_eq!(binary_search(10, -1, |x| x < 0), -1);
+        assert_eq!(binary_search(-1, 10, |x| x >= 0), 0);
+        assert_eq!(
+            binary_search(1_000_000_001, 0, |x| x * x <= 1_000_000_000_000_000_000),
+            1_000_000_000
+        );
+    }
+
+    #[test]
+    fn test_partition_point() {
+        let a = [1, 2, 2, 4, 7, 7, 9];
+        for x in 0..11 {
+            assert_eq!(
+                partition_point(&a, |&v| v < x),
+                a.partition_point(|&v| v < x)
+            );
+        }
+        assert_eq!(partition_point(&[] as &[i32], |_| true), 0);
+    }
+
+    #[test]
+    fn test_ternary_search() {
+        for c in -5..=5 {
+            let (x, v) = ternary_search(-10, 10, |x| (x - c).abs());
+            assert_eq!((x, v), (c, 0));
+        }
+        assert_eq!(ternary_search(3, 3, |x| x), (3, 3));
+        let (x, v) = ternary_search(0, 10, |x| -(x as f64));
+        assert_eq!((x, v), (10, -10.0));
+    }
+}
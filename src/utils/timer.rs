@@ -0,0 +1,98 @@
+use std::time::{Duration, Instant};
+
+/// 制限時間の管理を行う構造体
+///
+/// 反復深化や乱択の再スタート、焼きなましのループなどを、制限時間の少し手前で
+/// 打ち切るために使います。計測は`new`を呼んだ時点から始まります。
+///
+/// # 使用例
+/// ```
+/// # use rust_macro::TimeKeeper;
+/// let tk = TimeKeeper::new(50);
+/// let mut iterations = 0;
+/// // 制限時間の10ms前まで探索を続ける
+/// while tk.should_continue(10) {
+///     iterations += 1;
+/// }
+/// assert!(iterations > 0);
+/// assert!(tk.elapsed().as_millis() >= 40);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct TimeKeeper {
+    start: Instant,
+    limit: Duration,
+}
+
+impl TimeKeeper {
+    /// 制限時間をミリ秒で指定して計測を開始する
+    pub fn new(limit_ms: u64) -> Self {
+        TimeKeeper {
+            start: Instant::now(),
+            limit: Duration::from_millis(limit_ms),
+        }
+    }
+
+    /// 計測開始からの経過時間
+    pub fn elapsed(&self) -> Duration {
+        self.start.elapsed()
+    }
+
+    /// 制限時間までの残り時間（超過している場合は0）
+    pub fn remaining(&self) -> Duration {
+        self.remaining_at(self.elapsed())
+    }
+
+    /// 制限時間の`safety_margin_ms`ミリ秒前をまだ過ぎていないかどうか
+    pub fn should_continue(&self, safety_margin_ms: u64) -> bool {
+        self.remaining() > Duration::from_millis(safety_margin_ms)
+    }
+
+    /// 制限時間に対する経過時間の割合（0.0以上、1.0を超えることもある）
+    ///
+    /// 焼きなましの温度を経過時間に応じて下げる場合などに使います。
+    /// 制限時間が0の場合は常に1.0を返します。
+    pub fn progress(&self) -> f64 {
+        self.progress_at(self.elapsed())
+    }
+
+    fn remaining_at(&self, elapsed: Duration) -> Duration {
+        self.limit.saturating_sub(elapsed)
+    }
+
+    fn progress_at(&self, elapsed: Duration) -> f64 {
+        if self.limit.is_zero() {
+            return 1.0;
+        }
+        elapsed.as_secs_f64() / self.limit.as_secs_f64()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_time_keeper() {
+        // 実時間に依存する判定は、十分な余裕がある場合だけ確認する
+        let tk = TimeKeeper::new(60_000);
+        assert!(tk.should_continue(1000));
+        assert!(!tk.should_continue(120_000));
+        assert!(tk.progress() < 0.5);
+
+        let expired = TimeKeeper::new(0);
+        assert!(!expired.should_continue(0));
+        assert_eq!(expired.remaining(), Duration::ZERO);
+        assert_eq!(expired.progress(), 1.0);
+    }
+
+    #[test]
+    fn test_time_keeper_arithmetic() {
+        let tk = TimeKeeper::new(200);
+        let ms = Duration::from_millis;
+        assert_eq!(tk.remaining_at(ms(50)), ms(150));
+        assert_eq!(tk.remaining_at(ms(250)), Duration::ZERO);
+        assert!((tk.progress_at(ms(50)) - 0.25).abs() < 1e-9);
+        assert!((tk.progress_at(ms(300)) - 1.5).abs() < 1e-9);
+        assert_eq!(TimeKeeper::new(0).progress_at(Duration::ZERO), 1.0);
+    }
+}
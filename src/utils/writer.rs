@@ -0,0 +1,112 @@
+use std::fmt::Display;
+use std::io::{self, BufWriter, StdoutLock, Write};
+
+/// バッファ付きの出力を行う構造体
+///
+/// 標準出力を一度だけロックしてバッファリングするため、`println!`マクロを
+/// 何度も呼ぶより高速です。`std::io::Write`を実装しているので`write!`/`writeln!`も使えます。
+/// バッファの内容はdrop時に書き出されます。
+///
+/// # 使用例
+/// ```
+/// # use rust_macro::OutputWriter;
+/// use std::io::Write;
+///
+/// let mut out = OutputWriter::new(Vec::new());
+/// writeln!(out, "{}", 3).unwrap();
+/// out.writeln_iter([1, 2, 3].iter(), " ");
+/// out.write_grid(&[vec!['#', '.'], vec!['.', '#']], "");
+/// assert_eq!(out.into_inner(), b"3\n1 2 3\n#.\n.#\n");
+/// ```
+///
+/// 標準出力に書き出す場合は `OutputWriter::stdout()` とします。
+pub struct OutputWriter<W: Write> {
+    inner: BufWriter<W>,
+}
+
+impl OutputWriter<StdoutLock<'static>> {
+    /// 標準出力をロックしてOutputWriterを作成
+    pub fn stdout() -> Self {
+        Self::new(io::stdout().lock())
+    }
+}
+
+impl<W: Write> OutputWriter<W> {
+    /// 出力先を指定してOutputWriterを作成
+    pub fn new(writer: W) -> Self {
+        OutputWriter {
+            inner: BufWriter::new(writer),
+        }
+    }
+
+    /// イテレータの要素をsepで区切って書き出す（改行なし）
+    pub fn write_iter<I>(&mut self, iter: I, sep: &str)
+    where
+        I: IntoIterator,
+        I::Item: Display,
+    {
+        for (i, x) in iter.into_iter().enumerate() {
+            if i > 0 {
+                self.inner.write_all(sep.as_bytes()).unwrap();
+            }
+            write!(self.inner, "{}", x).unwrap();
+        }
+    }
+
+    /// イテレータの要素をsepで区切って書き出し、改行する
+    pub fn writeln_iter<I>(&mut self, iter: I, sep: &str)
+    where
+        I: IntoIterator,
+        I::Item: Display,
+    {
+        self.write_iter(iter, sep);
+        self.inner.write_all(b"\n").unwrap();
+    }
+
+    /// 2次元配列を1行ずつ、各行の要素をsepで区切って書き出す
+    pub fn write_grid<T: Display>(&mut self, grid: &[Vec<T>], sep: &str) {
+        for row in grid {
+            self.writeln_iter(row, sep);
+        }
+    }
+
+    /// バッファを書き出して内部の出力先を返す
+    pub fn into_inner(self) -> W {
+        self.inner
+            .into_inner()
+            .unwrap_or_else(|_| panic!("failed to flush output"))
+    }
+}
+
+impl<W: Write> Write for OutputWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.inner.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_writeln_iter() {
+        let mut out = OutputWriter::new(Vec::new());
+        out.writeln_iter(vec![1, 2, 3], " ");
+        out.writeln_iter(Vec::<i32>::new(), " ");
+        out.writeln_iter(["a", "b"].iter(), ", ");
+        out.write_iter(0..3, "");
+        assert_eq!(out.into_inner(), b"1 2 3\n\na, b\n012");
+    }
+
+    #[test]
+    fn test_write_grid() {
+        let mut out = OutputWriter::new(Vec::new());
+        out.write_grid(&[vec![1, 2], vec![3, 4]], " ");
+        writeln!(out, "done").unwrap();
+        assert_eq!(out.into_inner(), b"1 2\n3 4\ndone\n");
+    }
+}